@@ -0,0 +1,93 @@
+//! Serde helpers encoding public keys as base58 strings
+//!
+//! Account structs use these with `#[serde(with = "...")]` so their JSON form matches what
+//! explorers and RPC responses show, instead of arrays of 32 numbers.
+
+use std::str::FromStr;
+
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+use solana_sdk::pubkey::Pubkey;
+
+/// Serializes a public key as a base58 string
+pub fn serialize<S: Serializer>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(pubkey)
+}
+
+/// Deserializes a public key from a base58 string
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    Pubkey::from_str(&value).map_err(D::Error::custom)
+}
+
+/// Serde helpers encoding fixed-size arrays of public keys as base58 strings
+pub mod array {
+    use super::*;
+
+    /// Serializes public keys as a sequence of base58 strings
+    pub fn serialize<S: Serializer, const N: usize>(
+        pubkeys: &[Pubkey; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(pubkeys.iter().map(Pubkey::to_string))
+    }
+
+    /// Deserializes exactly `N` public keys from a sequence of base58 strings
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<[Pubkey; N], D::Error> {
+        let values = Vec::<String>::deserialize(deserializer)?;
+        let len = values.len();
+        let pubkeys = values
+            .iter()
+            .map(|value| Pubkey::from_str(value).map_err(D::Error::custom))
+            .collect::<Result<Vec<_>, _>>()?;
+        pubkeys
+            .try_into()
+            .map_err(|_| D::Error::invalid_length(len, &format!("{} public keys", N).as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::accounts::{BondingCurveAccount, GlobalAccount};
+
+    use super::*;
+
+    #[test]
+    fn test_base58_serde() {
+        let creator = Pubkey::new_unique();
+        let curve = BondingCurveAccount::new(1, 1_000, 500, 800, 200, 1_000, false, creator);
+        let json = serde_json::to_value(&curve).unwrap();
+        assert_eq!(json["creator"], creator.to_string());
+        assert_eq!(json["virtual_sol_reserves"], 500);
+        let decoded: BondingCurveAccount = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.creator, creator);
+
+        let fee_recipients = [Pubkey::new_unique(); 7];
+        let global = GlobalAccount::new(
+            1,
+            true,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            1,
+            2,
+            3,
+            4,
+            95,
+            Pubkey::new_unique(),
+            true,
+            5,
+            5,
+            fee_recipients,
+            Pubkey::new_unique(),
+        );
+        let json = serde_json::to_string(&global).unwrap();
+        let decoded: GlobalAccount = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.fee_recipients, fee_recipients);
+        assert_eq!(decoded.authority, global.authority);
+
+        let mut value = serde_json::to_value(&global).unwrap();
+        value["fee_recipients"] = serde_json::json!([fee_recipients[0].to_string()]);
+        assert!(serde_json::from_value::<GlobalAccount>(value).is_err());
+    }
+}
@@ -24,12 +24,26 @@
 //! - `get_market_cap_sol`: Calculates the current market cap in SOL
 //! - `get_final_market_cap_sol`: Calculates the final market cap in SOL after all tokens are sold
 //! - `get_buy_out_price`: Calculates the price to buy out all remaining tokens
+//! - `price_in_sol`: Returns the spot price of a whole token in SOL
+//! - `market_cap_in_sol`: Returns the current market cap in whole SOL
+//!
+//! # Layouts
+//!
+//! Bonding curves created before token creators were recorded end after `complete`; such
+//! accounts decode with the default public key as `creator`. See `VersionedLayout`.
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
+use super::VersionedLayout;
+use crate::{
+    constants,
+    utils::{self, SOL_DECIMALS, TOKEN_DECIMALS},
+};
+
 /// Represents a bonding curve for token pricing and liquidity management
-#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub struct BondingCurveAccount {
     /// Unique identifier for the bonding curve
     pub discriminator: u64,
@@ -46,9 +60,16 @@ pub struct BondingCurveAccount {
     /// Whether the bonding curve is complete/finalized
     pub complete: bool,
     /// Token creator's address
+    #[serde(with = "super::base58")]
     pub creator: Pubkey,
 }
 
+impl VersionedLayout for BondingCurveAccount {
+    const DISCRIMINATOR: [u8; 8] = constants::discriminators::BONDING_CURVE_ACCOUNT;
+    /// Before and after `creator` was added
+    const LAYOUT_SIZES: &'static [usize] = &[49, 81];
+}
+
 impl BondingCurveAccount {
     /// Creates a new bonding curve instance
     ///
@@ -160,6 +181,24 @@ impl BondingCurveAccount {
             / (self.virtual_token_reserves as u128)) as u64
     }
 
+    /// Returns the spot price of a whole token in SOL
+    ///
+    /// The price is the ratio of the virtual reserves, converted from lamports and raw token
+    /// units to display values. It is the marginal price before fees, not the average price
+    /// of a trade; use `get_buy_price` or `get_sell_price` to quote amounts.
+    pub fn price_in_sol(&self) -> f64 {
+        if self.virtual_token_reserves == 0 {
+            return 0.0;
+        }
+        utils::ui_amount(self.virtual_sol_reserves, SOL_DECIMALS)
+            / utils::ui_amount(self.virtual_token_reserves, TOKEN_DECIMALS)
+    }
+
+    /// Returns the current market cap in whole SOL
+    pub fn market_cap_in_sol(&self) -> f64 {
+        utils::ui_amount(self.get_market_cap_sol(), SOL_DECIMALS)
+    }
+
     /// Calculates the final market cap in SOL after all tokens are sold
     ///
     /// # Arguments
@@ -302,6 +341,27 @@ mod tests {
         assert!(buy_price <= bonding_curve.real_token_reserves);
     }
 
+    #[test]
+    fn test_ui_prices() {
+        let bonding_curve = BondingCurveAccount::new(
+            1,
+            1_073_000_000_000_000,
+            30_000_000_000,
+            793_100_000_000_000,
+            0,
+            1_000_000_000_000_000,
+            false,
+            Pubkey::new_unique(),
+        );
+        // 30 SOL against 1,073,000,000 tokens
+        assert!((bonding_curve.price_in_sol() - 30.0 / 1_073_000_000.0).abs() < 1e-15);
+        assert!((bonding_curve.market_cap_in_sol() - 27.958993476).abs() < 1e-9);
+
+        let empty = BondingCurveAccount::new(1, 0, 0, 0, 0, 0, true, Pubkey::new_unique());
+        assert_eq!(empty.price_in_sol(), 0.0);
+        assert_eq!(utils::raw_amount(-1.0, TOKEN_DECIMALS), 0);
+    }
+
     #[test]
     fn test_overflow_sell_price() {
         let bonding_curve = get_large_bonding_curve();
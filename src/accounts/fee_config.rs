@@ -0,0 +1,119 @@
+//! Fee configuration account of the Pump.fun fee program
+//!
+//! Newer deployments of the Pump.fun program read their trading fees from a configuration
+//! account owned by a separate fee program, which buys and sells reference after the volume
+//! accumulators.
+//!
+//! # Fee Configuration Account
+//!
+//! The account stores flat fees, charged when no tier applies, and fee tiers keyed by the
+//! market cap of the bonding curve.
+//!
+//! # Methods
+//!
+//! - `FeeConfigAccount::fees_for_market_cap`: Returns the fees charged at a market cap
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+/// Fees charged on a trade, in basis points
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+)]
+pub struct FeeRates {
+    /// Fee paid to liquidity providers; only charged by the AMM
+    pub lp_fee_bps: u64,
+    /// Fee paid to the protocol's fee recipient
+    pub protocol_fee_bps: u64,
+    /// Fee paid to the token creator's vault
+    pub creator_fee_bps: u64,
+}
+
+/// Fees charged from a market cap threshold
+#[derive(
+    Debug, Clone, Copy, BorshSerialize, BorshDeserialize, Serialize, Deserialize, PartialEq, Eq,
+)]
+pub struct MarketCapFeeTier {
+    /// Market cap from which the tier applies, in lamports
+    pub market_cap_lamports_threshold: u128,
+    /// Fees charged in the tier
+    pub fees: FeeRates,
+}
+
+/// Represents the fee configuration of the Pump.fun program
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FeeConfigAccount {
+    /// Unique identifier for the account
+    pub discriminator: u64,
+    /// Bump seed of the configuration PDA
+    pub bump: u8,
+    /// Authority allowed to update the configuration
+    #[serde(with = "super::base58")]
+    pub admin: Pubkey,
+    /// Fees charged when no tier applies
+    pub flat_fees: FeeRates,
+    /// Fee tiers, sorted by increasing market cap threshold
+    pub fee_tiers: Vec<MarketCapFeeTier>,
+}
+
+impl FeeConfigAccount {
+    /// Returns the fees charged at a market cap
+    ///
+    /// # Arguments
+    /// * `market_cap` - Market cap of the bonding curve, in lamports
+    ///
+    /// # Returns
+    /// The fees of the highest tier whose threshold the market cap reaches, or the flat fees
+    /// if there is none
+    pub fn fees_for_market_cap(&self, market_cap: u128) -> FeeRates {
+        self.fee_tiers
+            .iter()
+            .rev()
+            .find(|tier| market_cap >= tier.market_cap_lamports_threshold)
+            .map_or(self.flat_fees, |tier| tier.fees)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_config_account() {
+        let tier = |threshold, protocol_fee_bps| MarketCapFeeTier {
+            market_cap_lamports_threshold: threshold,
+            fees: FeeRates {
+                lp_fee_bps: 0,
+                protocol_fee_bps,
+                creator_fee_bps: 5,
+            },
+        };
+        let account = FeeConfigAccount {
+            discriminator: 0,
+            bump: 255,
+            admin: Pubkey::new_unique(),
+            flat_fees: FeeRates {
+                lp_fee_bps: 0,
+                protocol_fee_bps: 95,
+                creator_fee_bps: 5,
+            },
+            fee_tiers: vec![tier(1_000, 90), tier(10_000, 50)],
+        };
+        assert_eq!(account.fees_for_market_cap(999), account.flat_fees);
+        assert_eq!(account.fees_for_market_cap(1_000).protocol_fee_bps, 90);
+        assert_eq!(account.fees_for_market_cap(50_000).protocol_fee_bps, 50);
+
+        let data = borsh::to_vec(&account).unwrap();
+        assert_eq!(FeeConfigAccount::try_from_slice(&data).unwrap(), account);
+    }
+}
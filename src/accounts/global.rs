@@ -27,20 +27,31 @@
 //!
 //! - `new`: Creates a new global account instance
 //! - `get_initial_buy_price`: Calculates the initial amount of tokens received for a given SOL amount
+//!
+//! # Layouts
+//!
+//! Global accounts predating creator fees end after `pool_migration_fee`; such accounts
+//! decode with zero creator fees and default fee recipients. See `VersionedLayout`.
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 
+use super::VersionedLayout;
+use crate::constants;
+
 /// Represents the global configuration account for token pricing and fees
-#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub struct GlobalAccount {
     /// Unique identifier for the global account
     pub discriminator: u64,
     /// Whether the global account has been initialized
     pub initialized: bool,
     /// Authority that can modify global settings
+    #[serde(with = "super::base58")]
     pub authority: Pubkey,
     /// Account that receives fees
+    #[serde(with = "super::base58")]
     pub fee_recipient: Pubkey,
     /// Initial virtual token reserves for price calculations
     pub initial_virtual_token_reserves: u64,
@@ -53,6 +64,7 @@ pub struct GlobalAccount {
     /// Fee in basis points (1/100th of a percent)
     pub fee_basis_points: u64,
     /// Authority that can withdraw funds
+    #[serde(with = "super::base58")]
     pub withdraw_authority: Pubkey,
     /// Flag to enable pool migration
     pub enable_migrate: bool,
@@ -61,11 +73,19 @@ pub struct GlobalAccount {
     /// Fee for creators in base points
     pub creator_fee_basis_points: u64,
     /// Array of public keys for fee recipients
+    #[serde(with = "super::base58::array")]
     pub fee_recipients: [Pubkey; 7],
     /// Authority that sets the creator of the token
+    #[serde(with = "super::base58")]
     pub set_creator_authority: Pubkey,
 }
 
+impl VersionedLayout for GlobalAccount {
+    const DISCRIMINATOR: [u8; 8] = constants::discriminators::GLOBAL_ACCOUNT;
+    /// Before and after the creator fee, fee recipients, and set creator authority were added
+    const LAYOUT_SIZES: &'static [usize] = &[154, 418];
+}
+
 impl GlobalAccount {
     /// Creates a new global account instance
     ///
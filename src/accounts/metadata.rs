@@ -0,0 +1,117 @@
+//! Metaplex token metadata account
+//!
+//! This module contains the definition for the on-chain metadata account created by the
+//! Metaplex Token Metadata program for every Pump.fun token.
+//!
+//! # Metadata Account
+//!
+//! The metadata account stores the token's name, symbol, and the URI of its off-chain
+//! metadata JSON. Only the leading fields shared by every version of the account are
+//! deserialized; later fields such as collections and uses are ignored.
+//!
+//! # Fields
+//!
+//! - `key`: Metaplex account type, `4` for metadata accounts
+//! - `update_authority`: Authority that can update the metadata
+//! - `mint`: Mint the metadata describes
+//! - `name`: Token name
+//! - `symbol`: Token symbol
+//! - `uri`: URI of the off-chain metadata JSON
+//! - `seller_fee_basis_points`: Royalty in basis points
+//! - `creators`: Verified and unverified creators, if any
+//! - `primary_sale_happened`: Whether the primary sale has happened
+//! - `is_mutable`: Whether the metadata can be updated
+//!
+//! # Methods
+//!
+//! - `name`, `symbol`, `uri`: Return the fields without the padding Metaplex stores
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+/// Creator listed in a metadata account
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct MetadataCreator {
+    /// Creator address
+    #[serde(with = "super::base58")]
+    pub address: Pubkey,
+    /// Whether the creator signed the metadata
+    pub verified: bool,
+    /// Share of royalties, in percent
+    pub share: u8,
+}
+
+/// Represents the Metaplex metadata account of a token
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct MetadataAccount {
+    /// Metaplex account type
+    pub key: u8,
+    /// Authority that can update the metadata
+    #[serde(with = "super::base58")]
+    pub update_authority: Pubkey,
+    /// Mint the metadata describes
+    #[serde(with = "super::base58")]
+    pub mint: Pubkey,
+    /// Token name, padded with null bytes
+    pub name: String,
+    /// Token symbol, padded with null bytes
+    pub symbol: String,
+    /// URI of the off-chain metadata JSON, padded with null bytes
+    pub uri: String,
+    /// Royalty in basis points
+    pub seller_fee_basis_points: u16,
+    /// Creators of the token, if any
+    pub creators: Option<Vec<MetadataCreator>>,
+    /// Whether the primary sale has happened
+    pub primary_sale_happened: bool,
+    /// Whether the metadata can be updated
+    pub is_mutable: bool,
+}
+
+impl MetadataAccount {
+    /// Returns the token name without padding
+    pub fn name(&self) -> &str {
+        self.name.trim_end_matches('\0')
+    }
+
+    /// Returns the token symbol without padding
+    pub fn symbol(&self) -> &str {
+        self.symbol.trim_end_matches('\0')
+    }
+
+    /// Returns the URI of the off-chain metadata JSON without padding
+    pub fn uri(&self) -> &str {
+        self.uri.trim_end_matches('\0')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_account() {
+        let account = MetadataAccount {
+            key: 4,
+            update_authority: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            name: format!("{:\0<32}", "Token"),
+            symbol: format!("{:\0<10}", "TKN"),
+            uri: format!("{:\0<200}", "https://ipfs.io/ipfs/metadata"),
+            seller_fee_basis_points: 0,
+            creators: None,
+            primary_sale_happened: false,
+            is_mutable: false,
+        };
+        let mut data = borsh::to_vec(&account).unwrap();
+        // Fields of later account versions follow and are ignored
+        data.extend_from_slice(&[1, 255, 0, 0, 0]);
+
+        let parsed: MetadataAccount = solana_sdk::borsh1::try_from_slice_unchecked(&data).unwrap();
+        assert_eq!(parsed, account);
+        assert_eq!(parsed.name(), "Token");
+        assert_eq!(parsed.symbol(), "TKN");
+        assert_eq!(parsed.uri(), "https://ipfs.io/ipfs/metadata");
+    }
+}
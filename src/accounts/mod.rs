@@ -5,10 +5,223 @@
 //! # Accounts
 //!
 //! - `BondingCurve`: Represents a bonding curve account.
+//! - `FeeConfig`: Represents the fee configuration account of the fee program.
 //! - `Global`: Represents the global configuration account.
+//! - `Metadata`: Represents the Metaplex metadata account of a token.
+//! - `GlobalVolumeAccumulator`, `UserVolumeAccumulator`: Track the program's and each user's trading volume.
+//!
+//! Fetched accounts are checked against their Anchor discriminator with
+//! `try_from_account_data`. Accounts whose layout has grown implement `VersionedLayout` and
+//! are decoded with `try_from_versioned_account_data`, so accounts created before an upgrade
+//! decode into the current structs instead of failing.
+//!
+//! Every account struct also implements serde's `Serialize` and `Deserialize`, with public
+//! keys encoded as base58 strings, so accounts can be returned from web APIs or stored as
+//! JSON directly.
+//!
+//! For high-frequency decoding, `BondingCurveView` reads fields straight from the raw data
+//! without copying it into a struct.
 
+mod base58;
 mod bonding_curve;
+mod fee_config;
 mod global;
+mod metadata;
+mod view;
+mod volume_accumulator;
 
 pub use bonding_curve::*;
+pub use fee_config::*;
 pub use global::*;
+pub use metadata::*;
+pub use view::*;
+pub use volume_accumulator::*;
+
+use borsh::BorshDeserialize;
+
+use crate::error::ClientError;
+
+/// Deserializes account data after checking its Anchor discriminator
+///
+/// The account structs keep the discriminator as their first field, so the whole data is
+/// deserialized once the discriminator matches.
+///
+/// # Arguments
+///
+/// * `data` - Raw account data, starting with the 8-byte discriminator
+/// * `discriminator` - Discriminator of the expected account type, from
+///   `constants::discriminators`
+///
+/// # Returns
+///
+/// Returns the deserialized account, or a ClientError if the data is not of the expected
+/// account type or cannot be deserialized
+///
+/// # Errors
+///
+/// Returns `ClientError::AccountDiscriminatorMismatch` if the data starts with another
+/// discriminator, e.g. because the address points at another kind of account
+pub fn try_from_account_data<T: BorshDeserialize>(
+    data: &[u8],
+    discriminator: &[u8; 8],
+) -> Result<T, ClientError> {
+    check_discriminator(data, discriminator)?;
+    solana_sdk::borsh1::try_from_slice_unchecked::<T>(data).map_err(ClientError::BorshError)
+}
+
+fn check_discriminator(data: &[u8], discriminator: &[u8; 8]) -> Result<(), ClientError> {
+    if data.starts_with(discriminator) {
+        return Ok(());
+    }
+    let mut found = [0; 8];
+    let len = data.len().min(8);
+    found[..len].copy_from_slice(&data[..len]);
+    Err(ClientError::AccountDiscriminatorMismatch {
+        expected: *discriminator,
+        found,
+    })
+}
+
+/// Account types whose on-chain layout has grown over time
+///
+/// Accounts created before a program upgrade keep their old size until they are extended,
+/// so they lack the fields appended since. Implementors list the size of every layout so
+/// older accounts can be detected and decoded into the current struct.
+pub trait VersionedLayout: BorshDeserialize {
+    /// Discriminator of the account type
+    const DISCRIMINATOR: [u8; 8];
+    /// Serialized size of each layout in bytes, oldest first; the last one is current
+    const LAYOUT_SIZES: &'static [usize];
+
+    /// Returns the layout of account data
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Raw account data
+    ///
+    /// # Returns
+    ///
+    /// Returns the index of the newest layout in `LAYOUT_SIZES` that fits in the data, or
+    /// None if the data is shorter than the oldest layout
+    fn layout_version(data: &[u8]) -> Option<usize> {
+        Self::LAYOUT_SIZES
+            .iter()
+            .rposition(|size| data.len() >= *size)
+    }
+}
+
+/// Deserializes account data of any known layout into the current struct
+///
+/// Data of an older layout is decoded as if the missing trailing fields were zeroed, so
+/// they take their default values: zero, false, or the default public key.
+///
+/// # Arguments
+///
+/// * `data` - Raw account data, starting with the 8-byte discriminator
+///
+/// # Returns
+///
+/// Returns the deserialized account, or a ClientError if the data is not of the expected
+/// account type or cannot be deserialized
+///
+/// # Errors
+///
+/// Returns `ClientError::AccountDiscriminatorMismatch` if the data starts with another
+/// discriminator, or `ClientError::BorshError` if it is shorter than every known layout
+pub fn try_from_versioned_account_data<T: VersionedLayout>(data: &[u8]) -> Result<T, ClientError> {
+    let current = T::LAYOUT_SIZES.last().copied().unwrap_or_default();
+    match T::layout_version(data) {
+        Some(_) if data.len() < current => {
+            let mut padded = data.to_vec();
+            padded.resize(current, 0);
+            try_from_account_data(&padded, &T::DISCRIMINATOR)
+        }
+        _ => try_from_account_data(data, &T::DISCRIMINATOR),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::pubkey::Pubkey;
+
+    use super::*;
+    use crate::constants::discriminators;
+
+    #[test]
+    fn test_try_from_account_data() {
+        let mut data = discriminators::USER_VOLUME_ACCUMULATOR_ACCOUNT.to_vec();
+        data.extend_from_slice(&[0; 32 + 1 + 8 * 4 + 1]);
+        let account: UserVolumeAccumulator =
+            try_from_account_data(&data, &discriminators::USER_VOLUME_ACCUMULATOR_ACCOUNT).unwrap();
+        assert_eq!(account.current_sol_volume, 0);
+
+        let err = try_from_account_data::<GlobalAccount>(&data, &discriminators::GLOBAL_ACCOUNT)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ClientError::AccountDiscriminatorMismatch { expected, found }
+                if expected == discriminators::GLOBAL_ACCOUNT
+                    && found == discriminators::USER_VOLUME_ACCUMULATOR_ACCOUNT
+        ));
+        assert!(matches!(
+            try_from_account_data::<GlobalAccount>(&[1, 2], &discriminators::GLOBAL_ACCOUNT),
+            Err(ClientError::AccountDiscriminatorMismatch {
+                found: [1, 2, 0, 0, 0, 0, 0, 0],
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_versioned_layouts() {
+        let creator = Pubkey::new_unique();
+        let mut curve = BondingCurveAccount::new(0, 1_000, 2_000, 500, 100, 1_000, false, creator);
+        curve.discriminator = u64::from_le_bytes(discriminators::BONDING_CURVE_ACCOUNT);
+        let data = borsh::to_vec(&curve).unwrap();
+        assert_eq!(data.len(), BondingCurveAccount::LAYOUT_SIZES[1]);
+
+        // Accounts created before creators were recorded end after `complete`
+        let legacy = &data[..BondingCurveAccount::LAYOUT_SIZES[0]];
+        assert_eq!(BondingCurveAccount::layout_version(legacy), Some(0));
+        let decoded: BondingCurveAccount = try_from_versioned_account_data(legacy).unwrap();
+        assert_eq!(decoded.virtual_sol_reserves, 2_000);
+        assert_eq!(decoded.creator, Pubkey::default());
+
+        // Extended accounts may carry trailing bytes past the current layout
+        let mut extended = data.clone();
+        extended.resize(150, 0);
+        assert_eq!(BondingCurveAccount::layout_version(&extended), Some(1));
+        let decoded: BondingCurveAccount = try_from_versioned_account_data(&extended).unwrap();
+        assert_eq!(decoded.creator, creator);
+
+        assert_eq!(BondingCurveAccount::layout_version(&data[..40]), None);
+        assert!(matches!(
+            try_from_versioned_account_data::<BondingCurveAccount>(&data[..40]),
+            Err(ClientError::BorshError(_))
+        ));
+
+        let global = GlobalAccount::new(
+            u64::from_le_bytes(discriminators::GLOBAL_ACCOUNT),
+            true,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            1,
+            2,
+            3,
+            4,
+            95,
+            Pubkey::new_unique(),
+            true,
+            5,
+            5,
+            [Pubkey::new_unique(); 7],
+            Pubkey::new_unique(),
+        );
+        let data = borsh::to_vec(&global).unwrap();
+        assert_eq!(data.len(), GlobalAccount::LAYOUT_SIZES[1]);
+        let decoded: GlobalAccount =
+            try_from_versioned_account_data(&data[..GlobalAccount::LAYOUT_SIZES[0]]).unwrap();
+        assert_eq!(decoded.pool_migration_fee, 5);
+        assert_eq!(decoded.creator_fee_basis_points, 0);
+    }
+}
@@ -0,0 +1,166 @@
+//! Zero-copy views over raw account data
+//!
+//! Decoding an account into its Borsh struct copies every field. Feeds that decode
+//! thousands of accounts per second, such as Geyser account subscriptions, can instead wrap
+//! the raw data in a view, which checks the discriminator and length once and reads each
+//! field from its offset on access, without allocating.
+//!
+//! # Views
+//!
+//! - `BondingCurveView`: Read-only view of a bonding curve account
+
+use solana_sdk::pubkey::Pubkey;
+
+use super::{check_discriminator, BondingCurveAccount, VersionedLayout};
+use crate::error::ClientError;
+
+const VIRTUAL_TOKEN_RESERVES: usize = 8;
+const VIRTUAL_SOL_RESERVES: usize = 16;
+const REAL_TOKEN_RESERVES: usize = 24;
+const REAL_SOL_RESERVES: usize = 32;
+const TOKEN_TOTAL_SUPPLY: usize = 40;
+const COMPLETE: usize = 48;
+const CREATOR: usize = 49;
+
+/// Read-only view of a bonding curve account's raw data
+///
+/// Accepts every layout listed by `BondingCurveAccount::LAYOUT_SIZES`; accounts created
+/// before creators were recorded have no `creator`.
+///
+/// # Examples
+///
+/// ```
+/// use pumpfun::{accounts::{BondingCurveAccount, BondingCurveView}, constants};
+/// use solana_sdk::pubkey::Pubkey;
+///
+/// let mut curve = BondingCurveAccount::new(0, 1_000, 500, 800, 200, 1_000, false, Pubkey::new_unique());
+/// curve.discriminator = u64::from_le_bytes(constants::discriminators::BONDING_CURVE_ACCOUNT);
+/// let data = borsh::to_vec(&curve).unwrap();
+///
+/// let view = BondingCurveView::new(&data).unwrap();
+/// assert_eq!(view.virtual_sol_reserves(), 500);
+/// assert_eq!(view.creator(), Some(curve.creator));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct BondingCurveView<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> BondingCurveView<'a> {
+    /// Wraps raw bonding curve account data
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Raw account data, starting with the 8-byte discriminator
+    ///
+    /// # Returns
+    ///
+    /// Returns the view, or a ClientError if the data is not a bonding curve account
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClientError::AccountDiscriminatorMismatch` if the data starts with another
+    /// discriminator, or `ClientError::BorshError` if it is shorter than every known layout
+    pub fn new(data: &'a [u8]) -> Result<Self, ClientError> {
+        check_discriminator(data, &BondingCurveAccount::DISCRIMINATOR)?;
+        if BondingCurveAccount::layout_version(data).is_none() {
+            return Err(ClientError::BorshError(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "bonding curve account data is too short",
+            )));
+        }
+        Ok(Self { data })
+    }
+
+    /// Returns the virtual token reserves used for price calculations
+    pub fn virtual_token_reserves(&self) -> u64 {
+        self.read_u64(VIRTUAL_TOKEN_RESERVES)
+    }
+
+    /// Returns the virtual SOL reserves used for price calculations
+    pub fn virtual_sol_reserves(&self) -> u64 {
+        self.read_u64(VIRTUAL_SOL_RESERVES)
+    }
+
+    /// Returns the actual token reserves available for trading
+    pub fn real_token_reserves(&self) -> u64 {
+        self.read_u64(REAL_TOKEN_RESERVES)
+    }
+
+    /// Returns the actual SOL reserves available for trading
+    pub fn real_sol_reserves(&self) -> u64 {
+        self.read_u64(REAL_SOL_RESERVES)
+    }
+
+    /// Returns the total supply of tokens
+    pub fn token_total_supply(&self) -> u64 {
+        self.read_u64(TOKEN_TOTAL_SUPPLY)
+    }
+
+    /// Returns whether the bonding curve is complete
+    pub fn complete(&self) -> bool {
+        self.data[COMPLETE] != 0
+    }
+
+    /// Returns the token creator, or None for accounts created before creators were recorded
+    pub fn creator(&self) -> Option<Pubkey> {
+        let bytes: &[u8; 32] = self.data.get(CREATOR..CREATOR + 32)?.try_into().ok()?;
+        Some(Pubkey::new_from_array(*bytes))
+    }
+
+    /// Copies the fields into an owned bonding curve account
+    pub fn to_account(&self) -> BondingCurveAccount {
+        BondingCurveAccount::new(
+            self.read_u64(0),
+            self.virtual_token_reserves(),
+            self.virtual_sol_reserves(),
+            self.real_token_reserves(),
+            self.real_sol_reserves(),
+            self.token_total_supply(),
+            self.complete(),
+            self.creator().unwrap_or_default(),
+        )
+    }
+
+    fn read_u64(&self, offset: usize) -> u64 {
+        // The length is checked against the oldest layout on construction
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&self.data[offset..offset + 8]);
+        u64::from_le_bytes(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::discriminators;
+
+    #[test]
+    fn test_bonding_curve_view() {
+        let mut curve =
+            BondingCurveAccount::new(0, 1_000, 500, 800, 200, 1_000, true, Pubkey::new_unique());
+        curve.discriminator = u64::from_le_bytes(discriminators::BONDING_CURVE_ACCOUNT);
+        let data = borsh::to_vec(&curve).unwrap();
+
+        let view = BondingCurveView::new(&data).unwrap();
+        assert_eq!(view.virtual_token_reserves(), 1_000);
+        assert_eq!(view.real_token_reserves(), 800);
+        assert_eq!(view.real_sol_reserves(), 200);
+        assert_eq!(view.token_total_supply(), 1_000);
+        assert!(view.complete());
+        assert_eq!(view.to_account().creator, curve.creator);
+
+        let legacy = BondingCurveView::new(&data[..49]).unwrap();
+        assert_eq!(legacy.creator(), None);
+        assert_eq!(legacy.virtual_sol_reserves(), 500);
+
+        assert!(matches!(
+            BondingCurveView::new(&data[..30]),
+            Err(ClientError::BorshError(_))
+        ));
+        assert!(matches!(
+            BondingCurveView::new(&[0; 81]),
+            Err(ClientError::AccountDiscriminatorMismatch { .. })
+        ));
+    }
+}
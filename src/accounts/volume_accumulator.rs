@@ -0,0 +1,128 @@
+//! Volume accumulator accounts for the Pump.fun Solana Program
+//!
+//! This module contains the definitions for the accounts tracking trading volume.
+//!
+//! # Volume Accumulator Accounts
+//!
+//! The global volume accumulator records the SOL volume traded on each day of the current
+//! incentive period, along with the incentive tokens distributed that day. Each user volume
+//! accumulator records one user's SOL volume and incentive tokens. Buys and sells reference
+//! both accounts, and the fee schedule and token incentives are derived from them.
+//!
+//! # Methods
+//!
+//! - `GlobalVolumeAccumulator::day_index`: Returns the day of the incentive period containing a timestamp
+//! - `GlobalVolumeAccumulator::sol_volume_at`: Returns the SOL volume traded on the day containing a timestamp
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+/// Number of days tracked by the global volume accumulator
+pub const VOLUME_ACCUMULATOR_DAYS: usize = 30;
+
+/// Represents the daily trading volume of the whole program
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GlobalVolumeAccumulator {
+    /// Unique identifier for the account
+    pub discriminator: u64,
+    /// Unix timestamp at which the incentive period starts
+    pub start_time: i64,
+    /// Unix timestamp at which the incentive period ends
+    pub end_time: i64,
+    /// Length of a day of the period, in seconds
+    pub seconds_in_a_day: i64,
+    /// Mint of the incentive token
+    #[serde(with = "super::base58")]
+    pub mint: Pubkey,
+    /// Incentive tokens distributed on each day
+    pub total_token_supply: [u64; VOLUME_ACCUMULATOR_DAYS],
+    /// SOL volume traded on each day, in lamports
+    pub sol_volumes: [u64; VOLUME_ACCUMULATOR_DAYS],
+}
+
+impl GlobalVolumeAccumulator {
+    /// Returns the day of the incentive period containing a timestamp
+    ///
+    /// # Arguments
+    /// * `timestamp` - Unix timestamp to look up
+    ///
+    /// # Returns
+    /// The index of the day, or None if the timestamp is outside of the period
+    pub fn day_index(&self, timestamp: i64) -> Option<usize> {
+        if timestamp < self.start_time || timestamp >= self.end_time || self.seconds_in_a_day <= 0 {
+            return None;
+        }
+        let index = ((timestamp - self.start_time) / self.seconds_in_a_day) as usize;
+        (index < VOLUME_ACCUMULATOR_DAYS).then_some(index)
+    }
+
+    /// Returns the SOL volume traded on the day containing a timestamp
+    ///
+    /// # Arguments
+    /// * `timestamp` - Unix timestamp to look up
+    ///
+    /// # Returns
+    /// The volume in lamports, or None if the timestamp is outside of the period
+    pub fn sol_volume_at(&self, timestamp: i64) -> Option<u64> {
+        self.day_index(timestamp)
+            .map(|index| self.sol_volumes[index])
+    }
+}
+
+/// Represents the trading volume and incentive tokens of a single user
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UserVolumeAccumulator {
+    /// Unique identifier for the account
+    pub discriminator: u64,
+    /// User whose volume is tracked
+    #[serde(with = "super::base58")]
+    pub user: Pubkey,
+    /// Whether incentive tokens are waiting to be claimed
+    pub needs_claim: bool,
+    /// Incentive tokens earned but not claimed yet
+    pub total_unclaimed_tokens: u64,
+    /// Incentive tokens claimed so far
+    pub total_claimed_tokens: u64,
+    /// SOL volume traded by the user in the current day, in lamports
+    pub current_sol_volume: u64,
+    /// Unix timestamp of the last volume update
+    pub last_update_timestamp: i64,
+    /// Whether `total_claimed_tokens` has been initialized
+    pub has_total_claimed_tokens: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_global_volume_accumulator() {
+        let mut sol_volumes = [0; VOLUME_ACCUMULATOR_DAYS];
+        sol_volumes[2] = 1_000;
+        let global = GlobalVolumeAccumulator {
+            discriminator: 0,
+            start_time: 1_000,
+            end_time: 1_000 + 86_400 * VOLUME_ACCUMULATOR_DAYS as i64,
+            seconds_in_a_day: 86_400,
+            mint: Pubkey::new_unique(),
+            total_token_supply: [0; VOLUME_ACCUMULATOR_DAYS],
+            sol_volumes,
+        };
+        assert_eq!(global.day_index(999), None);
+        assert_eq!(global.day_index(1_000), Some(0));
+        assert_eq!(global.sol_volume_at(1_000 + 86_400 * 2 + 5), Some(1_000));
+        assert_eq!(global.day_index(global.end_time), None);
+
+        let data = borsh::to_vec(&global).unwrap();
+        assert_eq!(data.len(), 8 + 24 + 32 + 8 * 2 * VOLUME_ACCUMULATOR_DAYS);
+        assert_eq!(
+            GlobalVolumeAccumulator::try_from_slice(&data).unwrap(),
+            global
+        );
+        assert_eq!(
+            crate::PumpFun::get_global_volume_accumulator_pda(),
+            crate::constants::accounts::GLOBAL_VOLUME_ACCUMULATOR
+        );
+    }
+}
@@ -0,0 +1,366 @@
+//! OHLCV candles for the Pump.fun trade stream
+//!
+//! This module aggregates trade events into open/high/low/close/volume candles per mint at
+//! any number of intervals, from live events or from backfilled history.
+//!
+//! # Candles
+//!
+//! Each trade contributes its execution price, the SOL amount divided by the token amount,
+//! in lamports per token base unit. Candles are aligned to multiples of their interval since
+//! the Unix epoch and measured using the on-chain `timestamp` of each trade, so replaying
+//! recorded events produces exactly the same candles as processing them live. Intervals
+//! without trades produce no candle.
+//!
+//! When a SOL/USD rate is set, every trade is also priced in USD at the rate current when it
+//! is recorded, and candles carry a USD series next to the SOL one.
+
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+
+use crate::common::stream::{PumpFunEvent, TradeEvent};
+
+/// Open, high, low, and close prices of a candle
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Ohlc {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl Ohlc {
+    fn new(price: f64) -> Self {
+        Self {
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+        }
+    }
+
+    fn update(&mut self, price: f64) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+    }
+}
+
+/// Candle of a single mint over a single interval
+///
+/// # Fields
+///
+/// * `mint` - Token mint the candle belongs to
+/// * `interval` - Length of the candle
+/// * `start` - Unix timestamp the candle starts at, a multiple of the interval
+/// * `sol` - Prices in lamports per token base unit
+/// * `usd` - Prices in USD per token base unit, if a SOL/USD rate was set for every trade
+///   of the candle
+/// * `sol_volume` - Total SOL amount of the candle's trades, in lamports
+/// * `token_volume` - Total token amount of the candle's trades, in base units
+/// * `trades` - Number of trades in the candle
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Candle {
+    pub mint: Pubkey,
+    pub interval: Duration,
+    pub start: i64,
+    pub sol: Ohlc,
+    pub usd: Option<Ohlc>,
+    pub sol_volume: u64,
+    pub token_volume: u64,
+    pub trades: u64,
+}
+
+impl Candle {
+    /// Returns the Unix timestamp the candle ends at, exclusive
+    pub fn end(&self) -> i64 {
+        self.start + self.interval.as_secs() as i64
+    }
+}
+
+/// Aggregates trades into candles per mint and interval
+///
+/// Trades are expected in timestamp order per mint. A trade in a later interval closes the
+/// mint's open candle, which is returned from `record`; trades older than the open candle
+/// are ignored. Use `flush` to close candles whose interval has passed without further
+/// trades.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use pumpfun::analytics::CandleAggregator;
+/// # use solana_sdk::pubkey::Pubkey;
+/// # use std::time::Duration;
+/// #
+/// let candles = CandleAggregator::new(vec![Duration::from_secs(15), Duration::from_secs(60)]);
+/// candles.set_sol_usd_price(Some(150.0));
+///
+/// // Trades are fed in from the event stream with `candles.record_event(&event)`, which
+/// // returns the candles closed by the trade
+/// # let mint = Pubkey::new_unique();
+/// let open = candles.current(&mint, Duration::from_secs(60));
+/// ```
+pub struct CandleAggregator {
+    intervals: Vec<Duration>,
+    sol_usd: Mutex<Option<f64>>,
+    candles: Mutex<HashMap<(Pubkey, Duration), Candle>>,
+}
+
+impl CandleAggregator {
+    /// Creates a new candle aggregator
+    ///
+    /// # Arguments
+    ///
+    /// * `intervals` - Candle intervals to aggregate. Intervals are truncated to whole
+    ///   seconds, and intervals shorter than a second are ignored
+    ///
+    /// # Returns
+    ///
+    /// A new `CandleAggregator` with no recorded trades
+    pub fn new(intervals: Vec<Duration>) -> Self {
+        let mut intervals: Vec<Duration> = intervals
+            .into_iter()
+            .filter(|interval| interval.as_secs() > 0)
+            .map(|interval| Duration::from_secs(interval.as_secs()))
+            .collect();
+        intervals.sort();
+        intervals.dedup();
+        Self {
+            intervals,
+            sol_usd: Mutex::new(None),
+            candles: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets the SOL/USD rate used to price subsequently recorded trades in USD
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - USD per SOL, or None to stop pricing trades in USD
+    pub fn set_sol_usd_price(&self, price: Option<f64>) {
+        let mut sol_usd = match self.sol_usd.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        *sol_usd = price;
+    }
+
+    /// Records a parsed program event, ignoring anything that is not a trade
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - Event received from the Pump.fun event stream
+    ///
+    /// # Returns
+    ///
+    /// The candles closed by the trade, if any
+    pub fn record_event(&self, event: &PumpFunEvent) -> Vec<Candle> {
+        match event {
+            PumpFunEvent::Trade(trade) => self.record(trade),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Records a trade
+    ///
+    /// # Arguments
+    ///
+    /// * `trade` - Trade event to record
+    ///
+    /// # Returns
+    ///
+    /// The candles closed by the trade, one per interval whose open candle ended before it
+    pub fn record(&self, trade: &TradeEvent) -> Vec<Candle> {
+        if trade.token_amount == 0 {
+            return Vec::new();
+        }
+        let price = trade.sol_amount as f64 / trade.token_amount as f64;
+        let usd_price = self
+            .sol_usd_price()
+            .map(|rate| price * rate / LAMPORTS_PER_SOL as f64);
+
+        let mut candles = match self.candles.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let mut closed = Vec::new();
+        for interval in &self.intervals {
+            let length = interval.as_secs() as i64;
+            let start = trade.timestamp.div_euclid(length) * length;
+            let key = (trade.mint, *interval);
+
+            if let Some(candle) = candles.get_mut(&key) {
+                if start < candle.start {
+                    continue;
+                }
+                if start == candle.start {
+                    candle.sol.update(price);
+                    candle.usd = match (candle.usd, usd_price) {
+                        (Some(mut usd), Some(price)) => {
+                            usd.update(price);
+                            Some(usd)
+                        }
+                        _ => None,
+                    };
+                    candle.sol_volume = candle.sol_volume.saturating_add(trade.sol_amount);
+                    candle.token_volume = candle.token_volume.saturating_add(trade.token_amount);
+                    candle.trades += 1;
+                    continue;
+                }
+            }
+
+            let candle = Candle {
+                mint: trade.mint,
+                interval: *interval,
+                start,
+                sol: Ohlc::new(price),
+                usd: usd_price.map(Ohlc::new),
+                sol_volume: trade.sol_amount,
+                token_volume: trade.token_amount,
+                trades: 1,
+            };
+            if let Some(previous) = candles.insert(key, candle) {
+                closed.push(previous);
+            }
+        }
+        closed
+    }
+
+    /// Gets the open candle of a mint
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - Token mint to query
+    /// * `interval` - Candle interval, which must be one of the aggregated intervals
+    ///
+    /// # Returns
+    ///
+    /// The candle containing the mint's most recent trade, or None if it has no trades
+    pub fn current(&self, mint: &Pubkey, interval: Duration) -> Option<Candle> {
+        let candles = match self.candles.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        candles.get(&(*mint, interval)).cloned()
+    }
+
+    /// Closes all candles that ended at or before a timestamp
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - Current Unix timestamp
+    ///
+    /// # Returns
+    ///
+    /// The closed candles, which are no longer returned by `current`
+    pub fn flush(&self, now: i64) -> Vec<Candle> {
+        let mut candles = match self.candles.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let ended: Vec<_> = candles
+            .iter()
+            .filter(|(_, candle)| candle.end() <= now)
+            .map(|(key, _)| *key)
+            .collect();
+        let mut closed: Vec<Candle> = ended.iter().filter_map(|key| candles.remove(key)).collect();
+        closed.sort_by_key(|candle| (candle.start, candle.interval));
+        closed
+    }
+
+    fn sol_usd_price(&self) -> Option<f64> {
+        match self.sol_usd.lock() {
+            Ok(guard) => *guard,
+            Err(poisoned) => *poisoned.into_inner(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_trade(mint: Pubkey, sol_amount: u64, token_amount: u64, ts: i64) -> TradeEvent {
+        TradeEvent {
+            mint,
+            sol_amount,
+            token_amount,
+            is_buy: true,
+            user: Pubkey::new_unique(),
+            timestamp: ts,
+            virtual_sol_reserves: 0,
+            virtual_token_reserves: 0,
+            real_sol_reserves: 0,
+            real_token_reserves: 0,
+            fee_recipient: Pubkey::new_unique(),
+            fee_basis_points: 0,
+            fee: 0,
+            creator: Pubkey::new_unique(),
+            creator_fee_basis_points: 0,
+            creator_fee: 0,
+            track_volume: false,
+            total_unclaimed_tokens: 0,
+            total_claimed_tokens: 0,
+            current_sol_volume: 0,
+            last_update_timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_candles() {
+        let mint = Pubkey::new_unique();
+        let minute = Duration::from_secs(60);
+        let candles = CandleAggregator::new(vec![Duration::from_secs(15), minute]);
+
+        assert!(candles.record(&get_trade(mint, 100, 1_000, 60)).is_empty());
+        assert!(candles.record(&get_trade(mint, 300, 1_000, 62)).is_empty());
+        assert!(candles.record(&get_trade(mint, 50, 1_000, 70)).is_empty());
+
+        let candle = candles.current(&mint, minute).unwrap();
+        assert_eq!(candle.start, 60);
+        assert_eq!(
+            candle.sol,
+            Ohlc {
+                open: 0.1,
+                high: 0.3,
+                low: 0.05,
+                close: 0.05
+            }
+        );
+        assert_eq!(candle.sol_volume, 450);
+        assert_eq!(candle.trades, 3);
+        assert!(candle.usd.is_none());
+
+        // A trade in the next 15s interval closes only the 15s candle
+        let closed = candles.record(&get_trade(mint, 200, 1_000, 75));
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].interval, Duration::from_secs(15));
+        assert_eq!(closed[0].trades, 3);
+
+        // Trades older than the open candle are ignored
+        assert!(candles.record(&get_trade(mint, 1, 1_000, 10)).is_empty());
+        assert_eq!(candles.current(&mint, minute).unwrap().trades, 4);
+
+        let flushed = candles.flush(120);
+        assert_eq!(flushed.len(), 2);
+        assert!(candles.current(&mint, minute).is_none());
+    }
+
+    #[test]
+    fn test_candles_usd() {
+        let mint = Pubkey::new_unique();
+        let minute = Duration::from_secs(60);
+        let candles = CandleAggregator::new(vec![minute]);
+        candles.set_sol_usd_price(Some(200.0));
+
+        candles.record(&get_trade(mint, LAMPORTS_PER_SOL, 1_000, 0));
+        let usd = candles.current(&mint, minute).unwrap().usd.unwrap();
+        assert!((usd.close - 0.2).abs() < f64::EPSILON);
+
+        // The USD series is dropped once a trade cannot be priced
+        candles.set_sol_usd_price(None);
+        candles.record(&get_trade(mint, LAMPORTS_PER_SOL, 1_000, 1));
+        assert!(candles.current(&mint, minute).unwrap().usd.is_none());
+    }
+}
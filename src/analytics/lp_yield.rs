@@ -0,0 +1,325 @@
+//! Fee earnings and yield estimates for Pump.fun AMM liquidity providers
+//!
+//! This module totals the swap volume of a pool from its buy and sell events, for example
+//! events backfilled from the pool's transaction history, and estimates what a liquidity
+//! position earned from LP fees over that period and the annualized yield it implies.
+//!
+//! # Estimates
+//!
+//! LP fees are charged on the quote side of every swap, so the fees of a period are the
+//! quote volume times the LP fee. The fee is passed in rather than read from the events so
+//! the same volume can be evaluated at another rate, such as the `lp_fee_basis_points` of
+//! the AMM's global configuration after an update.
+//!
+//! A position earns the fees in proportion to its share of the pool's liquidity. Positions
+//! are valued at twice the quote side of their share of the reserves, as both sides of a
+//! constant-product pool hold the same value. Reserves are taken from the latest swap, and
+//! impermanent loss and reserve changes over the period are not accounted for.
+
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::common::stream::PumpFunEvent;
+
+const SECONDS_PER_YEAR: f64 = 365.0 * 24.0 * 60.0 * 60.0;
+
+/// Swap volume of a pool over a period
+///
+/// # Fields
+///
+/// * `pool` - Pool the swaps were made in
+/// * `quote_volume` - Total quote amount of the swaps before LP fees, in quote base units
+/// * `swaps` - Number of swaps
+/// * `start` - Unix timestamp of the first swap
+/// * `end` - Unix timestamp of the last swap
+/// * `pool_quote_reserves` - Quote reserves of the pool at the last swap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwapVolume {
+    pub pool: Pubkey,
+    pub quote_volume: u64,
+    pub swaps: u64,
+    pub start: i64,
+    pub end: i64,
+    pub pool_quote_reserves: u64,
+}
+
+impl SwapVolume {
+    /// Totals the swap volume of a pool from program events
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Pool to total the swaps of
+    /// * `events` - Events to include; anything other than buys and sells of the pool is
+    ///   ignored
+    ///
+    /// # Returns
+    ///
+    /// The swap volume of the pool, or None if the events contain no swap of the pool
+    pub fn from_events<'a, I>(pool: &Pubkey, events: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'a PumpFunEvent>,
+    {
+        let mut volume: Option<Self> = None;
+        for event in events {
+            let (timestamp, quote_amount, reserves) = match event {
+                PumpFunEvent::Buy(buy) if buy.pool == *pool => (
+                    buy.timestamp,
+                    buy.quote_amount_in,
+                    buy.pool_quote_token_reserves,
+                ),
+                PumpFunEvent::Sell(sell) if sell.pool == *pool => (
+                    sell.timestamp,
+                    sell.quote_amount_out,
+                    sell.pool_quote_token_reserves,
+                ),
+                _ => continue,
+            };
+            let volume = volume.get_or_insert(Self {
+                pool: *pool,
+                quote_volume: 0,
+                swaps: 0,
+                start: timestamp,
+                end: timestamp,
+                pool_quote_reserves: reserves,
+            });
+            volume.quote_volume = volume.quote_volume.saturating_add(quote_amount);
+            volume.swaps += 1;
+            volume.start = volume.start.min(timestamp);
+            if timestamp >= volume.end {
+                volume.end = timestamp;
+                volume.pool_quote_reserves = reserves;
+            }
+        }
+        volume
+    }
+
+    /// Returns the LP fees charged on the volume
+    ///
+    /// # Arguments
+    ///
+    /// * `lp_fee_basis_points` - LP fee in basis points
+    ///
+    /// # Returns
+    ///
+    /// The fees in quote base units
+    pub fn lp_fees(&self, lp_fee_basis_points: u64) -> u64 {
+        (self.quote_volume as u128 * lp_fee_basis_points as u128 / 10_000) as u64
+    }
+}
+
+/// Liquidity position to estimate the yield of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LpPosition {
+    /// Position held in the pool, as an amount of LP tokens out of the LP mint supply
+    Existing { lp_amount: u64, lp_supply: u64 },
+    /// Position that would be added by depositing a quote amount and the matching base
+    /// amount, growing the pool
+    Hypothetical { quote_amount: u64 },
+}
+
+/// Estimated LP fee earnings of a position
+///
+/// # Fields
+///
+/// * `pool_share` - Share of the pool's liquidity held by the position, from 0 to 1
+/// * `position_value` - Value of the position, in quote base units
+/// * `fee_earnings` - LP fees earned by the position over the period, in quote base units
+/// * `period_secs` - Length of the period, in seconds
+/// * `apr` - Annualized fee earnings relative to the position value, without compounding
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LpYield {
+    pub pool_share: f64,
+    pub position_value: f64,
+    pub fee_earnings: f64,
+    pub period_secs: i64,
+    pub apr: f64,
+}
+
+/// Estimates the LP fee earnings and annualized yield of a position
+///
+/// # Arguments
+///
+/// * `volume` - Swap volume of the pool over the period
+/// * `lp_fee_basis_points` - LP fee in basis points
+/// * `position` - Position to estimate the yield of
+///
+/// # Returns
+///
+/// The estimate, or None if the period is empty, the pool has no quote reserves, or the
+/// position is empty
+///
+/// # Examples
+///
+/// ```
+/// # use pumpfun::analytics::{estimate_lp_yield, LpPosition, SwapVolume};
+/// # use solana_sdk::pubkey::Pubkey;
+/// let volume = SwapVolume {
+///     pool: Pubkey::new_unique(),
+///     quote_volume: 1_000_000_000_000,
+///     swaps: 5_000,
+///     start: 0,
+///     end: 24 * 60 * 60,
+///     pool_quote_reserves: 100_000_000_000,
+/// };
+/// let position = LpPosition::Existing { lp_amount: 1, lp_supply: 4 };
+/// let estimate = estimate_lp_yield(&volume, 20, position).unwrap();
+/// assert_eq!(estimate.fee_earnings, 500_000_000.0);
+/// ```
+pub fn estimate_lp_yield(
+    volume: &SwapVolume,
+    lp_fee_basis_points: u64,
+    position: LpPosition,
+) -> Option<LpYield> {
+    let period_secs = volume.end - volume.start;
+    if period_secs <= 0 || volume.pool_quote_reserves == 0 {
+        return None;
+    }
+    let reserves = volume.pool_quote_reserves as f64;
+    let (pool_share, position_value) = match position {
+        LpPosition::Existing {
+            lp_amount,
+            lp_supply,
+        } => {
+            if lp_amount == 0 || lp_supply == 0 {
+                return None;
+            }
+            let share = (lp_amount as f64 / lp_supply as f64).min(1.0);
+            (share, 2.0 * share * reserves)
+        }
+        LpPosition::Hypothetical { quote_amount } => {
+            if quote_amount == 0 {
+                return None;
+            }
+            let quote_amount = quote_amount as f64;
+            (quote_amount / (reserves + quote_amount), 2.0 * quote_amount)
+        }
+    };
+    let fee_earnings = volume.lp_fees(lp_fee_basis_points) as f64 * pool_share;
+    Some(LpYield {
+        pool_share,
+        position_value,
+        fee_earnings,
+        period_secs,
+        apr: fee_earnings / position_value * SECONDS_PER_YEAR / period_secs as f64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::stream::{BuyEvent, SellEvent};
+
+    fn get_buy(pool: Pubkey, quote_amount_in: u64, reserves: u64, timestamp: i64) -> PumpFunEvent {
+        PumpFunEvent::Buy(BuyEvent {
+            timestamp,
+            base_amount_out: 0,
+            max_quote_amount_in: 0,
+            user_base_token_reserves: 0,
+            user_quote_token_reserves: 0,
+            pool_base_token_reserves: 0,
+            pool_quote_token_reserves: reserves,
+            quote_amount_in,
+            lp_fee_basis_points: 20,
+            lp_fee: 0,
+            protocol_fee_basis_points: 0,
+            protocol_fee: 0,
+            quote_amount_in_with_lp_fee: 0,
+            user_quote_amount_in: 0,
+            pool,
+            user: Pubkey::default(),
+            user_base_token_account: Pubkey::default(),
+            user_quote_token_account: Pubkey::default(),
+            protocol_fee_recipient: Pubkey::default(),
+            protocol_fee_recipient_token_account: Pubkey::default(),
+            coin_creator: Pubkey::default(),
+            coin_creator_fee_basis_points: 0,
+            coin_creator_fee: 0,
+        })
+    }
+
+    fn get_sell(
+        pool: Pubkey,
+        quote_amount_out: u64,
+        reserves: u64,
+        timestamp: i64,
+    ) -> PumpFunEvent {
+        PumpFunEvent::Sell(SellEvent {
+            timestamp,
+            base_amount_in: 0,
+            min_quote_amount_out: 0,
+            user_base_token_reserves: 0,
+            user_quote_token_reserves: 0,
+            pool_base_token_reserves: 0,
+            pool_quote_token_reserves: reserves,
+            quote_amount_out,
+            lp_fee_basis_points: 20,
+            lp_fee: 0,
+            protocol_fee_basis_points: 0,
+            protocol_fee: 0,
+            quote_amount_out_without_lp_fee: 0,
+            user_quote_amount_out: 0,
+            pool,
+            user: Pubkey::default(),
+            user_base_token_account: Pubkey::default(),
+            user_quote_token_account: Pubkey::default(),
+            protocol_fee_recipient: Pubkey::default(),
+            protocol_fee_recipient_token_account: Pubkey::default(),
+            coin_creator: Pubkey::default(),
+            coin_creator_fee_basis_points: 0,
+            coin_creator_fee: 0,
+        })
+    }
+
+    #[test]
+    fn test_lp_yield() {
+        let pool = Pubkey::new_unique();
+        let events = vec![
+            get_buy(pool, 600_000, 1_000_000, 100),
+            get_buy(Pubkey::new_unique(), 5_000_000, 1, 150),
+            get_sell(pool, 400_000, 1_500_000, 200),
+        ];
+        let volume = SwapVolume::from_events(&pool, &events).unwrap();
+        assert_eq!(volume.quote_volume, 1_000_000);
+        assert_eq!(volume.swaps, 2);
+        assert_eq!((volume.start, volume.end), (100, 200));
+        assert_eq!(volume.pool_quote_reserves, 1_500_000);
+        assert_eq!(volume.lp_fees(20), 2_000);
+        assert!(SwapVolume::from_events(&Pubkey::new_unique(), &events).is_none());
+
+        let existing = estimate_lp_yield(
+            &volume,
+            20,
+            LpPosition::Existing {
+                lp_amount: 1,
+                lp_supply: 4,
+            },
+        )
+        .unwrap();
+        assert_eq!(existing.pool_share, 0.25);
+        assert_eq!(existing.position_value, 750_000.0);
+        assert_eq!(existing.fee_earnings, 500.0);
+        assert_eq!(existing.period_secs, 100);
+        let apr = 500.0 / 750_000.0 * SECONDS_PER_YEAR / 100.0;
+        assert!((existing.apr - apr).abs() < 1e-9);
+
+        // A deposit of the same size as the quote reserves holds half the grown pool
+        let hypothetical = estimate_lp_yield(
+            &volume,
+            20,
+            LpPosition::Hypothetical {
+                quote_amount: 1_500_000,
+            },
+        )
+        .unwrap();
+        assert_eq!(hypothetical.pool_share, 0.5);
+        assert_eq!(hypothetical.fee_earnings, 1_000.0);
+
+        assert!(
+            estimate_lp_yield(&volume, 20, LpPosition::Hypothetical { quote_amount: 0 }).is_none()
+        );
+        let single = SwapVolume::from_events(&pool, &events[..1]).unwrap();
+        assert!(
+            estimate_lp_yield(&single, 20, LpPosition::Hypothetical { quote_amount: 1 }).is_none()
+        );
+    }
+}
@@ -0,0 +1,25 @@
+//! Analytics derived from the Pump.fun event stream
+//!
+//! This module contains components that consume parsed program events and turn them
+//! into higher-level market metrics that strategies and risk checks can build on.
+//!
+//! # Components
+//!
+//! - `CandleAggregator`: OHLCV candles per mint at configurable intervals, in SOL and
+//!   optionally USD.
+//! - `estimate_lp_yield`: LP fee earnings and annualized yield of an AMM liquidity
+//!   position, from the swap volume of its pool.
+//! - `OrderFlowTracker`: Rolling buy/sell volume, imbalance, and unique-trader counts per
+//!   mint, with a ranking of the most traded mints.
+//! - `VwapTracker`: Rolling volume-weighted average price per mint and per wallet, with
+//!   `vwap` and `vwap_between` for stored trades.
+
+mod candles;
+mod lp_yield;
+mod order_flow;
+mod vwap;
+
+pub use candles::*;
+pub use lp_yield::*;
+pub use order_flow::*;
+pub use vwap::*;
@@ -0,0 +1,470 @@
+//! Order-flow imbalance metrics for the Pump.fun trade stream
+//!
+//! This module contains a tracker that keeps a rolling record of trades per mint and
+//! derives buy/sell volume imbalance and unique-trader counts over configurable windows.
+//!
+//! # Order Flow Tracker
+//!
+//! Every trade fed into the tracker produces one `OrderFlowMetrics` per configured window,
+//! which is both returned to the caller and published on a broadcast channel so any number
+//! of consumers (strategies, risk checks, dashboards) can subscribe to the derived stream.
+//!
+//! Metrics can also be queried on demand, per mint with `snapshot` or across mints with
+//! `top_mints`, which ranks the most traded mints for momentum screens.
+//!
+//! Windows are measured using the on-chain `timestamp` of each trade, so replaying recorded
+//! events produces exactly the same metrics as processing them live.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Mutex,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::broadcast;
+
+use crate::common::stream::{PumpFunEvent, TradeEvent};
+
+/// Configuration for the order-flow tracker
+///
+/// # Fields
+///
+/// * `windows` - Rolling windows over which metrics are computed
+/// * `channel_capacity` - Number of metric updates buffered for slow subscribers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderFlowConfig {
+    pub windows: Vec<Duration>,
+    pub channel_capacity: usize,
+}
+
+impl Default for OrderFlowConfig {
+    fn default() -> Self {
+        Self {
+            windows: vec![
+                Duration::from_secs(60),
+                Duration::from_secs(5 * 60),
+                Duration::from_secs(60 * 60),
+            ],
+            channel_capacity: 1024,
+        }
+    }
+}
+
+/// Order-flow metrics for a single mint over a single window
+///
+/// # Fields
+///
+/// * `mint` - Token mint the metrics belong to
+/// * `window` - Length of the rolling window
+/// * `timestamp` - Timestamp of the most recent trade included in the window
+/// * `buy_volume` - SOL volume of buys in the window, in lamports
+/// * `sell_volume` - SOL volume of sells in the window, in lamports
+/// * `buy_count` - Number of buys in the window
+/// * `sell_count` - Number of sells in the window
+/// * `unique_traders` - Number of distinct wallets that traded in the window
+/// * `imbalance` - `(buy_volume - sell_volume) / (buy_volume + sell_volume)`, in `[-1, 1]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderFlowMetrics {
+    pub mint: Pubkey,
+    pub window: Duration,
+    pub timestamp: i64,
+    pub buy_volume: u64,
+    pub sell_volume: u64,
+    pub buy_count: u64,
+    pub sell_count: u64,
+    pub unique_traders: usize,
+    pub imbalance: f64,
+}
+
+/// Minimal trade record retained for window calculations
+#[derive(Debug, Clone, Copy)]
+struct TradeSample {
+    timestamp: i64,
+    user: Pubkey,
+    sol_amount: u64,
+    is_buy: bool,
+}
+
+/// Rolling order-flow tracker keyed by mint
+///
+/// The tracker is internally synchronized so it can be shared through an `Arc` and fed
+/// directly from a subscription callback.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use pumpfun::analytics::{OrderFlowConfig, OrderFlowTracker};
+/// # use std::sync::Arc;
+/// #
+/// # async fn example() {
+/// let tracker = Arc::new(OrderFlowTracker::new(OrderFlowConfig::default()));
+///
+/// // Consumers subscribe to the derived metric stream
+/// let mut metrics = tracker.subscribe();
+/// tokio::spawn(async move {
+///     while let Ok(update) = metrics.recv().await {
+///         println!("{} imbalance over {:?}: {:.2}", update.mint, update.window, update.imbalance);
+///     }
+/// });
+///
+/// // Trades are fed in from the event stream with `tracker.record_event(&event)`
+/// # }
+/// ```
+pub struct OrderFlowTracker {
+    config: OrderFlowConfig,
+    longest_window: i64,
+    trades: Mutex<HashMap<Pubkey, VecDeque<TradeSample>>>,
+    sender: broadcast::Sender<OrderFlowMetrics>,
+}
+
+impl OrderFlowTracker {
+    /// Creates a new order-flow tracker
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Windows and channel configuration for the tracker
+    ///
+    /// # Returns
+    ///
+    /// A new `OrderFlowTracker` with no recorded trades
+    pub fn new(config: OrderFlowConfig) -> Self {
+        let (sender, _) = broadcast::channel(config.channel_capacity.max(1));
+        let longest_window = config
+            .windows
+            .iter()
+            .map(|window| window.as_secs() as i64)
+            .max()
+            .unwrap_or(0);
+
+        Self {
+            config,
+            longest_window,
+            trades: Mutex::new(HashMap::new()),
+            sender,
+        }
+    }
+
+    /// Subscribes to the derived metric stream
+    ///
+    /// # Returns
+    ///
+    /// A broadcast receiver that yields an `OrderFlowMetrics` per window for every recorded trade
+    pub fn subscribe(&self) -> broadcast::Receiver<OrderFlowMetrics> {
+        self.sender.subscribe()
+    }
+
+    /// Records a parsed program event, ignoring anything that is not a trade
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - Event received from the Pump.fun event stream
+    ///
+    /// # Returns
+    ///
+    /// The updated metrics for the trade's mint, or an empty vector for non-trade events
+    pub fn record_event(&self, event: &PumpFunEvent) -> Vec<OrderFlowMetrics> {
+        match event {
+            PumpFunEvent::Trade(trade) => self.record(trade),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Records a trade and publishes the updated metrics for its mint
+    ///
+    /// # Arguments
+    ///
+    /// * `trade` - Trade event to record
+    ///
+    /// # Returns
+    ///
+    /// The updated metrics for each configured window
+    pub fn record(&self, trade: &TradeEvent) -> Vec<OrderFlowMetrics> {
+        let metrics = {
+            let mut trades = match self.trades.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            let samples = trades.entry(trade.mint).or_default();
+            samples.push_back(TradeSample {
+                timestamp: trade.timestamp,
+                user: trade.user,
+                sol_amount: trade.sol_amount,
+                is_buy: trade.is_buy,
+            });
+
+            // Drop samples that have fallen out of the longest window
+            let now = samples.iter().map(|s| s.timestamp).max().unwrap_or(0);
+            while samples
+                .front()
+                .is_some_and(|s| s.timestamp <= now - self.longest_window)
+            {
+                samples.pop_front();
+            }
+
+            self.config
+                .windows
+                .iter()
+                .map(|window| Self::compute(&trade.mint, samples, *window, now))
+                .collect::<Vec<_>>()
+        };
+
+        for update in &metrics {
+            // Sending only fails when nobody is subscribed
+            let _ = self.sender.send(update.clone());
+        }
+
+        metrics
+    }
+
+    /// Gets the current metrics for a mint without recording a trade
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - Token mint to query
+    /// * `window` - Window over which to compute the metrics
+    ///
+    /// # Returns
+    ///
+    /// The metrics ending at the mint's most recent trade, or None if no trades were recorded
+    pub fn snapshot(&self, mint: &Pubkey, window: Duration) -> Option<OrderFlowMetrics> {
+        let trades = match self.trades.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let samples = trades.get(mint)?;
+        let now = samples.iter().map(|s| s.timestamp).max()?;
+        Some(Self::compute(mint, samples, window, now))
+    }
+
+    /// Gets the mints with the highest SOL volume
+    ///
+    /// Unlike `snapshot`, all windows end at the most recent trade across every mint, so
+    /// mints that stopped trading fall out of the ranking.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Window over which to compute the metrics
+    /// * `limit` - Maximum number of mints to return
+    ///
+    /// # Returns
+    ///
+    /// The metrics of the most traded mints, by buy and sell volume combined, in descending
+    /// order. Mints without trades in the window are omitted
+    pub fn top_mints(&self, window: Duration, limit: usize) -> Vec<OrderFlowMetrics> {
+        let trades = match self.trades.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let Some(now) = trades
+            .values()
+            .filter_map(|samples| samples.iter().map(|s| s.timestamp).max())
+            .max()
+        else {
+            return Vec::new();
+        };
+
+        let mut metrics: Vec<OrderFlowMetrics> = trades
+            .iter()
+            .map(|(mint, samples)| Self::compute(mint, samples, window, now))
+            .filter(|metrics| metrics.buy_count + metrics.sell_count > 0)
+            .collect();
+        metrics.sort_by_key(|metrics| {
+            std::cmp::Reverse(metrics.buy_volume.saturating_add(metrics.sell_volume))
+        });
+        metrics.truncate(limit);
+        metrics
+    }
+
+    /// Removes all recorded trades for a mint
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - Token mint to forget
+    pub fn clear(&self, mint: &Pubkey) {
+        let mut trades = match self.trades.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        trades.remove(mint);
+    }
+
+    fn compute(
+        mint: &Pubkey,
+        samples: &VecDeque<TradeSample>,
+        window: Duration,
+        now: i64,
+    ) -> OrderFlowMetrics {
+        let start = now - window.as_secs() as i64;
+        let mut buy_volume: u64 = 0;
+        let mut sell_volume: u64 = 0;
+        let mut buy_count: u64 = 0;
+        let mut sell_count: u64 = 0;
+        let mut traders: HashSet<Pubkey> = HashSet::new();
+
+        for sample in samples.iter().filter(|s| s.timestamp > start) {
+            if sample.is_buy {
+                buy_volume = buy_volume.saturating_add(sample.sol_amount);
+                buy_count += 1;
+            } else {
+                sell_volume = sell_volume.saturating_add(sample.sol_amount);
+                sell_count += 1;
+            }
+            traders.insert(sample.user);
+        }
+
+        let total = buy_volume as f64 + sell_volume as f64;
+        let imbalance = if total == 0.0 {
+            0.0
+        } else {
+            (buy_volume as f64 - sell_volume as f64) / total
+        };
+
+        OrderFlowMetrics {
+            mint: *mint,
+            window,
+            timestamp: now,
+            buy_volume,
+            sell_volume,
+            buy_count,
+            sell_count,
+            unique_traders: traders.len(),
+            imbalance,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_trade(mint: Pubkey, user: Pubkey, sol_amount: u64, is_buy: bool, ts: i64) -> TradeEvent {
+        TradeEvent {
+            mint,
+            sol_amount,
+            token_amount: sol_amount * 1000,
+            is_buy,
+            user,
+            timestamp: ts,
+            virtual_sol_reserves: 0,
+            virtual_token_reserves: 0,
+            real_sol_reserves: 0,
+            real_token_reserves: 0,
+            fee_recipient: Pubkey::default(),
+            fee_basis_points: 0,
+            fee: 0,
+            creator: Pubkey::default(),
+            creator_fee_basis_points: 0,
+            creator_fee: 0,
+            track_volume: false,
+            total_unclaimed_tokens: 0,
+            total_claimed_tokens: 0,
+            current_sol_volume: 0,
+            last_update_timestamp: 0,
+        }
+    }
+
+    fn get_tracker() -> OrderFlowTracker {
+        OrderFlowTracker::new(OrderFlowConfig {
+            windows: vec![Duration::from_secs(10), Duration::from_secs(100)],
+            channel_capacity: 16,
+        })
+    }
+
+    #[test]
+    fn test_order_flow_imbalance() {
+        let tracker = get_tracker();
+        let mint = Pubkey::new_unique();
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+
+        tracker.record(&get_trade(mint, alice, 300, true, 1_000));
+        let metrics = tracker.record(&get_trade(mint, bob, 100, false, 1_001));
+        assert_eq!(metrics.len(), 2);
+
+        let short = &metrics[0];
+        assert_eq!(short.buy_volume, 300);
+        assert_eq!(short.sell_volume, 100);
+        assert_eq!(short.buy_count, 1);
+        assert_eq!(short.sell_count, 1);
+        assert_eq!(short.unique_traders, 2);
+        assert!((short.imbalance - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_order_flow_windows() {
+        let tracker = get_tracker();
+        let mint = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+
+        tracker.record(&get_trade(mint, user, 500, true, 1_000));
+        let metrics = tracker.record(&get_trade(mint, user, 200, false, 1_050));
+
+        // The buy has left the short window but not the long one
+        assert_eq!(metrics[0].buy_volume, 0);
+        assert_eq!(metrics[0].sell_volume, 200);
+        assert!((metrics[0].imbalance + 1.0).abs() < f64::EPSILON);
+        assert_eq!(metrics[1].buy_volume, 500);
+        assert_eq!(metrics[1].unique_traders, 1);
+
+        // Samples older than the longest window are pruned
+        tracker.record(&get_trade(mint, user, 1, true, 1_200));
+        let snapshot = tracker.snapshot(&mint, Duration::from_secs(100)).unwrap();
+        assert_eq!(snapshot.buy_volume, 1);
+        assert_eq!(snapshot.sell_volume, 0);
+    }
+
+    #[test]
+    fn test_order_flow_per_mint_and_broadcast() {
+        let tracker = get_tracker();
+        let mut receiver = tracker.subscribe();
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+
+        tracker.record(&get_trade(mint_a, user, 10, true, 1));
+        tracker.record(&get_trade(mint_b, user, 20, false, 1));
+
+        let a = tracker.snapshot(&mint_a, Duration::from_secs(10)).unwrap();
+        let b = tracker.snapshot(&mint_b, Duration::from_secs(10)).unwrap();
+        assert_eq!(a.buy_volume, 10);
+        assert_eq!(b.sell_volume, 20);
+
+        // One update per window per trade
+        for _ in 0..4 {
+            assert!(receiver.try_recv().is_ok());
+        }
+        assert!(receiver.try_recv().is_err());
+
+        tracker.clear(&mint_a);
+        assert!(tracker.snapshot(&mint_a, Duration::from_secs(10)).is_none());
+    }
+
+    #[test]
+    fn test_order_flow_top_mints() {
+        let tracker = get_tracker();
+        let (quiet, busy, stale) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let user = Pubkey::new_unique();
+
+        tracker.record(&get_trade(stale, user, 1_000, true, 1));
+        tracker.record(&get_trade(quiet, user, 10, true, 95));
+        tracker.record(&get_trade(busy, user, 30, true, 96));
+        tracker.record(&get_trade(busy, user, 20, false, 100));
+
+        // The stale mint's trade is outside the window ending at the latest trade overall
+        let top = tracker.top_mints(Duration::from_secs(10), 5);
+        let mints: Vec<_> = top.iter().map(|metrics| metrics.mint).collect();
+        assert_eq!(mints, [busy, quiet]);
+        assert_eq!(top[0].buy_count + top[0].sell_count, 2);
+
+        assert_eq!(
+            tracker.top_mints(Duration::from_secs(100), 1)[0].mint,
+            stale
+        );
+    }
+}
@@ -0,0 +1,380 @@
+//! Volume-weighted average price for the Pump.fun trade stream
+//!
+//! This module computes VWAP from trade events, either directly over a slice of stored
+//! trades or through a rolling tracker fed from the live event stream.
+//!
+//! # VWAP
+//!
+//! Prices are expressed in lamports per token base unit, i.e. the total SOL amount divided by
+//! the total token amount of the included trades. Market-wide VWAP covers every trade of a
+//! mint, while wallet VWAP only covers one wallet's fills and can be restricted to buys or
+//! sells, which gives the wallet's average entry or exit price.
+//!
+//! Windows are measured using the on-chain `timestamp` of each trade, so replaying recorded
+//! events produces exactly the same values as processing them live.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::common::stream::{PumpFunEvent, TradeEvent};
+
+/// Volume-weighted average price over a set of trades
+///
+/// # Fields
+///
+/// * `price` - Average price in lamports per token base unit
+/// * `sol_volume` - Total SOL amount of the included trades, in lamports
+/// * `token_volume` - Total token amount of the included trades, in base units
+/// * `trades` - Number of included trades
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Vwap {
+    pub price: f64,
+    pub sol_volume: u64,
+    pub token_volume: u64,
+    pub trades: u64,
+}
+
+impl Vwap {
+    fn from_totals(sol_volume: u64, token_volume: u64, trades: u64) -> Option<Self> {
+        if token_volume == 0 {
+            return None;
+        }
+        Some(Self {
+            price: sol_volume as f64 / token_volume as f64,
+            sol_volume,
+            token_volume,
+            trades,
+        })
+    }
+}
+
+/// Computes the VWAP of a set of trades
+///
+/// # Arguments
+///
+/// * `trades` - Trades to include
+///
+/// # Returns
+///
+/// The VWAP of the trades, or None if they contain no token volume
+///
+/// # Examples
+///
+/// ```
+/// # use pumpfun::{analytics::vwap, common::stream::TradeEvent};
+/// let trades: Vec<TradeEvent> = Vec::new();
+/// assert!(vwap(&trades).is_none());
+/// ```
+pub fn vwap<'a, I>(trades: I) -> Option<Vwap>
+where
+    I: IntoIterator<Item = &'a TradeEvent>,
+{
+    let mut sol_volume: u64 = 0;
+    let mut token_volume: u64 = 0;
+    let mut count: u64 = 0;
+
+    for trade in trades {
+        sol_volume = sol_volume.saturating_add(trade.sol_amount);
+        token_volume = token_volume.saturating_add(trade.token_amount);
+        count += 1;
+    }
+
+    Vwap::from_totals(sol_volume, token_volume, count)
+}
+
+/// Computes the VWAP of the trades within a time range
+///
+/// # Arguments
+///
+/// * `trades` - Trades to filter
+/// * `start` - Start of the range as a Unix timestamp, exclusive
+/// * `end` - End of the range as a Unix timestamp, inclusive
+///
+/// # Returns
+///
+/// The VWAP of the trades in the range, or None if the range contains no token volume
+pub fn vwap_between<'a, I>(trades: I, start: i64, end: i64) -> Option<Vwap>
+where
+    I: IntoIterator<Item = &'a TradeEvent>,
+{
+    vwap(
+        trades
+            .into_iter()
+            .filter(|trade| trade.timestamp > start && trade.timestamp <= end),
+    )
+}
+
+/// Minimal trade record retained for VWAP calculations
+#[derive(Debug, Clone, Copy)]
+struct VwapSample {
+    timestamp: i64,
+    user: Pubkey,
+    sol_amount: u64,
+    token_amount: u64,
+    is_buy: bool,
+}
+
+/// Rolling VWAP tracker keyed by mint
+///
+/// Trades are retained for the configured retention period, so any window up to that length
+/// can be queried. The tracker is internally synchronized so it can be shared through an
+/// `Arc` and fed directly from a subscription callback.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use pumpfun::analytics::VwapTracker;
+/// # use solana_sdk::pubkey::Pubkey;
+/// # use std::time::Duration;
+/// #
+/// let tracker = VwapTracker::new(Duration::from_secs(60 * 60));
+///
+/// // Trades are fed in from the event stream with `tracker.record_event(&event)`
+/// # let mint = Pubkey::new_unique();
+/// # let wallet = Pubkey::new_unique();
+/// let market = tracker.mint_vwap(&mint, Duration::from_secs(5 * 60));
+/// let entry = tracker.wallet_vwap(&mint, &wallet, Duration::from_secs(60 * 60), Some(true));
+/// ```
+pub struct VwapTracker {
+    retention: i64,
+    trades: Mutex<HashMap<Pubkey, VecDeque<VwapSample>>>,
+}
+
+impl VwapTracker {
+    /// Creates a new VWAP tracker
+    ///
+    /// # Arguments
+    ///
+    /// * `retention` - How long trades are kept, which bounds the longest queryable window
+    ///
+    /// # Returns
+    ///
+    /// A new `VwapTracker` with no recorded trades
+    pub fn new(retention: Duration) -> Self {
+        Self {
+            retention: retention.as_secs() as i64,
+            trades: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Records a parsed program event, ignoring anything that is not a trade
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - Event received from the Pump.fun event stream
+    pub fn record_event(&self, event: &PumpFunEvent) {
+        if let PumpFunEvent::Trade(trade) = event {
+            self.record(trade);
+        }
+    }
+
+    /// Records a trade
+    ///
+    /// # Arguments
+    ///
+    /// * `trade` - Trade event to record
+    pub fn record(&self, trade: &TradeEvent) {
+        let mut trades = match self.trades.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let samples = trades.entry(trade.mint).or_default();
+        samples.push_back(VwapSample {
+            timestamp: trade.timestamp,
+            user: trade.user,
+            sol_amount: trade.sol_amount,
+            token_amount: trade.token_amount,
+            is_buy: trade.is_buy,
+        });
+
+        // Drop samples that have fallen out of the retention period
+        let now = samples.iter().map(|s| s.timestamp).max().unwrap_or(0);
+        while samples
+            .front()
+            .is_some_and(|s| s.timestamp <= now - self.retention)
+        {
+            samples.pop_front();
+        }
+    }
+
+    /// Gets the market-wide VWAP of a mint
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - Token mint to query
+    /// * `window` - Window ending at the mint's most recent trade
+    ///
+    /// # Returns
+    ///
+    /// The VWAP over the window, or None if no trades fall within it
+    pub fn mint_vwap(&self, mint: &Pubkey, window: Duration) -> Option<Vwap> {
+        self.compute(mint, window, |_| true)
+    }
+
+    /// Gets the VWAP of a single wallet's fills for a mint
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - Token mint to query
+    /// * `wallet` - Wallet whose fills are included
+    /// * `window` - Window ending at the mint's most recent trade
+    /// * `is_buy` - Optional side filter: `Some(true)` for buys only, `Some(false)` for sells only
+    ///
+    /// # Returns
+    ///
+    /// The VWAP of the wallet's fills over the window, or None if it has none
+    pub fn wallet_vwap(
+        &self,
+        mint: &Pubkey,
+        wallet: &Pubkey,
+        window: Duration,
+        is_buy: Option<bool>,
+    ) -> Option<Vwap> {
+        self.compute(mint, window, |sample| {
+            sample.user == *wallet && is_buy.is_none_or(|side| sample.is_buy == side)
+        })
+    }
+
+    /// Removes all recorded trades for a mint
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - Token mint to forget
+    pub fn clear(&self, mint: &Pubkey) {
+        let mut trades = match self.trades.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        trades.remove(mint);
+    }
+
+    fn compute<F>(&self, mint: &Pubkey, window: Duration, include: F) -> Option<Vwap>
+    where
+        F: Fn(&VwapSample) -> bool,
+    {
+        let trades = match self.trades.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let samples = trades.get(mint)?;
+        let now = samples.iter().map(|s| s.timestamp).max()?;
+        let start = now - window.as_secs() as i64;
+
+        let mut sol_volume: u64 = 0;
+        let mut token_volume: u64 = 0;
+        let mut count: u64 = 0;
+        for sample in samples.iter().filter(|s| s.timestamp > start && include(s)) {
+            sol_volume = sol_volume.saturating_add(sample.sol_amount);
+            token_volume = token_volume.saturating_add(sample.token_amount);
+            count += 1;
+        }
+
+        Vwap::from_totals(sol_volume, token_volume, count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_trade(
+        mint: Pubkey,
+        user: Pubkey,
+        sol_amount: u64,
+        token_amount: u64,
+        is_buy: bool,
+        ts: i64,
+    ) -> TradeEvent {
+        TradeEvent {
+            mint,
+            sol_amount,
+            token_amount,
+            is_buy,
+            user,
+            timestamp: ts,
+            virtual_sol_reserves: 0,
+            virtual_token_reserves: 0,
+            real_sol_reserves: 0,
+            real_token_reserves: 0,
+            fee_recipient: Pubkey::default(),
+            fee_basis_points: 0,
+            fee: 0,
+            creator: Pubkey::default(),
+            creator_fee_basis_points: 0,
+            creator_fee: 0,
+            track_volume: false,
+            total_unclaimed_tokens: 0,
+            total_claimed_tokens: 0,
+            current_sol_volume: 0,
+            last_update_timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_vwap() {
+        let mint = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let trades = vec![
+            get_trade(mint, user, 100, 1_000, true, 10),
+            get_trade(mint, user, 300, 1_000, true, 20),
+        ];
+
+        let value = vwap(&trades).unwrap();
+        assert_eq!(value.sol_volume, 400);
+        assert_eq!(value.token_volume, 2_000);
+        assert_eq!(value.trades, 2);
+        assert!((value.price - 0.2).abs() < f64::EPSILON);
+
+        let late = vwap_between(&trades, 10, 20).unwrap();
+        assert!((late.price - 0.3).abs() < f64::EPSILON);
+        assert!(vwap_between(&trades, 20, 30).is_none());
+    }
+
+    #[test]
+    fn test_vwap_tracker() {
+        let tracker = VwapTracker::new(Duration::from_secs(100));
+        let mint = Pubkey::new_unique();
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+
+        tracker.record(&get_trade(mint, alice, 100, 1_000, true, 1_000));
+        tracker.record(&get_trade(mint, bob, 400, 1_000, true, 1_040));
+        tracker.record(&get_trade(mint, alice, 300, 1_000, false, 1_050));
+
+        let market = tracker.mint_vwap(&mint, Duration::from_secs(100)).unwrap();
+        assert_eq!(market.trades, 3);
+        assert!((market.price - 800.0 / 3_000.0).abs() < f64::EPSILON);
+
+        // The first trade has left the short window
+        let recent = tracker.mint_vwap(&mint, Duration::from_secs(20)).unwrap();
+        assert_eq!(recent.trades, 2);
+
+        let alice_all = tracker
+            .wallet_vwap(&mint, &alice, Duration::from_secs(100), None)
+            .unwrap();
+        assert!((alice_all.price - 0.2).abs() < f64::EPSILON);
+        let alice_entry = tracker
+            .wallet_vwap(&mint, &alice, Duration::from_secs(100), Some(true))
+            .unwrap();
+        assert!((alice_entry.price - 0.1).abs() < f64::EPSILON);
+        assert!(tracker
+            .wallet_vwap(&mint, &bob, Duration::from_secs(100), Some(false))
+            .is_none());
+
+        // Trades older than the retention period are pruned
+        tracker.record(&get_trade(mint, bob, 1, 10, true, 1_200));
+        let pruned = tracker
+            .mint_vwap(&mint, Duration::from_secs(1_000))
+            .unwrap();
+        assert_eq!(pruned.trades, 1);
+
+        tracker.clear(&mint);
+        assert!(tracker.mint_vwap(&mint, Duration::from_secs(100)).is_none());
+    }
+}
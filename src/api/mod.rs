@@ -0,0 +1,443 @@
+//! Client for the public pump.fun frontend API
+//!
+//! `PumpFunApi` wraps the HTTP endpoints behind the pump.fun website, which serve the
+//! off-chain view of tokens: metadata and social links, market caps, the current king of the
+//! hill, search, price candles, and reply counts. Responses are deserialized into typed
+//! structs so they can be merged with the on-chain accounts and events of this crate.
+//!
+//! The frontend API is not part of the Pump.fun program and may change or rate limit
+//! without notice. Throttled requests fail with `ClientError::RateLimited`, and other
+//! failures with `ClientError::ApiError`.
+
+use std::{str::FromStr, sync::Arc};
+
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    error,
+    utils::http::{self, HttpClient, HttpRequest},
+};
+
+/// Base URL of the public pump.fun frontend API
+pub const FRONTEND_API_URL: &str = "https://frontend-api-v3.pump.fun";
+
+/// Token as listed by the pump.fun frontend
+///
+/// # Fields
+///
+/// * `mint` - Mint of the token
+/// * `name` - Token name
+/// * `symbol` - Token symbol
+/// * `description` - Token description
+/// * `image_uri` - URI of the token image
+/// * `metadata_uri` - URI of the metadata JSON
+/// * `twitter` - Twitter link, if set
+/// * `telegram` - Telegram link, if set
+/// * `website` - Website URL, if set
+/// * `bonding_curve` - Bonding curve account of the token
+/// * `creator` - Creator of the token
+/// * `created_timestamp` - Creation time, in Unix milliseconds
+/// * `complete` - Whether the bonding curve has completed
+/// * `pump_swap_pool` - PumpSwap pool the token migrated to, if any
+/// * `virtual_sol_reserves` - Virtual SOL reserves last seen by the frontend
+/// * `virtual_token_reserves` - Virtual token reserves last seen by the frontend
+/// * `total_supply` - Total token supply, in base units
+/// * `market_cap` - Market cap, in SOL
+/// * `usd_market_cap` - Market cap, in USD
+/// * `king_of_the_hill_timestamp` - Time the token became king of the hill, in Unix
+///   milliseconds, if it did
+/// * `reply_count` - Number of replies on the token's page
+/// * `last_reply` - Time of the last reply, in Unix milliseconds, if any
+/// * `nsfw` - Whether the token is flagged as not safe for work
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Coin {
+    #[serde(deserialize_with = "pubkey")]
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub image_uri: String,
+    #[serde(default)]
+    pub metadata_uri: String,
+    #[serde(default)]
+    pub twitter: Option<String>,
+    #[serde(default)]
+    pub telegram: Option<String>,
+    #[serde(default)]
+    pub website: Option<String>,
+    #[serde(deserialize_with = "pubkey")]
+    pub bonding_curve: Pubkey,
+    #[serde(deserialize_with = "pubkey")]
+    pub creator: Pubkey,
+    pub created_timestamp: i64,
+    #[serde(default)]
+    pub complete: bool,
+    #[serde(default, deserialize_with = "optional_pubkey")]
+    pub pump_swap_pool: Option<Pubkey>,
+    #[serde(default)]
+    pub virtual_sol_reserves: u64,
+    #[serde(default)]
+    pub virtual_token_reserves: u64,
+    #[serde(default)]
+    pub total_supply: u64,
+    #[serde(default)]
+    pub market_cap: f64,
+    #[serde(default)]
+    pub usd_market_cap: f64,
+    #[serde(default)]
+    pub king_of_the_hill_timestamp: Option<i64>,
+    #[serde(default)]
+    pub reply_count: u64,
+    #[serde(default)]
+    pub last_reply: Option<i64>,
+    #[serde(default)]
+    pub nsfw: bool,
+}
+
+/// Price candle of a token
+///
+/// # Fields
+///
+/// * `timestamp` - Start of the candle, in Unix seconds
+/// * `open` - Opening price, in SOL per token
+/// * `high` - Highest price, in SOL per token
+/// * `low` - Lowest price, in SOL per token
+/// * `close` - Closing price, in SOL per token
+/// * `volume` - Traded volume, in token base units
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct Candlestick {
+    pub timestamp: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    #[serde(default)]
+    pub volume: f64,
+}
+
+/// Order of search results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CoinSort {
+    /// Highest market cap first
+    #[default]
+    MarketCap,
+    /// Newest tokens first
+    Created,
+    /// Most recently traded tokens first
+    LastTrade,
+    /// Most recently replied to tokens first
+    LastReply,
+}
+
+impl CoinSort {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::MarketCap => "market_cap",
+            Self::Created => "created_timestamp",
+            Self::LastTrade => "last_trade_timestamp",
+            Self::LastReply => "last_reply",
+        }
+    }
+}
+
+/// Token search parameters
+///
+/// # Fields
+///
+/// * `term` - Text matched against token names, symbols, and mints
+/// * `sort` - Order of the results
+/// * `offset` - Number of results to skip
+/// * `limit` - Maximum number of results
+/// * `include_nsfw` - Whether to include tokens flagged as not safe for work
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchQuery {
+    pub term: String,
+    pub sort: CoinSort,
+    pub offset: u32,
+    pub limit: u32,
+    pub include_nsfw: bool,
+}
+
+impl SearchQuery {
+    /// Creates a search for the first 50 safe-for-work tokens by market cap
+    pub fn new(term: impl Into<String>) -> Self {
+        Self {
+            term: term.into(),
+            sort: CoinSort::MarketCap,
+            offset: 0,
+            limit: 50,
+            include_nsfw: false,
+        }
+    }
+
+    fn to_query_string(&self) -> String {
+        format!(
+            "offset={}&limit={}&sort={}&order=DESC&includeNsfw={}&searchTerm={}",
+            self.offset,
+            self.limit,
+            self.sort.as_str(),
+            self.include_nsfw,
+            encode(&self.term)
+        )
+    }
+}
+
+/// Client for the pump.fun frontend API
+///
+/// # Examples
+///
+/// ```no_run
+/// use pumpfun::api::{PumpFunApi, SearchQuery};
+/// use solana_sdk::pubkey;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let api = PumpFunApi::new()?;
+///
+/// let king = api.king_of_the_hill(false).await?;
+/// println!("King of the hill: {} ({:.0} USD)", king.symbol, king.usd_market_cap);
+///
+/// for coin in api.search(&SearchQuery::new("cat")).await? {
+///     println!("{} {} replies", coin.symbol, coin.reply_count);
+/// }
+///
+/// let mint = pubkey!("TokenM1ntPubk3yXXXXXXXXXXXXXXXXXXXXXXXXXXXX");
+/// let candles = api.candlesticks(&mint, 5, 100).await?;
+/// println!("{} candles", candles.len());
+/// # Ok(())
+/// # }
+/// ```
+pub struct PumpFunApi {
+    http: Arc<dyn HttpClient>,
+    base_url: String,
+}
+
+impl PumpFunApi {
+    /// Creates a client for the public frontend API, using the default HTTP client
+    ///
+    /// # Errors
+    ///
+    /// Returns a ClientError if the HTTP client cannot be created
+    pub fn new() -> Result<Self, error::ClientError> {
+        Ok(Self::with_http_client(http::default_client()))
+    }
+
+    /// Creates a client for the public frontend API, sending requests through the given
+    /// HTTP client
+    pub fn with_http_client(http: Arc<dyn HttpClient>) -> Self {
+        Self {
+            http,
+            base_url: FRONTEND_API_URL.to_string(),
+        }
+    }
+
+    /// Sends requests to the given base URL instead, such as a proxy or mirror
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Fetches a token
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - Mint of the token
+    ///
+    /// # Returns
+    ///
+    /// Returns the token, None if the frontend does not list it, or a ClientError if the
+    /// request fails
+    pub async fn coin(&self, mint: &Pubkey) -> Result<Option<Coin>, error::ClientError> {
+        self.get(&format!("/coins/{}", mint)).await
+    }
+
+    /// Fetches the current king of the hill, the token closest to completing its curve
+    ///
+    /// # Arguments
+    ///
+    /// * `include_nsfw` - Whether tokens flagged as not safe for work can be returned
+    ///
+    /// # Errors
+    ///
+    /// Returns a ClientError if the request fails or no token is king of the hill
+    pub async fn king_of_the_hill(&self, include_nsfw: bool) -> Result<Coin, error::ClientError> {
+        let path = format!("/coins/king-of-the-hill?includeNsfw={}", include_nsfw);
+        self.get(&path)
+            .await?
+            .ok_or_else(|| error::ClientError::ApiError("No king of the hill".to_string()))
+    }
+
+    /// Searches tokens
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Search term, order, and page of the results
+    ///
+    /// # Errors
+    ///
+    /// Returns a ClientError if the request fails
+    pub async fn search(&self, query: &SearchQuery) -> Result<Vec<Coin>, error::ClientError> {
+        let path = format!("/coins?{}", query.to_query_string());
+        Ok(self.get(&path).await?.unwrap_or_default())
+    }
+
+    /// Fetches the price candles of a token, oldest first
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - Mint of the token
+    /// * `timeframe` - Length of a candle, in minutes
+    /// * `limit` - Maximum number of candles
+    ///
+    /// # Errors
+    ///
+    /// Returns a ClientError if the request fails
+    pub async fn candlesticks(
+        &self,
+        mint: &Pubkey,
+        timeframe: u32,
+        limit: u32,
+    ) -> Result<Vec<Candlestick>, error::ClientError> {
+        let path = format!(
+            "/candlesticks/{}?offset=0&limit={}&timeframe={}",
+            mint, limit, timeframe
+        );
+        Ok(self.get(&path).await?.unwrap_or_default())
+    }
+
+    /// Fetches the number of replies on a token's page
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - Mint of the token
+    ///
+    /// # Errors
+    ///
+    /// Returns a ClientError if the request fails or the frontend does not list the token
+    pub async fn reply_count(&self, mint: &Pubkey) -> Result<u64, error::ClientError> {
+        self.coin(mint)
+            .await?
+            .map(|coin| coin.reply_count)
+            .ok_or_else(|| error::ClientError::ApiError(format!("Token {} not found", mint)))
+    }
+
+    /// Sends a GET request, returning None for missing resources and empty responses
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>, error::ClientError> {
+        let uri = format!("{}{}", self.base_url, path);
+        let request = HttpRequest::get(&uri).header("Accept", "application/json");
+        let response = self.http.send(request).await.map_err(|err| {
+            error::ClientError::ApiError(format!("Request to {} failed: {}", uri, err))
+        })?;
+
+        if response.status == 429 {
+            let retry_after = response
+                .header("Retry-After")
+                .and_then(error::parse_retry_after);
+            return Err(error::ClientError::rate_limited(retry_after));
+        }
+        if response.status == 404 {
+            return Ok(None);
+        }
+
+        let text = response.text();
+        if !response.is_success() {
+            return Err(error::ClientError::ApiError(format!(
+                "{} responded with {}: {}",
+                uri, response.status, text
+            )));
+        }
+        if text.trim().is_empty() {
+            return Ok(None);
+        }
+        serde_json::from_str(&text).map(Some).map_err(|err| {
+            error::ClientError::ApiError(format!("Invalid response from {}: {}", uri, err))
+        })
+    }
+}
+
+/// Percent-encodes a query parameter value
+fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Deserializes a base58 public key
+fn pubkey<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    Pubkey::from_str(&value).map_err(serde::de::Error::custom)
+}
+
+/// Deserializes an optional base58 public key, treating null and empty strings as None
+fn optional_pubkey<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Pubkey>, D::Error> {
+    match Option::<String>::deserialize(deserializer)? {
+        Some(value) if !value.is_empty() => Pubkey::from_str(&value)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coin_response() {
+        let mint = Pubkey::new_unique();
+        let json = serde_json::json!({
+            "mint": mint.to_string(),
+            "name": "Token",
+            "symbol": "TKN",
+            "description": "A token",
+            "image_uri": "https://ipfs.io/ipfs/image",
+            "metadata_uri": "https://ipfs.io/ipfs/metadata",
+            "twitter": null,
+            "telegram": "https://t.me/token",
+            "bonding_curve": Pubkey::new_unique().to_string(),
+            "associated_bonding_curve": Pubkey::new_unique().to_string(),
+            "creator": Pubkey::new_unique().to_string(),
+            "created_timestamp": 1_700_000_000_000i64,
+            "raydium_pool": null,
+            "pump_swap_pool": "",
+            "complete": false,
+            "virtual_sol_reserves": 30_000_000_000u64,
+            "virtual_token_reserves": 1_073_000_000_000_000u64,
+            "total_supply": 1_000_000_000_000_000u64,
+            "market_cap": 27.96,
+            "usd_market_cap": 4_194.0,
+            "king_of_the_hill_timestamp": null,
+            "reply_count": 12,
+        });
+        let coin: Coin = serde_json::from_value(json).unwrap();
+        assert_eq!(coin.mint, mint);
+        assert_eq!(coin.telegram.as_deref(), Some("https://t.me/token"));
+        assert_eq!((coin.twitter, coin.website), (None, None));
+        assert_eq!(coin.pump_swap_pool, None);
+        assert_eq!(coin.reply_count, 12);
+        assert_eq!(coin.last_reply, None);
+        assert!(!coin.nsfw);
+    }
+
+    #[test]
+    fn test_search_query() {
+        let query = SearchQuery {
+            sort: CoinSort::Created,
+            include_nsfw: true,
+            ..SearchQuery::new("dog & cat")
+        };
+        assert_eq!(
+            query.to_query_string(),
+            "offset=0&limit=50&sort=created_timestamp&order=DESC&includeNsfw=true&searchTerm=dog%20%26%20cat"
+        );
+    }
+}
@@ -0,0 +1,830 @@
+//! Backtesting of trading strategies over recorded events
+//!
+//! `Backtester` replays `Create`, `Trade`, and `Complete` events in order through a
+//! `Strategy` and simulates the orders it places against the bonding curve state
+//! reconstructed from the events, charging fees and enforcing each order's slippage the way
+//! the program does. The run produces a `BacktestReport` with the simulated trades, the
+//! rejected orders, and the strategy's positions and PnL as tracked by a
+//! `portfolio::PositionManager`.
+//!
+//! # Simulation
+//!
+//! The bonding curve of a token starts from its `CreateEvent` and takes the reserves
+//! reported by each of its `TradeEvent`s. Orders fill against the curve after the event
+//! they were placed on, or after `latency` further trades of the token, so latency turns
+//! into slippage. A simulated trade moves the curve it fills against, but recorded events
+//! that follow reset the reserves, so the strategy's impact does not carry over.
+//!
+//! Events recorded by an `EventStore` can be loaded with `load_events` (requires the
+//! `storage` feature).
+//!
+//! The same reconstruction is available on its own through `reconstruct_curve_state`, which
+//! computes the historical states of a single bonding curve for charts and analysis.
+
+use std::collections::{HashMap, VecDeque};
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    accounts::BondingCurveAccount,
+    common::{
+        stream::{EventContext, PumpFunEvent},
+        trades::TradeSide,
+    },
+    portfolio::{Fill, Position, PositionManager},
+    utils,
+};
+
+#[cfg(feature = "storage")]
+mod store;
+
+#[cfg(feature = "storage")]
+pub use store::*;
+
+/// Order placed by a strategy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    /// Buys tokens for `amount_sol` lamports, excluding fees
+    Buy {
+        mint: Pubkey,
+        amount_sol: u64,
+        slippage_basis_points: u64,
+    },
+    /// Sells `amount_token` tokens, or the whole balance if None
+    Sell {
+        mint: Pubkey,
+        amount_token: Option<u64>,
+        slippage_basis_points: u64,
+    },
+}
+
+impl Order {
+    /// Returns the mint of the order
+    pub fn mint(&self) -> Pubkey {
+        match self {
+            Self::Buy { mint, .. } | Self::Sell { mint, .. } => *mint,
+        }
+    }
+}
+
+/// Market state visible to a strategy
+pub struct Market {
+    curves: HashMap<Pubkey, BondingCurveAccount>,
+    balance: u64,
+    holdings: HashMap<Pubkey, u64>,
+}
+
+impl Market {
+    /// Returns the reconstructed bonding curve of a token
+    pub fn curve(&self, mint: &Pubkey) -> Option<&BondingCurveAccount> {
+        self.curves.get(mint)
+    }
+
+    /// Returns the spot price of a token in lamports per token base unit
+    pub fn price(&self, mint: &Pubkey) -> Option<f64> {
+        self.curves
+            .get(mint)
+            .filter(|curve| curve.virtual_token_reserves > 0)
+            .map(|curve| curve.virtual_sol_reserves as f64 / curve.virtual_token_reserves as f64)
+    }
+
+    /// Returns the strategy's SOL balance, in lamports
+    pub fn balance(&self) -> u64 {
+        self.balance
+    }
+
+    /// Returns the strategy's balance of a token, in base units
+    pub fn tokens(&self, mint: &Pubkey) -> u64 {
+        self.holdings.get(mint).copied().unwrap_or(0)
+    }
+}
+
+/// Trading logic run by a `Backtester`
+pub trait Strategy {
+    /// Handles an event after it has been applied to the market, returning orders to place
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - Transaction and position the event was emitted in
+    /// * `event` - Replayed event
+    /// * `market` - Market state including the event
+    fn on_event(
+        &mut self,
+        context: &EventContext,
+        event: &PumpFunEvent,
+        market: &Market,
+    ) -> Vec<Order>;
+}
+
+/// Settings of a backtest
+///
+/// # Fields
+///
+/// * `initial_balance` - Lamports available to the strategy
+/// * `fee_basis_points` - Protocol and creator fees charged on simulated trades
+/// * `latency` - Number of trades of a token between placing and filling an order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BacktestConfig {
+    pub initial_balance: u64,
+    pub fee_basis_points: u64,
+    pub latency: u32,
+}
+
+impl BacktestConfig {
+    /// Creates settings with Pump.fun's 1% trading fee and no latency
+    pub fn new(initial_balance: u64) -> Self {
+        Self {
+            initial_balance,
+            fee_basis_points: 100,
+            latency: 0,
+        }
+    }
+}
+
+/// Trade simulated for a strategy
+///
+/// # Fields
+///
+/// * `slot` - Slot of the event the trade filled after
+/// * `timestamp` - Unix timestamp of the event the trade filled after
+/// * `mint` - Mint of the traded token
+/// * `side` - Whether tokens were bought or sold
+/// * `sol_amount` - Lamports exchanged, excluding fees
+/// * `token_amount` - Tokens exchanged, in base units
+/// * `fee` - Fees paid, in lamports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulatedTrade {
+    pub slot: u64,
+    pub timestamp: i64,
+    pub mint: Pubkey,
+    pub side: TradeSide,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub fee: u64,
+}
+
+/// Order that could not be filled
+///
+/// # Fields
+///
+/// * `slot` - Slot of the event the order was to fill after
+/// * `order` - Rejected order
+/// * `reason` - Why the order was rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedOrder {
+    pub slot: u64,
+    pub order: Order,
+    pub reason: String,
+}
+
+/// Outcome of a backtest
+///
+/// # Fields
+///
+/// * `trades` - Simulated trades, in order
+/// * `rejected` - Orders that could not be filled, in order
+/// * `final_balance` - Lamports held by the strategy at the end of the run
+/// * `realized_pnl` - Lamports realized by sells
+/// * `positions` - Open positions at the end of the run, valued at the last price seen
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestReport {
+    pub trades: Vec<SimulatedTrade>,
+    pub rejected: Vec<RejectedOrder>,
+    pub final_balance: u64,
+    pub realized_pnl: i64,
+    pub positions: Vec<Position>,
+}
+
+/// Order waiting for its latency to elapse
+struct Pending {
+    order: Order,
+    /// Quote at the time the order was placed: tokens for buys, lamports for sells
+    quote: u64,
+    remaining: u32,
+}
+
+/// Replays recorded events through a strategy
+///
+/// # Examples
+///
+/// ```
+/// use pumpfun::{
+///     backtest::{BacktestConfig, Backtester, Market, Order, Strategy},
+///     common::stream::{EventContext, PumpFunEvent},
+/// };
+/// use solana_sdk::native_token::sol_to_lamports;
+///
+/// /// Buys 0.1 SOL of every new token
+/// struct BuyLaunches;
+///
+/// impl Strategy for BuyLaunches {
+///     fn on_event(&mut self, _: &EventContext, event: &PumpFunEvent, _: &Market) -> Vec<Order> {
+///         match event {
+///             PumpFunEvent::Create(create) => vec![Order::Buy {
+///                 mint: create.mint,
+///                 amount_sol: sol_to_lamports(0.1),
+///                 slippage_basis_points: 500,
+///             }],
+///             _ => Vec::new(),
+///         }
+///     }
+/// }
+///
+/// # let events: Vec<(EventContext, PumpFunEvent)> = Vec::new();
+/// let backtester = Backtester::new(BacktestConfig::new(sol_to_lamports(10.0)));
+/// let report = backtester.run(&mut BuyLaunches, events);
+/// println!("{} trades, realized PnL {}", report.trades.len(), report.realized_pnl);
+/// ```
+pub struct Backtester {
+    config: BacktestConfig,
+}
+
+impl Backtester {
+    /// Creates a backtester with the given settings
+    pub fn new(config: BacktestConfig) -> Self {
+        Self { config }
+    }
+
+    /// Replays events through a strategy
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - Strategy placing orders
+    /// * `events` - Events in the order they were emitted
+    ///
+    /// # Returns
+    ///
+    /// The simulated trades, rejected orders, and resulting positions
+    pub fn run<S: Strategy>(
+        &self,
+        strategy: &mut S,
+        events: impl IntoIterator<Item = (EventContext, PumpFunEvent)>,
+    ) -> BacktestReport {
+        let mut run = Run {
+            config: self.config,
+            market: Market {
+                curves: HashMap::new(),
+                balance: self.config.initial_balance,
+                holdings: HashMap::new(),
+            },
+            positions: PositionManager::new(Pubkey::default()),
+            pending: VecDeque::new(),
+            trades: Vec::new(),
+            rejected: Vec::new(),
+        };
+
+        let mut last_slot = 0;
+        for (context, event) in events {
+            last_slot = context.slot;
+            let Some(mint) = apply_event(&mut run.market.curves, &event) else {
+                continue;
+            };
+            if let Some(price) = run.market.price(&mint) {
+                run.positions.set_price(&mint, price);
+            }
+            let timestamp = event_timestamp(&event);
+            if matches!(event, PumpFunEvent::Trade(_)) {
+                run.fill_pending(&mint, context.slot, timestamp);
+            }
+            for order in strategy.on_event(&context, &event, &run.market) {
+                run.place(order, context.slot, timestamp);
+            }
+        }
+
+        // Orders still waiting for their latency never filled
+        for pending in std::mem::take(&mut run.pending) {
+            run.rejected.push(RejectedOrder {
+                slot: last_slot,
+                order: pending.order,
+                reason: "Events ended before the order filled".to_string(),
+            });
+        }
+
+        BacktestReport {
+            trades: run.trades,
+            rejected: run.rejected,
+            final_balance: run.market.balance,
+            realized_pnl: run.positions.total_realized_pnl(),
+            positions: run.positions.positions(),
+        }
+    }
+}
+
+/// State of a running backtest
+struct Run {
+    config: BacktestConfig,
+    market: Market,
+    positions: PositionManager,
+    pending: VecDeque<Pending>,
+    trades: Vec<SimulatedTrade>,
+    rejected: Vec<RejectedOrder>,
+}
+
+impl Run {
+    fn place(&mut self, order: Order, slot: u64, timestamp: i64) {
+        let quote = match self.quote(&order) {
+            Ok(quote) => quote,
+            Err(reason) => {
+                self.reject(order, slot, reason);
+                return;
+            }
+        };
+        if self.config.latency == 0 {
+            self.fill(order, quote, slot, timestamp);
+        } else {
+            self.pending.push_back(Pending {
+                order,
+                quote,
+                remaining: self.config.latency,
+            });
+        }
+    }
+
+    /// Counts a trade of a token against its pending orders, filling those that are due
+    fn fill_pending(&mut self, mint: &Pubkey, slot: u64, timestamp: i64) {
+        let mut due = Vec::new();
+        self.pending.retain_mut(|pending| {
+            if pending.order.mint() != *mint {
+                return true;
+            }
+            pending.remaining -= 1;
+            if pending.remaining > 0 {
+                return true;
+            }
+            due.push((pending.order, pending.quote));
+            false
+        });
+        for (order, quote) in due {
+            self.fill(order, quote, slot, timestamp);
+        }
+    }
+
+    /// Quotes an order: tokens received for buys, lamports received after fees for sells
+    fn quote(&self, order: &Order) -> Result<u64, String> {
+        let curve = self.tradable_curve(&order.mint())?;
+        match *order {
+            Order::Buy { amount_sol, .. } => {
+                curve.get_buy_price(amount_sol).map_err(str::to_string)
+            }
+            Order::Sell {
+                mint, amount_token, ..
+            } => {
+                let amount = self.sell_amount(&mint, amount_token)?;
+                curve
+                    .get_sell_price(amount, self.config.fee_basis_points)
+                    .map_err(str::to_string)
+            }
+        }
+    }
+
+    fn fill(&mut self, order: Order, quote: u64, slot: u64, timestamp: i64) {
+        if let Err(reason) = self.try_fill(order, quote, slot, timestamp) {
+            self.reject(order, slot, reason);
+        }
+    }
+
+    fn try_fill(
+        &mut self,
+        order: Order,
+        quote: u64,
+        slot: u64,
+        timestamp: i64,
+    ) -> Result<(), String> {
+        let mint = order.mint();
+        let fee_basis_points = self.config.fee_basis_points;
+        let mut curve = self.tradable_curve(&mint)?.clone();
+        let trade = match order {
+            Order::Buy {
+                amount_sol,
+                slippage_basis_points,
+                ..
+            } => {
+                // The program buys the quoted tokens for at most the maximum SOL cost
+                let tokens = quote;
+                let sol_amount = sol_for_tokens(&curve, tokens)
+                    .ok_or_else(|| "Not enough tokens left on the curve".to_string())?;
+                let max_sol_cost =
+                    utils::calculate_with_slippage_buy(amount_sol, slippage_basis_points);
+                if sol_amount > max_sol_cost {
+                    return Err(format!(
+                        "Slippage exceeded: cost {} lamports, maximum {}",
+                        sol_amount, max_sol_cost
+                    ));
+                }
+                let fee = sol_amount * fee_basis_points / 10_000;
+                let cost = sol_amount + fee;
+                if cost > self.market.balance {
+                    return Err(format!(
+                        "Insufficient balance: cost {} lamports, balance {}",
+                        cost, self.market.balance
+                    ));
+                }
+                self.market.balance -= cost;
+                *self.market.holdings.entry(mint).or_default() += tokens;
+                curve.virtual_sol_reserves += sol_amount;
+                curve.virtual_token_reserves -= tokens;
+                curve.real_sol_reserves += sol_amount;
+                curve.real_token_reserves = curve.real_token_reserves.saturating_sub(tokens);
+                SimulatedTrade {
+                    slot,
+                    timestamp,
+                    mint,
+                    side: TradeSide::Buy,
+                    sol_amount,
+                    token_amount: tokens,
+                    fee,
+                }
+            }
+            Order::Sell {
+                amount_token,
+                slippage_basis_points,
+                ..
+            } => {
+                let tokens = self.sell_amount(&mint, amount_token)?;
+                let gross = curve.get_sell_price(tokens, 0).map_err(str::to_string)?;
+                let proceeds = curve
+                    .get_sell_price(tokens, fee_basis_points)
+                    .map_err(str::to_string)?;
+                let min_sol_output =
+                    utils::calculate_with_slippage_sell(quote, slippage_basis_points);
+                if proceeds < min_sol_output {
+                    return Err(format!(
+                        "Slippage exceeded: output {} lamports, minimum {}",
+                        proceeds, min_sol_output
+                    ));
+                }
+                self.market.balance += proceeds;
+                if let Some(held) = self.market.holdings.get_mut(&mint) {
+                    *held -= tokens;
+                }
+                curve.virtual_sol_reserves -= gross;
+                curve.virtual_token_reserves += tokens;
+                curve.real_sol_reserves = curve.real_sol_reserves.saturating_sub(gross);
+                curve.real_token_reserves += tokens;
+                SimulatedTrade {
+                    slot,
+                    timestamp,
+                    mint,
+                    side: TradeSide::Sell,
+                    sol_amount: gross,
+                    token_amount: tokens,
+                    fee: gross - proceeds,
+                }
+            }
+        };
+
+        self.market.curves.insert(mint, curve);
+        if let Some(price) = self.market.price(&mint) {
+            self.positions.set_price(&mint, price);
+        }
+        self.positions.record_fill(Fill {
+            mint,
+            side: trade.side,
+            token_amount: trade.token_amount,
+            sol_amount: trade.sol_amount,
+            fee: trade.fee,
+            timestamp,
+        });
+        self.trades.push(trade);
+        Ok(())
+    }
+
+    fn tradable_curve(&self, mint: &Pubkey) -> Result<&BondingCurveAccount, String> {
+        match self.market.curves.get(mint) {
+            Some(curve) if curve.complete => Err("Bonding curve is complete".to_string()),
+            Some(curve) => Ok(curve),
+            None => Err(format!("No bonding curve seen for {}", mint)),
+        }
+    }
+
+    fn sell_amount(&self, mint: &Pubkey, amount_token: Option<u64>) -> Result<u64, String> {
+        let held = self.market.tokens(mint);
+        let amount = amount_token.unwrap_or(held);
+        if amount == 0 || amount > held {
+            return Err(format!(
+                "Insufficient tokens: selling {}, holding {}",
+                amount, held
+            ));
+        }
+        Ok(amount)
+    }
+
+    fn reject(&mut self, order: Order, slot: u64, reason: String) {
+        self.rejected.push(RejectedOrder {
+            slot,
+            order,
+            reason,
+        });
+    }
+}
+
+/// Reconstructs the historical states of a token's bonding curve from its events
+///
+/// Starting from an optional snapshot, the curve is reset by the token's `CreateEvent`,
+/// takes the reserves reported by each of its `TradeEvent`s, and is marked complete by its
+/// `CompleteEvent`. Events of other tokens are ignored, so a mixed event log can be passed
+/// as is. Trades seen without a snapshot or creation start a curve whose token supply is
+/// unknown and left at zero.
+///
+/// # Arguments
+///
+/// * `mint` - Mint of the token
+/// * `initial` - State of the curve before the first event, if known
+/// * `events` - Events in the order they were emitted
+///
+/// # Returns
+///
+/// The slot and state of the curve after each event of the token
+///
+/// # Examples
+///
+/// ```
+/// use pumpfun::{backtest::reconstruct_curve_state, common::stream::{EventContext, PumpFunEvent}};
+/// use solana_sdk::pubkey::Pubkey;
+///
+/// # let events: Vec<(EventContext, PumpFunEvent)> = Vec::new();
+/// let mint = Pubkey::new_unique();
+/// for (slot, curve) in reconstruct_curve_state(&mint, None, &events) {
+///     let price = curve.virtual_sol_reserves as f64 / curve.virtual_token_reserves as f64;
+///     println!("{}: {} lamports per token", slot, price);
+/// }
+/// ```
+pub fn reconstruct_curve_state<'a>(
+    mint: &Pubkey,
+    initial: Option<BondingCurveAccount>,
+    events: impl IntoIterator<Item = &'a (EventContext, PumpFunEvent)>,
+) -> Vec<(u64, BondingCurveAccount)> {
+    let mut curves = HashMap::new();
+    if let Some(curve) = initial {
+        curves.insert(*mint, curve);
+    }
+    let mut states = Vec::new();
+    for (context, event) in events {
+        if event_mint(event) != Some(*mint) {
+            continue;
+        }
+        apply_event(&mut curves, event);
+        if let Some(curve) = curves.get(mint) {
+            states.push((context.slot, curve.clone()));
+        }
+    }
+    states
+}
+
+/// Applies an event to the bonding curves it changes, returning the affected mint
+fn apply_event(
+    curves: &mut HashMap<Pubkey, BondingCurveAccount>,
+    event: &PumpFunEvent,
+) -> Option<Pubkey> {
+    match event {
+        PumpFunEvent::Create(create) => {
+            curves.insert(
+                create.mint,
+                BondingCurveAccount::new(
+                    0,
+                    create.virtual_token_reserves,
+                    create.virtual_sol_reserves,
+                    create.real_token_reserves,
+                    0,
+                    create.token_total_supply,
+                    false,
+                    create.creator,
+                ),
+            );
+            Some(create.mint)
+        }
+        PumpFunEvent::Trade(trade) => {
+            let curve = curves.entry(trade.mint).or_insert_with(|| {
+                BondingCurveAccount::new(0, 0, 0, 0, 0, 0, false, trade.creator)
+            });
+            curve.virtual_token_reserves = trade.virtual_token_reserves;
+            curve.virtual_sol_reserves = trade.virtual_sol_reserves;
+            curve.real_token_reserves = trade.real_token_reserves;
+            curve.real_sol_reserves = trade.real_sol_reserves;
+            Some(trade.mint)
+        }
+        PumpFunEvent::Complete(complete) => {
+            if let Some(curve) = curves.get_mut(&complete.mint) {
+                curve.complete = true;
+            }
+            Some(complete.mint)
+        }
+        _ => None,
+    }
+}
+
+fn event_mint(event: &PumpFunEvent) -> Option<Pubkey> {
+    match event {
+        PumpFunEvent::Create(create) => Some(create.mint),
+        PumpFunEvent::Trade(trade) => Some(trade.mint),
+        PumpFunEvent::Complete(complete) => Some(complete.mint),
+        _ => None,
+    }
+}
+
+fn event_timestamp(event: &PumpFunEvent) -> i64 {
+    match event {
+        PumpFunEvent::Create(create) => create.timestamp,
+        PumpFunEvent::Trade(trade) => trade.timestamp,
+        PumpFunEvent::Complete(complete) => complete.timestamp,
+        _ => 0,
+    }
+}
+
+/// Returns the lamports the program charges, excluding fees, to buy `tokens` from a curve
+fn sol_for_tokens(curve: &BondingCurveAccount, tokens: u64) -> Option<u64> {
+    if tokens == 0 || tokens >= curve.virtual_token_reserves || tokens > curve.real_token_reserves {
+        return None;
+    }
+    let sol = curve.virtual_sol_reserves as u128 * tokens as u128
+        / (curve.virtual_token_reserves - tokens) as u128
+        + 1;
+    u64::try_from(sol).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::stream::{CompleteEvent, CreateEvent, TradeEvent};
+
+    fn context(slot: u64) -> EventContext {
+        EventContext {
+            signature: format!("sig{}", slot),
+            slot,
+            block_time: None,
+            index: 0,
+        }
+    }
+
+    fn create(mint: Pubkey) -> PumpFunEvent {
+        PumpFunEvent::Create(CreateEvent {
+            name: "Token".to_string(),
+            symbol: "TKN".to_string(),
+            uri: String::new(),
+            mint,
+            bonding_curve: Pubkey::new_unique(),
+            user: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            timestamp: 0,
+            virtual_token_reserves: 1_073_000_000_000_000,
+            virtual_sol_reserves: 30_000_000_000,
+            real_token_reserves: 793_100_000_000_000,
+            token_total_supply: 1_000_000_000_000_000,
+        })
+    }
+
+    /// Trade of another wallet moving the curve to the given SOL reserves
+    fn trade(mint: Pubkey, virtual_sol_reserves: u64) -> PumpFunEvent {
+        let k = 30_000_000_000u128 * 1_073_000_000_000_000u128;
+        let virtual_token_reserves = (k / virtual_sol_reserves as u128) as u64;
+        PumpFunEvent::Trade(TradeEvent {
+            mint,
+            sol_amount: 0,
+            token_amount: 0,
+            is_buy: true,
+            user: Pubkey::new_unique(),
+            timestamp: 1,
+            virtual_sol_reserves,
+            virtual_token_reserves,
+            real_sol_reserves: virtual_sol_reserves - 30_000_000_000,
+            real_token_reserves: virtual_token_reserves - 279_900_000_000_000,
+            fee_recipient: Pubkey::new_unique(),
+            fee_basis_points: 95,
+            fee: 0,
+            creator: Pubkey::new_unique(),
+            creator_fee_basis_points: 5,
+            creator_fee: 0,
+            track_volume: false,
+            total_unclaimed_tokens: 0,
+            total_claimed_tokens: 0,
+            current_sol_volume: 0,
+            last_update_timestamp: 0,
+        })
+    }
+
+    /// Buys 1 SOL of every launch and sells everything on the first trade doubling the price
+    struct Flip {
+        entry: HashMap<Pubkey, f64>,
+    }
+
+    impl Strategy for Flip {
+        fn on_event(
+            &mut self,
+            _: &EventContext,
+            event: &PumpFunEvent,
+            market: &Market,
+        ) -> Vec<Order> {
+            match event {
+                PumpFunEvent::Create(create) => {
+                    self.entry
+                        .insert(create.mint, market.price(&create.mint).unwrap());
+                    vec![Order::Buy {
+                        mint: create.mint,
+                        amount_sol: 1_000_000_000,
+                        slippage_basis_points: 100,
+                    }]
+                }
+                PumpFunEvent::Trade(trade) if market.tokens(&trade.mint) > 0 => {
+                    let price = market.price(&trade.mint).unwrap();
+                    if price < self.entry[&trade.mint] * 2.0 {
+                        return Vec::new();
+                    }
+                    vec![Order::Sell {
+                        mint: trade.mint,
+                        amount_token: None,
+                        slippage_basis_points: 100,
+                    }]
+                }
+                _ => Vec::new(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_backtest() {
+        let mint = Pubkey::new_unique();
+        let events = vec![
+            (context(1), create(mint)),
+            (context(2), trade(mint, 35_000_000_000)),
+            (context(3), trade(mint, 45_000_000_000)),
+        ];
+        let mut strategy = Flip {
+            entry: HashMap::new(),
+        };
+        let report = Backtester::new(BacktestConfig::new(2_000_000_000)).run(&mut strategy, events);
+
+        assert!(report.rejected.is_empty());
+        assert_eq!(report.trades.len(), 2);
+        let buy = report.trades[0];
+        assert_eq!(buy.side, TradeSide::Buy);
+        assert_eq!(buy.fee, buy.sol_amount / 100);
+        let sell = report.trades[1];
+        assert_eq!((sell.side, sell.slot), (TradeSide::Sell, 3));
+        assert_eq!(sell.token_amount, buy.token_amount);
+        assert!(report.positions.is_empty());
+
+        // Selling at more than twice the entry price doubles the SOL less fees
+        let spent = buy.sol_amount + buy.fee;
+        let received = sell.sol_amount - sell.fee;
+        assert_eq!(report.realized_pnl, received as i64 - spent as i64);
+        assert_eq!(report.final_balance, 2_000_000_000 - spent + received);
+        assert!(received > 2 * spent - spent / 10);
+    }
+
+    #[test]
+    fn test_reconstruct_curve_state() {
+        let mint = Pubkey::new_unique();
+        let events = vec![
+            (context(1), create(mint)),
+            (context(2), trade(Pubkey::new_unique(), 50_000_000_000)),
+            (context(3), trade(mint, 40_000_000_000)),
+            (
+                context(4),
+                PumpFunEvent::Complete(CompleteEvent {
+                    user: Pubkey::new_unique(),
+                    mint,
+                    bonding_curve: Pubkey::new_unique(),
+                    timestamp: 2,
+                }),
+            ),
+        ];
+
+        let states = reconstruct_curve_state(&mint, None, &events);
+        let slots: Vec<u64> = states.iter().map(|(slot, _)| *slot).collect();
+        assert_eq!(slots, [1, 3, 4]);
+        assert_eq!(states[0].1.virtual_sol_reserves, 30_000_000_000);
+        assert_eq!(states[0].1.token_total_supply, 1_000_000_000_000_000);
+        assert_eq!(states[1].1.virtual_sol_reserves, 40_000_000_000);
+        assert_eq!(states[1].1.real_sol_reserves, 10_000_000_000);
+        assert_eq!(states[1].1.token_total_supply, 1_000_000_000_000_000);
+        assert!(!states[1].1.complete && states[2].1.complete);
+
+        // Without a creation, trades apply to the given snapshot
+        let snapshot = states[0].1.clone();
+        let states = reconstruct_curve_state(&mint, Some(snapshot), &events[1..3]);
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].1.token_total_supply, 1_000_000_000_000_000);
+    }
+
+    #[test]
+    fn test_backtest_latency_slippage() {
+        let mint = Pubkey::new_unique();
+        let events = vec![
+            (context(1), create(mint)),
+            (context(2), trade(mint, 40_000_000_000)),
+        ];
+        let mut strategy = Flip {
+            entry: HashMap::new(),
+        };
+        let config = BacktestConfig {
+            latency: 1,
+            ..BacktestConfig::new(2_000_000_000)
+        };
+        // The buy fills after a trade that moved the price far beyond its 1% slippage
+        let report = Backtester::new(config).run(&mut strategy, events);
+        assert!(report.trades.is_empty());
+        assert_eq!(report.rejected.len(), 1);
+        assert!(report.rejected[0].reason.starts_with("Slippage exceeded"));
+        assert_eq!(report.final_balance, 2_000_000_000);
+    }
+}
@@ -0,0 +1,105 @@
+//! Loading of backtest events from an `EventStore`
+
+use crate::{
+    accounts::GlobalAccount,
+    common::{
+        storage::{EventQuery, EventStore, StoredCreate, StoredTrade},
+        stream::{CompleteEvent, CreateEvent, EventContext, PumpFunEvent, TradeEvent},
+    },
+    error,
+};
+
+/// Loads stored events matching a query in the order they were emitted
+///
+/// Stored rows keep only what backtests need, so the reserves missing from them are derived
+/// from the global account: creations start from the initial reserves, and the real
+/// reserves of trades are their virtual reserves less the virtual liquidity every curve
+/// starts with. Fields that are not stored, such as token metadata of trades and fee
+/// recipients, are left empty.
+///
+/// # Arguments
+///
+/// * `store` - Store to read from
+/// * `query` - Filters applied to the creations, trades, and completions
+/// * `global` - Global account whose initial reserves the stored tokens were created with
+///
+/// # Returns
+///
+/// Returns the events ordered by slot and position, or a ClientError if the store cannot be
+/// read
+pub async fn load_events(
+    store: &EventStore,
+    query: &EventQuery,
+    global: &GlobalAccount,
+) -> Result<Vec<(EventContext, PumpFunEvent)>, error::ClientError> {
+    let mut events: Vec<(EventContext, PumpFunEvent)> = Vec::new();
+    for create in store.creates(query).await? {
+        events.push(create_event(create, global));
+    }
+    for trade in store.trades(query).await? {
+        events.push(trade_event(trade, global));
+    }
+    for complete in store.completes(query).await? {
+        let event = PumpFunEvent::Complete(CompleteEvent {
+            user: complete.user,
+            mint: complete.mint,
+            bonding_curve: complete.bonding_curve,
+            timestamp: complete.timestamp,
+        });
+        events.push((complete.context, event));
+    }
+    events.sort_by(|(a, _), (b, _)| {
+        (a.slot, &a.signature, a.index).cmp(&(b.slot, &b.signature, b.index))
+    });
+    Ok(events)
+}
+
+fn create_event(create: StoredCreate, global: &GlobalAccount) -> (EventContext, PumpFunEvent) {
+    let event = PumpFunEvent::Create(CreateEvent {
+        name: create.name,
+        symbol: create.symbol,
+        uri: create.uri,
+        mint: create.mint,
+        bonding_curve: create.bonding_curve,
+        user: create.user,
+        creator: create.creator,
+        timestamp: create.timestamp,
+        virtual_token_reserves: global.initial_virtual_token_reserves,
+        virtual_sol_reserves: global.initial_virtual_sol_reserves,
+        real_token_reserves: global.initial_real_token_reserves,
+        token_total_supply: global.token_total_supply,
+    });
+    (create.context, event)
+}
+
+fn trade_event(trade: StoredTrade, global: &GlobalAccount) -> (EventContext, PumpFunEvent) {
+    let virtual_tokens = global
+        .initial_virtual_token_reserves
+        .saturating_sub(global.initial_real_token_reserves);
+    let event = PumpFunEvent::Trade(TradeEvent {
+        mint: trade.mint,
+        sol_amount: trade.sol_amount,
+        token_amount: trade.token_amount,
+        is_buy: trade.is_buy,
+        user: trade.user,
+        timestamp: trade.timestamp,
+        virtual_sol_reserves: trade.virtual_sol_reserves,
+        virtual_token_reserves: trade.virtual_token_reserves,
+        real_sol_reserves: trade
+            .virtual_sol_reserves
+            .saturating_sub(global.initial_virtual_sol_reserves),
+        real_token_reserves: trade.virtual_token_reserves.saturating_sub(virtual_tokens),
+        fee_recipient: Default::default(),
+        fee_basis_points: global.fee_basis_points,
+        fee: trade.fee,
+        creator: Default::default(),
+        creator_fee_basis_points: global.creator_fee_basis_points,
+        creator_fee: trade.creator_fee,
+        track_volume: false,
+        total_unclaimed_tokens: 0,
+        total_claimed_tokens: 0,
+        current_sol_volume: 0,
+        last_update_timestamp: 0,
+    });
+    (trade.context, event)
+}
@@ -0,0 +1,352 @@
+//! Builder for configuring a `PumpFun` client
+//!
+//! `PumpFunBuilder` collects every client setting in one place, including an externally
+//! created RPC client so several components can share one connection pool, the RPC timeout
+//! and commitment, trading defaults, the retry policy, and the transaction sender.
+//!
+//! `PumpFun::new` is equivalent to `PumpFun::builder(payer, cluster).build()`.
+
+use std::{sync::Arc, time::Duration};
+
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, signature::Keypair};
+
+use crate::{
+    common::{
+        failover::FailoverRpcSender,
+        risk::{RiskLimits, RiskManager},
+        types::{AtaPolicy, Cluster, PriorityFee, ProgramVersion, RetryPolicy},
+    },
+    sender::{MultiRpcSender, RpcSender, TransactionHook, TransactionSender},
+    utils::{
+        fees::FeeSchedule,
+        upload::{MetadataUploader, PumpFunUploader},
+    },
+    PumpFun,
+};
+
+/// Slippage applied to trades when none is given, in basis points (5%)
+pub const DEFAULT_SLIPPAGE_BASIS_POINTS: u64 = 500;
+
+/// Per-endpoint request timeout used for RPC failover when no timeout is set, matching the
+/// Solana RPC client's default
+const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Builder for a `PumpFun` client
+///
+/// # Examples
+///
+/// ```no_run
+/// use pumpfun::{
+///     common::types::{Cluster, PriorityFee, RetryPolicy},
+///     PumpFun,
+/// };
+/// use solana_client::nonblocking::rpc_client::RpcClient;
+/// use solana_sdk::{commitment_config::CommitmentConfig, signature::Keypair};
+/// use std::{sync::Arc, time::Duration};
+///
+/// let payer = Arc::new(Keypair::new());
+/// let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+///
+/// // Reuse an RPC client that the rest of the application already holds
+/// let rpc = Arc::new(RpcClient::new(cluster.rpc.http.clone()));
+///
+/// let client = PumpFun::builder(payer, cluster)
+///     .rpc_client(rpc)
+///     .default_slippage(300)
+///     .priority_fee(PriorityFee::new(Some(200_000), Some(50_000)))
+///     .retry_policy(RetryPolicy::none())
+///     .build();
+/// ```
+pub struct PumpFunBuilder {
+    payer: Arc<Keypair>,
+    cluster: Cluster,
+    rpc: Option<Arc<RpcClient>>,
+    timeout: Option<Duration>,
+    default_slippage_basis_points: u64,
+    retry_policy: RetryPolicy,
+    sender: Option<Arc<dyn TransactionSender>>,
+    preflight_checks: bool,
+    risk_limits: Option<RiskLimits>,
+    metadata_uploader: Option<Arc<dyn MetadataUploader>>,
+    ata_policy: AtaPolicy,
+    hooks: Vec<Arc<dyn TransactionHook>>,
+    fee_schedule: FeeSchedule,
+    program_version: ProgramVersion,
+}
+
+impl PumpFunBuilder {
+    /// Creates a new builder
+    ///
+    /// # Arguments
+    ///
+    /// * `payer` - Keypair used to sign and pay for transactions
+    /// * `cluster` - Solana cluster configuration including RPC endpoints and transaction parameters
+    ///
+    /// # Returns
+    ///
+    /// A builder with the same defaults as `PumpFun::new`
+    pub fn new(payer: Arc<Keypair>, cluster: Cluster) -> Self {
+        Self {
+            payer,
+            cluster,
+            rpc: None,
+            timeout: None,
+            default_slippage_basis_points: DEFAULT_SLIPPAGE_BASIS_POINTS,
+            retry_policy: RetryPolicy::default(),
+            sender: None,
+            preflight_checks: false,
+            risk_limits: None,
+            metadata_uploader: None,
+            ata_policy: AtaPolicy::default(),
+            hooks: Vec::new(),
+            fee_schedule: FeeSchedule::default(),
+            program_version: ProgramVersion::default(),
+        }
+    }
+
+    /// Uses an existing RPC client instead of creating one from the cluster's HTTP endpoint
+    ///
+    /// The timeout set with `timeout` does not apply to an external client.
+    ///
+    /// # Arguments
+    ///
+    /// * `rpc` - RPC client to share with the `PumpFun` client
+    pub fn rpc_client(mut self, rpc: Arc<RpcClient>) -> Self {
+        self.rpc = Some(rpc);
+        self
+    }
+
+    /// Sets the timeout for RPC requests made by the client's own RPC client
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum duration of a single RPC request
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the commitment level for requests and confirmations
+    ///
+    /// # Arguments
+    ///
+    /// * `commitment` - Commitment level, overriding the one in the cluster configuration
+    pub fn commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.cluster.commitment = commitment;
+        self
+    }
+
+    /// Sets the slippage applied to trades when none is given
+    ///
+    /// # Arguments
+    ///
+    /// * `basis_points` - Default maximum slippage in basis points (1 bp = 0.01%)
+    pub fn default_slippage(mut self, basis_points: u64) -> Self {
+        self.default_slippage_basis_points = basis_points;
+        self
+    }
+
+    /// Sets the priority fee applied to transactions when none is given
+    ///
+    /// # Arguments
+    ///
+    /// * `priority_fee` - Default priority fee, overriding the one in the cluster configuration
+    pub fn priority_fee(mut self, priority_fee: PriorityFee) -> Self {
+        self.cluster.priority_fee = priority_fee;
+        self
+    }
+
+    /// Sets the policy for retrying transactions that could not be confirmed
+    ///
+    /// # Arguments
+    ///
+    /// * `retry_policy` - Policy applied by every method that sends a transaction
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets the sender used to submit transactions
+    ///
+    /// # Arguments
+    ///
+    /// * `sender` - Sender used instead of the default RPC or multi-RPC sender
+    pub fn sender(mut self, sender: Arc<dyn TransactionSender>) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// Enables or disables client-side checks before trading
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to run the checks; see `PumpFun::with_preflight_checks`
+    pub fn preflight_checks(mut self, enabled: bool) -> Self {
+        self.preflight_checks = enabled;
+        self
+    }
+
+    /// Sets how trades handle the user's associated token account
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - Policy applied to every trade; see `PumpFun::with_ata_policy`
+    pub fn ata_policy(mut self, policy: AtaPolicy) -> Self {
+        self.ata_policy = policy;
+        self
+    }
+
+    /// Enforces risk limits on every buy
+    ///
+    /// # Arguments
+    ///
+    /// * `limits` - Limits to enforce; see `PumpFun::with_risk_limits`
+    pub fn risk_limits(mut self, limits: RiskLimits) -> Self {
+        self.risk_limits = Some(limits);
+        self
+    }
+
+    /// Uploads the metadata of created tokens through the given provider
+    ///
+    /// # Arguments
+    ///
+    /// * `uploader` - Provider used instead of the pump.fun API; see
+    ///   `PumpFun::with_metadata_uploader`
+    pub fn metadata_uploader(mut self, uploader: Arc<dyn MetadataUploader>) -> Self {
+        self.metadata_uploader = Some(uploader);
+        self
+    }
+
+    /// Registers a hook invoked around every transaction the client sends
+    ///
+    /// # Arguments
+    ///
+    /// * `hook` - Hook to add after the already registered ones; see `PumpFun::with_hook`
+    pub fn hook(mut self, hook: Arc<dyn TransactionHook>) -> Self {
+        self.hooks.push(hook);
+        self
+    }
+
+    /// Quotes trades with volume-based fee tiers
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule` - Fee tiers applied to every trade; see `PumpFun::with_fee_schedule`
+    pub fn fee_schedule(mut self, schedule: FeeSchedule) -> Self {
+        self.fee_schedule = schedule;
+        self
+    }
+
+    /// Targets a version of the Pump.fun program with buys and sells
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Version targeted by every trade; see `PumpFun::with_program_version`
+    pub fn program_version(mut self, version: ProgramVersion) -> Self {
+        self.program_version = version;
+        self
+    }
+
+    /// Builds the client
+    ///
+    /// # Returns
+    ///
+    /// A `PumpFun` client configured with the builder's settings
+    pub fn build(self) -> PumpFun {
+        let cluster = self.cluster;
+
+        // Create Solana RPC Client with HTTP endpoint unless one was provided
+        let rpc = self.rpc.unwrap_or_else(|| {
+            Arc::new(if !cluster.fallback_endpoints.is_empty() {
+                // Fail over from the primary endpoint to the fallbacks in order
+                let mut urls = vec![cluster.rpc.http.clone()];
+                urls.extend(cluster.fallback_endpoints.iter().cloned());
+                FailoverRpcSender::new(&urls, self.timeout.unwrap_or(DEFAULT_RPC_TIMEOUT))
+                    .into_rpc_client(cluster.commitment)
+            } else {
+                match self.timeout {
+                    Some(timeout) => RpcClient::new_with_timeout_and_commitment(
+                        cluster.rpc.http.clone(),
+                        timeout,
+                        cluster.commitment,
+                    ),
+                    None => {
+                        RpcClient::new_with_commitment(cluster.rpc.http.clone(), cluster.commitment)
+                    }
+                }
+            })
+        });
+
+        // Send through the same RPC client, or broadcast when extra endpoints are configured
+        let sender: Arc<dyn TransactionSender> = match self.sender {
+            Some(sender) => sender,
+            None if cluster.send_endpoints.is_empty() => Arc::new(RpcSender::new(rpc.clone())),
+            None => {
+                let mut rpcs = vec![rpc.clone()];
+                rpcs.extend(cluster.send_endpoints.iter().map(|url| {
+                    Arc::new(RpcClient::new_with_commitment(
+                        url.clone(),
+                        cluster.commitment,
+                    ))
+                }));
+                Arc::new(MultiRpcSender::from_clients(rpcs))
+            }
+        };
+
+        PumpFun {
+            payer: self.payer,
+            rpc,
+            cluster,
+            sender,
+            retry_policy: self.retry_policy,
+            default_slippage_basis_points: self.default_slippage_basis_points,
+            preflight_checks: self.preflight_checks,
+            fixed_blockhash: None,
+            #[cfg(feature = "versioned-tx")]
+            lookup_tables: Vec::new(),
+            risk: self
+                .risk_limits
+                .map(|limits| Arc::new(RiskManager::new(limits))),
+            metadata_uploader: self
+                .metadata_uploader
+                .unwrap_or_else(|| Arc::new(PumpFunUploader::new())),
+            ata_policy: self.ata_policy,
+            hooks: self.hooks,
+            fee_schedule: self.fee_schedule,
+            program_version: self.program_version,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::PriorityFee;
+
+    #[test]
+    fn test_builder() {
+        let payer = Arc::new(Keypair::new());
+        let cluster = Cluster::localnet(CommitmentConfig::confirmed(), PriorityFee::default());
+        let rpc = Arc::new(RpcClient::new_mock("succeeds".to_string()));
+
+        let client = PumpFun::builder(payer, cluster)
+            .rpc_client(rpc.clone())
+            .commitment(CommitmentConfig::finalized())
+            .default_slippage(100)
+            .priority_fee(PriorityFee::new(Some(1), Some(2)))
+            .retry_policy(RetryPolicy::none())
+            .ata_policy(AtaPolicy::AssumeExists)
+            .build();
+
+        assert!(Arc::ptr_eq(&client.rpc, &rpc));
+        assert_eq!(client.cluster.commitment, CommitmentConfig::finalized());
+        assert_eq!(
+            client.cluster.priority_fee,
+            PriorityFee::new(Some(1), Some(2))
+        );
+        assert_eq!(client.default_slippage_basis_points, 100);
+        assert_eq!(client.retry_policy, RetryPolicy::none());
+        assert_eq!(client.ata_policy, AtaPolicy::AssumeExists);
+    }
+}
@@ -0,0 +1,516 @@
+//! Hot-reloadable configuration for long-running strategies
+//!
+//! This module provides `HotConfig`, a shared configuration cell that can be updated at
+//! runtime from a JSON config file or from an admin channel, without restarting the process.
+//!
+//! Every applied update produces a `ConfigDiff` listing the changed fields, which is returned
+//! to the caller and broadcast to subscribers so operators can see exactly what changed.
+//! Components following `HotConfig::subscribe`, such as `DcaScheduler` and `TpSlMonitor`
+//! with `with_config_updates`, read the latest value on each decision, so new thresholds
+//! take effect immediately.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use tokio::{
+    sync::{broadcast, mpsc, watch},
+    task::JoinHandle,
+};
+
+use crate::error;
+
+/// A single changed field in a configuration update
+///
+/// # Fields
+///
+/// * `path` - Dotted path of the changed field (e.g. `filters.max_dev_buy`)
+/// * `old` - Previous value, or `Value::Null` if the field was added
+/// * `new` - New value, or `Value::Null` if the field was removed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigChange {
+    pub path: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+/// The set of changes applied by a configuration update
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfigDiff {
+    /// Changed fields, ordered by path
+    pub changes: Vec<ConfigChange>,
+}
+
+impl ConfigDiff {
+    /// Computes the field-level difference between two JSON values
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - Previous configuration value
+    /// * `new` - Updated configuration value
+    ///
+    /// # Returns
+    ///
+    /// A `ConfigDiff` containing one change per differing leaf field
+    pub fn between(old: &Value, new: &Value) -> Self {
+        let mut changes = Vec::new();
+        Self::collect("", old, new, &mut changes);
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+        Self { changes }
+    }
+
+    /// Returns true if the update did not change anything
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    fn collect(path: &str, old: &Value, new: &Value, changes: &mut Vec<ConfigChange>) {
+        match (old, new) {
+            (Value::Object(old_map), Value::Object(new_map)) => {
+                for (key, old_value) in old_map {
+                    let child = Self::join(path, key);
+                    match new_map.get(key) {
+                        Some(new_value) => Self::collect(&child, old_value, new_value, changes),
+                        None => changes.push(ConfigChange {
+                            path: child,
+                            old: old_value.clone(),
+                            new: Value::Null,
+                        }),
+                    }
+                }
+                for (key, new_value) in new_map {
+                    if !old_map.contains_key(key) {
+                        changes.push(ConfigChange {
+                            path: Self::join(path, key),
+                            old: Value::Null,
+                            new: new_value.clone(),
+                        });
+                    }
+                }
+            }
+            _ if old != new => changes.push(ConfigChange {
+                path: path.to_string(),
+                old: old.clone(),
+                new: new.clone(),
+            }),
+            _ => {}
+        }
+    }
+
+    fn join(path: &str, key: &str) -> String {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, change) in self.changes.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}: {} -> {}", change.path, change.old, change.new)?;
+        }
+        Ok(())
+    }
+}
+
+/// Shared, hot-reloadable configuration value
+///
+/// # Examples
+///
+/// ```no_run
+/// # use pumpfun::common::config::HotConfig;
+/// # use serde::{Deserialize, Serialize};
+/// # use std::{sync::Arc, time::Duration};
+/// #
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct Thresholds {
+///     max_buy_sol: f64,
+///     min_liquidity_sol: f64,
+/// }
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let config = Arc::new(HotConfig::from_file("strategy.json")?);
+///
+/// // Reload whenever the file changes on disk
+/// let _watcher = config.watch_file("strategy.json", Duration::from_secs(2));
+///
+/// // Print every applied change
+/// let mut changes = config.subscribe_changes();
+/// tokio::spawn(async move {
+///     while let Ok(diff) = changes.recv().await {
+///         println!("Config updated:\n{}", diff);
+///     }
+/// });
+///
+/// let thresholds: Thresholds = config.get();
+/// # Ok(())
+/// # }
+/// ```
+pub struct HotConfig<T> {
+    current: watch::Sender<T>,
+    changes: broadcast::Sender<ConfigDiff>,
+}
+
+impl<T> HotConfig<T>
+where
+    T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    /// Creates a new hot-reloadable configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - Initial configuration value
+    ///
+    /// # Returns
+    ///
+    /// A new `HotConfig` holding the initial value
+    pub fn new(initial: T) -> Self {
+        let (current, _) = watch::channel(initial);
+        let (changes, _) = broadcast::channel(64);
+        Self { current, changes }
+    }
+
+    /// Creates a new hot-reloadable configuration from a JSON file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the JSON config file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or does not match the config type
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, error::ClientError> {
+        Ok(Self::new(Self::read_file(path.as_ref())?))
+    }
+
+    /// Gets a copy of the current configuration
+    pub fn get(&self) -> T {
+        self.current.borrow().clone()
+    }
+
+    /// Subscribes to configuration values
+    ///
+    /// # Returns
+    ///
+    /// A watch receiver that always holds the latest configuration
+    pub fn subscribe(&self) -> watch::Receiver<T> {
+        self.current.subscribe()
+    }
+
+    /// Subscribes to applied configuration changes
+    ///
+    /// # Returns
+    ///
+    /// A broadcast receiver yielding a `ConfigDiff` for every non-empty update
+    pub fn subscribe_changes(&self) -> broadcast::Receiver<ConfigDiff> {
+        self.changes.subscribe()
+    }
+
+    /// Replaces the configuration with a new value
+    ///
+    /// The current value is read, compared, and replaced under the same lock, so concurrent
+    /// updates are applied one after the other and each diff is relative to the value it
+    /// replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `new` - New configuration value
+    ///
+    /// # Returns
+    ///
+    /// The changes that were applied, or an error if the values cannot be serialized
+    pub fn apply(&self, new: T) -> Result<ConfigDiff, error::ClientError> {
+        self.update(|_| Ok(new))
+    }
+
+    /// Merges a partial JSON update into the current configuration
+    ///
+    /// Objects are merged recursively; any other value replaces the existing field.
+    ///
+    /// # Arguments
+    ///
+    /// * `patch` - Partial configuration, e.g. `{"filters": {"max_dev_buy": 2.5}}`
+    ///
+    /// # Returns
+    ///
+    /// The changes that were applied, or an error if the merged value is not a valid config
+    pub fn apply_patch(&self, patch: Value) -> Result<ConfigDiff, error::ClientError> {
+        self.update(|current| {
+            let mut merged = Self::to_value(current)?;
+            Self::merge(&mut merged, patch);
+            serde_json::from_value(merged)
+                .map_err(|err| error::ClientError::ConfigError(err.to_string()))
+        })
+    }
+
+    /// Reloads the configuration from a JSON file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the JSON config file
+    ///
+    /// # Returns
+    ///
+    /// The changes that were applied, or an error if the file is unreadable or invalid
+    pub fn reload_from_file(
+        &self,
+        path: impl AsRef<Path>,
+    ) -> Result<ConfigDiff, error::ClientError> {
+        self.apply(Self::read_file(path.as_ref())?)
+    }
+
+    /// Watches a JSON config file and reloads it whenever it is modified
+    ///
+    /// Invalid files are reported and skipped, keeping the last good configuration active.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the JSON config file
+    /// * `poll_interval` - How often to check the file's modification time
+    ///
+    /// # Returns
+    ///
+    /// The handle of the background watcher task; abort it to stop watching
+    pub fn watch_file(
+        self: &Arc<Self>,
+        path: impl Into<PathBuf>,
+        poll_interval: Duration,
+    ) -> JoinHandle<()> {
+        let config = Arc::clone(self);
+        let path = path.into();
+
+        tokio::spawn(async move {
+            let modified = |path: &Path| -> Option<SystemTime> {
+                std::fs::metadata(path).and_then(|m| m.modified()).ok()
+            };
+            let mut last_modified = modified(&path);
+
+            loop {
+                tokio::time::sleep(poll_interval).await;
+
+                let current = modified(&path);
+                if current.is_none() || current == last_modified {
+                    continue;
+                }
+                last_modified = current;
+
+                if let Err(err) = config.reload_from_file(&path) {
+                    log_warn!("Failed to reload config from {}: {}", path.display(), err);
+                }
+            }
+        })
+    }
+
+    /// Opens an admin channel that accepts partial JSON updates at runtime
+    ///
+    /// Each value sent on the returned channel is applied with `apply_patch`. Invalid
+    /// patches are reported and skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Number of pending updates the channel can buffer
+    ///
+    /// # Returns
+    ///
+    /// The sending half of the admin channel; the listener stops when it is dropped
+    pub fn admin_channel(self: &Arc<Self>, capacity: usize) -> mpsc::Sender<Value> {
+        let config = Arc::clone(self);
+        let (tx, mut rx) = mpsc::channel::<Value>(capacity.max(1));
+
+        tokio::spawn(async move {
+            while let Some(patch) = rx.recv().await {
+                if let Err(err) = config.apply_patch(patch) {
+                    log_warn!("Rejected config update: {}", err);
+                }
+            }
+        });
+
+        tx
+    }
+
+    fn read_file(path: &Path) -> Result<T, error::ClientError> {
+        let contents = std::fs::read_to_string(path).map_err(|err| {
+            error::ClientError::ConfigError(format!("Failed to read {}: {}", path.display(), err))
+        })?;
+        serde_json::from_str(&contents).map_err(|err| {
+            error::ClientError::ConfigError(format!("Failed to parse {}: {}", path.display(), err))
+        })
+    }
+
+    /// Replaces the configuration with a value computed from the current one
+    ///
+    /// The watch channel's lock is held from reading the current value until the change is
+    /// broadcast, so diffs are broadcast in the order they are applied.
+    fn update(
+        &self,
+        next: impl FnOnce(&T) -> Result<T, error::ClientError>,
+    ) -> Result<ConfigDiff, error::ClientError> {
+        let mut result = Ok(ConfigDiff::default());
+        self.current.send_if_modified(|current| {
+            let update = next(current).and_then(|new| {
+                let diff = ConfigDiff::between(&Self::to_value(current)?, &Self::to_value(&new)?);
+                Ok((new, diff))
+            });
+            match update {
+                Ok((new, diff)) => {
+                    let modified = !diff.is_empty();
+                    if modified {
+                        *current = new;
+                        // Sending only fails when nobody is subscribed
+                        let _ = self.changes.send(diff.clone());
+                    }
+                    result = Ok(diff);
+                    modified
+                }
+                Err(err) => {
+                    result = Err(err);
+                    false
+                }
+            }
+        });
+        result
+    }
+
+    fn to_value(value: &T) -> Result<Value, error::ClientError> {
+        serde_json::to_value(value).map_err(|err| error::ClientError::ConfigError(err.to_string()))
+    }
+
+    fn merge(target: &mut Value, patch: Value) {
+        match (target, patch) {
+            (Value::Object(target_map), Value::Object(patch_map)) => {
+                for (key, value) in patch_map {
+                    Self::merge(target_map.entry(key).or_insert(Value::Null), value);
+                }
+            }
+            (target, patch) => *target = patch,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Filters {
+        max_dev_buy: f64,
+        symbols: Vec<String>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Strategy {
+        enabled: bool,
+        budget: u64,
+        filters: Filters,
+    }
+
+    fn get_strategy() -> Strategy {
+        Strategy {
+            enabled: true,
+            budget: 100,
+            filters: Filters {
+                max_dev_buy: 1.0,
+                symbols: vec!["PUMP".to_string()],
+            },
+        }
+    }
+
+    #[test]
+    fn test_config_diff() {
+        let diff = ConfigDiff::between(
+            &json!({"a": 1, "b": {"c": true, "d": "x"}, "e": 1}),
+            &json!({"a": 2, "b": {"c": true, "d": "y"}, "f": 3}),
+        );
+        let paths: Vec<&str> = diff.changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec!["a", "b.d", "e", "f"]);
+        assert_eq!(diff.changes[2].new, Value::Null);
+        assert_eq!(diff.changes[3].old, Value::Null);
+
+        assert!(ConfigDiff::between(&json!({"a": 1}), &json!({"a": 1})).is_empty());
+    }
+
+    #[test]
+    fn test_apply_and_patch() {
+        let config = HotConfig::new(get_strategy());
+        let mut changes = config.subscribe_changes();
+
+        let diff = config
+            .apply_patch(json!({"filters": {"max_dev_buy": 2.5}}))
+            .unwrap();
+        assert_eq!(diff.changes.len(), 1);
+        assert_eq!(diff.changes[0].path, "filters.max_dev_buy");
+        assert_eq!(config.get().filters.max_dev_buy, 2.5);
+        assert_eq!(changes.try_recv().unwrap(), diff);
+
+        // No-op updates are not broadcast
+        assert!(config.apply(config.get()).unwrap().is_empty());
+        assert!(changes.try_recv().is_err());
+
+        // Invalid patches leave the config untouched
+        assert!(config.apply_patch(json!({"budget": "lots"})).is_err());
+        assert_eq!(config.get().budget, 100);
+    }
+
+    #[test]
+    fn test_concurrent_apply() {
+        let config = Arc::new(HotConfig::new(get_strategy()));
+        let mut changes = config.subscribe_changes();
+
+        let threads: Vec<_> = (0..4u64)
+            .map(|thread| {
+                let config = Arc::clone(&config);
+                std::thread::spawn(move || {
+                    for i in 0..15 {
+                        let budget = 1_000 + thread * 100 + i;
+                        config.apply_patch(json!({ "budget": budget })).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        // Every diff starts from the value the previous one left behind
+        let mut budget = json!(100);
+        while let Ok(diff) = changes.try_recv() {
+            assert_eq!(diff.changes[0].old, budget);
+            budget = diff.changes[0].new.clone();
+        }
+        assert_eq!(budget, json!(config.get().budget));
+        assert_ne!(config.get().budget, 100);
+    }
+
+    #[test]
+    fn test_reload_from_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("strategy.json");
+
+        std::fs::write(&path, serde_json::to_string(&get_strategy()).unwrap()).unwrap();
+        let config: HotConfig<Strategy> = HotConfig::from_file(&path).unwrap();
+        assert_eq!(config.get(), get_strategy());
+
+        let mut updated = get_strategy();
+        updated.enabled = false;
+        std::fs::write(&path, serde_json::to_string(&updated).unwrap()).unwrap();
+
+        let diff = config.reload_from_file(&path).unwrap();
+        assert_eq!(diff.changes[0].path, "enabled");
+        assert!(!config.get().enabled);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(config.reload_from_file(&path).is_err());
+        assert!(!config.get().enabled);
+    }
+}
@@ -0,0 +1,268 @@
+//! Deduplication of events received through redundant subscriptions
+//!
+//! Subscribing to the same events through several endpoints guards against a single
+//! provider dropping or delaying them, but delivers each event once per endpoint.
+//! `deduplicate` merges such streams back into one event per `(signature, index)` pair,
+//! for both WebSocket `EventStream`s and Yellowstone gRPC `GrpcEventStream`s.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::Stream;
+
+use super::stream::{EventContext, EventStreamItem};
+
+/// Item of an event stream that can be deduplicated
+pub trait EventItem {
+    /// Returns the context identifying the event, or None for items that are not events
+    fn event_context(&self) -> Option<&EventContext>;
+}
+
+impl EventItem for EventStreamItem {
+    fn event_context(&self) -> Option<&EventContext> {
+        Some(&self.0)
+    }
+}
+
+#[cfg(feature = "geyser")]
+impl EventItem for super::geyser::GrpcUpdate {
+    fn event_context(&self) -> Option<&EventContext> {
+        match self {
+            Self::Event { context, .. } => Some(context),
+            Self::Account { .. } => None,
+        }
+    }
+}
+
+/// Bounded set of recently seen events
+///
+/// Events are identified by their transaction signature and their index within the
+/// transaction. Once `capacity` events have been recorded, the least recently seen event
+/// is forgotten, so duplicates are only detected while the original is among the last
+/// `capacity` distinct events.
+#[derive(Debug, Clone)]
+pub struct EventDeduplicator {
+    capacity: usize,
+    /// Tick at which each remembered event was last seen
+    seen: HashMap<(String, usize), u64>,
+    /// Events in the order they were seen, including stale entries of events seen again
+    order: VecDeque<((String, usize), u64)>,
+    tick: u64,
+}
+
+impl EventDeduplicator {
+    /// Creates a deduplicator remembering up to `capacity` events
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of events to remember. A capacity of zero is treated
+    ///   as one
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            seen: HashMap::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+            tick: 0,
+        }
+    }
+
+    /// Records an event and returns whether it had not been seen before
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - Context of the received event
+    ///
+    /// # Returns
+    ///
+    /// Returns true the first time an event is seen, or false for duplicates
+    pub fn insert(&mut self, context: &EventContext) -> bool {
+        self.tick += 1;
+        let key = (context.signature.clone(), context.index);
+        let new = self.seen.insert(key.clone(), self.tick).is_none();
+        self.order.push_back((key, self.tick));
+
+        // Forget the least recently seen events, skipping entries of events seen since
+        while self.seen.len() > self.capacity {
+            let Some((key, tick)) = self.order.pop_front() else {
+                break;
+            };
+            if self.seen.get(&key) == Some(&tick) {
+                self.seen.remove(&key);
+            }
+        }
+        // Drop stale entries once they outnumber the remembered events
+        if self.order.len() > 2 * self.capacity {
+            let seen = &self.seen;
+            self.order.retain(|(key, tick)| seen.get(key) == Some(tick));
+        }
+        new
+    }
+
+    /// Returns the number of remembered events
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Returns whether no events have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+}
+
+/// Stream returned by `deduplicate`
+pub struct Deduplicated<S> {
+    stream: S,
+    seen: EventDeduplicator,
+}
+
+impl<S> Stream for Deduplicated<S>
+where
+    S: Stream + Unpin,
+    S::Item: EventItem,
+{
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            match Pin::new(&mut this.stream).poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let duplicate = item
+                        .event_context()
+                        .is_some_and(|context| !this.seen.insert(context));
+                    if !duplicate {
+                        return Poll::Ready(Some(item));
+                    }
+                }
+                other => return other,
+            }
+        }
+    }
+}
+
+/// Drops events already yielded by a stream
+///
+/// Combine the streams of redundant subscriptions, e.g. with `futures::stream::select_all`,
+/// and pass the result to receive every event once. Items that are not events, such as
+/// gRPC account updates, are passed through unchanged.
+///
+/// # Arguments
+///
+/// * `stream` - Stream of events, possibly containing duplicates
+/// * `capacity` - Number of recent events to remember, see `EventDeduplicator`
+///
+/// # Returns
+///
+/// Returns a stream yielding the first occurrence of each event
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::{stream, StreamExt};
+/// use pumpfun::common::{
+///     dedupe::deduplicate,
+///     stream::subscribe_stream,
+///     types::{Cluster, PriorityFee},
+/// };
+/// use solana_sdk::commitment_config::CommitmentConfig;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let commitment = CommitmentConfig::confirmed();
+/// let primary = Cluster::mainnet(commitment, PriorityFee::default());
+/// let backup = Cluster::new(
+///     "https://rpc.example.com".to_string(),
+///     "wss://rpc.example.com".to_string(),
+///     commitment,
+///     PriorityFee::default(),
+/// );
+///
+/// let streams = vec![
+///     subscribe_stream(primary, None, None).await?,
+///     subscribe_stream(backup, None, None).await?,
+/// ];
+/// let mut events = deduplicate(stream::select_all(streams), 10_000);
+///
+/// while let Some((context, event, _)) = events.next().await {
+///     println!("Event {} of tx {}: {:?}", context.index, context.signature, event);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn deduplicate<S>(stream: S, capacity: usize) -> Deduplicated<S>
+where
+    S: Stream + Unpin,
+    S::Item: EventItem,
+{
+    Deduplicated {
+        stream,
+        seen: EventDeduplicator::new(capacity),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use solana_client::rpc_response::{Response, RpcLogsResponse, RpcResponseContext};
+
+    use crate::common::stream::parse_event;
+
+    fn item(signature: &str, index: usize) -> EventStreamItem {
+        let context = EventContext {
+            signature: signature.to_string(),
+            slot: 1,
+            block_time: None,
+            index,
+        };
+        let response = Response {
+            context: RpcResponseContext::new(1),
+            value: RpcLogsResponse {
+                signature: signature.to_string(),
+                err: None,
+                logs: Vec::new(),
+            },
+        };
+        (context, parse_event(signature, "AAAAAAAAAAA="), response)
+    }
+
+    #[tokio::test]
+    async fn test_deduplicate() {
+        let items = vec![
+            item("a", 0),
+            item("a", 1),
+            item("a", 0),
+            item("b", 0),
+            item("a", 1),
+        ];
+        let events: Vec<_> = deduplicate(futures::stream::iter(items), 10)
+            .map(|(context, _, _)| (context.signature, context.index))
+            .collect()
+            .await;
+        assert_eq!(
+            events,
+            [
+                ("a".to_string(), 0),
+                ("a".to_string(), 1),
+                ("b".to_string(), 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deduplicator_capacity() {
+        let mut seen = EventDeduplicator::new(2);
+        assert!(seen.insert(&item("a", 0).0));
+        assert!(seen.insert(&item("b", 0).0));
+        // Seeing a again makes b the least recently seen event
+        assert!(!seen.insert(&item("a", 0).0));
+        assert!(seen.insert(&item("c", 0).0));
+        assert_eq!(seen.len(), 2);
+
+        assert!(!seen.insert(&item("a", 0).0));
+        assert!(seen.insert(&item("b", 0).0));
+    }
+}
@@ -0,0 +1,303 @@
+//! RPC transport that fails over between several endpoints
+//!
+//! `FailoverRpcSender` implements Solana's `RpcSender` transport, so an `RpcClient` built on
+//! it transparently retries every read and send on the next endpoint when the current one
+//! times out, is unreachable, or rate limits the request. Endpoints are tried in priority
+//! order, and endpoints that recently failed are skipped for a cooldown that grows with each
+//! consecutive failure.
+//!
+//! Errors returned by a healthy node, such as a failed preflight simulation, are returned
+//! immediately since another endpoint would answer the same way.
+
+use std::{
+    sync::{
+        atomic::{AtomicU32, AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use serde_json::Value;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    nonblocking::rpc_client::RpcClient,
+    rpc_client::RpcClientConfig,
+    rpc_request::RpcRequest,
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+use solana_sdk::commitment_config::CommitmentConfig;
+
+use crate::error;
+
+/// Cooldown after the first consecutive failure of an endpoint
+const BASE_COOLDOWN: Duration = Duration::from_secs(1);
+/// Longest cooldown applied to a failing endpoint
+const MAX_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Health of a single endpoint
+///
+/// # Fields
+///
+/// * `url` - HTTP endpoint URL
+/// * `healthy` - Whether the endpoint is currently used, i.e. not cooling down after failures
+/// * `consecutive_failures` - Failures since the last successful request
+/// * `total_failures` - Failures since the sender was created
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointHealth {
+    pub url: String,
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+    pub total_failures: u64,
+}
+
+struct Endpoint {
+    rpc: RpcClient,
+    consecutive_failures: AtomicU32,
+    total_failures: AtomicU64,
+    /// Milliseconds since `started` until which the endpoint is skipped
+    unhealthy_until: AtomicU64,
+}
+
+/// RPC transport that retries requests on the next endpoint when one fails
+///
+/// The sender is cheap to clone; clones share the same endpoints and health tracking, so a
+/// clone can be kept to inspect `health` after the sender is moved into an `RpcClient`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pumpfun::common::failover::FailoverRpcSender;
+/// use solana_sdk::commitment_config::CommitmentConfig;
+/// use std::time::Duration;
+///
+/// let failover = FailoverRpcSender::new(
+///     &[
+///         "https://my-primary.example.com".to_string(),
+///         "https://api.mainnet-beta.solana.com".to_string(),
+///     ],
+///     Duration::from_secs(10),
+/// );
+/// let rpc = failover.clone().into_rpc_client(CommitmentConfig::confirmed());
+///
+/// for endpoint in failover.health() {
+///     println!("{}: healthy={}", endpoint.url, endpoint.healthy);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct FailoverRpcSender {
+    endpoints: Arc<[Endpoint]>,
+    started: Instant,
+}
+
+impl FailoverRpcSender {
+    /// Creates a new failover sender
+    ///
+    /// # Arguments
+    ///
+    /// * `urls` - HTTP RPC endpoints in priority order
+    /// * `timeout` - Timeout for a single request to one endpoint
+    ///
+    /// # Returns
+    ///
+    /// A sender that tries the endpoints in the given order
+    pub fn new(urls: &[String], timeout: Duration) -> Self {
+        Self::from_clients(
+            urls.iter()
+                .map(|url| RpcClient::new_with_timeout(url.clone(), timeout))
+                .collect(),
+        )
+    }
+
+    /// Creates a new failover sender from existing RPC clients
+    ///
+    /// # Arguments
+    ///
+    /// * `rpcs` - RPC clients in priority order
+    pub fn from_clients(rpcs: Vec<RpcClient>) -> Self {
+        let endpoints = rpcs
+            .into_iter()
+            .map(|rpc| Endpoint {
+                rpc,
+                consecutive_failures: AtomicU32::new(0),
+                total_failures: AtomicU64::new(0),
+                unhealthy_until: AtomicU64::new(0),
+            })
+            .collect();
+        Self {
+            endpoints,
+            started: Instant::now(),
+        }
+    }
+
+    /// Wraps the sender in an RPC client
+    ///
+    /// # Arguments
+    ///
+    /// * `commitment` - Default commitment of the RPC client
+    ///
+    /// # Returns
+    ///
+    /// An `RpcClient` whose requests fail over between the sender's endpoints
+    pub fn into_rpc_client(self, commitment: CommitmentConfig) -> RpcClient {
+        RpcClient::new_sender(self, RpcClientConfig::with_commitment(commitment))
+    }
+
+    /// Gets the health of every endpoint, in priority order
+    pub fn health(&self) -> Vec<EndpointHealth> {
+        let now = self.now_ms();
+        self.endpoints
+            .iter()
+            .map(|endpoint| EndpointHealth {
+                url: endpoint.rpc.url(),
+                healthy: endpoint.unhealthy_until.load(Ordering::Relaxed) <= now,
+                consecutive_failures: endpoint.consecutive_failures.load(Ordering::Relaxed),
+                total_failures: endpoint.total_failures.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+
+    /// Orders endpoints for a request: healthy ones by priority, then cooling ones
+    fn candidates(&self) -> Vec<&Endpoint> {
+        let now = self.now_ms();
+        let (mut healthy, cooling): (Vec<&Endpoint>, Vec<&Endpoint>) = self
+            .endpoints
+            .iter()
+            .partition(|endpoint| endpoint.unhealthy_until.load(Ordering::Relaxed) <= now);
+        healthy.extend(cooling);
+        healthy
+    }
+
+    fn record_success(endpoint: &Endpoint) {
+        endpoint.consecutive_failures.store(0, Ordering::Relaxed);
+        endpoint.unhealthy_until.store(0, Ordering::Relaxed);
+    }
+
+    fn record_failure(&self, endpoint: &Endpoint) {
+        let failures = endpoint
+            .consecutive_failures
+            .fetch_add(1, Ordering::Relaxed)
+            + 1;
+        endpoint.total_failures.fetch_add(1, Ordering::Relaxed);
+        let cooldown = BASE_COOLDOWN
+            .saturating_mul(2u32.saturating_pow(failures - 1))
+            .min(MAX_COOLDOWN);
+        endpoint.unhealthy_until.store(
+            self.now_ms() + cooldown.as_millis() as u64,
+            Ordering::Relaxed,
+        );
+    }
+
+    fn now_ms(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
+    }
+}
+
+/// Returns whether an error means the endpoint itself is unavailable
+fn is_endpoint_failure(kind: &ClientErrorKind) -> bool {
+    matches!(
+        kind,
+        ClientErrorKind::Reqwest(_) | ClientErrorKind::Io(_) | ClientErrorKind::Middleware(_)
+    ) || error::is_rate_limited(kind)
+}
+
+#[async_trait]
+impl RpcSender for FailoverRpcSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let mut last_error: Option<ClientError> = None;
+
+        for endpoint in self.candidates() {
+            match endpoint.rpc.send::<Value>(request, params.clone()).await {
+                Ok(value) => {
+                    Self::record_success(endpoint);
+                    return Ok(value);
+                }
+                Err(err) if is_endpoint_failure(err.kind()) => {
+                    self.record_failure(endpoint);
+                    last_error = Some(err);
+                }
+                Err(err) => {
+                    // The node answered; another endpoint would answer the same way
+                    Self::record_success(endpoint);
+                    return Err(err);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            ClientErrorKind::Custom("No RPC endpoints configured".to_string()).into()
+        }))
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.endpoints
+            .iter()
+            .map(|endpoint| endpoint.rpc.get_transport_stats())
+            .fold(RpcTransportStats::default(), |mut total, stats| {
+                total.request_count += stats.request_count;
+                total.elapsed_time += stats.elapsed_time;
+                total.rate_limited_time += stats.rate_limited_time;
+                total
+            })
+    }
+
+    fn url(&self) -> String {
+        self.endpoints
+            .first()
+            .map(|endpoint| endpoint.rpc.url())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_tracking() {
+        let sender = FailoverRpcSender::new(
+            &[
+                "http://primary.invalid".to_string(),
+                "http://backup.invalid".to_string(),
+            ],
+            Duration::from_secs(1),
+        );
+        assert!(sender.health().iter().all(|endpoint| endpoint.healthy));
+
+        let primary = &sender.endpoints[0];
+        sender.record_failure(primary);
+        sender.record_failure(primary);
+
+        let health = sender.health();
+        assert!(!health[0].healthy);
+        assert_eq!(health[0].consecutive_failures, 2);
+        assert_eq!(health[0].total_failures, 2);
+        assert!(health[1].healthy);
+
+        // Failing endpoints are tried last
+        assert_eq!(sender.candidates()[0].rpc.url(), "http://backup.invalid");
+
+        FailoverRpcSender::record_success(primary);
+        let health = sender.health();
+        assert!(health[0].healthy);
+        assert_eq!(health[0].total_failures, 2);
+        assert_eq!(sender.candidates()[0].rpc.url(), "http://primary.invalid");
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_unreachable_endpoint() {
+        let sender = FailoverRpcSender::from_clients(vec![
+            RpcClient::new_with_timeout("http://127.0.0.1:1".to_string(), Duration::from_secs(1)),
+            RpcClient::new_mock("succeeds".to_string()),
+        ]);
+        let rpc = sender
+            .clone()
+            .into_rpc_client(CommitmentConfig::confirmed());
+
+        assert!(rpc.get_version().await.is_ok());
+        let health = sender.health();
+        assert!(!health[0].healthy);
+        assert_eq!(health[0].consecutive_failures, 1);
+        assert!(health[1].healthy);
+    }
+}
@@ -0,0 +1,350 @@
+//! Commitment tracking for events observed at processed commitment
+//!
+//! Events arrive fastest at `processed` commitment, but a processed transaction can still
+//! be dropped if its fork is abandoned. `subscribe_stream_tracked` delivers events as soon
+//! as they are processed and follows up once their transaction is confirmed and finalized,
+//! or when it disappears, so bots can act on processed data and reconcile on finality.
+
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use futures::Stream;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, signature::Signature};
+use tokio::sync::mpsc;
+
+use super::{
+    stream::{spawn_subscription, EventFilter, EventStreamItem, Subscription},
+    types::Cluster,
+};
+use crate::error;
+
+/// Maximum number of signatures accepted by a single `getSignatureStatuses` request
+const MAX_STATUSES_PER_REQUEST: usize = 256;
+
+/// How the commitment of delivered events is tracked
+///
+/// # Fields
+///
+/// * `poll_interval` - Interval between signature status checks of pending transactions
+/// * `timeout` - Time after which a transaction that has not been finalized is reported
+///   as dropped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommitmentTracking {
+    pub poll_interval: Duration,
+    pub timeout: Duration,
+}
+
+impl Default for CommitmentTracking {
+    /// Polls every second and gives up after 90 seconds, after which the blockhash of a
+    /// processed transaction has expired
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(1),
+            timeout: Duration::from_secs(90),
+        }
+    }
+}
+
+/// Item yielded by a `TrackedEventStream`
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum TrackedEvent {
+    /// Event observed at processed commitment
+    Event(EventStreamItem),
+    /// Transaction of previously delivered events reached confirmed commitment
+    Confirmed {
+        /// Signature of the transaction
+        signature: String,
+        /// Slot the transaction was confirmed in
+        slot: u64,
+    },
+    /// Transaction of previously delivered events reached finalized commitment
+    Finalized {
+        /// Signature of the transaction
+        signature: String,
+        /// Slot the transaction was finalized in
+        slot: u64,
+    },
+    /// Transaction of previously delivered events was not finalized within the timeout,
+    /// usually because its fork was abandoned
+    Dropped {
+        /// Signature of the transaction
+        signature: String,
+    },
+}
+
+/// Stream of events and commitment updates returned by `subscribe_stream_tracked`
+///
+/// The underlying subscription is terminated when the stream is dropped.
+pub struct TrackedEventStream {
+    receiver: mpsc::Receiver<TrackedEvent>,
+    _subscription: Subscription,
+}
+
+impl Stream for TrackedEventStream {
+    type Item = TrackedEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// Subscribes to Pump.fun events at processed commitment and tracks their finality
+///
+/// Events are yielded as `TrackedEvent::Event` as soon as they are processed. The
+/// transactions they belong to are then polled with `getSignatureStatuses`, yielding
+/// `Confirmed` and `Finalized` once for each transaction, or `Dropped` if it is not
+/// finalized within `tracking.timeout`. Follow-ups of a transaction are yielded at most
+/// once, even when it emitted several events.
+///
+/// # Arguments
+///
+/// * `cluster` - Solana cluster configuration containing RPC endpoints
+/// * `mentioned` - Optional public key to filter events by mentions. If None, subscribes to all Pump.fun events
+/// * `tracking` - Polling interval and timeout for commitment tracking
+///
+/// # Returns
+///
+/// Returns a `TrackedEventStream` of events and commitment updates, or a ClientError if the
+/// subscription cannot be established
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use pumpfun::common::{
+///     finality::{subscribe_stream_tracked, CommitmentTracking, TrackedEvent},
+///     types::{Cluster, PriorityFee},
+/// };
+/// use solana_sdk::commitment_config::CommitmentConfig;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let cluster = Cluster::mainnet(CommitmentConfig::processed(), PriorityFee::default());
+/// let mut events = subscribe_stream_tracked(cluster, None, CommitmentTracking::default()).await?;
+///
+/// while let Some(update) = events.next().await {
+///     match update {
+///         TrackedEvent::Event((context, event, _)) => {
+///             println!("Processed {:?} in tx {}", event, context.signature)
+///         }
+///         TrackedEvent::Finalized { signature, .. } => println!("Finalized tx {}", signature),
+///         TrackedEvent::Dropped { signature } => println!("Dropped tx {}", signature),
+///         TrackedEvent::Confirmed { .. } => {}
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn subscribe_stream_tracked(
+    cluster: Cluster,
+    mentioned: Option<String>,
+    tracking: CommitmentTracking,
+) -> Result<TrackedEventStream, error::ClientError> {
+    let (event_tx, mut event_rx) = mpsc::channel::<EventStreamItem>(1000);
+    let (update_tx, receiver) = mpsc::channel(1000);
+
+    let rpc =
+        RpcClient::new_with_commitment(cluster.rpc.http.clone(), CommitmentConfig::processed());
+    let subscription = spawn_subscription(
+        cluster,
+        mentioned,
+        Some(CommitmentConfig::processed()),
+        EventFilter::default(),
+        event_tx,
+    )
+    .await?;
+
+    tokio::spawn(async move {
+        let mut pending = PendingTransactions::new(tracking.timeout);
+        let mut interval = tokio::time::interval(tracking.poll_interval);
+        loop {
+            let updates = tokio::select! {
+                item = event_rx.recv() => {
+                    let Some(item) = item else {
+                        return;
+                    };
+                    pending.insert(&item.0.signature, Instant::now());
+                    vec![TrackedEvent::Event(item)]
+                }
+                _ = interval.tick() => {
+                    let statuses = fetch_statuses(&rpc, pending.signatures()).await;
+                    pending.update(statuses, Instant::now())
+                }
+            };
+            for update in updates {
+                if update_tx.send(update).await.is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    Ok(TrackedEventStream {
+        receiver,
+        _subscription: subscription,
+    })
+}
+
+/// Commitment reached by a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Status {
+    slot: u64,
+    confirmed: bool,
+    finalized: bool,
+}
+
+/// Transactions whose events were delivered but that have not been finalized yet
+struct PendingTransactions {
+    timeout: Duration,
+    /// Time each transaction was first seen, and whether it was reported as confirmed
+    transactions: HashMap<String, (Instant, bool)>,
+}
+
+impl PendingTransactions {
+    fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            transactions: HashMap::new(),
+        }
+    }
+
+    fn insert(&mut self, signature: &str, now: Instant) {
+        self.transactions
+            .entry(signature.to_string())
+            .or_insert((now, false));
+    }
+
+    fn signatures(&self) -> Vec<String> {
+        self.transactions.keys().cloned().collect()
+    }
+
+    /// Applies fetched statuses and returns the resulting commitment updates
+    ///
+    /// Transactions whose status could not be fetched are left pending until they time out.
+    fn update(
+        &mut self,
+        statuses: Vec<(String, Option<Status>)>,
+        now: Instant,
+    ) -> Vec<TrackedEvent> {
+        let mut updates = Vec::new();
+        for (signature, status) in statuses {
+            let Some((first_seen, confirmed)) = self.transactions.get_mut(&signature) else {
+                continue;
+            };
+            match status {
+                Some(status) if status.confirmed || status.finalized => {
+                    if !*confirmed {
+                        *confirmed = true;
+                        updates.push(TrackedEvent::Confirmed {
+                            signature: signature.clone(),
+                            slot: status.slot,
+                        });
+                    }
+                    if status.finalized {
+                        self.transactions.remove(&signature);
+                        updates.push(TrackedEvent::Finalized {
+                            signature,
+                            slot: status.slot,
+                        });
+                    }
+                }
+                _ if now.duration_since(*first_seen) >= self.timeout => {
+                    self.transactions.remove(&signature);
+                    updates.push(TrackedEvent::Dropped { signature });
+                }
+                _ => {}
+            }
+        }
+        updates
+    }
+}
+
+/// Fetches the statuses of transactions, in batches accepted by the RPC node
+///
+/// Transactions whose batch could not be fetched are omitted.
+async fn fetch_statuses(rpc: &RpcClient, signatures: Vec<String>) -> Vec<(String, Option<Status>)> {
+    let mut statuses = Vec::with_capacity(signatures.len());
+    for chunk in signatures.chunks(MAX_STATUSES_PER_REQUEST) {
+        let parsed: Vec<Signature> = chunk.iter().filter_map(|sig| sig.parse().ok()).collect();
+        let Ok(response) = rpc.get_signature_statuses(&parsed).await else {
+            continue;
+        };
+        for (signature, status) in parsed.iter().zip(response.value) {
+            let status = status.map(|status| Status {
+                slot: status.slot,
+                confirmed: status.satisfies_commitment(CommitmentConfig::confirmed()),
+                finalized: status.satisfies_commitment(CommitmentConfig::finalized()),
+            });
+            statuses.push((signature.to_string(), status));
+        }
+    }
+    statuses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_transactions() {
+        let start = Instant::now();
+        let mut pending = PendingTransactions::new(Duration::from_secs(60));
+        pending.insert("a", start);
+        pending.insert("b", start);
+        pending.insert("c", start);
+        // Further events of the same transaction are tracked once
+        pending.insert("a", start);
+        assert_eq!(pending.signatures().len(), 3);
+
+        let confirmed = Status {
+            slot: 10,
+            confirmed: true,
+            finalized: false,
+        };
+        let updates = pending.update(
+            vec![
+                ("a".to_string(), Some(confirmed)),
+                ("b".to_string(), None),
+                ("c".to_string(), None),
+            ],
+            start + Duration::from_secs(1),
+        );
+        assert!(matches!(
+            updates.as_slice(),
+            [TrackedEvent::Confirmed { signature, slot: 10 }] if signature == "a"
+        ));
+
+        let finalized = Status {
+            finalized: true,
+            ..confirmed
+        };
+        let updates = pending.update(
+            vec![
+                ("a".to_string(), Some(finalized)),
+                ("b".to_string(), Some(finalized)),
+                ("c".to_string(), None),
+            ],
+            start + Duration::from_secs(60),
+        );
+        let updates: Vec<_> = updates
+            .iter()
+            .map(|update| match update {
+                TrackedEvent::Confirmed { signature, .. } => format!("confirmed {}", signature),
+                TrackedEvent::Finalized { signature, .. } => format!("finalized {}", signature),
+                TrackedEvent::Dropped { signature } => format!("dropped {}", signature),
+                TrackedEvent::Event(_) => unreachable!(),
+            })
+            .collect();
+        // Transactions skipping confirmed are reported as confirmed before finalized
+        assert_eq!(
+            updates,
+            ["finalized a", "confirmed b", "finalized b", "dropped c"]
+        );
+        assert!(pending.signatures().is_empty());
+    }
+}
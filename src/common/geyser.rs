@@ -0,0 +1,465 @@
+//! Yellowstone gRPC (Geyser) event source
+//!
+//! `GrpcEventSource` subscribes to Pump.fun and Pump.fun AMM transactions, and optionally to
+//! updates of accounts they own, through a Yellowstone gRPC endpoint. Events are emitted as
+//! the same `PumpFunEvent` types as the WebSocket subscriptions in `stream`, but they are
+//! extracted from the full transaction, so they arrive with lower latency and are not lost
+//! to log truncation.
+
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{SinkExt, Stream, StreamExt};
+use solana_sdk::{
+    commitment_config::{CommitmentConfig, CommitmentLevel},
+    pubkey::Pubkey,
+    signature::Signature,
+};
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+};
+use yellowstone_grpc_client::GeyserGrpcClient;
+use yellowstone_grpc_proto::prelude::{
+    subscribe_update::UpdateOneof, CommitmentLevel as GrpcCommitmentLevel, SubscribeRequest,
+    SubscribeRequestFilterAccounts, SubscribeRequestFilterTransactions, SubscribeRequestPing,
+    SubscribeUpdateAccountInfo, SubscribeUpdateTransactionInfo,
+};
+
+use super::{
+    stream::{collect_events, EventContext, EventFilter, ParseError, PumpFunEvent},
+    types::ReconnectPolicy,
+};
+use crate::{constants, error};
+
+/// Update received from a Yellowstone gRPC subscription
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum GrpcUpdate {
+    /// Event emitted by a Pump.fun or Pump.fun AMM transaction
+    Event {
+        /// Signature, slot, and index of the event. The block time is not included in
+        /// transaction updates and is always None
+        context: EventContext,
+        /// Parsed event, or the error if the event data could not be parsed
+        event: Result<PumpFunEvent, ParseError>,
+    },
+    /// Update of an account owned by a watched program
+    Account {
+        /// Account address
+        pubkey: Pubkey,
+        /// Program owning the account
+        owner: Pubkey,
+        /// Account balance in lamports
+        lamports: u64,
+        /// Raw account data
+        data: Vec<u8>,
+        /// Slot of the update
+        slot: u64,
+    },
+}
+
+/// Stream of updates returned by `GrpcEventSource::subscribe`
+///
+/// The gRPC subscription is terminated when the stream is dropped.
+pub struct GrpcEventStream {
+    receiver: mpsc::Receiver<GrpcUpdate>,
+    task: JoinHandle<()>,
+}
+
+impl Stream for GrpcEventStream {
+    type Item = GrpcUpdate;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl Drop for GrpcEventStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Pump.fun event source backed by a Yellowstone gRPC endpoint
+///
+/// By default, the source watches transactions of the Pump.fun and Pump.fun AMM programs at
+/// confirmed commitment and reconnects with the default `ReconnectPolicy`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use pumpfun::common::{
+///     geyser::{GrpcEventSource, GrpcUpdate},
+///     stream::{EventFilter, EventKind},
+/// };
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut updates = GrpcEventSource::new("https://grpc.example.com", Some("token".to_string()))
+///     .with_filter(EventFilter::new().kinds([EventKind::Create]))
+///     .subscribe()
+///     .await?;
+///
+/// while let Some(update) = updates.next().await {
+///     if let GrpcUpdate::Event { context, event: Ok(event) } = update {
+///         println!("Event in tx {}: {:?}", context.signature, event);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct GrpcEventSource {
+    endpoint: String,
+    x_token: Option<String>,
+    commitment: CommitmentConfig,
+    programs: Vec<Pubkey>,
+    filter: EventFilter,
+    account_updates: bool,
+    reconnect: ReconnectPolicy,
+}
+
+impl GrpcEventSource {
+    /// Creates an event source for a Yellowstone gRPC endpoint
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - URL of the gRPC endpoint
+    /// * `x_token` - Optional authentication token sent in the `x-token` header
+    ///
+    /// # Returns
+    ///
+    /// A new `GrpcEventSource` watching the Pump.fun and Pump.fun AMM programs
+    pub fn new(endpoint: impl Into<String>, x_token: Option<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            x_token,
+            commitment: CommitmentConfig::confirmed(),
+            programs: vec![constants::accounts::PUMPFUN, constants::accounts::PUMPAMM],
+            filter: EventFilter::default(),
+            account_updates: false,
+            reconnect: ReconnectPolicy::default(),
+        }
+    }
+
+    /// Sets the commitment level of the subscription
+    pub fn with_commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    /// Sets the programs whose transactions and accounts are watched
+    pub fn with_programs(mut self, programs: Vec<Pubkey>) -> Self {
+        self.programs = programs;
+        self
+    }
+
+    /// Sets the filter events must match to be emitted
+    pub fn with_filter(mut self, filter: EventFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Sets whether to also emit updates of accounts owned by the watched programs
+    pub fn with_account_updates(mut self, enabled: bool) -> Self {
+        self.account_updates = enabled;
+        self
+    }
+
+    /// Sets how the subscription reconnects when the gRPC stream fails
+    pub fn with_reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = policy;
+        self
+    }
+
+    /// Starts the subscription
+    ///
+    /// # Returns
+    ///
+    /// Returns a `GrpcEventStream` of updates, or a ClientError if the initial connection or
+    /// subscription fails
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The endpoint URL or token is invalid
+    /// - The connection cannot be established
+    /// - The subscription request fails
+    pub async fn subscribe(self) -> Result<GrpcEventStream, error::ClientError> {
+        let (update_tx, receiver) = mpsc::channel(1000);
+        let (ready_tx, ready_rx) = oneshot::channel();
+        let task = tokio::spawn(self.run(update_tx, ready_tx));
+
+        match ready_rx.await {
+            Ok(Ok(())) => Ok(GrpcEventStream { receiver, task }),
+            Ok(Err(err)) => Err(error::ClientError::GeyserError(err)),
+            Err(_) => Err(error::ClientError::GeyserError(
+                "Subscription task ended before subscribing".to_string(),
+            )),
+        }
+    }
+
+    /// Builds the subscription request
+    fn request(&self) -> SubscribeRequest {
+        let programs: Vec<String> = self.programs.iter().map(Pubkey::to_string).collect();
+        let mut accounts = HashMap::new();
+        if self.account_updates {
+            accounts.insert(
+                "pumpfun".to_string(),
+                SubscribeRequestFilterAccounts {
+                    owner: programs.clone(),
+                    ..Default::default()
+                },
+            );
+        }
+
+        SubscribeRequest {
+            accounts,
+            transactions: HashMap::from([(
+                "pumpfun".to_string(),
+                SubscribeRequestFilterTransactions {
+                    vote: Some(false),
+                    failed: Some(false),
+                    account_include: programs,
+                    ..Default::default()
+                },
+            )]),
+            commitment: Some(grpc_commitment(self.commitment) as i32),
+            ..Default::default()
+        }
+    }
+
+    async fn run(
+        self,
+        update_tx: mpsc::Sender<GrpcUpdate>,
+        ready_tx: oneshot::Sender<Result<(), String>>,
+    ) {
+        let mut ready_tx = Some(ready_tx);
+        let mut attempt = 0;
+
+        loop {
+            match self
+                .stream_updates(&update_tx, &mut ready_tx, &mut attempt)
+                .await
+            {
+                Ok(()) => return,
+                Err(err) => {
+                    // Report the initial failure back to the caller
+                    if let Some(ready_tx) = ready_tx.take() {
+                        let _ = ready_tx.send(Err(err));
+                        return;
+                    }
+                }
+            }
+
+            match self.reconnect.reconnect_delay(attempt) {
+                Some(delay) => {
+                    attempt += 1;
+                    tokio::time::sleep(delay).await;
+                }
+                None => return,
+            }
+        }
+    }
+
+    /// Connects, subscribes and forwards updates until the stream ends or fails
+    ///
+    /// Returns Ok once the receiving side of the channel has been closed. Errors are
+    /// returned as strings, since `ClientError` cannot be sent between tasks.
+    async fn stream_updates(
+        &self,
+        update_tx: &mpsc::Sender<GrpcUpdate>,
+        ready_tx: &mut Option<oneshot::Sender<Result<(), String>>>,
+        attempt: &mut u32,
+    ) -> Result<(), String> {
+        let mut client = GeyserGrpcClient::build_from_shared(self.endpoint.clone())
+            .and_then(|builder| builder.x_token(self.x_token.clone()))
+            .map_err(geyser_error)?
+            .connect()
+            .await
+            .map_err(geyser_error)?;
+        let (mut sink, mut stream) = client
+            .subscribe_with_request(Some(self.request()))
+            .await
+            .map_err(geyser_error)?;
+
+        if let Some(ready_tx) = ready_tx.take() {
+            let _ = ready_tx.send(Ok(()));
+        }
+        *attempt = 0;
+
+        while let Some(update) = stream.next().await {
+            let update = update.map_err(geyser_error)?;
+            let updates = match update.update_oneof {
+                Some(UpdateOneof::Transaction(update)) => update
+                    .transaction
+                    .map(|transaction| self.transaction_updates(transaction, update.slot))
+                    .unwrap_or_default(),
+                Some(UpdateOneof::Account(update)) => update
+                    .account
+                    .and_then(|account| account_update(account, update.slot))
+                    .into_iter()
+                    .collect(),
+                Some(UpdateOneof::Ping(_)) => {
+                    // Keep the connection alive through load balancers
+                    sink.send(SubscribeRequest {
+                        ping: Some(SubscribeRequestPing { id: 1 }),
+                        ..Default::default()
+                    })
+                    .await
+                    .map_err(geyser_error)?;
+                    continue;
+                }
+                _ => continue,
+            };
+
+            for update in updates {
+                if update_tx.send(update).await.is_err() {
+                    return Ok(());
+                }
+            }
+        }
+
+        if update_tx.is_closed() {
+            return Ok(());
+        }
+        Err("gRPC stream ended".to_string())
+    }
+
+    /// Extracts the events of a transaction matching the filter
+    fn transaction_updates(
+        &self,
+        transaction: SubscribeUpdateTransactionInfo,
+        slot: u64,
+    ) -> Vec<GrpcUpdate> {
+        let Ok(signature) = Signature::try_from(transaction.signature.as_slice()) else {
+            return Vec::new();
+        };
+        let signature = signature.to_string();
+        let meta = transaction.meta.unwrap_or_default();
+
+        // Static keys are followed by the keys loaded from lookup tables
+        let keys: Vec<Pubkey> = transaction
+            .transaction
+            .and_then(|transaction| transaction.message)
+            .map(|message| message.account_keys)
+            .unwrap_or_default()
+            .iter()
+            .chain(&meta.loaded_writable_addresses)
+            .chain(&meta.loaded_readonly_addresses)
+            .filter_map(|key| Pubkey::try_from(key.as_slice()).ok())
+            .collect();
+
+        let inner = meta
+            .inner_instructions
+            .iter()
+            .flat_map(|inner| &inner.instructions)
+            .map(|instruction| {
+                (
+                    instruction.program_id_index as usize,
+                    instruction.data.as_slice(),
+                )
+            });
+
+        collect_events(&signature, &keys, inner, &meta.log_messages)
+            .into_iter()
+            .enumerate()
+            .filter(|(_, event)| !matches!(event, Ok(event) if !self.filter.matches(event)))
+            .map(|(index, event)| GrpcUpdate::Event {
+                context: EventContext {
+                    signature: signature.clone(),
+                    slot,
+                    block_time: None,
+                    index,
+                },
+                event,
+            })
+            .collect()
+    }
+}
+
+fn account_update(account: SubscribeUpdateAccountInfo, slot: u64) -> Option<GrpcUpdate> {
+    Some(GrpcUpdate::Account {
+        pubkey: Pubkey::try_from(account.pubkey.as_slice()).ok()?,
+        owner: Pubkey::try_from(account.owner.as_slice()).ok()?,
+        lamports: account.lamports,
+        data: account.data,
+        slot,
+    })
+}
+
+fn grpc_commitment(commitment: CommitmentConfig) -> GrpcCommitmentLevel {
+    match commitment.commitment {
+        CommitmentLevel::Processed => GrpcCommitmentLevel::Processed,
+        CommitmentLevel::Confirmed => GrpcCommitmentLevel::Confirmed,
+        CommitmentLevel::Finalized => GrpcCommitmentLevel::Finalized,
+    }
+}
+
+fn geyser_error(err: impl std::fmt::Display) -> String {
+    err.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yellowstone_grpc_proto::prelude::{
+        InnerInstruction, InnerInstructions, Message, Transaction, TransactionStatusMeta,
+    };
+
+    #[test]
+    fn test_transaction_updates() {
+        let mut data = constants::discriminators::EVENT_IX_TAG.to_vec();
+        data.extend_from_slice(&constants::discriminators::COMPLETE_EVENT);
+        data.extend_from_slice(&[0; 32 * 3 + 8]);
+
+        let transaction = SubscribeUpdateTransactionInfo {
+            signature: vec![1; 64],
+            transaction: Some(Transaction {
+                message: Some(Message {
+                    account_keys: vec![
+                        Pubkey::new_unique().to_bytes().to_vec(),
+                        constants::accounts::PUMPFUN.to_bytes().to_vec(),
+                    ],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            meta: Some(TransactionStatusMeta {
+                inner_instructions: vec![InnerInstructions {
+                    index: 0,
+                    instructions: vec![InnerInstruction {
+                        program_id_index: 1,
+                        data,
+                        ..Default::default()
+                    }],
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let source = GrpcEventSource::new("http://localhost:10000", None);
+        let updates = source.transaction_updates(transaction.clone(), 5);
+        assert!(matches!(
+            updates.as_slice(),
+            [GrpcUpdate::Event {
+                context: EventContext {
+                    slot: 5,
+                    index: 0,
+                    ..
+                },
+                event: Ok(PumpFunEvent::Complete(_)),
+            }]
+        ));
+
+        let filtered = source
+            .with_filter(EventFilter::new().kinds([crate::common::stream::EventKind::Trade]))
+            .transaction_updates(transaction, 5);
+        assert!(filtered.is_empty());
+    }
+}
@@ -1,3 +1,23 @@
+pub mod config;
+#[cfg(feature = "stream")]
+pub mod dedupe;
+pub mod failover;
+#[cfg(feature = "stream")]
+pub mod finality;
+#[cfg(feature = "geyser")]
+pub mod geyser;
+#[cfg(feature = "stream")]
+pub mod price_feed;
+pub mod risk;
+pub mod scheduler;
+#[cfg(feature = "stream")]
+pub mod sink;
+#[cfg(feature = "storage")]
+pub mod storage;
 #[cfg(feature = "stream")]
 pub mod stream;
+#[cfg(feature = "stream")]
+pub mod trades;
 pub mod types;
+#[cfg(feature = "stream")]
+pub mod watch;
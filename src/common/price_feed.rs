@@ -0,0 +1,388 @@
+//! Live price feed for a single token
+//!
+//! `price_feed` combines the token's trade events with updates of its bonding curve account
+//! into a stream of `PriceUpdate`s carrying the spot price, the reserves it was derived from,
+//! and the latest trade. Once the token migrates, prices are taken from its Pump.fun AMM
+//! pool instead, without any action from the consumer.
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use tokio::sync::mpsc;
+
+use super::{
+    stream::{
+        spawn_subscription, EventContext, EventFilter, EventKind, PumpFunEvent, Subscription,
+    },
+    trades::{normalize, PoolMints, TradeVenue, UnifiedTrade},
+    types::Cluster,
+    watch::{watch_bonding_curve, BondingCurveUpdate},
+};
+use crate::{constants, error};
+
+/// Reserves a price was derived from
+///
+/// # Fields
+///
+/// * `sol` - Lamports in the market. Virtual SOL reserves on the bonding curve, quote
+///   reserves of the AMM pool after migration
+/// * `token` - Tokens in the market, in base units. Virtual token reserves on the bonding
+///   curve, base reserves of the AMM pool after migration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reserves {
+    pub sol: u64,
+    pub token: u64,
+}
+
+impl Reserves {
+    /// Returns the spot price in lamports per token base unit, or None without tokens
+    pub fn price(&self) -> Option<f64> {
+        (self.token > 0).then(|| self.sol as f64 / self.token as f64)
+    }
+}
+
+/// Price of a token after a trade or a change of its market
+///
+/// # Fields
+///
+/// * `mint` - Mint of the token
+/// * `slot` - Slot the price was observed in
+/// * `venue` - Market the price was taken from
+/// * `price` - Spot price in lamports per token base unit
+/// * `reserves` - Reserves the price was derived from
+/// * `last_trade` - Most recent trade of the token seen by the feed, if any
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceUpdate {
+    pub mint: Pubkey,
+    pub slot: u64,
+    pub venue: TradeVenue,
+    pub price: f64,
+    pub reserves: Reserves,
+    pub last_trade: Option<UnifiedTrade>,
+}
+
+/// Stream of price updates returned by `price_feed`
+///
+/// The underlying subscriptions are terminated when the stream is dropped.
+pub struct PriceFeed {
+    receiver: mpsc::Receiver<PriceUpdate>,
+    _subscription: Subscription,
+}
+
+impl Stream for PriceFeed {
+    type Item = PriceUpdate;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// Subscribes to the live price of a token
+///
+/// A price update is yielded for every trade of the token and every change of its bonding
+/// curve account, so a trade usually yields two updates with the same reserves. After the
+/// token migrates, updates come from its Pump.fun AMM pool, starting with the pool's
+/// `CreatePoolEvent`, and bonding curve account changes are ignored. Pools quoted in
+/// tokens other than wrapped SOL are ignored.
+///
+/// # Arguments
+///
+/// * `cluster` - Solana cluster configuration containing RPC endpoints
+/// * `mint` - Public key of the token mint
+/// * `commitment` - Optional commitment level for the subscriptions. If None, uses the
+///   default from the cluster configuration
+///
+/// # Returns
+///
+/// Returns a `PriceFeed` of price updates, or a ClientError if either subscription cannot
+/// be established
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use pumpfun::common::{price_feed::price_feed, types::{Cluster, PriorityFee}};
+/// use solana_sdk::{commitment_config::CommitmentConfig, pubkey};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+/// let mint = pubkey!("TokenM1ntPubk3yXXXXXXXXXXXXXXXXXXXXXXXXXXXX");
+/// let mut prices = price_feed(cluster, &mint, None).await?;
+///
+/// while let Some(update) = prices.next().await {
+///     println!("{:?} price at slot {}: {}", update.venue, update.slot, update.price);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn price_feed(
+    cluster: Cluster,
+    mint: &Pubkey,
+    commitment: Option<CommitmentConfig>,
+) -> Result<PriceFeed, error::ClientError> {
+    let (event_tx, mut event_rx) = mpsc::channel(1000);
+    let (update_tx, receiver) = mpsc::channel(1000);
+
+    // Both bonding curve and AMM transactions of the token mention its mint
+    let subscription = spawn_subscription(
+        cluster.clone(),
+        Some(mint.to_string()),
+        commitment,
+        EventFilter::new().kinds([
+            EventKind::Trade,
+            EventKind::Buy,
+            EventKind::Sell,
+            EventKind::CreatePool,
+        ]),
+        event_tx,
+    )
+    .await?;
+    let mut curve = watch_bonding_curve(cluster.clone(), mint, commitment).await?;
+
+    let rpc = RpcClient::new_with_commitment(
+        cluster.rpc.http.clone(),
+        commitment.unwrap_or(cluster.commitment),
+    );
+    let mut feed = Feed::new(*mint);
+    tokio::spawn(async move {
+        loop {
+            let update = tokio::select! {
+                item = event_rx.recv() => {
+                    let Some((context, event, _)) = item else {
+                        return;
+                    };
+                    match event {
+                        Ok(event) => feed.apply_event(&rpc, context, event).await,
+                        Err(_) => None,
+                    }
+                }
+                update = curve.next() => match update {
+                    Some(update) => feed.apply_curve(update),
+                    None => return,
+                },
+                _ = update_tx.closed() => return,
+            };
+            if let Some(update) = update {
+                if update_tx.send(update).await.is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    Ok(PriceFeed {
+        receiver,
+        _subscription: subscription,
+    })
+}
+
+/// Price state of the token followed by a feed
+struct Feed {
+    mint: Pubkey,
+    pools: PoolMints,
+    last_trade: Option<UnifiedTrade>,
+    migrated: bool,
+}
+
+impl Feed {
+    fn new(mint: Pubkey) -> Self {
+        Self {
+            mint,
+            pools: PoolMints::default(),
+            last_trade: None,
+            migrated: false,
+        }
+    }
+
+    async fn apply_event(
+        &mut self,
+        rpc: &RpcClient,
+        context: EventContext,
+        event: PumpFunEvent,
+    ) -> Option<PriceUpdate> {
+        let slot = context.slot;
+        let (venue, reserves) = match &event {
+            PumpFunEvent::Trade(trade) if trade.mint == self.mint => (
+                TradeVenue::BondingCurve,
+                Reserves {
+                    sol: trade.virtual_sol_reserves,
+                    token: trade.virtual_token_reserves,
+                },
+            ),
+            // Reserves in AMM events are those before the trade
+            PumpFunEvent::Buy(buy) => (
+                TradeVenue::Amm,
+                Reserves {
+                    sol: buy
+                        .pool_quote_token_reserves
+                        .saturating_add(buy.quote_amount_in_with_lp_fee),
+                    token: buy
+                        .pool_base_token_reserves
+                        .saturating_sub(buy.base_amount_out),
+                },
+            ),
+            PumpFunEvent::Sell(sell) => (
+                TradeVenue::Amm,
+                Reserves {
+                    sol: sell
+                        .pool_quote_token_reserves
+                        .saturating_sub(sell.quote_amount_out_without_lp_fee),
+                    token: sell
+                        .pool_base_token_reserves
+                        .saturating_add(sell.base_amount_in),
+                },
+            ),
+            PumpFunEvent::CreatePool(pool) if pool.base_mint == self.mint => {
+                if pool.quote_mint != constants::accounts::WSOL {
+                    return None;
+                }
+                self.pools.insert(pool.pool, Some(self.mint));
+                self.migrated = true;
+                let reserves = Reserves {
+                    sol: pool.pool_quote_amount,
+                    token: pool.pool_base_amount,
+                };
+                return self.update(slot, TradeVenue::Amm, reserves);
+            }
+            _ => return None,
+        };
+
+        // Resolves the pool of AMM trades, skipping trades of other pools in the transaction
+        let trade = normalize(rpc, &mut self.pools, context, event).await?;
+        if trade.mint != self.mint {
+            return None;
+        }
+        if venue == TradeVenue::Amm {
+            self.migrated = true;
+        }
+        self.last_trade = Some(trade);
+        self.update(slot, venue, reserves)
+    }
+
+    fn apply_curve(&mut self, update: BondingCurveUpdate) -> Option<PriceUpdate> {
+        if self.migrated {
+            return None;
+        }
+        let reserves = Reserves {
+            sol: update.account.virtual_sol_reserves,
+            token: update.account.virtual_token_reserves,
+        };
+        self.update(update.slot, TradeVenue::BondingCurve, reserves)
+    }
+
+    fn update(&self, slot: u64, venue: TradeVenue, reserves: Reserves) -> Option<PriceUpdate> {
+        Some(PriceUpdate {
+            mint: self.mint,
+            slot,
+            venue,
+            price: reserves.price()?,
+            reserves,
+            last_trade: self.last_trade.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        accounts::BondingCurveAccount,
+        common::{stream::TradeEvent, trades::TradeSide},
+    };
+
+    fn context(slot: u64) -> EventContext {
+        EventContext {
+            signature: "sig".to_string(),
+            slot,
+            block_time: None,
+            index: 0,
+        }
+    }
+
+    fn trade(mint: Pubkey, virtual_sol_reserves: u64, virtual_token_reserves: u64) -> PumpFunEvent {
+        PumpFunEvent::Trade(TradeEvent {
+            mint,
+            sol_amount: 10,
+            token_amount: 100,
+            is_buy: true,
+            user: Pubkey::new_unique(),
+            timestamp: 0,
+            virtual_sol_reserves,
+            virtual_token_reserves,
+            real_sol_reserves: 0,
+            real_token_reserves: 0,
+            fee_recipient: Pubkey::new_unique(),
+            fee_basis_points: 0,
+            fee: 0,
+            creator: Pubkey::new_unique(),
+            creator_fee_basis_points: 0,
+            creator_fee: 0,
+            track_volume: false,
+            total_unclaimed_tokens: 0,
+            total_claimed_tokens: 0,
+            current_sol_volume: 0,
+            last_update_timestamp: 0,
+        })
+    }
+
+    fn curve(virtual_sol_reserves: u64, virtual_token_reserves: u64) -> BondingCurveUpdate {
+        BondingCurveUpdate {
+            slot: 3,
+            account: BondingCurveAccount::new(
+                0,
+                virtual_token_reserves,
+                virtual_sol_reserves,
+                0,
+                0,
+                0,
+                false,
+                Pubkey::new_unique(),
+            ),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_feed() {
+        // Bonding curve trades never need to be resolved through RPC
+        let rpc = RpcClient::new("http://127.0.0.1:1".to_string());
+        let mint = Pubkey::new_unique();
+        let mut feed = Feed::new(mint);
+
+        let update = feed.apply_curve(curve(30, 1_000)).unwrap();
+        assert_eq!(update.venue, TradeVenue::BondingCurve);
+        assert!((update.price - 0.03).abs() < f64::EPSILON);
+        assert!(update.last_trade.is_none());
+
+        let update = feed
+            .apply_event(&rpc, context(4), trade(mint, 40, 1_000))
+            .await
+            .unwrap();
+        assert_eq!(update.slot, 4);
+        assert_eq!(
+            update.reserves,
+            Reserves {
+                sol: 40,
+                token: 1_000
+            }
+        );
+        assert_eq!(update.last_trade.unwrap().side, TradeSide::Buy);
+
+        // Trades of other tokens in the same transaction are skipped
+        assert!(feed
+            .apply_event(&rpc, context(4), trade(Pubkey::new_unique(), 1, 1))
+            .await
+            .is_none());
+
+        // Empty reserves have no price
+        assert!(feed.apply_curve(curve(40, 0)).is_none());
+
+        feed.migrated = true;
+        assert!(feed.apply_curve(curve(30, 1_000)).is_none());
+    }
+}
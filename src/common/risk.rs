@@ -0,0 +1,492 @@
+//! Risk limits enforced before buys are sent
+//!
+//! A client configured with `RiskLimits` checks every buy it builds against them and fails
+//! with `ClientError::RiskRejected` instead of trading past a limit. Limits cover the size
+//! of a single trade, the number of open positions, the SOL lost in the current UTC day,
+//! and the SOL exposed to tokens of a single creator.
+//!
+//! # Bookkeeping
+//!
+//! Limits are checked when a buy is built, but positions only change once a trade lands:
+//! the client holds the trade behind each buy or sell instruction it builds for its payer,
+//! and records it when a transaction carrying the instruction is confirmed. Buys add their
+//! SOL amount and quoted tokens to the mint's position, and sells remove the average cost of
+//! the tokens sold and realize the difference to their quoted proceeds. Trades that fail or
+//! are never sent are not recorded, and neither are unsigned transactions built for external
+//! signers.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Mutex, MutexGuard},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+/// Seconds in a UTC day, after which the daily loss resets
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Maximum number of built trades waiting for their transaction, after which the oldest
+/// are dropped
+const MAX_PENDING_TRADES: usize = 256;
+
+/// Limits checked before every buy
+///
+/// Unset limits are not enforced, so the default limits allow every buy.
+///
+/// # Fields
+///
+/// * `max_trade_sol` - Maximum lamports spent by a single buy
+/// * `max_open_positions` - Maximum number of mints held at once. Buys adding to an open
+///   position are always allowed by this limit
+/// * `max_daily_loss` - Buys are rejected once the net SOL realized in the current UTC day
+///   is a loss of at least this many lamports
+/// * `max_creator_exposure` - Maximum lamports held in open positions of tokens by a
+///   single creator, including the buy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RiskLimits {
+    pub max_trade_sol: Option<u64>,
+    pub max_open_positions: Option<usize>,
+    pub max_daily_loss: Option<u64>,
+    pub max_creator_exposure: Option<u64>,
+}
+
+/// Limit a rejected buy would have exceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RiskRejection {
+    /// The buy spends more than the maximum trade size
+    TradeSize { amount: u64, max: u64 },
+    /// The buy opens a position while the maximum number of positions is open
+    OpenPositions { max: usize },
+    /// The day's realized loss has reached the maximum daily loss
+    DailyLoss { loss: u64, max: u64 },
+    /// The buy raises the exposure to a creator's tokens above the maximum
+    CreatorExposure {
+        creator: Pubkey,
+        exposure: u64,
+        max: u64,
+    },
+}
+
+impl std::fmt::Display for RiskRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TradeSize { amount, max } => write!(
+                f,
+                "buy of {} lamports exceeds the maximum trade size of {} lamports",
+                amount, max
+            ),
+            Self::OpenPositions { max } => {
+                write!(f, "maximum of {} open positions reached", max)
+            }
+            Self::DailyLoss { loss, max } => write!(
+                f,
+                "daily loss of {} lamports reached the maximum of {} lamports",
+                loss, max
+            ),
+            Self::CreatorExposure {
+                creator,
+                exposure,
+                max,
+            } => write!(
+                f,
+                "exposure of {} lamports to creator {} exceeds the maximum of {} lamports",
+                exposure, creator, max
+            ),
+        }
+    }
+}
+
+/// Trade counted against the limits once it lands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RiskTrade {
+    /// Buy spending `amount_sol` lamports for `tokens` quoted tokens of a creator's mint
+    Buy {
+        mint: Pubkey,
+        creator: Pubkey,
+        amount_sol: u64,
+        tokens: u64,
+    },
+    /// Sell of `tokens` tokens for `proceeds` quoted lamports
+    Sell {
+        mint: Pubkey,
+        tokens: u64,
+        proceeds: u64,
+    },
+}
+
+/// Trade instruction built by the client, waiting for its transaction to land
+#[derive(Debug)]
+struct PendingTrade {
+    instruction: Instruction,
+    trade: RiskTrade,
+}
+
+/// Tracked position of a mint
+#[derive(Debug, Clone, Copy)]
+struct Exposure {
+    creator: Pubkey,
+    tokens: u64,
+    cost: u64,
+}
+
+/// Positions and realized PnL tracked against the limits
+#[derive(Debug, Default)]
+struct RiskState {
+    positions: HashMap<Pubkey, Exposure>,
+    day: u64,
+    realized: i64,
+}
+
+impl RiskState {
+    /// Returns the loss realized in a day, resetting the realized PnL when a new day starts
+    fn daily_loss(&mut self, day: u64) -> u64 {
+        if self.day != day {
+            self.day = day;
+            self.realized = 0;
+        }
+        self.realized.min(0).unsigned_abs()
+    }
+}
+
+/// Enforces `RiskLimits` against the positions built by a client
+pub struct RiskManager {
+    limits: RiskLimits,
+    state: Mutex<RiskState>,
+    pending: Mutex<VecDeque<PendingTrade>>,
+}
+
+impl RiskManager {
+    /// Creates a risk manager without tracked positions
+    pub fn new(limits: RiskLimits) -> Self {
+        Self {
+            limits,
+            state: Mutex::new(RiskState::default()),
+            pending: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Returns the enforced limits
+    pub fn limits(&self) -> &RiskLimits {
+        &self.limits
+    }
+
+    /// Checks a buy against the limits and the recorded positions
+    ///
+    /// Nothing is recorded; record the buy with `record` once it lands.
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - Mint of the bought token
+    /// * `creator` - Creator of the token
+    /// * `amount_sol` - Lamports spent by the buy
+    ///
+    /// # Errors
+    ///
+    /// Returns the exceeded limit if the buy is rejected
+    pub fn check_buy(
+        &self,
+        mint: &Pubkey,
+        creator: &Pubkey,
+        amount_sol: u64,
+    ) -> Result<(), RiskRejection> {
+        self.check_buy_on(today(), mint, creator, amount_sol)
+    }
+
+    fn check_buy_on(
+        &self,
+        day: u64,
+        mint: &Pubkey,
+        creator: &Pubkey,
+        amount_sol: u64,
+    ) -> Result<(), RiskRejection> {
+        let limits = &self.limits;
+        if let Some(max) = limits.max_trade_sol.filter(|max| amount_sol > *max) {
+            return Err(RiskRejection::TradeSize {
+                amount: amount_sol,
+                max,
+            });
+        }
+
+        let mut state = self.lock_state();
+        let loss = state.daily_loss(day);
+        if let Some(max) = limits.max_daily_loss.filter(|max| loss >= *max) {
+            return Err(RiskRejection::DailyLoss { loss, max });
+        }
+        if let Some(max) = limits.max_open_positions {
+            if !state.positions.contains_key(mint) && state.positions.len() >= max {
+                return Err(RiskRejection::OpenPositions { max });
+            }
+        }
+        if let Some(max) = limits.max_creator_exposure {
+            let exposure = state
+                .positions
+                .values()
+                .filter(|position| position.creator == *creator)
+                .fold(amount_sol, |total, position| {
+                    total.saturating_add(position.cost)
+                });
+            if exposure > max {
+                return Err(RiskRejection::CreatorExposure {
+                    creator: *creator,
+                    exposure,
+                    max,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Records a trade that landed
+    ///
+    /// Buys add to the mint's position. Sells realize their quoted proceeds against the
+    /// average cost of the tokens sold; sells of mints without a tracked position are
+    /// ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `trade` - Trade to record
+    pub fn record(&self, trade: &RiskTrade) {
+        self.record_on(today(), trade)
+    }
+
+    fn record_on(&self, day: u64, trade: &RiskTrade) {
+        match *trade {
+            RiskTrade::Buy {
+                mint,
+                creator,
+                amount_sol,
+                tokens,
+            } => {
+                let mut state = self.lock_state();
+                let position = state.positions.entry(mint).or_insert(Exposure {
+                    creator,
+                    tokens: 0,
+                    cost: 0,
+                });
+                position.tokens = position.tokens.saturating_add(tokens);
+                position.cost = position.cost.saturating_add(amount_sol);
+            }
+            RiskTrade::Sell {
+                mint,
+                tokens,
+                proceeds,
+            } => self.record_sell_on(day, &mint, tokens, proceeds),
+        }
+    }
+
+    /// Holds the trade made by an instruction until a transaction carrying it is settled
+    ///
+    /// # Arguments
+    ///
+    /// * `instruction` - Buy or sell instruction making the trade
+    /// * `trade` - Trade recorded once the instruction lands
+    pub(crate) fn hold(&self, instruction: &Instruction, trade: RiskTrade) {
+        let mut pending = lock(&self.pending);
+        if pending.len() >= MAX_PENDING_TRADES {
+            pending.pop_front();
+        }
+        pending.push_back(PendingTrade {
+            instruction: instruction.clone(),
+            trade,
+        });
+    }
+
+    /// Settles the held trades of a transaction's instructions
+    ///
+    /// # Arguments
+    ///
+    /// * `instructions` - Instructions of the transaction
+    /// * `landed` - Whether the transaction was confirmed. The trades are recorded if it was
+    ///   and dropped otherwise
+    pub(crate) fn settle(&self, instructions: &[Instruction], landed: bool) {
+        let mut settled = Vec::new();
+        {
+            let mut pending = lock(&self.pending);
+            for instruction in instructions {
+                if let Some(index) = pending
+                    .iter()
+                    .position(|held| held.instruction == *instruction)
+                {
+                    settled.extend(pending.remove(index).map(|held| held.trade));
+                }
+            }
+        }
+        if landed {
+            for trade in &settled {
+                self.record(trade);
+            }
+        }
+    }
+
+    fn record_sell_on(&self, day: u64, mint: &Pubkey, tokens: u64, proceeds: u64) {
+        let mut state = self.lock_state();
+        state.daily_loss(day);
+        let Some(position) = state.positions.get_mut(mint) else {
+            return;
+        };
+        let sold = tokens.min(position.tokens);
+        let cost = match position.tokens {
+            0 => position.cost,
+            held => (position.cost as u128 * sold as u128 / held as u128) as u64,
+        };
+        position.tokens -= sold;
+        position.cost -= cost;
+        if position.tokens == 0 {
+            state.positions.remove(mint);
+        }
+        state.realized = state
+            .realized
+            .saturating_add(proceeds as i64)
+            .saturating_sub(cost as i64);
+    }
+
+    /// Stops tracking the position of a mint, e.g. after tokens were moved out of the wallet
+    pub fn clear_position(&self, mint: &Pubkey) {
+        self.lock_state().positions.remove(mint);
+    }
+
+    /// Returns the number of open positions
+    pub fn open_positions(&self) -> usize {
+        self.lock_state().positions.len()
+    }
+
+    /// Returns the loss realized in the current UTC day, in lamports
+    pub fn daily_loss(&self) -> u64 {
+        self.lock_state().daily_loss(today())
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, RiskState> {
+        lock(&self.state)
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Returns the number of the current UTC day since the Unix epoch
+fn today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() / SECONDS_PER_DAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buy(mint: Pubkey, creator: Pubkey, amount_sol: u64, tokens: u64) -> RiskTrade {
+        RiskTrade::Buy {
+            mint,
+            creator,
+            amount_sol,
+            tokens,
+        }
+    }
+
+    fn sell(mint: Pubkey, tokens: u64, proceeds: u64) -> RiskTrade {
+        RiskTrade::Sell {
+            mint,
+            tokens,
+            proceeds,
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        let risk = RiskManager::new(RiskLimits {
+            max_trade_sol: Some(100),
+            max_open_positions: Some(2),
+            max_creator_exposure: Some(150),
+            ..RiskLimits::default()
+        });
+        let creator = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let [a, b, c] = [(); 3].map(|_| Pubkey::new_unique());
+
+        assert_eq!(
+            risk.check_buy(&a, &creator, 101),
+            Err(RiskRejection::TradeSize {
+                amount: 101,
+                max: 100
+            })
+        );
+        risk.check_buy(&a, &creator, 100).unwrap();
+        risk.record(&buy(a, creator, 100, 1_000));
+        assert!(matches!(
+            risk.check_buy(&b, &creator, 60),
+            Err(RiskRejection::CreatorExposure { exposure: 160, .. })
+        ));
+        risk.record(&buy(b, other, 60, 1_000));
+        assert_eq!(
+            risk.check_buy(&c, &other, 10),
+            Err(RiskRejection::OpenPositions { max: 2 })
+        );
+        // Adding to an open position does not open a new one
+        risk.check_buy(&a, &other, 50).unwrap();
+
+        // Closing a position frees a slot
+        risk.record(&sell(b, 1_000, 60));
+        assert_eq!(risk.open_positions(), 1);
+        risk.check_buy(&c, &other, 10).unwrap();
+    }
+
+    #[test]
+    fn test_daily_loss() {
+        let risk = RiskManager::new(RiskLimits {
+            max_daily_loss: Some(50),
+            ..RiskLimits::default()
+        });
+        let creator = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        risk.check_buy_on(1, &mint, &creator, 100).unwrap();
+        risk.record_on(1, &buy(mint, creator, 100, 1_000));
+        // Half the position sold at a loss of 30, then the rest at a loss of 20
+        risk.record_on(1, &sell(mint, 500, 20));
+        risk.record_on(1, &buy(mint, creator, 10, 100));
+        risk.record_on(1, &sell(mint, 600, 40));
+        assert!(matches!(
+            risk.check_buy_on(1, &mint, &creator, 10),
+            Err(RiskRejection::DailyLoss { max: 50, .. })
+        ));
+
+        // The loss resets on the next day
+        risk.check_buy_on(2, &mint, &creator, 10).unwrap();
+    }
+
+    #[test]
+    fn test_held_trades() {
+        let risk = RiskManager::new(RiskLimits::default());
+        let [a, b] = [(); 2].map(|_| Pubkey::new_unique());
+        let instruction =
+            |data: u8| Instruction::new_with_bytes(Pubkey::new_unique(), &[data], vec![]);
+        let (buy_a, buy_b) = (instruction(1), instruction(2));
+        risk.hold(&buy_a, buy(a, Pubkey::new_unique(), 100, 1_000));
+        risk.hold(&buy_b, buy(b, Pubkey::new_unique(), 100, 1_000));
+
+        // Trades that are built but not confirmed are not recorded
+        assert_eq!(risk.open_positions(), 0);
+        risk.settle(std::slice::from_ref(&buy_a), false);
+        risk.settle(std::slice::from_ref(&buy_a), true);
+        assert_eq!(risk.open_positions(), 0);
+
+        risk.settle(&[instruction(3), buy_b.clone()], true);
+        assert_eq!(risk.open_positions(), 1);
+        // Each held trade is recorded once
+        risk.settle(&[buy_b], true);
+        assert_eq!(
+            risk.lock_state()
+                .positions
+                .get(&b)
+                .map(|position| position.cost),
+            Some(100)
+        );
+    }
+}
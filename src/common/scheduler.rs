@@ -0,0 +1,324 @@
+//! Scheduled maintenance jobs for long-running processes
+//!
+//! This module provides `Scheduler`, a small cron-like runner for routine maintenance such as
+//! claiming creator fees, closing dust token accounts, or generating reports. Jobs are async
+//! closures registered with a `Schedule`, so they can use any part of the SDK, including a
+//! shared `PumpFun` client.
+//!
+//! Every run produces a `JobReport` that is broadcast to subscribers, which makes it easy to
+//! log job outcomes or forward failures to an alerting system.
+//!
+//! All calendar schedules are evaluated in UTC.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::{sync::broadcast, task::JoinHandle};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// When a scheduled job runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Schedule {
+    /// Runs repeatedly with a fixed delay between runs, starting one interval after startup
+    Every(Duration),
+    /// Runs once a day at the given UTC time
+    Daily {
+        /// Hour of the day (0-23)
+        hour: u8,
+        /// Minute of the hour (0-59)
+        minute: u8,
+    },
+    /// Runs once a week on the given day at the given UTC time
+    Weekly {
+        /// Day of the week, where 0 is Monday and 6 is Sunday
+        weekday: u8,
+        /// Hour of the day (0-23)
+        hour: u8,
+        /// Minute of the hour (0-59)
+        minute: u8,
+    },
+}
+
+impl Schedule {
+    /// Computes the next run time after the given time
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - Current time as a Unix timestamp in seconds
+    ///
+    /// # Returns
+    ///
+    /// The next run time as a Unix timestamp in seconds, always later than `now`
+    pub fn next_after(&self, now: u64) -> u64 {
+        match *self {
+            Self::Every(interval) => now + interval.as_secs().max(1),
+            Self::Daily { hour, minute } => {
+                let next = now - now % SECONDS_PER_DAY + Self::time_of_day(hour, minute);
+                if next > now {
+                    next
+                } else {
+                    next + SECONDS_PER_DAY
+                }
+            }
+            Self::Weekly {
+                weekday,
+                hour,
+                minute,
+            } => {
+                let days = now / SECONDS_PER_DAY;
+                // The Unix epoch fell on a Thursday
+                let today = (days + 3) % 7;
+                let ahead = (u64::from(weekday % 7) + 7 - today) % 7;
+                let next = (days + ahead) * SECONDS_PER_DAY + Self::time_of_day(hour, minute);
+                if next > now {
+                    next
+                } else {
+                    next + 7 * SECONDS_PER_DAY
+                }
+            }
+        }
+    }
+
+    fn time_of_day(hour: u8, minute: u8) -> u64 {
+        u64::from(hour.min(23)) * 3600 + u64::from(minute.min(59)) * 60
+    }
+}
+
+/// Outcome of a single job run
+///
+/// # Fields
+///
+/// * `job` - Name the job was registered with
+/// * `started_at` - Unix timestamp in seconds when the run started
+/// * `elapsed` - How long the run took
+/// * `error` - Error message if the run failed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobReport {
+    pub job: String,
+    pub started_at: u64,
+    pub elapsed: Duration,
+    pub error: Option<String>,
+}
+
+/// Future returned by a scheduled job
+pub type JobFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+
+struct Job {
+    name: String,
+    schedule: Schedule,
+    run: Arc<dyn Fn() -> JobFuture + Send + Sync>,
+}
+
+/// Cron-like runner for maintenance jobs
+///
+/// Register jobs with `add_job`, then call `start` to run each of them on its own background
+/// task. A job never overlaps with itself: the next run is scheduled after the previous one
+/// finishes.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use pumpfun::common::scheduler::{Schedule, Scheduler};
+/// # use std::time::Duration;
+/// #
+/// # async fn example() {
+/// let handle = Scheduler::new()
+///     .add_job("claim-creator-fees", Schedule::Daily { hour: 0, minute: 0 }, || async {
+///         // Claim fees using a shared `PumpFun` client
+///         Ok(())
+///     })
+///     .add_job("heartbeat", Schedule::Every(Duration::from_secs(60)), || async { Ok(()) })
+///     .start();
+///
+/// let mut reports = handle.subscribe();
+/// while let Ok(report) = reports.recv().await {
+///     if let Some(err) = report.error {
+///         eprintln!("{} failed: {}", report.job, err);
+///     }
+/// }
+/// # }
+/// ```
+pub struct Scheduler {
+    jobs: Vec<Job>,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scheduler {
+    /// Creates a scheduler with no jobs
+    pub fn new() -> Self {
+        Self { jobs: Vec::new() }
+    }
+
+    /// Registers a job
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name reported with each run of the job
+    /// * `schedule` - When the job runs
+    /// * `job` - Closure producing the future to run; errors are reported, not propagated
+    ///
+    /// # Returns
+    ///
+    /// The scheduler with the job registered
+    pub fn add_job<F, Fut>(mut self, name: impl Into<String>, schedule: Schedule, job: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.jobs.push(Job {
+            name: name.into(),
+            schedule,
+            run: Arc::new(move || Box::pin(job()) as JobFuture),
+        });
+        self
+    }
+
+    /// Starts running all registered jobs in the background
+    ///
+    /// # Returns
+    ///
+    /// A handle used to observe job reports and stop the scheduler
+    pub fn start(self) -> SchedulerHandle {
+        let (reports, _) = broadcast::channel(64);
+
+        let tasks = self
+            .jobs
+            .into_iter()
+            .map(|job| {
+                let reports = reports.clone();
+                tokio::spawn(async move {
+                    loop {
+                        let now = unix_now();
+                        let delay = job.schedule.next_after(now).saturating_sub(now);
+                        tokio::time::sleep(Duration::from_secs(delay)).await;
+
+                        let started_at = unix_now();
+                        let start = Instant::now();
+                        let result = (job.run)().await;
+
+                        // Sending only fails when nobody is subscribed
+                        let _ = reports.send(JobReport {
+                            job: job.name.clone(),
+                            started_at,
+                            elapsed: start.elapsed(),
+                            error: result.err(),
+                        });
+                    }
+                })
+            })
+            .collect();
+
+        SchedulerHandle { tasks, reports }
+    }
+}
+
+/// Handle to a running `Scheduler`
+///
+/// Dropping the handle leaves the jobs running; call `stop` to cancel them.
+pub struct SchedulerHandle {
+    tasks: Vec<JoinHandle<()>>,
+    reports: broadcast::Sender<JobReport>,
+}
+
+impl SchedulerHandle {
+    /// Subscribes to job reports
+    ///
+    /// # Returns
+    ///
+    /// A broadcast receiver yielding a `JobReport` after every job run
+    pub fn subscribe(&self) -> broadcast::Receiver<JobReport> {
+        self.reports.subscribe()
+    }
+
+    /// Stops all jobs, cancelling any run that is in progress
+    pub fn stop(self) {
+        for task in self.tasks {
+            task.abort();
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Thursday, 2024-01-04 12:00:00 UTC
+    const NOW: u64 = 1_704_369_600;
+
+    #[test]
+    fn test_next_after() {
+        let every = Schedule::Every(Duration::from_secs(90));
+        assert_eq!(every.next_after(NOW), NOW + 90);
+
+        let later_today = Schedule::Daily {
+            hour: 13,
+            minute: 30,
+        };
+        assert_eq!(later_today.next_after(NOW), NOW + 5_400);
+
+        let tomorrow = Schedule::Daily {
+            hour: 12,
+            minute: 0,
+        };
+        assert_eq!(tomorrow.next_after(NOW), NOW + SECONDS_PER_DAY);
+
+        // Monday 2024-01-08 00:00:00 UTC
+        let monday = Schedule::Weekly {
+            weekday: 0,
+            hour: 0,
+            minute: 0,
+        };
+        assert_eq!(monday.next_after(NOW), 1_704_672_000);
+
+        let thursday = Schedule::Weekly {
+            weekday: 3,
+            hour: 12,
+            minute: 0,
+        };
+        assert_eq!(thursday.next_after(NOW), NOW + 7 * SECONDS_PER_DAY);
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_reports() {
+        let handle = Scheduler::new()
+            .add_job("ok", Schedule::Every(Duration::from_secs(1)), || async {
+                Ok(())
+            })
+            .add_job("fail", Schedule::Every(Duration::from_secs(1)), || async {
+                Err("boom".to_string())
+            })
+            .start();
+        let mut reports = handle.subscribe();
+
+        let mut seen = Vec::new();
+        while seen.len() < 2 {
+            let report = reports.recv().await.unwrap();
+            if !seen.iter().any(|r: &JobReport| r.job == report.job) {
+                seen.push(report);
+            }
+        }
+        handle.stop();
+
+        seen.sort_by(|a, b| a.job.cmp(&b.job));
+        assert_eq!(seen[0].error.as_deref(), Some("boom"));
+        assert_eq!(seen[1].error, None);
+    }
+}
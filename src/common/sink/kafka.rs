@@ -0,0 +1,90 @@
+//! Kafka event sink
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use rskafka::{
+    client::{
+        partition::{Compression, PartitionClient, UnknownTopicHandling},
+        ClientBuilder,
+    },
+    record::Record,
+};
+
+use super::{EventSink, SinkMessage};
+use crate::error;
+
+/// Produces events to a partition of a Kafka topic
+///
+/// Each event is produced as a record whose value is the JSON-encoded `SinkMessage` and
+/// whose key is `SinkMessage::key`. The event kind is attached in the `kind` header, so
+/// consumers can skip events without decoding them.
+pub struct KafkaSink {
+    client: PartitionClient,
+}
+
+impl KafkaSink {
+    /// Connects to a Kafka cluster
+    ///
+    /// # Arguments
+    ///
+    /// * `brokers` - Bootstrap broker addresses, e.g. `localhost:9092`
+    /// * `topic` - Topic to produce events to
+    /// * `partition` - Partition of the topic to produce events to
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `KafkaSink`, or a ClientError if the cluster cannot be reached
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pumpfun::common::sink::KafkaSink;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sink = KafkaSink::connect(vec!["localhost:9092".to_string()], "pumpfun-events", 0).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect(
+        brokers: Vec<String>,
+        topic: &str,
+        partition: i32,
+    ) -> Result<Self, error::ClientError> {
+        let client = ClientBuilder::new(brokers).build().await.map_err(|err| {
+            error::ClientError::SinkError(format!("Failed to connect to Kafka: {}", err))
+        })?;
+        let client = client
+            .partition_client(topic, partition, UnknownTopicHandling::Retry)
+            .await
+            .map_err(|err| {
+                error::ClientError::SinkError(format!(
+                    "Failed to open Kafka partition {} of {}: {}",
+                    partition, topic, err
+                ))
+            })?;
+        Ok(Self { client })
+    }
+}
+
+#[async_trait]
+impl EventSink for KafkaSink {
+    async fn publish(&self, message: &SinkMessage) -> Result<(), error::ClientError> {
+        let record = Record {
+            key: Some(message.key().into_bytes()),
+            value: Some(message.to_json()?),
+            headers: BTreeMap::from([(
+                "kind".to_string(),
+                format!("{:?}", message.event.kind()).into_bytes(),
+            )]),
+            timestamp: chrono::Utc::now(),
+        };
+        self.client
+            .produce(vec![record], Compression::NoCompression)
+            .await
+            .map_err(|err| {
+                error::ClientError::SinkError(format!("Failed to produce to Kafka: {}", err))
+            })?;
+        Ok(())
+    }
+}
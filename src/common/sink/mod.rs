@@ -0,0 +1,271 @@
+//! Event sinks for publishing parsed events to external systems
+//!
+//! This module defines the `EventSink` trait, which receives every event of a subscription,
+//! and `subscribe_to_sinks`, which feeds a Pump.fun event subscription into one or more
+//! sinks. Events are published as `SinkMessage`s, serialized to JSON with `SinkMessage::to_json`.
+//!
+//! # Sinks
+//!
+//! - `RedisSink`: Publishes to a Redis pub/sub channel (requires the `redis` feature).
+//! - `KafkaSink`: Produces records to a Kafka topic partition (requires the `kafka` feature).
+//! - `NatsSink`: Publishes to a NATS subject (requires the `nats` feature).
+//!
+//! Custom destinations, such as databases or in-process queues, implement `EventSink`
+//! directly.
+
+#[cfg(feature = "kafka")]
+mod kafka;
+#[cfg(feature = "nats")]
+mod nats;
+#[cfg(feature = "redis")]
+mod redis;
+
+#[cfg(feature = "redis")]
+pub use self::redis::*;
+#[cfg(feature = "kafka")]
+pub use kafka::*;
+#[cfg(feature = "nats")]
+pub use nats::*;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use solana_sdk::commitment_config::CommitmentConfig;
+use tokio::sync::mpsc;
+
+use super::{
+    stream::{
+        spawn_subscription, EventContext, EventFilter, EventStreamItem, PumpFunEvent, Subscription,
+    },
+    types::Cluster,
+};
+use crate::error;
+
+/// Event published to an `EventSink`
+///
+/// # Fields
+///
+/// * `context` - Transaction and position the event was emitted in
+/// * `event` - Parsed event
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SinkMessage {
+    pub context: EventContext,
+    pub event: PumpFunEvent,
+}
+
+impl SinkMessage {
+    /// Serializes the message to JSON
+    ///
+    /// # Returns
+    ///
+    /// Returns the JSON-encoded message, or a ClientError if serialization fails
+    pub fn to_json(&self) -> Result<Vec<u8>, error::ClientError> {
+        serde_json::to_vec(self).map_err(|err| {
+            error::ClientError::SinkError(format!("Failed to serialize event: {}", err))
+        })
+    }
+
+    /// Returns the key identifying the message, formatted as `<signature>:<index>`
+    ///
+    /// Sinks that partition or deduplicate messages by key use it to keep redeliveries of
+    /// the same event together.
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.context.signature, self.context.index)
+    }
+}
+
+/// Receives the events of a subscription
+///
+/// Implementations publish each message to their destination and return once it has been
+/// accepted, so events are delivered to a sink in the order they were received.
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    /// Publishes a single event
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Event to publish
+    ///
+    /// # Returns
+    ///
+    /// Returns Ok(()) once the event has been published, or a ClientError if it could not be
+    async fn publish(&self, message: &SinkMessage) -> Result<(), error::ClientError>;
+}
+
+#[async_trait]
+impl<T: EventSink + ?Sized> EventSink for Arc<T> {
+    async fn publish(&self, message: &SinkMessage) -> Result<(), error::ClientError> {
+        (**self).publish(message).await
+    }
+}
+
+/// Subscribes to Pump.fun program events and publishes them to event sinks
+///
+/// Every event matching `filter` is published to each sink in turn. Events that fail to
+/// parse are skipped, and an event a sink fails to publish is dropped for that sink only,
+/// so a failing destination does not hold up the others. Sinks that need delivery
+/// guarantees should retry within `publish`.
+///
+/// # Arguments
+///
+/// * `cluster` - Solana cluster configuration containing RPC endpoints
+/// * `filter` - Event kinds and accounts to publish events for
+/// * `commitment` - Optional commitment level for the subscription. If None, uses the
+///   default from the cluster configuration
+/// * `sinks` - Sinks to publish events to
+///
+/// # Returns
+///
+/// Returns a `Subscription` that stops publishing when dropped, or a ClientError if the
+/// subscription cannot be established
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+///
+/// use async_trait::async_trait;
+/// use pumpfun::{
+///     common::{
+///         sink::{subscribe_to_sinks, EventSink, SinkMessage},
+///         stream::{EventFilter, EventKind},
+///         types::{Cluster, PriorityFee},
+///     },
+///     error::ClientError,
+/// };
+/// use solana_sdk::commitment_config::CommitmentConfig;
+///
+/// struct StdoutSink;
+///
+/// #[async_trait]
+/// impl EventSink for StdoutSink {
+///     async fn publish(&self, message: &SinkMessage) -> Result<(), ClientError> {
+///         println!("{}", String::from_utf8_lossy(&message.to_json()?));
+///         Ok(())
+///     }
+/// }
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+/// let filter = EventFilter::new().kinds([EventKind::Create, EventKind::Complete]);
+/// let subscription = subscribe_to_sinks(cluster, filter, None, vec![Arc::new(StdoutSink)]).await?;
+///
+/// tokio::signal::ctrl_c().await?;
+/// subscription.shutdown().await;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn subscribe_to_sinks(
+    cluster: Cluster,
+    filter: EventFilter,
+    commitment: Option<CommitmentConfig>,
+    sinks: Vec<Arc<dyn EventSink>>,
+) -> Result<Subscription, error::ClientError> {
+    let (event_tx, event_rx) = mpsc::channel(1000);
+    let mentioned = filter.mentioned().map(|key| key.to_string());
+    let subscription = spawn_subscription(cluster, mentioned, commitment, filter, event_tx).await?;
+    tokio::spawn(forward(event_rx, sinks));
+    Ok(subscription)
+}
+
+/// Publishes received events to every sink until the subscription ends
+async fn forward(mut event_rx: mpsc::Receiver<EventStreamItem>, sinks: Vec<Arc<dyn EventSink>>) {
+    while let Some((context, event, _)) = event_rx.recv().await {
+        let Ok(event) = event else {
+            continue;
+        };
+        let message = SinkMessage { context, event };
+        for sink in &sinks {
+            let _ = sink.publish(&message).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    use solana_client::rpc_response::{Response, RpcLogsResponse, RpcResponseContext};
+    use solana_sdk::pubkey::Pubkey;
+
+    use crate::common::stream::CompleteEvent;
+
+    #[derive(Default)]
+    struct MemorySink {
+        fail: bool,
+        keys: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl EventSink for MemorySink {
+        async fn publish(&self, message: &SinkMessage) -> Result<(), error::ClientError> {
+            self.keys.lock().unwrap().push(message.key());
+            if self.fail {
+                return Err(error::ClientError::SinkError("unavailable".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    fn item(signature: &str, index: usize, parsed: bool) -> EventStreamItem {
+        let context = EventContext {
+            signature: signature.to_string(),
+            slot: 1,
+            block_time: None,
+            index,
+        };
+        let event = match parsed {
+            true => Ok(PumpFunEvent::Complete(CompleteEvent {
+                user: Pubkey::new_unique(),
+                mint: Pubkey::new_unique(),
+                bonding_curve: Pubkey::new_unique(),
+                timestamp: 0,
+            })),
+            false => Err("invalid event".into()),
+        };
+        let response = Response {
+            context: RpcResponseContext::new(1),
+            value: RpcLogsResponse {
+                signature: signature.to_string(),
+                err: None,
+                logs: Vec::new(),
+            },
+        };
+        (context, event, response)
+    }
+
+    #[tokio::test]
+    async fn test_forward() {
+        let failing = Arc::new(MemorySink {
+            fail: true,
+            ..Default::default()
+        });
+        let healthy = Arc::new(MemorySink::default());
+        let (event_tx, event_rx) = mpsc::channel(10);
+        for item in [item("a", 0, true), item("a", 1, false), item("b", 0, true)] {
+            event_tx.send(item).await.unwrap();
+        }
+        drop(event_tx);
+
+        forward(event_rx, vec![failing.clone(), healthy.clone()]).await;
+
+        // Unparsed events are skipped and failures do not affect other sinks
+        let expected = ["a:0".to_string(), "b:0".to_string()];
+        assert_eq!(*failing.keys.lock().unwrap(), expected);
+        assert_eq!(*healthy.keys.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_sink_message_json() {
+        let (context, event, _) = item("sig", 2, true);
+        let message = SinkMessage {
+            context,
+            event: event.unwrap(),
+        };
+        let decoded: SinkMessage = serde_json::from_slice(&message.to_json().unwrap()).unwrap();
+        assert_eq!(decoded.context, message.context);
+        assert!(matches!(decoded.event, PumpFunEvent::Complete(_)));
+    }
+}
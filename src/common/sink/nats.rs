@@ -0,0 +1,72 @@
+//! NATS event sink
+
+use async_trait::async_trait;
+
+use super::{EventSink, SinkMessage};
+use crate::error;
+
+/// Publishes events to a NATS subject
+///
+/// Each event is published as a JSON-encoded `SinkMessage`. Publishing is fire-and-forget,
+/// as with core NATS; messages are buffered by the client and flushed in the background.
+#[derive(Clone)]
+pub struct NatsSink {
+    client: async_nats::Client,
+    subject: String,
+}
+
+impl NatsSink {
+    /// Connects to a NATS server
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Server address, e.g. `nats://127.0.0.1:4222`
+    /// * `subject` - Subject to publish events to
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `NatsSink`, or a ClientError if the connection cannot be established
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pumpfun::common::sink::NatsSink;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sink = NatsSink::connect("nats://127.0.0.1:4222", "pumpfun.events").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect(url: &str, subject: &str) -> Result<Self, error::ClientError> {
+        let client = async_nats::connect(url).await.map_err(|err| {
+            error::ClientError::SinkError(format!("Failed to connect to NATS: {}", err))
+        })?;
+        Ok(Self::new(client, subject))
+    }
+
+    /// Creates a sink from an existing NATS client
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Connected NATS client
+    /// * `subject` - Subject to publish events to
+    pub fn new(client: async_nats::Client, subject: &str) -> Self {
+        Self {
+            client,
+            subject: subject.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for NatsSink {
+    async fn publish(&self, message: &SinkMessage) -> Result<(), error::ClientError> {
+        let payload = message.to_json()?;
+        self.client
+            .publish(self.subject.clone(), payload.into())
+            .await
+            .map_err(|err| {
+                error::ClientError::SinkError(format!("Failed to publish to NATS: {}", err))
+            })
+    }
+}
@@ -0,0 +1,69 @@
+//! Redis pub/sub event sink
+
+use async_trait::async_trait;
+use redis::{aio::MultiplexedConnection, AsyncCommands};
+
+use super::{EventSink, SinkMessage};
+use crate::error;
+
+/// Publishes events to a Redis pub/sub channel
+///
+/// Each event is published as a JSON-encoded `SinkMessage` with `PUBLISH`. The connection
+/// is multiplexed, so the sink can be shared between subscriptions.
+#[derive(Clone)]
+pub struct RedisSink {
+    connection: MultiplexedConnection,
+    channel: String,
+}
+
+impl RedisSink {
+    /// Connects to a Redis server
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Connection URL, e.g. `redis://127.0.0.1:6379`
+    /// * `channel` - Channel to publish events to
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `RedisSink`, or a ClientError if the connection cannot be established
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pumpfun::common::sink::RedisSink;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let sink = RedisSink::connect("redis://127.0.0.1:6379", "pumpfun:events").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect(url: &str, channel: &str) -> Result<Self, error::ClientError> {
+        let client = redis::Client::open(url)
+            .map_err(|err| error::ClientError::SinkError(format!("Invalid Redis URL: {}", err)))?;
+        let connection = client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|err| {
+                error::ClientError::SinkError(format!("Failed to connect to Redis: {}", err))
+            })?;
+        Ok(Self {
+            connection,
+            channel: channel.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl EventSink for RedisSink {
+    async fn publish(&self, message: &SinkMessage) -> Result<(), error::ClientError> {
+        let payload = message.to_json()?;
+        let mut connection = self.connection.clone();
+        connection
+            .publish::<_, _, ()>(&self.channel, payload)
+            .await
+            .map_err(|err| {
+                error::ClientError::SinkError(format!("Failed to publish to Redis: {}", err))
+            })
+    }
+}
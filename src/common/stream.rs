@@ -1,20 +1,30 @@
-use std::error::Error;
+use std::{
+    collections::{HashSet, VecDeque},
+    error::Error,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
 
 use base64::Engine;
 use borsh::{BorshDeserialize, BorshSerialize};
-use futures::StreamExt;
+use futures::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use solana_client::{
-    nonblocking::pubsub_client::PubsubClient,
+    nonblocking::{pubsub_client::PubsubClient, rpc_client::RpcClient},
+    pubsub_client::PubsubClientError,
+    rpc_client::GetConfirmedSignaturesForAddress2Config,
     rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter},
-    rpc_response::{Response, RpcLogsResponse},
+    rpc_request::RpcRequest,
+    rpc_response::{Response, RpcLogsResponse, RpcResponseContext},
 };
-use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
-use tokio::sync::mpsc;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use tokio::sync::{mpsc, oneshot, watch};
 use tokio::task::JoinHandle;
 
-use super::types::Cluster;
-use crate::{constants, error};
+use super::types::{Cluster, ReconnectPolicy};
+use crate::{constants, constants::discriminators, error};
 
 /// Event emitted when a new token is created
 ///
@@ -99,24 +109,363 @@ pub struct SetParamsEvent {
     pub admin_set_creator_authority: Pubkey,
 }
 
+/// Event emitted by the Pump.fun AMM when base tokens are bought from a pool
+///
+/// This event contains the amounts exchanged, the fees charged, and the pool reserves
+/// before the trade.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize)]
+pub struct BuyEvent {
+    pub timestamp: i64,
+    pub base_amount_out: u64,
+    pub max_quote_amount_in: u64,
+    pub user_base_token_reserves: u64,
+    pub user_quote_token_reserves: u64,
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+    pub quote_amount_in: u64,
+    pub lp_fee_basis_points: u64,
+    pub lp_fee: u64,
+    pub protocol_fee_basis_points: u64,
+    pub protocol_fee: u64,
+    pub quote_amount_in_with_lp_fee: u64,
+    pub user_quote_amount_in: u64,
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub user_base_token_account: Pubkey,
+    pub user_quote_token_account: Pubkey,
+    pub protocol_fee_recipient: Pubkey,
+    pub protocol_fee_recipient_token_account: Pubkey,
+    pub coin_creator: Pubkey,
+    pub coin_creator_fee_basis_points: u64,
+    pub coin_creator_fee: u64,
+}
+
+/// Event emitted by the Pump.fun AMM when base tokens are sold to a pool
+///
+/// This event contains the amounts exchanged, the fees charged, and the pool reserves
+/// before the trade.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize)]
+pub struct SellEvent {
+    pub timestamp: i64,
+    pub base_amount_in: u64,
+    pub min_quote_amount_out: u64,
+    pub user_base_token_reserves: u64,
+    pub user_quote_token_reserves: u64,
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+    pub quote_amount_out: u64,
+    pub lp_fee_basis_points: u64,
+    pub lp_fee: u64,
+    pub protocol_fee_basis_points: u64,
+    pub protocol_fee: u64,
+    pub quote_amount_out_without_lp_fee: u64,
+    pub user_quote_amount_out: u64,
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub user_base_token_account: Pubkey,
+    pub user_quote_token_account: Pubkey,
+    pub protocol_fee_recipient: Pubkey,
+    pub protocol_fee_recipient_token_account: Pubkey,
+    pub coin_creator: Pubkey,
+    pub coin_creator_fee_basis_points: u64,
+    pub coin_creator_fee: u64,
+}
+
+/// Event emitted by the Pump.fun AMM when liquidity is deposited into a pool
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize)]
+pub struct DepositEvent {
+    pub timestamp: i64,
+    pub lp_token_amount_out: u64,
+    pub max_base_amount_in: u64,
+    pub max_quote_amount_in: u64,
+    pub user_base_token_reserves: u64,
+    pub user_quote_token_reserves: u64,
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+    pub base_amount_in: u64,
+    pub quote_amount_in: u64,
+    pub lp_mint_supply: u64,
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub user_base_token_account: Pubkey,
+    pub user_quote_token_account: Pubkey,
+    pub user_pool_token_account: Pubkey,
+}
+
+/// Event emitted by the Pump.fun AMM when liquidity is withdrawn from a pool
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize)]
+pub struct WithdrawEvent {
+    pub timestamp: i64,
+    pub lp_token_amount_in: u64,
+    pub min_base_amount_out: u64,
+    pub min_quote_amount_out: u64,
+    pub user_base_token_reserves: u64,
+    pub user_quote_token_reserves: u64,
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+    pub base_amount_out: u64,
+    pub quote_amount_out: u64,
+    pub lp_mint_supply: u64,
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub user_base_token_account: Pubkey,
+    pub user_quote_token_account: Pubkey,
+    pub user_pool_token_account: Pubkey,
+}
+
+/// Event emitted by the Pump.fun AMM when a pool is created
+///
+/// Pools for migrated bonding curves are created with the token as the base mint and
+/// wrapped SOL as the quote mint.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize)]
+pub struct CreatePoolEvent {
+    pub timestamp: i64,
+    pub index: u16,
+    pub creator: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_mint_decimals: u8,
+    pub quote_mint_decimals: u8,
+    pub base_amount_in: u64,
+    pub quote_amount_in: u64,
+    pub pool_base_amount: u64,
+    pub pool_quote_amount: u64,
+    pub minimum_liquidity: u64,
+    pub initial_liquidity: u64,
+    pub lp_token_amount_out: u64,
+    pub pool_bump: u8,
+    pub pool: Pubkey,
+    pub lp_mint: Pubkey,
+    pub user_base_token_account: Pubkey,
+    pub user_quote_token_account: Pubkey,
+    pub coin_creator: Pubkey,
+}
+
+/// Event emitted by the Pump.fun AMM when a coin creator collects their fees
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize)]
+pub struct CollectCoinCreatorFeeEvent {
+    pub timestamp: i64,
+    pub coin_creator: Pubkey,
+    pub coin_creator_fee: u64,
+    pub coin_creator_vault_ata: Pubkey,
+    pub coin_creator_token_account: Pubkey,
+}
+
 /// Enum representing all possible event types emitted by the Pump.fun program
 ///
 /// This enum acts as a container for the different event types that can be
 /// emitted by the program. It's used to provide a unified type for event handlers.
+/// The `Buy`, `Sell`, `Deposit`, `Withdraw`, `CreatePool` and `CollectCoinCreatorFee`
+/// variants are emitted by the Pump.fun AMM program.
 #[derive(Debug, Serialize, Deserialize)]
 pub enum PumpFunEvent {
     Create(CreateEvent),
     Trade(TradeEvent),
     Complete(CompleteEvent),
     SetParams(SetParamsEvent),
+    Buy(BuyEvent),
+    Sell(SellEvent),
+    Deposit(DepositEvent),
+    Withdraw(WithdrawEvent),
+    CreatePool(CreatePoolEvent),
+    CollectCoinCreatorFee(CollectCoinCreatorFeeEvent),
     Unhandled(String, Vec<u8>), // For unhandled events
     Unknown(String, Vec<u8>),   // For unknown events
 }
 
+/// Kind of a Pump.fun event, used to select events in an `EventFilter`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    Create,
+    Trade,
+    Complete,
+    SetParams,
+    Buy,
+    Sell,
+    Deposit,
+    Withdraw,
+    CreatePool,
+    CollectCoinCreatorFee,
+    /// Unhandled and unknown events
+    Other,
+}
+
+impl PumpFunEvent {
+    /// Returns the kind of this event
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Self::Create(_) => EventKind::Create,
+            Self::Trade(_) => EventKind::Trade,
+            Self::Complete(_) => EventKind::Complete,
+            Self::SetParams(_) => EventKind::SetParams,
+            Self::Buy(_) => EventKind::Buy,
+            Self::Sell(_) => EventKind::Sell,
+            Self::Deposit(_) => EventKind::Deposit,
+            Self::Withdraw(_) => EventKind::Withdraw,
+            Self::CreatePool(_) => EventKind::CreatePool,
+            Self::CollectCoinCreatorFee(_) => EventKind::CollectCoinCreatorFee,
+            Self::Unhandled(..) | Self::Unknown(..) => EventKind::Other,
+        }
+    }
+}
+
+/// Selects which events a subscription delivers
+///
+/// An event matches when its kind is selected and it involves one of the given mints,
+/// creators, and users. Empty selections match everything, so `EventFilter::default()`
+/// matches all events. Events that do not carry a field being filtered on, such as the
+/// creator of a `CompleteEvent`, do not match.
+///
+/// # Examples
+///
+/// ```
+/// use pumpfun::common::stream::{EventFilter, EventKind};
+///
+/// // Only token creations
+/// let filter = EventFilter::new().kinds([EventKind::Create]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    /// Event kinds to deliver, or all kinds if empty
+    pub kinds: HashSet<EventKind>,
+    /// Mints to deliver events for, or all mints if empty
+    pub mints: HashSet<Pubkey>,
+    /// Token creators to deliver events for, or all creators if empty
+    pub creators: HashSet<Pubkey>,
+    /// Users (creators of tokens, traders, or completers) to deliver events for, or all
+    /// users if empty
+    pub users: HashSet<Pubkey>,
+}
+
+impl EventFilter {
+    /// Creates a filter matching all events
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the filter to the given event kinds
+    pub fn kinds(mut self, kinds: impl IntoIterator<Item = EventKind>) -> Self {
+        self.kinds.extend(kinds);
+        self
+    }
+
+    /// Restricts the filter to events for the given mints
+    pub fn mints(mut self, mints: impl IntoIterator<Item = Pubkey>) -> Self {
+        self.mints.extend(mints);
+        self
+    }
+
+    /// Restricts the filter to events for tokens of the given creators
+    pub fn creators(mut self, creators: impl IntoIterator<Item = Pubkey>) -> Self {
+        self.creators.extend(creators);
+        self
+    }
+
+    /// Restricts the filter to events triggered by the given users
+    pub fn users(mut self, users: impl IntoIterator<Item = Pubkey>) -> Self {
+        self.users.extend(users);
+        self
+    }
+
+    /// Returns whether an event passes the filter
+    pub fn matches(&self, event: &PumpFunEvent) -> bool {
+        if !self.kinds.is_empty() && !self.kinds.contains(&event.kind()) {
+            return false;
+        }
+
+        let (mint, creator, user) = match event {
+            PumpFunEvent::Create(event) => {
+                (Some(event.mint), Some(event.creator), Some(event.user))
+            }
+            PumpFunEvent::Trade(event) => (Some(event.mint), Some(event.creator), Some(event.user)),
+            PumpFunEvent::Complete(event) => (Some(event.mint), None, Some(event.user)),
+            PumpFunEvent::Buy(event) => (None, Some(event.coin_creator), Some(event.user)),
+            PumpFunEvent::Sell(event) => (None, Some(event.coin_creator), Some(event.user)),
+            PumpFunEvent::Deposit(event) => (None, None, Some(event.user)),
+            PumpFunEvent::Withdraw(event) => (None, None, Some(event.user)),
+            PumpFunEvent::CreatePool(event) => (
+                Some(event.base_mint),
+                Some(event.coin_creator),
+                Some(event.creator),
+            ),
+            PumpFunEvent::CollectCoinCreatorFee(event) => (None, Some(event.coin_creator), None),
+            _ => (None, None, None),
+        };
+
+        let selected = |keys: &HashSet<Pubkey>, key: Option<Pubkey>| {
+            keys.is_empty() || key.is_some_and(|key| keys.contains(&key))
+        };
+        selected(&self.mints, mint)
+            && selected(&self.creators, creator)
+            && selected(&self.users, user)
+    }
+
+    /// Returns the account to request logs for, if the filter allows narrowing the
+    /// subscription
+    ///
+    /// Every matching event involves the single selected mint or user, so only logs of
+    /// transactions mentioning it are needed. Creators are not used, since trades reference
+    /// the creator vault rather than the creator account.
+    pub(crate) fn mentioned(&self) -> Option<Pubkey> {
+        [&self.mints, &self.users]
+            .into_iter()
+            .find(|keys| keys.len() == 1)
+            .and_then(|keys| keys.iter().next().copied())
+    }
+}
+
+/// Error returned when program log data cannot be parsed into an event
+pub type ParseError = Box<dyn Error + Send + Sync>;
+
+/// Where and when an event was emitted
+///
+/// # Fields
+///
+/// * `signature` - Signature of the transaction that emitted the event
+/// * `slot` - Slot the transaction was processed in
+/// * `block_time` - Production time of the block as a Unix timestamp, or None if it could
+///   not be resolved, e.g. because the block is not confirmed yet
+/// * `index` - Position of the event among the events emitted by the transaction
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EventContext {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub index: usize,
+}
+
+/// Item yielded by an `EventStream`
+///
+/// Contains the event context, the parsed event or parse error, and the complete RPC logs
+/// response for additional context.
+pub type EventStreamItem = (
+    EventContext,
+    Result<PumpFunEvent, ParseError>,
+    Response<RpcLogsResponse>,
+);
+
+/// Stream of Pump.fun events returned by `subscribe_stream`
+///
+/// The underlying subscription is terminated when the stream is dropped.
+pub struct EventStream {
+    receiver: mpsc::Receiver<EventStreamItem>,
+    _subscription: Subscription,
+}
+
+impl Stream for EventStream {
+    type Item = EventStreamItem;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
 /// Represents an active WebSocket subscription to Pump.fun events
 ///
 /// This struct manages the lifecycle of an event subscription, automatically
-/// unsubscribing when dropped to ensure proper cleanup of resources.
+/// unsubscribing when dropped to ensure proper cleanup of resources. Dropping it signals
+/// the subscription task to send `logsUnsubscribe` and close the WebSocket connection in
+/// the background; use `shutdown` to wait until that has happened.
 pub struct Subscription {
     pub task: JoinHandle<()>,
     pub unsubscribe: Box<dyn Fn() + Send>,
@@ -126,12 +475,18 @@ impl Subscription {
     pub fn new(task: JoinHandle<()>, unsubscribe: Box<dyn Fn() + Send>) -> Self {
         Subscription { task, unsubscribe }
     }
+
+    /// Unsubscribes and waits until the WebSocket connection has been closed
+    pub async fn shutdown(mut self) {
+        (self.unsubscribe)();
+        let _ = (&mut self.task).await;
+    }
 }
 
 impl Drop for Subscription {
     fn drop(&mut self) {
+        // The task unsubscribes and exits on its own, aborting it would skip the cleanup
         (self.unsubscribe)();
-        self.task.abort();
     }
 }
 
@@ -155,48 +510,87 @@ pub fn parse_event(
 ) -> Result<PumpFunEvent, Box<dyn Error + Send + Sync>> {
     // Decode base64
     let decoded = base64::engine::general_purpose::STANDARD.decode(data)?;
+    parse_event_data(signature, decoded)
+}
+
+/// Parses event data emitted through an `emit_cpi!` self-CPI instruction
+///
+/// Newer program versions emit events as the data of an inner instruction invoking the
+/// program itself, instead of (or in addition to) `Program data:` logs, which are also
+/// truncated when a transaction logs too much.
+///
+/// # Arguments
+///
+/// * `signature` - Transaction signature associated with the event
+/// * `data` - Raw instruction data of the inner instruction
+///
+/// # Returns
+///
+/// Returns None if the instruction does not carry an event, otherwise the parsed
+/// PumpFunEvent or the parse error
+pub fn parse_cpi_event(signature: &str, data: &[u8]) -> Option<Result<PumpFunEvent, ParseError>> {
+    let event = data.strip_prefix(&discriminators::EVENT_IX_TAG)?;
+    Some(parse_event_data(signature, event.to_vec()))
+}
 
+/// Parses decoded event data starting with the event discriminator
+fn parse_event_data(signature: &str, decoded: Vec<u8>) -> Result<PumpFunEvent, ParseError> {
     // Get event type from the first 8 bytes
     if decoded.len() < 8 {
-        return Err(format!("Data too short to contain discriminator: {}", data).into());
+        return Err(format!(
+            "Data too short to contain discriminator: {} bytes",
+            decoded.len()
+        )
+        .into());
     }
 
-    let discriminator = &decoded[..8];
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&decoded[..8]);
     match discriminator {
-        // CreateEvent
-        [27, 114, 169, 77, 222, 235, 99, 118] => Ok(PumpFunEvent::Create(
+        discriminators::CREATE_EVENT => Ok(PumpFunEvent::Create(
             CreateEvent::try_from_slice(&decoded[8..])
                 .map_err(|e| format!("Failed to decode CreateEvent: {}", e))?,
         )),
-        // TradeEvent
-        [189, 219, 127, 211, 78, 230, 97, 238] => Ok(PumpFunEvent::Trade(
+        discriminators::TRADE_EVENT => Ok(PumpFunEvent::Trade(
             TradeEvent::try_from_slice(&decoded[8..])
                 .map_err(|e| format!("Failed to decode TradeEvent: {}", e))?,
         )),
-        // CompleteEvent
-        [95, 114, 97, 156, 212, 46, 152, 8] => Ok(PumpFunEvent::Complete(
+        discriminators::COMPLETE_EVENT => Ok(PumpFunEvent::Complete(
             CompleteEvent::try_from_slice(&decoded[8..])
                 .map_err(|e| format!("Failed to decode CompleteEvent: {}", e))?,
         )),
-        // SetParamsEvent
-        [223, 195, 159, 246, 62, 48, 143, 131] => Ok(PumpFunEvent::SetParams(
+        discriminators::SET_PARAMS_EVENT => Ok(PumpFunEvent::SetParams(
             SetParamsEvent::try_from_slice(&decoded[8..])
                 .map_err(|e| format!("Failed to decode SetParamsEvent: {}", e))?,
         )),
+        discriminators::AMM_BUY_EVENT => Ok(PumpFunEvent::Buy(
+            decode_amm_event(&decoded[8..])
+                .map_err(|e| format!("Failed to decode BuyEvent: {}", e))?,
+        )),
+        discriminators::AMM_SELL_EVENT => Ok(PumpFunEvent::Sell(
+            decode_amm_event(&decoded[8..])
+                .map_err(|e| format!("Failed to decode SellEvent: {}", e))?,
+        )),
+        discriminators::AMM_DEPOSIT_EVENT => Ok(PumpFunEvent::Deposit(
+            decode_amm_event(&decoded[8..])
+                .map_err(|e| format!("Failed to decode DepositEvent: {}", e))?,
+        )),
+        discriminators::AMM_WITHDRAW_EVENT => Ok(PumpFunEvent::Withdraw(
+            decode_amm_event(&decoded[8..])
+                .map_err(|e| format!("Failed to decode WithdrawEvent: {}", e))?,
+        )),
+        discriminators::AMM_CREATE_POOL_EVENT => Ok(PumpFunEvent::CreatePool(
+            decode_amm_event(&decoded[8..])
+                .map_err(|e| format!("Failed to decode CreatePoolEvent: {}", e))?,
+        )),
+        discriminators::AMM_COLLECT_COIN_CREATOR_FEE_EVENT => {
+            Ok(PumpFunEvent::CollectCoinCreatorFee(
+                decode_amm_event(&decoded[8..])
+                    .map_err(|e| format!("Failed to decode CollectCoinCreatorFeeEvent: {}", e))?,
+            ))
+        }
         // Other unhandled Pump.fun events
-        [64, 69, 192, 104, 29, 30, 25, 107]
-        | [245, 59, 70, 34, 75, 185, 109, 92]
-        | [147, 250, 108, 120, 247, 29, 67, 222]
-        | [79, 172, 246, 49, 205, 91, 206, 232]
-        | [146, 159, 189, 172, 146, 88, 56, 244]
-        | [122, 2, 127, 1, 14, 191, 12, 175]
-        | [189, 233, 93, 185, 92, 148, 234, 148]
-        | [97, 97, 215, 144, 93, 146, 22, 124]
-        | [134, 36, 13, 72, 232, 101, 130, 216]
-        | [237, 52, 123, 37, 245, 251, 72, 210]
-        | [142, 203, 6, 32, 127, 105, 191, 162]
-        | [197, 122, 167, 124, 116, 81, 91, 255]
-        | [182, 195, 137, 42, 35, 206, 207, 247] => {
+        _ if discriminators::event_name(&decoded).is_some() => {
             Ok(PumpFunEvent::Unhandled(signature.to_string(), decoded))
         }
         // Unknown event type
@@ -204,6 +598,49 @@ pub fn parse_event(
     }
 }
 
+/// Decodes an AMM event, ignoring fields appended by newer program versions
+fn decode_amm_event<T: BorshDeserialize>(mut data: &[u8]) -> std::io::Result<T> {
+    T::deserialize(&mut data)
+}
+
+/// Subscribes to events emitted by the Pump.fun AMM program
+///
+/// Works like `subscribe`, but watches the Pump.fun AMM program that completed bonding
+/// curves migrate to. Pool events are delivered as the `Buy`, `Sell`, `Deposit`, `Withdraw`,
+/// `CreatePool` and `CollectCoinCreatorFee` variants of `PumpFunEvent`.
+///
+/// # Arguments
+///
+/// * `cluster` - Solana cluster configuration containing RPC endpoints
+/// * `commitment` - Optional commitment level for the subscription. If None, uses the
+///   default from the cluster configuration
+/// * `callback` - A function that will be called for each event, with the same parameters
+///   as in `subscribe`
+///
+/// # Returns
+///
+/// Returns a `Subscription` object that manages the lifecycle of the subscription, or a
+/// ClientError if the subscription cannot be established
+pub async fn subscribe_amm<F>(
+    cluster: Cluster,
+    commitment: Option<CommitmentConfig>,
+    callback: F,
+) -> Result<Subscription, error::ClientError>
+where
+    F: Fn(String, Option<PumpFunEvent>, Option<ParseError>, Response<RpcLogsResponse>)
+        + Send
+        + Sync
+        + 'static,
+{
+    subscribe(
+        cluster,
+        Some(constants::accounts::PUMPAMM.to_string()),
+        commitment,
+        callback,
+    )
+    .await
+}
+
 /// Subscribes to Pump.fun program events emitted on-chain
 ///
 /// This function establishes a WebSocket connection to the Solana cluster and
@@ -212,6 +649,11 @@ pub fn parse_event(
 ///
 /// Events are delivered through the provided callback function as they occur. The
 /// subscription continues until the returned `Subscription` object is dropped.
+/// If the WebSocket connection drops, it is re-established and the logs subscription is
+/// renewed according to the cluster's `ReconnectPolicy`.
+///
+/// Use `subscribe_with_context` to also receive the slot, block time, and index of each
+/// event.
 ///
 /// # Arguments
 ///
@@ -285,68 +727,773 @@ where
         + Sync
         + 'static,
 {
-    // Initialize PubsubClient
-    let ws_url = &cluster.rpc.ws;
-    let pubsub_client = PubsubClient::new(ws_url)
-        .await
-        .map_err(error::ClientError::PubsubClientError)?;
+    subscribe_with_context(
+        cluster,
+        mentioned,
+        commitment,
+        move |context, event, error, response| callback(context.signature, event, error, response),
+    )
+    .await
+}
+
+/// Subscribes to Pump.fun program events along with the context they were emitted in
+///
+/// Works like `subscribe`, but passes the callback an `EventContext` with the slot, block
+/// time, and index of each event instead of the bare transaction signature.
+///
+/// # Arguments
+///
+/// * `cluster` - Solana cluster configuration containing RPC endpoints
+/// * `mentioned` - Optional public key to filter events by mentions. If None, subscribes to all Pump.fun events
+/// * `commitment` - Optional commitment level for the subscription. If None, uses the
+///   default from the cluster configuration
+/// * `callback` - A function that will be called for each event with the following parameters:
+///   * `context`: The transaction signature, slot, block time, and index of the event
+///   * `event`: The parsed PumpFunEvent if successful, or None if parsing failed
+///   * `error`: Any error that occurred during parsing, or None if successful
+///   * `response`: The complete RPC logs response for additional context
+///
+/// # Returns
+///
+/// Returns a `Subscription` object that manages the lifecycle of the subscription, or a
+/// ClientError if the subscription cannot be established
+///
+/// # Examples
+///
+/// ```no_run
+/// use pumpfun::{PumpFun, common::{stream::EventContext, types::{Cluster, PriorityFee}}};
+/// use solana_sdk::commitment_config::CommitmentConfig;
+/// use std::{sync::Arc, error::Error};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn Error>> {
+///     // Create cluster configuration
+///     let cluster = Cluster::mainnet(
+///         CommitmentConfig::confirmed(),
+///         PriorityFee::default()
+///     );
+///
+///     // Define callback to process events
+///     let callback = |context: EventContext, event, error, _| {
+///         if let Some(event) = event {
+///             println!("Event received: {:#?} in tx: {}", event, context.signature);
+///         } else if let Some(err) = error {
+///             eprintln!("Error parsing event in tx {}: {}", context.signature, err);
+///         }
+///     };
+///
+///     // Subscribe to events
+///     let subscription = pumpfun::common::stream::subscribe_with_context(cluster, None, None, callback).await?;
+///
+///     // Keep subscription alive until program terminates
+///     tokio::signal::ctrl_c().await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn subscribe_with_context<F>(
+    cluster: Cluster,
+    mentioned: Option<String>,
+    commitment: Option<CommitmentConfig>,
+    callback: F,
+) -> Result<Subscription, error::ClientError>
+where
+    F: Fn(
+            EventContext,
+            Option<PumpFunEvent>,
+            Option<Box<dyn Error + Send + Sync>>,
+            Response<RpcLogsResponse>,
+        ) + Send
+        + Sync
+        + 'static,
+{
+    let (event_tx, mut event_rx) = mpsc::channel::<EventStreamItem>(1000);
 
-    let (tx, _) = mpsc::channel(1);
-    let (cb_tx, mut cb_rx) = mpsc::channel(1000);
+    tokio::spawn(async move {
+        while let Some((context, result, log)) = event_rx.recv().await {
+            match result {
+                Ok(event) => callback(context, Some(event), None, log),
+                Err(err) => callback(context, None, Some(err), log),
+            }
+        }
+    });
+
+    spawn_subscription(
+        cluster,
+        mentioned,
+        commitment,
+        EventFilter::default(),
+        event_tx,
+    )
+    .await
+}
+
+/// Subscribes to Pump.fun program events matching a filter
+///
+/// Works like `subscribe`, but only invokes the callback for events matching `filter`.
+/// Filtering happens before the callback fires, and when the filter names exactly one mint
+/// or user, the subscription only requests logs of transactions mentioning that account to
+/// reduce bandwidth. Parse errors are always delivered. Use `subscribe_stream_with_filter`
+/// to also receive the slot, block time, and index of each event.
+///
+/// # Arguments
+///
+/// * `cluster` - Solana cluster configuration containing RPC endpoints
+/// * `filter` - Event kinds and accounts to deliver events for
+/// * `commitment` - Optional commitment level for the subscription. If None, uses the
+///   default from the cluster configuration
+/// * `callback` - A function that will be called for each matching event, with the same
+///   parameters as in `subscribe`
+///
+/// # Returns
+///
+/// Returns a `Subscription` object that manages the lifecycle of the subscription, or a
+/// ClientError if the subscription cannot be established
+///
+/// # Examples
+///
+/// ```no_run
+/// use pumpfun::common::{
+///     stream::{subscribe_with_filter, EventFilter, EventKind},
+///     types::{Cluster, PriorityFee},
+/// };
+/// use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+/// let mint = Pubkey::new_unique();
+/// let filter = EventFilter::new().kinds([EventKind::Trade]).mints([mint]);
+///
+/// let subscription = subscribe_with_filter(cluster, filter, None, |signature, event, _, _| {
+///     println!("Trade in tx {}: {:?}", signature, event);
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn subscribe_with_filter<F>(
+    cluster: Cluster,
+    filter: EventFilter,
+    commitment: Option<CommitmentConfig>,
+    callback: F,
+) -> Result<Subscription, error::ClientError>
+where
+    F: Fn(String, Option<PumpFunEvent>, Option<ParseError>, Response<RpcLogsResponse>)
+        + Send
+        + Sync
+        + 'static,
+{
+    let (event_tx, mut event_rx) = mpsc::channel::<EventStreamItem>(1000);
 
     tokio::spawn(async move {
-        while let Some((sig, event, err, log)) = cb_rx.recv().await {
-            callback(sig, event, err, log);
+        while let Some((context, result, log)) = event_rx.recv().await {
+            match result {
+                Ok(event) => callback(context.signature, Some(event), None, log),
+                Err(err) => callback(context.signature, None, Some(err), log),
+            }
         }
     });
 
+    let mentioned = filter.mentioned().map(|key| key.to_string());
+    spawn_subscription(cluster, mentioned, commitment, filter, event_tx).await
+}
+
+/// Subscribes to Pump.fun program events as an asynchronous stream
+///
+/// This is the stream-based counterpart of `subscribe`: instead of invoking a callback,
+/// events are yielded from the returned `EventStream`, so they can be consumed with
+/// `while let Some(item) = stream.next().await` and the standard stream combinators.
+/// Dropping the stream terminates the subscription.
+///
+/// # Arguments
+///
+/// * `cluster` - Solana cluster configuration containing RPC endpoints
+/// * `mentioned` - Optional public key to filter events by mentions. If None, subscribes to all Pump.fun events
+/// * `commitment` - Optional commitment level for the subscription. If None, uses the
+///   default from the cluster configuration
+///
+/// # Returns
+///
+/// Returns an `EventStream` yielding the event context, the parse result, and the
+/// complete RPC logs response for each event, or a ClientError if the subscription cannot
+/// be established
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The WebSocket connection cannot be established
+/// - The subscription request fails
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use pumpfun::common::{stream::{subscribe_stream, PumpFunEvent}, types::{Cluster, PriorityFee}};
+/// use solana_sdk::commitment_config::CommitmentConfig;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+/// let mut stream = subscribe_stream(cluster, None, None).await?;
+///
+/// while let Some((context, event, _)) = stream.next().await {
+///     if let Ok(PumpFunEvent::Trade(trade)) = event {
+///         println!("Trade of {} in tx {} at slot {}", trade.mint, context.signature, context.slot);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn subscribe_stream(
+    cluster: Cluster,
+    mentioned: Option<String>,
+    commitment: Option<CommitmentConfig>,
+) -> Result<EventStream, error::ClientError> {
+    let (event_tx, receiver) = mpsc::channel(1000);
+    let subscription = spawn_subscription(
+        cluster,
+        mentioned,
+        commitment,
+        EventFilter::default(),
+        event_tx,
+    )
+    .await?;
+    Ok(EventStream {
+        receiver,
+        _subscription: subscription,
+    })
+}
+
+/// Subscribes to Pump.fun program events matching a filter as an asynchronous stream
+///
+/// Works like `subscribe_stream`, but only yields events matching `filter`. See
+/// `subscribe_with_filter` for how the filter is applied.
+///
+/// # Arguments
+///
+/// * `cluster` - Solana cluster configuration containing RPC endpoints
+/// * `filter` - Event kinds and accounts to yield events for
+/// * `commitment` - Optional commitment level for the subscription. If None, uses the
+///   default from the cluster configuration
+///
+/// # Returns
+///
+/// Returns an `EventStream` of matching events, or a ClientError if the subscription
+/// cannot be established
+pub async fn subscribe_stream_with_filter(
+    cluster: Cluster,
+    filter: EventFilter,
+    commitment: Option<CommitmentConfig>,
+) -> Result<EventStream, error::ClientError> {
+    let (event_tx, receiver) = mpsc::channel(1000);
+    let mentioned = filter.mentioned().map(|key| key.to_string());
+    let subscription = spawn_subscription(cluster, mentioned, commitment, filter, event_tx).await?;
+    Ok(EventStream {
+        receiver,
+        _subscription: subscription,
+    })
+}
+
+/// Connects to the cluster and forwards parsed events to the given channel
+///
+/// The connection is re-established according to the cluster's `ReconnectPolicy` whenever
+/// the WebSocket drops, until the receiving side of the channel is closed.
+pub(crate) async fn spawn_subscription(
+    cluster: Cluster,
+    mentioned: Option<String>,
+    commitment: Option<CommitmentConfig>,
+    filter: EventFilter,
+    event_tx: mpsc::Sender<EventStreamItem>,
+) -> Result<Subscription, error::ClientError> {
+    // Initialize PubsubClient
+    let ws_url = cluster.rpc.ws.clone();
+    let pubsub_client = PubsubClient::new(&ws_url)
+        .await
+        .map_err(error::ClientError::PubsubClientError)?;
+
+    let (shutdown_tx, mut shutdown) = watch::channel(false);
+
+    let (ready_tx, ready_rx) = oneshot::channel();
+
     let task = tokio::spawn(async move {
-        // Subscribe to logs for the program
-        let (mut stream, _unsubscribe) = pubsub_client
-            .logs_subscribe(
-                RpcTransactionLogsFilter::Mentions(vec![
-                    mentioned.unwrap_or(constants::accounts::PUMPFUN.to_string())
-                ]),
-                RpcTransactionLogsConfig {
-                    commitment: Some(commitment.unwrap_or(cluster.commitment)),
-                },
-            )
-            .await
-            .unwrap();
+        let policy = cluster.reconnect;
+        let commitment = commitment.unwrap_or(cluster.commitment);
+        let mentioned = mentioned.unwrap_or(constants::accounts::PUMPFUN.to_string());
+        let mut ready_tx = Some(ready_tx);
+        let mut pubsub_client = Some(pubsub_client);
+        let mut last_signature: Option<String> = None;
+        let mut block_times = BlockTimes::default();
+        let mut attempt = 0;
+        // Used to fetch transactions whose events are missing from the logs
+        let rpc = RpcClient::new_with_commitment(
+            cluster.rpc.http.clone(),
+            history_commitment(commitment),
+        );
 
-        // Process incoming logs
-        while let Some(log) = stream.next().await {
-            // Get the signature of the transaction
-            let signature = &log.value.signature;
-            // Check for logs with "Program data:" prefix
-            for log_line in &log.value.logs {
-                // Extract base64-encoded data
-                if let Some(data) = log_line.strip_prefix("Program data: ") {
-                    match parse_event(signature, data) {
-                        Ok(event) => {
-                            let _ = cb_tx
-                                .send((signature.to_string(), Some(event), None, log.clone()))
-                                .await;
-                        }
-                        Err(err) => {
-                            let _ = cb_tx
-                                .send((signature.to_string(), None, Some(err), log.clone()))
-                                .await;
+        loop {
+            // Reuse the initial connection, otherwise reconnect
+            let client = match pubsub_client.take() {
+                Some(client) => client,
+                None => {
+                    let connection = tokio::select! {
+                        connection = PubsubClient::new(&ws_url) => connection,
+                        _ = stopped(&mut shutdown) => return,
+                    };
+                    match connection {
+                        Ok(client) => client,
+                        Err(_) if wait_reconnect(&policy, &mut attempt, &mut shutdown).await => {
+                            continue
                         }
+                        Err(_) => return,
+                    }
+                }
+            };
+
+            // Subscribe to logs for the program
+            let (mut stream, unsubscribe) = match tokio::select! {
+                subscription = client.logs_subscribe(
+                    RpcTransactionLogsFilter::Mentions(vec![mentioned.clone()]),
+                    RpcTransactionLogsConfig {
+                        commitment: Some(commitment),
+                    },
+                ) => subscription,
+                _ = stopped(&mut shutdown) => return,
+            } {
+                Ok(subscription) => subscription,
+                Err(err) => {
+                    // Report the initial subscription failure back to the caller
+                    if let Some(ready_tx) = ready_tx.take() {
+                        let _ = ready_tx.send(Err(err));
+                        return;
                     }
+                    if wait_reconnect(&policy, &mut attempt, &mut shutdown).await {
+                        continue;
+                    }
+                    return;
+                }
+            };
+            attempt = 0;
+
+            let replay_from = match ready_tx.take() {
+                Some(ready_tx) => {
+                    let _ = ready_tx.send(Ok(()));
+                    None
                 }
+                None if policy.gap_fill => last_signature.clone(),
+                None => None,
+            };
+
+            // Forward events until the subscription is stopped or the connection drops
+            let stop = tokio::select! {
+                stop = async {
+                    // Replay events emitted while disconnected
+                    if let Some(until) = replay_from {
+                        for log in fetch_missed_logs(&rpc, &mentioned, &until).await {
+                            last_signature = Some(log.value.signature.clone());
+                            if !forward_logs(&rpc, &mut block_times, log, &filter, &event_tx).await {
+                                return true;
+                            }
+                        }
+                    }
+
+                    // Process incoming logs
+                    while let Some(log) = stream.next().await {
+                        last_signature = Some(log.value.signature.clone());
+                        if !forward_logs(&rpc, &mut block_times, log, &filter, &event_tx).await {
+                            return true;
+                        }
+                    }
+                    false
+                } => stop,
+                _ = stopped(&mut shutdown) => true,
+            };
+
+            // Unsubscribe and close the connection before reconnecting or exiting
+            drop(stream);
+            unsubscribe().await;
+            let _ = client.shutdown().await;
+
+            if stop || !wait_reconnect(&policy, &mut attempt, &mut shutdown).await {
+                return;
             }
         }
     });
 
+    // Wait until the logs subscription is established
+    subscribed(ready_rx).await?;
+
     Ok(Subscription::new(
         task,
         Box::new(move || {
-            let _ = tx.try_send(());
+            let _ = shutdown_tx.send(true);
         }),
     ))
 }
 
+/// Waits for a subscription task to report whether its initial subscription succeeded
+pub(crate) async fn subscribed(
+    ready_rx: oneshot::Receiver<Result<(), PubsubClientError>>,
+) -> Result<(), error::ClientError> {
+    match ready_rx.await {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(err)) => Err(error::ClientError::PubsubClientError(err)),
+        Err(_) => Err(error::ClientError::OtherError(
+            "Subscription task ended before subscribing".to_string(),
+        )),
+    }
+}
+
+/// Completes once the subscription has been asked to stop or dropped
+pub(crate) async fn stopped(shutdown: &mut watch::Receiver<bool>) {
+    let _ = shutdown.wait_for(|stopped| *stopped).await;
+}
+
+/// Waits before the next reconnection attempt
+///
+/// Returns false if the policy allows no further attempts or the subscription was stopped.
+pub(crate) async fn wait_reconnect(
+    policy: &ReconnectPolicy,
+    attempt: &mut u32,
+    shutdown: &mut watch::Receiver<bool>,
+) -> bool {
+    let Some(delay) = policy.reconnect_delay(*attempt) else {
+        return false;
+    };
+    *attempt += 1;
+    tokio::select! {
+        _ = tokio::time::sleep(delay) => true,
+        _ = stopped(shutdown) => false,
+    }
+}
+
+/// Parses the events in a logs response and sends those matching the filter
+///
+/// When the logs are truncated or the events were emitted through self-CPI instructions,
+/// the transaction is fetched to extract its events instead. Returns false once the
+/// receiving side of the channel has been closed.
+async fn forward_logs(
+    rpc: &RpcClient,
+    block_times: &mut BlockTimes,
+    log: Response<RpcLogsResponse>,
+    filter: &EventFilter,
+    event_tx: &mpsc::Sender<EventStreamItem>,
+) -> bool {
+    let signature = &log.value.signature;
+    let results = match logs_incomplete(&log.value.logs) {
+        true => fetch_events_with_retry(rpc, signature).await,
+        false => None,
+    }
+    .unwrap_or_else(|| log_events(signature, &log.value.logs));
+
+    let mut resolved = None;
+    for (index, result) in results.into_iter().enumerate() {
+        if matches!(&result, Ok(event) if !filter.matches(event)) {
+            continue;
+        }
+        // Only resolved once an event passes the filter
+        let block_time = match resolved {
+            Some(block_time) => block_time,
+            None => *resolved.insert(block_times.get(rpc, log.context.slot).await),
+        };
+        let context = EventContext {
+            signature: signature.to_string(),
+            slot: log.context.slot,
+            block_time,
+            index,
+        };
+        if event_tx.send((context, result, log.clone())).await.is_err() {
+            return false;
+        }
+    }
+    !event_tx.is_closed()
+}
+
+/// Block times of recently seen slots
+///
+/// Events of the same slot usually arrive together, so only a few slots are kept. Slots
+/// whose block time could not be resolved are cached as well to avoid repeating the request
+/// for every event in them.
+#[derive(Default)]
+struct BlockTimes(VecDeque<(u64, Option<i64>)>);
+
+impl BlockTimes {
+    const CAPACITY: usize = 16;
+
+    async fn get(&mut self, rpc: &RpcClient, slot: u64) -> Option<i64> {
+        if let Some((_, block_time)) = self.0.iter().find(|(cached, _)| *cached == slot) {
+            return *block_time;
+        }
+        let block_time = rpc.get_block_time(slot).await.ok();
+        if self.0.len() == Self::CAPACITY {
+            self.0.pop_front();
+        }
+        self.0.push_back((slot, block_time));
+        block_time
+    }
+}
+
+/// Parses the events in `Program data:` log lines
+pub(crate) fn log_events(
+    signature: &str,
+    logs: &[String],
+) -> Vec<Result<PumpFunEvent, ParseError>> {
+    logs.iter()
+        // Extract base64-encoded data
+        .filter_map(|line| line.strip_prefix("Program data: "))
+        .map(|data| parse_event(signature, data))
+        .collect()
+}
+
+/// Returns whether events may be missing from the logs of a transaction
+///
+/// This is the case when the logs were truncated, or when a watched program invoked itself,
+/// which is how `emit_cpi!` emits events, without logging any event data.
+fn logs_incomplete(logs: &[String]) -> bool {
+    if logs.iter().any(|line| line == "Log truncated") {
+        return true;
+    }
+    if logs.iter().any(|line| line.starts_with("Program data: ")) {
+        return false;
+    }
+
+    let programs = [
+        constants::accounts::PUMPFUN.to_string(),
+        constants::accounts::PUMPAMM.to_string(),
+    ];
+    let mut stack: Vec<&str> = Vec::new();
+    for line in logs {
+        let Some(rest) = line.strip_prefix("Program ") else {
+            continue;
+        };
+        let Some((program, status)) = rest.split_once(' ') else {
+            continue;
+        };
+        if status.starts_with("invoke [") {
+            if stack.last() == Some(&program) && programs.iter().any(|id| id == program) {
+                return true;
+            }
+            stack.push(program);
+        } else if status == "success" || status.starts_with("failed") {
+            stack.pop();
+        }
+    }
+    false
+}
+
+/// Fetches the events of a transaction, waiting briefly for it to become available
+///
+/// Returns None if the transaction could not be fetched.
+async fn fetch_events_with_retry(
+    rpc: &RpcClient,
+    signature: &str,
+) -> Option<Vec<Result<PumpFunEvent, ParseError>>> {
+    for attempt in 0..3 {
+        if attempt > 0 {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+        if let Ok(Some(transaction)) = get_transaction(rpc, signature).await {
+            return Some(transaction_events(signature, &transaction));
+        }
+    }
+    None
+}
+
+/// Fetches a transaction and extracts all Pump.fun and Pump.fun AMM events it emitted
+///
+/// Events emitted through `emit_cpi!` self-CPI instructions are preferred, since they are
+/// not affected by log truncation. If the transaction has none, the events in its
+/// `Program data:` logs are returned.
+///
+/// # Arguments
+///
+/// * `rpc` - RPC client used to fetch the transaction, at confirmed commitment or higher
+/// * `signature` - Signature of the transaction
+///
+/// # Returns
+///
+/// Returns the parsed events, or a ClientError if the transaction could not be fetched
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the transaction was not found
+pub async fn fetch_transaction_events(
+    rpc: &RpcClient,
+    signature: &str,
+) -> Result<Vec<PumpFunEvent>, error::ClientError> {
+    let transaction = get_transaction(rpc, signature).await?.ok_or_else(|| {
+        error::ClientError::OtherError(format!("Transaction not found: {}", signature))
+    })?;
+    Ok(transaction_events(signature, &transaction)
+        .into_iter()
+        .filter_map(Result::ok)
+        .collect())
+}
+
+/// Fetches a transaction in JSON encoding, returning None if it was not found
+async fn get_transaction(
+    rpc: &RpcClient,
+    signature: &str,
+) -> Result<Option<Value>, error::ClientError> {
+    let params = serde_json::json!([
+        signature,
+        {
+            "encoding": "json",
+            "commitment": history_commitment(rpc.commitment()).commitment,
+            "maxSupportedTransactionVersion": 0,
+        }
+    ]);
+    let transaction = rpc
+        .send::<Value>(RpcRequest::GetTransaction, params)
+        .await?;
+    Ok((!transaction.is_null()).then_some(transaction))
+}
+
+/// Extracts events from a transaction in JSON encoding
+fn transaction_events(
+    signature: &str,
+    transaction: &Value,
+) -> Vec<Result<PumpFunEvent, ParseError>> {
+    let meta = &transaction["meta"];
+    let strings = |value: &Value| -> Vec<String> {
+        value
+            .as_array()
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|value| value.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    // Static keys are followed by the keys loaded from lookup tables
+    let keys: Vec<Pubkey> = [
+        &transaction["transaction"]["message"]["accountKeys"],
+        &meta["loadedAddresses"]["writable"],
+        &meta["loadedAddresses"]["readonly"],
+    ]
+    .into_iter()
+    .flat_map(strings)
+    .filter_map(|key| key.parse().ok())
+    .collect();
+
+    let inner: Vec<(usize, Vec<u8>)> = meta["innerInstructions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|inner| inner["instructions"].as_array().into_iter().flatten())
+        .filter_map(|instruction| {
+            let index = instruction["programIdIndex"].as_u64()?;
+            let data = solana_sdk::bs58::decode(instruction["data"].as_str()?)
+                .into_vec()
+                .ok()?;
+            Some((index as usize, data))
+        })
+        .collect();
+
+    collect_events(
+        signature,
+        &keys,
+        inner.iter().map(|(index, data)| (*index, data.as_slice())),
+        &strings(&meta["logMessages"]),
+    )
+}
+
+/// Extracts events from the parts of a transaction
+///
+/// Events in self-CPI inner instructions of the Pump.fun and Pump.fun AMM programs are
+/// preferred. If there are none, the events in `Program data:` logs are returned.
+///
+/// # Arguments
+///
+/// * `signature` - Transaction signature associated with the events
+/// * `keys` - Account keys of the transaction, followed by keys loaded from lookup tables
+/// * `inner_instructions` - Program ID index and data of each inner instruction
+/// * `logs` - Log messages of the transaction
+pub(crate) fn collect_events<'a>(
+    signature: &str,
+    keys: &[Pubkey],
+    inner_instructions: impl IntoIterator<Item = (usize, &'a [u8])>,
+    logs: &[String],
+) -> Vec<Result<PumpFunEvent, ParseError>> {
+    let programs = [constants::accounts::PUMPFUN, constants::accounts::PUMPAMM];
+    let cpi_events: Vec<_> = inner_instructions
+        .into_iter()
+        .filter(|(index, _)| {
+            keys.get(*index)
+                .is_some_and(|program| programs.contains(program))
+        })
+        .filter_map(|(_, data)| parse_cpi_event(signature, data))
+        .collect();
+
+    if cpi_events.is_empty() {
+        log_events(signature, logs)
+    } else {
+        cpi_events
+    }
+}
+
+/// Returns the lowest commitment transaction history is available at
+fn history_commitment(commitment: CommitmentConfig) -> CommitmentConfig {
+    // Transaction history is not available at processed commitment
+    if commitment.is_at_least_confirmed() {
+        commitment
+    } else {
+        CommitmentConfig::confirmed()
+    }
+}
+
+/// Fetches the logs of transactions mentioning an address after a given signature
+///
+/// Transactions are returned oldest first. At most one page of 1000 signatures is
+/// replayed, and transactions that cannot be fetched are skipped.
+async fn fetch_missed_logs(
+    rpc: &RpcClient,
+    address: &str,
+    until: &str,
+) -> Vec<Response<RpcLogsResponse>> {
+    let (Ok(address), Ok(until)) = (address.parse::<Pubkey>(), until.parse::<Signature>()) else {
+        return Vec::new();
+    };
+
+    let Ok(signatures) = rpc
+        .get_signatures_for_address_with_config(
+            &address,
+            GetConfirmedSignaturesForAddress2Config {
+                until: Some(until),
+                commitment: Some(history_commitment(rpc.commitment())),
+                ..Default::default()
+            },
+        )
+        .await
+    else {
+        return Vec::new();
+    };
+
+    let mut logs = Vec::with_capacity(signatures.len());
+    for status in signatures.into_iter().rev() {
+        let Ok(Some(transaction)) = get_transaction(rpc, &status.signature).await else {
+            continue;
+        };
+        let messages = transaction["meta"]["logMessages"]
+            .as_array()
+            .map(|messages| {
+                messages
+                    .iter()
+                    .filter_map(|message| message.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        logs.push(Response {
+            context: RpcResponseContext::new(status.slot),
+            value: RpcLogsResponse {
+                signature: status.signature,
+                err: status.err,
+                logs: messages,
+            },
+        });
+    }
+    logs
+}
+
 #[cfg(test)]
 mod tests {
     use crate::common::types::PriorityFee;
@@ -372,7 +1519,7 @@ mod tests {
         // Define the callback to store events
         let callback = {
             let events = Arc::clone(&events);
-            move |signature: String,
+            move |context: EventContext,
                   event: Option<PumpFunEvent>,
                   err: Option<Box<dyn Error + Send + Sync>>,
                   _: Response<RpcLogsResponse>| {
@@ -383,13 +1530,13 @@ mod tests {
                         events.push(event);
                     });
                 } else if err.is_some() {
-                    eprintln!("Error in subscription: signature={}", signature);
+                    eprintln!("Error in subscription: signature={}", context.signature);
                 }
             }
         };
 
         // Start the subscription
-        let subscription = subscribe(cluster, None, None, callback)
+        let subscription = subscribe_with_context(cluster, None, None, callback)
             .await
             .expect("Failed to start subscription");
 
@@ -416,4 +1563,226 @@ mod tests {
 
         println!("Received {} events", events.len());
     }
+
+    #[tokio::test]
+    async fn test_event_stream() {
+        let (event_tx, receiver) = mpsc::channel(10);
+        let mut stream = EventStream {
+            receiver,
+            _subscription: Subscription::new(tokio::spawn(async {}), Box::new(|| {})),
+        };
+
+        let response = Response {
+            context: solana_client::rpc_response::RpcResponseContext::new(1),
+            value: RpcLogsResponse {
+                signature: "sig".to_string(),
+                err: None,
+                logs: Vec::new(),
+            },
+        };
+        event_tx
+            .send((
+                EventContext {
+                    signature: "sig".to_string(),
+                    slot: 1,
+                    block_time: None,
+                    index: 0,
+                },
+                parse_event("sig", "AAAAAAAAAAA="),
+                response,
+            ))
+            .await
+            .unwrap();
+        drop(event_tx);
+
+        let (context, event, _) = stream.next().await.unwrap();
+        assert_eq!(context.signature, "sig");
+        assert!(matches!(event, Ok(PumpFunEvent::Unknown(..))));
+        assert!(stream.next().await.is_none());
+    }
+
+    #[test]
+    fn test_event_filter() {
+        let mint = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let event = PumpFunEvent::Complete(CompleteEvent {
+            user,
+            mint,
+            bonding_curve: Pubkey::new_unique(),
+            timestamp: 0,
+        });
+
+        assert!(EventFilter::default().matches(&event));
+        assert!(EventFilter::new()
+            .kinds([EventKind::Complete])
+            .matches(&event));
+        assert!(!EventFilter::new()
+            .kinds([EventKind::Create])
+            .matches(&event));
+        assert!(EventFilter::new()
+            .mints([mint])
+            .users([user])
+            .matches(&event));
+        assert!(!EventFilter::new()
+            .mints([Pubkey::new_unique()])
+            .matches(&event));
+        // Complete events carry no creator
+        assert!(!EventFilter::new().creators([user]).matches(&event));
+
+        assert_eq!(EventFilter::new().mints([mint]).mentioned(), Some(mint));
+        assert_eq!(EventFilter::new().creators([user]).mentioned(), None);
+    }
+
+    #[tokio::test]
+    async fn test_subscription_shutdown() {
+        let (shutdown_tx, mut shutdown) = watch::channel(false);
+        let task = tokio::spawn(async move { stopped(&mut shutdown).await });
+        let subscription = Subscription::new(
+            task,
+            Box::new(move || {
+                let _ = shutdown_tx.send(true);
+            }),
+        );
+
+        timeout(Duration::from_secs(1), subscription.shutdown())
+            .await
+            .expect("Subscription task did not stop");
+    }
+
+    #[test]
+    fn test_parse_amm_event() {
+        let event = CollectCoinCreatorFeeEvent {
+            timestamp: 1,
+            coin_creator: Pubkey::new_unique(),
+            coin_creator_fee: 42,
+            coin_creator_vault_ata: Pubkey::new_unique(),
+            coin_creator_token_account: Pubkey::new_unique(),
+        };
+        let mut data = discriminators::AMM_COLLECT_COIN_CREATOR_FEE_EVENT.to_vec();
+        BorshSerialize::serialize(&event, &mut data).unwrap();
+        // Fields appended by newer program versions are ignored
+        data.extend_from_slice(&[0; 16]);
+
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&data);
+        match parse_event("sig", &encoded).unwrap() {
+            PumpFunEvent::CollectCoinCreatorFee(parsed) => {
+                assert_eq!(parsed.coin_creator, event.coin_creator);
+                assert_eq!(parsed.coin_creator_fee, 42);
+            }
+            other => panic!("Unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_logs_incomplete() {
+        let pump = constants::accounts::PUMPFUN.to_string();
+        let logs = |lines: &[&str]| {
+            lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let self_cpi = logs(&[
+            &format!("Program {} invoke [1]", pump),
+            &format!("Program {} invoke [2]", pump),
+            &format!("Program {} success", pump),
+            &format!("Program {} success", pump),
+        ]);
+        assert!(logs_incomplete(&self_cpi));
+
+        let with_data = logs(&[
+            &format!("Program {} invoke [1]", pump),
+            "Program data: AAAAAAAAAAA=",
+            &format!("Program {} success", pump),
+        ]);
+        assert!(!logs_incomplete(&with_data));
+        assert!(logs_incomplete(&logs(&[
+            "Program data: AAAAAAAAAAA=",
+            "Log truncated"
+        ])));
+    }
+
+    #[test]
+    fn test_transaction_events() {
+        let event = CompleteEvent {
+            user: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            bonding_curve: Pubkey::new_unique(),
+            timestamp: 7,
+        };
+        let mut data = discriminators::EVENT_IX_TAG.to_vec();
+        data.extend_from_slice(&discriminators::COMPLETE_EVENT);
+        BorshSerialize::serialize(&event, &mut data).unwrap();
+
+        let transaction = serde_json::json!({
+            "transaction": {
+                "message": {
+                    "accountKeys": [Pubkey::new_unique().to_string(), constants::accounts::PUMPFUN.to_string()],
+                },
+            },
+            "meta": {
+                "logMessages": ["Log truncated"],
+                "innerInstructions": [{
+                    "index": 0,
+                    "instructions": [
+                        {"programIdIndex": 1, "data": solana_sdk::bs58::encode(&data).into_string()},
+                        // Instructions of other programs are ignored
+                        {"programIdIndex": 0, "data": solana_sdk::bs58::encode(&data).into_string()},
+                    ],
+                }],
+            },
+        });
+
+        let events = transaction_events("sig", &transaction);
+        assert_eq!(events.len(), 1);
+        assert!(
+            matches!(&events[0], Ok(PumpFunEvent::Complete(parsed)) if parsed.mint == event.mint)
+        );
+    }
+    #[tokio::test]
+    async fn test_forward_logs_context() {
+        let complete = |mint| {
+            let mut data = discriminators::COMPLETE_EVENT.to_vec();
+            let event = CompleteEvent {
+                user: Pubkey::new_unique(),
+                mint,
+                bonding_curve: Pubkey::new_unique(),
+                timestamp: 0,
+            };
+            BorshSerialize::serialize(&event, &mut data).unwrap();
+            format!(
+                "Program data: {}",
+                base64::engine::general_purpose::STANDARD.encode(data)
+            )
+        };
+        let mint = Pubkey::new_unique();
+        let log = Response {
+            context: RpcResponseContext::new(9),
+            value: RpcLogsResponse {
+                signature: "sig".to_string(),
+                err: None,
+                logs: vec![complete(Pubkey::new_unique()), complete(mint)],
+            },
+        };
+
+        // Block times cannot be resolved without a reachable RPC node
+        let rpc = RpcClient::new("http://127.0.0.1:1".to_string());
+        let (event_tx, mut event_rx) = mpsc::channel(10);
+        let filter = EventFilter::new().mints([mint]);
+        assert!(forward_logs(&rpc, &mut BlockTimes::default(), log, &filter, &event_tx).await);
+
+        let (context, event, _) = event_rx.try_recv().unwrap();
+        assert_eq!(
+            context,
+            EventContext {
+                signature: "sig".to_string(),
+                slot: 9,
+                block_time: None,
+                index: 1,
+            }
+        );
+        assert!(matches!(event, Ok(PumpFunEvent::Complete(parsed)) if parsed.mint == mint));
+        assert!(event_rx.try_recv().is_err());
+    }
 }
@@ -0,0 +1,279 @@
+//! Unified trade stream across the bonding curve and the Pump.fun AMM
+//!
+//! Tokens trade on their bonding curve until it completes, and on a Pump.fun AMM pool after
+//! migrating. `subscribe_all_trades` watches both programs and normalizes bonding curve
+//! `TradeEvent`s and AMM `BuyEvent`/`SellEvent`s into `UnifiedTrade`s, so price trackers can
+//! follow a token through its whole lifetime with a single pipeline.
+
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use tokio::sync::mpsc;
+
+use super::{
+    stream::{
+        spawn_subscription, EventContext, EventFilter, EventKind, PumpFunEvent, Subscription,
+    },
+    types::Cluster,
+};
+use crate::{constants, error};
+
+/// Offset of the base mint in Pump.fun AMM pool account data
+///
+/// The discriminator is followed by the pool bump (u8), index (u16) and creator.
+const POOL_BASE_MINT_OFFSET: usize = 8 + 1 + 2 + 32;
+/// Offset of the quote mint in Pump.fun AMM pool account data
+const POOL_QUOTE_MINT_OFFSET: usize = POOL_BASE_MINT_OFFSET + 32;
+
+/// Market a trade was executed on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TradeVenue {
+    /// The token's Pump.fun bonding curve
+    BondingCurve,
+    /// A Pump.fun AMM pool
+    Amm,
+}
+
+/// Direction of a trade from the trader's point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+/// Trade normalized across venues
+///
+/// # Fields
+///
+/// * `signature` - Signature of the transaction containing the trade
+/// * `mint` - Mint of the traded token
+/// * `venue` - Market the trade was executed on
+/// * `sol_amount` - Lamports exchanged, before fees
+/// * `token_amount` - Tokens exchanged, in base units
+/// * `side` - Whether the trader bought or sold tokens
+/// * `user` - Trader
+/// * `slot` - Slot the trade was observed in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnifiedTrade {
+    pub signature: String,
+    pub mint: Pubkey,
+    pub venue: TradeVenue,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub side: TradeSide,
+    pub user: Pubkey,
+    pub slot: u64,
+}
+
+/// Stream of trades returned by `subscribe_all_trades`
+///
+/// The underlying subscriptions are terminated when the stream is dropped.
+pub struct UnifiedTradeStream {
+    receiver: mpsc::Receiver<UnifiedTrade>,
+    _subscriptions: [Subscription; 2],
+}
+
+impl Stream for UnifiedTradeStream {
+    type Item = UnifiedTrade;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// Subscribes to trades on bonding curves and Pump.fun AMM pools
+///
+/// AMM events identify the pool rather than the token, so the pool account is fetched once
+/// per pool to resolve its base mint. Trades in pools whose quote mint is not wrapped SOL,
+/// and trades whose pool cannot be fetched, are skipped.
+///
+/// # Arguments
+///
+/// * `cluster` - Solana cluster configuration containing RPC endpoints
+/// * `commitment` - Optional commitment level for the subscriptions. If None, uses the
+///   default from the cluster configuration
+///
+/// # Returns
+///
+/// Returns a `UnifiedTradeStream` of trades across both venues, or a ClientError if either
+/// subscription cannot be established
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use pumpfun::common::{trades::subscribe_all_trades, types::{Cluster, PriorityFee}};
+/// use solana_sdk::commitment_config::CommitmentConfig;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+/// let mut trades = subscribe_all_trades(cluster, None).await?;
+///
+/// while let Some(trade) = trades.next().await {
+///     println!(
+///         "{:?} {:?} {} tokens of {} for {} lamports",
+///         trade.venue, trade.side, trade.token_amount, trade.mint, trade.sol_amount
+///     );
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn subscribe_all_trades(
+    cluster: Cluster,
+    commitment: Option<CommitmentConfig>,
+) -> Result<UnifiedTradeStream, error::ClientError> {
+    let (event_tx, mut event_rx) = mpsc::channel(1000);
+    let (trade_tx, receiver) = mpsc::channel(1000);
+
+    let bonding_curve = spawn_subscription(
+        cluster.clone(),
+        None,
+        commitment,
+        EventFilter::new().kinds([EventKind::Trade]),
+        event_tx.clone(),
+    )
+    .await?;
+    let amm = spawn_subscription(
+        cluster.clone(),
+        Some(constants::accounts::PUMPAMM.to_string()),
+        commitment,
+        EventFilter::new().kinds([EventKind::Buy, EventKind::Sell]),
+        event_tx,
+    )
+    .await?;
+
+    let rpc = RpcClient::new_with_commitment(
+        cluster.rpc.http.clone(),
+        commitment.unwrap_or(cluster.commitment),
+    );
+    tokio::spawn(async move {
+        let mut pools = PoolMints::default();
+        while let Some((context, event, _)) = event_rx.recv().await {
+            let Ok(event) = event else {
+                continue;
+            };
+            let Some(trade) = normalize(&rpc, &mut pools, context, event).await else {
+                continue;
+            };
+            if trade_tx.send(trade).await.is_err() {
+                return;
+            }
+        }
+    });
+
+    Ok(UnifiedTradeStream {
+        receiver,
+        _subscriptions: [bonding_curve, amm],
+    })
+}
+
+/// Cache of AMM pool base mints, with None for pools that are not quoted in SOL
+#[derive(Default)]
+pub(crate) struct PoolMints(HashMap<Pubkey, Option<Pubkey>>);
+
+impl PoolMints {
+    /// Records the base mint of a pool, e.g. from its `CreatePoolEvent`
+    pub(crate) fn insert(&mut self, pool: Pubkey, mint: Option<Pubkey>) {
+        self.0.insert(pool, mint);
+    }
+
+    pub(crate) async fn resolve(&mut self, rpc: &RpcClient, pool: &Pubkey) -> Option<Pubkey> {
+        if let Some(mint) = self.0.get(pool) {
+            return *mint;
+        }
+        let data = rpc.get_account_data(pool).await.ok()?;
+        let mint = pool_base_mint(&data)?;
+        self.0.insert(*pool, mint);
+        mint
+    }
+}
+
+/// Reads the base mint of a pool, or None inside if the pool is not quoted in wrapped SOL
+fn pool_base_mint(data: &[u8]) -> Option<Option<Pubkey>> {
+    let base = data.get(POOL_BASE_MINT_OFFSET..POOL_QUOTE_MINT_OFFSET)?;
+    let quote = data.get(POOL_QUOTE_MINT_OFFSET..POOL_QUOTE_MINT_OFFSET + 32)?;
+    let base = Pubkey::try_from(base).ok()?;
+    let quote = Pubkey::try_from(quote).ok()?;
+    Some((quote == constants::accounts::WSOL).then_some(base))
+}
+
+/// Converts a bonding curve or AMM trade event into a `UnifiedTrade`
+///
+/// Returns None for other events, and for AMM trades whose pool is not quoted in SOL.
+pub(crate) async fn normalize(
+    rpc: &RpcClient,
+    pools: &mut PoolMints,
+    context: EventContext,
+    event: PumpFunEvent,
+) -> Option<UnifiedTrade> {
+    let EventContext {
+        signature, slot, ..
+    } = context;
+    let trade = match event {
+        PumpFunEvent::Trade(trade) => UnifiedTrade {
+            signature,
+            mint: trade.mint,
+            venue: TradeVenue::BondingCurve,
+            sol_amount: trade.sol_amount,
+            token_amount: trade.token_amount,
+            side: if trade.is_buy {
+                TradeSide::Buy
+            } else {
+                TradeSide::Sell
+            },
+            user: trade.user,
+            slot,
+        },
+        PumpFunEvent::Buy(buy) => UnifiedTrade {
+            signature,
+            mint: pools.resolve(rpc, &buy.pool).await?,
+            venue: TradeVenue::Amm,
+            sol_amount: buy.quote_amount_in,
+            token_amount: buy.base_amount_out,
+            side: TradeSide::Buy,
+            user: buy.user,
+            slot,
+        },
+        PumpFunEvent::Sell(sell) => UnifiedTrade {
+            signature,
+            mint: pools.resolve(rpc, &sell.pool).await?,
+            venue: TradeVenue::Amm,
+            sol_amount: sell.quote_amount_out,
+            token_amount: sell.base_amount_in,
+            side: TradeSide::Sell,
+            user: sell.user,
+            slot,
+        },
+        _ => return None,
+    };
+    Some(trade)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_base_mint() {
+        let base = Pubkey::new_unique();
+        let mut data = vec![0u8; POOL_BASE_MINT_OFFSET];
+        data.extend_from_slice(base.as_ref());
+        data.extend_from_slice(constants::accounts::WSOL.as_ref());
+        data.extend_from_slice(&[0; 64]);
+        assert_eq!(pool_base_mint(&data), Some(Some(base)));
+
+        // Pools quoted in other tokens are skipped
+        data[POOL_QUOTE_MINT_OFFSET..POOL_QUOTE_MINT_OFFSET + 32]
+            .copy_from_slice(Pubkey::new_unique().as_ref());
+        assert_eq!(pool_base_mint(&data), Some(None));
+
+        assert_eq!(pool_base_mint(&data[..40]), None);
+    }
+}
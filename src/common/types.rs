@@ -4,13 +4,24 @@
 //!
 //! - Configuration structures for Solana clusters
 //! - Priority fee settings for transactions
+//! - Retry policies for submitting transactions
 //! - Helper methods for connecting to different Solana networks
+//! - Reports of bulk operations such as liquidating all positions
+//! - On-chain and off-chain metadata of tokens
 //!
 //! These utilities help with configuring the connection to the Solana blockchain
 //! and managing transaction parameters.
 
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
-use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+
+use crate::{
+    accounts::MetadataAccount,
+    error,
+    utils::{http::HttpError, OffchainMetadata},
+};
 
 /// Configuration for priority fee compute unit parameters
 ///
@@ -43,6 +54,290 @@ impl PriorityFee {
     }
 }
 
+/// Policy for retrying transactions that could not be confirmed
+///
+/// Only failures that are safe to retry are retried: blockhash expiry (including
+/// `BlockhashNotFound` rejections) and provider rate limiting. Any other error is returned
+/// to the caller immediately, since resending could execute the transaction twice.
+///
+/// # Fields
+///
+/// * `max_attempts` - Maximum number of times the transaction is sent, including the first
+/// * `backoff` - Delay before the first retry, doubled on every following retry
+/// * `max_backoff` - Upper bound for the exponential backoff
+/// * `max_retry_after` - Upper bound for a delay suggested by the provider, e.g. with a
+///   `Retry-After` header
+/// * `refresh_blockhash` - Whether to rebuild and re-sign the transaction with a fresh
+///   blockhash before each retry
+/// * `resimulate` - Whether to simulate the rebuilt transaction before resending it, so
+///   retries stop as soon as the transaction would fail on-chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub backoff: Duration,
+    pub max_backoff: Duration,
+    pub max_retry_after: Duration,
+    pub refresh_blockhash: bool,
+    pub resimulate: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(5),
+            max_retry_after: Duration::from_secs(30),
+            refresh_blockhash: true,
+            resimulate: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy that sends each transaction exactly once
+    ///
+    /// # Returns
+    ///
+    /// A `RetryPolicy` that never retries
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Determines whether a failed attempt should be retried
+    ///
+    /// # Arguments
+    ///
+    /// * `attempt` - Number of attempts made so far, starting at 1
+    /// * `err` - Error returned by the last attempt
+    ///
+    /// # Returns
+    ///
+    /// The delay to wait before the next attempt, or None if the error should be returned
+    pub fn retry_delay(&self, attempt: u32, err: &error::ClientError) -> Option<Duration> {
+        if attempt >= self.max_attempts || !(err.is_blockhash_expired() || err.is_rate_limited()) {
+            return None;
+        }
+
+        Some(retry_backoff(
+            self.backoff,
+            self.max_backoff,
+            self.max_retry_after,
+            attempt,
+            err,
+        ))
+    }
+}
+
+/// Returns the delay before retrying a failed attempt
+///
+/// The provider's suggested delay is preferred, up to `max_retry_after`; otherwise the delay
+/// doubles from `backoff` on every attempt, up to `max_backoff`.
+fn retry_backoff(
+    backoff: Duration,
+    max_backoff: Duration,
+    max_retry_after: Duration,
+    attempt: u32,
+    err: &error::ClientError,
+) -> Duration {
+    match err.retry_after() {
+        Some(retry_after) => retry_after.min(max_retry_after),
+        None => backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(max_backoff),
+    }
+}
+
+/// Policy for reconnecting WebSocket event subscriptions
+///
+/// When the WebSocket connection of a subscription drops, the subscription reconnects and
+/// resubscribes with exponential backoff. Optionally, events emitted while disconnected are
+/// replayed from transaction history before live events resume.
+///
+/// # Fields
+///
+/// * `max_attempts` - Maximum number of consecutive failed reconnection attempts before the
+///   subscription ends, or None to keep reconnecting
+/// * `backoff` - Delay before the first reconnection attempt, doubled on every failed attempt
+/// * `max_backoff` - Upper bound for the delay between attempts
+/// * `gap_fill` - Whether to replay events missed while disconnected, using
+///   `getSignaturesForAddress` and `getTransaction` on the HTTP endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReconnectPolicy {
+    pub max_attempts: Option<u32>,
+    pub backoff: Duration,
+    pub max_backoff: Duration,
+    pub gap_fill: bool,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: None,
+            backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            gap_fill: false,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Creates a policy that ends the subscription when the connection drops
+    ///
+    /// # Returns
+    ///
+    /// A `ReconnectPolicy` that never reconnects
+    pub fn none() -> Self {
+        Self {
+            max_attempts: Some(0),
+            ..Self::default()
+        }
+    }
+
+    /// Determines whether to attempt another reconnection
+    ///
+    /// # Arguments
+    ///
+    /// * `attempt` - Number of consecutive failed attempts so far, starting at 0
+    ///
+    /// # Returns
+    ///
+    /// The delay to wait before the next attempt, or None if the subscription should end
+    pub fn reconnect_delay(&self, attempt: u32) -> Option<Duration> {
+        if self.max_attempts.is_some_and(|max| attempt >= max) {
+            return None;
+        }
+        Some(
+            self.backoff
+                .saturating_mul(2u32.saturating_pow(attempt))
+                .min(self.max_backoff),
+        )
+    }
+}
+
+/// Policy for retrying metadata uploads
+///
+/// Uploads are retried when the provider rate limits them, fails with a server error, or
+/// cannot be reached. Rejections of the upload itself, such as invalid credentials or an
+/// oversized image, are returned immediately.
+///
+/// # Fields
+///
+/// * `max_attempts` - Maximum number of times the upload is sent, including the first
+/// * `backoff` - Delay before the first retry, doubled on every following retry
+/// * `max_backoff` - Upper bound for the exponential backoff
+/// * `max_retry_after` - Upper bound for a delay suggested by the provider, e.g. with a
+///   `Retry-After` header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadRetryPolicy {
+    pub max_attempts: u32,
+    pub backoff: Duration,
+    pub max_backoff: Duration,
+    pub max_retry_after: Duration,
+}
+
+impl Default for UploadRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(10),
+            max_retry_after: Duration::from_secs(60),
+        }
+    }
+}
+
+impl UploadRetryPolicy {
+    /// Creates a policy that sends each upload exactly once
+    ///
+    /// # Returns
+    ///
+    /// An `UploadRetryPolicy` that never retries
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Determines whether a failed upload should be retried
+    ///
+    /// # Arguments
+    ///
+    /// * `attempt` - Number of attempts made so far, starting at 1
+    /// * `err` - Error returned by the last attempt
+    ///
+    /// # Returns
+    ///
+    /// The delay to wait before the next attempt, or None if the error should be returned
+    pub fn retry_delay(&self, attempt: u32, err: &error::ClientError) -> Option<Duration> {
+        let retryable = match err {
+            error::ClientError::RateLimited { .. } => true,
+            error::ClientError::MetadataUploadRejected { status, .. } => *status >= 500,
+            error::ClientError::UploadMetadataError(err) => err.is::<HttpError>(),
+            _ => false,
+        };
+        if attempt >= self.max_attempts || !retryable {
+            return None;
+        }
+
+        Some(retry_backoff(
+            self.backoff,
+            self.max_backoff,
+            self.max_retry_after,
+            attempt,
+            err,
+        ))
+    }
+}
+
+/// How buys and sells handle the user's associated token account (ATA)
+///
+/// Checking whether the ATA exists costs an RPC request per trade. Latency-sensitive
+/// callers can skip it, either by always emitting an idempotent create instruction, which
+/// succeeds whether or not the account exists at a small compute cost, or by assuming the
+/// account exists, e.g. after creating it ahead of time with `utils::ata::ensure_atas`.
+///
+/// # Variants
+///
+/// * `Check` - Fetch the ATA and only create it if it is missing
+/// * `AlwaysCreateIdempotent` - Always add an idempotent create instruction without fetching
+/// * `AssumeExists` - Never create the ATA and skip every existence check
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AtaPolicy {
+    #[default]
+    Check,
+    AlwaysCreateIdempotent,
+    AssumeExists,
+}
+
+/// Version of the Pump.fun program that buy and sell instructions target
+///
+/// Current deployments read their fees from the fee program and reject buys and sells
+/// missing its configuration accounts. Older deployments and forks predating the fee
+/// program reject instructions that include them, so they need `Legacy`.
+///
+/// # Variants
+///
+/// * `Legacy` - Omit the fee configuration and fee program accounts
+/// * `FeeProgram` - Append the fee configuration and fee program accounts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProgramVersion {
+    Legacy,
+    #[default]
+    FeeProgram,
+}
+
+impl ProgramVersion {
+    /// Returns whether buys and sells reference the fee program's accounts
+    pub fn uses_fee_program(&self) -> bool {
+        matches!(self, Self::FeeProgram)
+    }
+}
+
 /// RPC connection endpoints for a Solana cluster
 ///
 /// # Fields
@@ -81,11 +376,19 @@ impl RpcEndpoint {
 /// * `rpc` - RPC endpoints for the cluster
 /// * `commitment` - Commitment level for confirmations
 /// * `priority_fee` - Priority fee configuration for transactions
+/// * `send_endpoints` - Additional HTTP endpoints transactions are broadcast to. When non-empty,
+///   the client sends through a `MultiRpcSender` covering these and the primary endpoint
+/// * `fallback_endpoints` - HTTP endpoints used, in order, when the primary endpoint times out,
+///   is unreachable, or rate limits a request
+/// * `reconnect` - How event subscriptions reconnect when their WebSocket connection drops
 #[derive(Debug, Clone)]
 pub struct Cluster {
     pub rpc: RpcEndpoint,
     pub commitment: CommitmentConfig,
     pub priority_fee: PriorityFee,
+    pub send_endpoints: Vec<String>,
+    pub fallback_endpoints: Vec<String>,
+    pub reconnect: ReconnectPolicy,
 }
 
 impl Cluster {
@@ -111,9 +414,57 @@ impl Cluster {
             rpc: RpcEndpoint { http, ws },
             commitment,
             priority_fee,
+            send_endpoints: Vec::new(),
+            fallback_endpoints: Vec::new(),
+            reconnect: ReconnectPolicy::default(),
         }
     }
 
+    /// Sets additional HTTP endpoints that transactions are broadcast to
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoints` - HTTP endpoint URLs to send transactions through alongside the primary one
+    ///
+    /// # Returns
+    ///
+    /// The `Cluster` configured with the additional send endpoints
+    pub fn with_send_endpoints(mut self, endpoints: Vec<String>) -> Self {
+        self.send_endpoints = endpoints;
+        self
+    }
+
+    /// Sets fallback HTTP endpoints for RPC failover
+    ///
+    /// When non-empty, the client's RPC requests go through a `FailoverRpcSender` that tries
+    /// the primary endpoint first and then each fallback in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoints` - HTTP endpoint URLs in priority order, after the primary one
+    ///
+    /// # Returns
+    ///
+    /// The `Cluster` configured with the fallback endpoints
+    pub fn with_fallback_endpoints(mut self, endpoints: Vec<String>) -> Self {
+        self.fallback_endpoints = endpoints;
+        self
+    }
+
+    /// Sets how event subscriptions reconnect when their WebSocket connection drops
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - Reconnection policy for subscriptions created with this cluster
+    ///
+    /// # Returns
+    ///
+    /// The `Cluster` configured with the reconnection policy
+    pub fn with_reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = policy;
+        self
+    }
+
     /// Creates a configuration for the Solana mainnet-beta cluster
     ///
     /// # Arguments
@@ -190,3 +541,161 @@ impl Cluster {
         )
     }
 }
+
+/// Transaction of a bulk operation covering several tokens
+///
+/// # Fields
+///
+/// * `mints` - Mints of the tokens covered by the transaction
+/// * `result` - Signature of the confirmed transaction, or why it failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenBatch {
+    pub mints: Vec<Pubkey>,
+    pub result: Result<Signature, String>,
+}
+
+/// Outcome of `PumpFun::liquidate_all`
+///
+/// # Fields
+///
+/// * `sells` - Transactions selling the payer's bonding curve tokens
+/// * `closes` - Transactions closing the payer's empty token accounts
+/// * `migrated` - Mints of held tokens whose bonding curve has completed, which were not sold
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Liquidation {
+    pub sells: Vec<TokenBatch>,
+    pub closes: Vec<TokenBatch>,
+    pub migrated: Vec<Pubkey>,
+}
+
+/// Metadata of a token
+///
+/// # Fields
+///
+/// * `account` - Metaplex metadata account of the token
+/// * `offchain` - Metadata JSON referenced by the account URI, if it was fetched
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenMetadataInfo {
+    pub account: MetadataAccount,
+    pub offchain: Option<OffchainMetadata>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::Signature;
+
+    #[test]
+    fn test_retry_delay() {
+        let policy = RetryPolicy::default();
+        let expired = error::ClientError::TransactionExpired(Signature::default());
+
+        assert_eq!(
+            policy.retry_delay(1, &expired),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            policy.retry_delay(2, &expired),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(policy.retry_delay(3, &expired), None);
+        assert_eq!(RetryPolicy::none().retry_delay(1, &expired), None);
+
+        let limited = error::ClientError::RateLimited {
+            retry_after: Some(Duration::from_secs(2)),
+        };
+        assert_eq!(
+            policy.retry_delay(1, &limited),
+            Some(Duration::from_secs(2))
+        );
+        let throttled = error::ClientError::RateLimited {
+            retry_after: Some(Duration::from_secs(30)),
+        };
+        assert_eq!(
+            policy.retry_delay(1, &throttled),
+            Some(Duration::from_secs(30))
+        );
+        let stalled = error::ClientError::RateLimited {
+            retry_after: Some(Duration::from_secs(3_600)),
+        };
+        assert_eq!(
+            policy.retry_delay(1, &stalled),
+            Some(policy.max_retry_after)
+        );
+        let capped = RetryPolicy {
+            max_attempts: 10,
+            ..policy
+        };
+        assert_eq!(
+            capped.retry_delay(8, &expired),
+            Some(Duration::from_secs(5))
+        );
+
+        let other = error::ClientError::OtherError("failed".to_string());
+        assert_eq!(policy.retry_delay(1, &other), None);
+    }
+
+    #[test]
+    fn test_reconnect_delay() {
+        let policy = ReconnectPolicy::default();
+        assert_eq!(policy.reconnect_delay(0), Some(Duration::from_secs(1)));
+        assert_eq!(policy.reconnect_delay(3), Some(Duration::from_secs(8)));
+        assert_eq!(policy.reconnect_delay(40), Some(Duration::from_secs(30)));
+
+        let limited = ReconnectPolicy {
+            max_attempts: Some(2),
+            ..policy
+        };
+        assert!(limited.reconnect_delay(1).is_some());
+        assert_eq!(limited.reconnect_delay(2), None);
+        assert_eq!(ReconnectPolicy::none().reconnect_delay(0), None);
+    }
+
+    #[test]
+    fn test_upload_retry_delay() {
+        let policy = UploadRetryPolicy::default();
+        let unavailable = error::ClientError::MetadataUploadRejected {
+            status: 503,
+            message: "unavailable".to_string(),
+        };
+        assert_eq!(
+            policy.retry_delay(1, &unavailable),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            policy.retry_delay(2, &unavailable),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(policy.retry_delay(3, &unavailable), None);
+
+        let unauthorized = error::ClientError::MetadataUploadRejected {
+            status: 401,
+            message: "invalid key".to_string(),
+        };
+        assert_eq!(policy.retry_delay(1, &unauthorized), None);
+        let limited = error::ClientError::RateLimited {
+            retry_after: Some(Duration::from_secs(30)),
+        };
+        assert_eq!(
+            policy.retry_delay(1, &limited),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(UploadRetryPolicy::none().retry_delay(1, &limited), None);
+        let stalled = error::ClientError::RateLimited {
+            retry_after: Some(Duration::from_secs(3_600)),
+        };
+        assert_eq!(
+            policy.retry_delay(1, &stalled),
+            Some(Duration::from_secs(60))
+        );
+
+        let network =
+            error::ClientError::UploadMetadataError(Box::new(HttpError::new("connection reset")));
+        assert_eq!(
+            policy.retry_delay(1, &network),
+            Some(Duration::from_secs(1))
+        );
+        let invalid = error::ClientError::UploadMetadataError("invalid response".into());
+        assert_eq!(policy.retry_delay(1, &invalid), None);
+    }
+}
@@ -0,0 +1,270 @@
+//! Account subscriptions for tracking bonding curve state
+//!
+//! `watch_bonding_curve` subscribes to a token's bonding curve account with `accountSubscribe`
+//! and decodes every update, so reserve changes can be followed slot by slot without
+//! parsing trade logs.
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{Stream, StreamExt};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::{
+    nonblocking::{pubsub_client::PubsubClient, rpc_client::RpcClient},
+    rpc_config::RpcAccountInfoConfig,
+};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use tokio::sync::{mpsc, oneshot, watch};
+
+use super::{
+    stream::{stopped, subscribed, wait_reconnect, Subscription},
+    types::Cluster,
+};
+use crate::{accounts::BondingCurveAccount, error, PumpFun};
+
+/// Bonding curve state observed in a slot
+///
+/// # Fields
+///
+/// * `slot` - Slot the account was updated in
+/// * `account` - Decoded bonding curve account
+#[derive(Debug, Clone)]
+pub struct BondingCurveUpdate {
+    pub slot: u64,
+    pub account: BondingCurveAccount,
+}
+
+/// Stream of bonding curve updates returned by `watch_bonding_curve`
+///
+/// The underlying subscription is terminated when the stream is dropped.
+pub struct BondingCurveStream {
+    receiver: mpsc::Receiver<BondingCurveUpdate>,
+    _subscription: Subscription,
+}
+
+impl Stream for BondingCurveStream {
+    type Item = BondingCurveUpdate;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// Watches a token's bonding curve account for changes
+///
+/// Yields the decoded account every time it changes. The WebSocket connection is
+/// re-established according to the cluster's `ReconnectPolicy`; when `gap_fill` is enabled,
+/// the current account state is fetched after reconnecting so changes made while
+/// disconnected are not missed. Updates that cannot be decoded are skipped.
+///
+/// # Arguments
+///
+/// * `cluster` - Solana cluster configuration containing RPC endpoints
+/// * `mint` - Public key of the token mint
+/// * `commitment` - Optional commitment level for the subscription. If None, uses the
+///   default from the cluster configuration
+///
+/// # Returns
+///
+/// Returns a `BondingCurveStream` of account updates, or a ClientError if the subscription
+/// cannot be established
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use pumpfun::common::{types::{Cluster, PriorityFee}, watch::watch_bonding_curve};
+/// use solana_sdk::{commitment_config::CommitmentConfig, pubkey};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+/// let mint = pubkey!("TokenM1ntPubk3yXXXXXXXXXXXXXXXXXXXXXXXXXXXX");
+/// let mut updates = watch_bonding_curve(cluster, &mint, None).await?;
+///
+/// while let Some(update) = updates.next().await {
+///     println!(
+///         "Slot {}: {} virtual SOL, {} virtual tokens",
+///         update.slot,
+///         update.account.virtual_sol_reserves,
+///         update.account.virtual_token_reserves
+///     );
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn watch_bonding_curve(
+    cluster: Cluster,
+    mint: &Pubkey,
+    commitment: Option<CommitmentConfig>,
+) -> Result<BondingCurveStream, error::ClientError> {
+    let bonding_curve =
+        PumpFun::get_bonding_curve_pda(mint).ok_or(error::ClientError::BondingCurveNotFound)?;
+
+    let ws_url = cluster.rpc.ws.clone();
+    let pubsub_client = PubsubClient::new(&ws_url)
+        .await
+        .map_err(error::ClientError::PubsubClientError)?;
+
+    let (shutdown_tx, mut shutdown) = watch::channel(false);
+    let (ready_tx, ready_rx) = oneshot::channel();
+    let (update_tx, receiver) = mpsc::channel(1000);
+
+    let task = tokio::spawn(async move {
+        let policy = cluster.reconnect;
+        let commitment = commitment.unwrap_or(cluster.commitment);
+        let config = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(commitment),
+            ..Default::default()
+        };
+        let mut ready_tx = Some(ready_tx);
+        let mut pubsub_client = Some(pubsub_client);
+        let mut attempt = 0;
+        // Used to fetch the current state after reconnecting
+        let rpc = RpcClient::new_with_commitment(cluster.rpc.http.clone(), commitment);
+
+        loop {
+            // Reuse the initial connection, otherwise reconnect
+            let client = match pubsub_client.take() {
+                Some(client) => client,
+                None => {
+                    let connection = tokio::select! {
+                        connection = PubsubClient::new(&ws_url) => connection,
+                        _ = stopped(&mut shutdown) => return,
+                    };
+                    match connection {
+                        Ok(client) => client,
+                        Err(_) if wait_reconnect(&policy, &mut attempt, &mut shutdown).await => {
+                            continue
+                        }
+                        Err(_) => return,
+                    }
+                }
+            };
+
+            let (mut stream, unsubscribe) = match tokio::select! {
+                subscription = client.account_subscribe(&bonding_curve, Some(config.clone())) => {
+                    subscription
+                }
+                _ = stopped(&mut shutdown) => return,
+            } {
+                Ok(subscription) => subscription,
+                Err(err) => {
+                    // Report the initial subscription failure back to the caller
+                    if let Some(ready_tx) = ready_tx.take() {
+                        let _ = ready_tx.send(Err(err));
+                        return;
+                    }
+                    if wait_reconnect(&policy, &mut attempt, &mut shutdown).await {
+                        continue;
+                    }
+                    return;
+                }
+            };
+            attempt = 0;
+
+            let refresh = match ready_tx.take() {
+                Some(ready_tx) => {
+                    let _ = ready_tx.send(Ok(()));
+                    false
+                }
+                None => policy.gap_fill,
+            };
+
+            // Forward updates until the subscription is stopped or the connection drops
+            let stop = tokio::select! {
+                stop = async {
+                    // Catch up on changes made while disconnected
+                    if refresh {
+                        if let Some(update) = fetch_update(&rpc, &bonding_curve, commitment).await {
+                            if update_tx.send(update).await.is_err() {
+                                return true;
+                            }
+                        }
+                    }
+
+                    while let Some(response) = stream.next().await {
+                        let Some(update) = response
+                            .value
+                            .data
+                            .decode()
+                            .and_then(|data| decode_update(response.context.slot, &data))
+                        else {
+                            continue;
+                        };
+                        if update_tx.send(update).await.is_err() {
+                            return true;
+                        }
+                    }
+                    false
+                } => stop,
+                _ = stopped(&mut shutdown) => true,
+            };
+
+            // Unsubscribe and close the connection before reconnecting or exiting
+            drop(stream);
+            unsubscribe().await;
+            let _ = client.shutdown().await;
+
+            if stop || !wait_reconnect(&policy, &mut attempt, &mut shutdown).await {
+                return;
+            }
+        }
+    });
+
+    // Wait until the account subscription is established
+    subscribed(ready_rx).await?;
+
+    Ok(BondingCurveStream {
+        receiver,
+        _subscription: Subscription::new(
+            task,
+            Box::new(move || {
+                let _ = shutdown_tx.send(true);
+            }),
+        ),
+    })
+}
+
+/// Fetches the current state of a bonding curve account
+async fn fetch_update(
+    rpc: &RpcClient,
+    bonding_curve: &Pubkey,
+    commitment: CommitmentConfig,
+) -> Option<BondingCurveUpdate> {
+    let response = rpc
+        .get_account_with_commitment(bonding_curve, commitment)
+        .await
+        .ok()?;
+    decode_update(response.context.slot, &response.value?.data)
+}
+
+fn decode_update(slot: u64, data: &[u8]) -> Option<BondingCurveUpdate> {
+    let account = solana_sdk::borsh1::try_from_slice_unchecked::<BondingCurveAccount>(data).ok()?;
+    Some(BondingCurveUpdate { slot, account })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::BorshSerialize;
+
+    #[test]
+    fn test_decode_update() {
+        let account =
+            BondingCurveAccount::new(1, 1000, 500, 800, 200, 1000, false, Pubkey::new_unique());
+        let mut data = Vec::new();
+        BorshSerialize::serialize(&account, &mut data).unwrap();
+        // Newer program versions append fields to the account
+        data.extend_from_slice(&[0; 16]);
+
+        let update = decode_update(42, &data).unwrap();
+        assert_eq!(update.slot, 42);
+        assert_eq!(update.account.virtual_sol_reserves, 500);
+        assert_eq!(update.account.creator, account.creator);
+
+        assert!(decode_update(42, &data[..10]).is_none());
+    }
+}
@@ -4,11 +4,13 @@
 //!
 //! - Seeds for deriving Program Derived Addresses (PDAs)
 //! - Program account addresses and public keys
+//! - Anchor discriminators for events, instructions and accounts
 //!
 //! The constants are organized into submodules for better organization:
 //!
 //! - `seeds`: Contains seed values used for PDA derivation
 //! - `accounts`: Contains important program account addresses
+//! - `discriminators`: Contains the 8-byte discriminators identifying events, instructions and accounts
 
 /// Constants used as seeds for deriving PDAs (Program Derived Addresses)
 pub mod seeds {
@@ -26,6 +28,15 @@ pub mod seeds {
 
     /// Seed for creator vault PDA
     pub const CREATOR_VAULT_SEED: &[u8] = b"creator-vault";
+
+    /// Seed for the global volume accumulator PDA
+    pub const GLOBAL_VOLUME_ACCUMULATOR_SEED: &[u8] = b"global_volume_accumulator";
+
+    /// Seed for user volume accumulator PDAs
+    pub const USER_VOLUME_ACCUMULATOR_SEED: &[u8] = b"user_volume_accumulator";
+
+    /// Seed for the fee configuration PDA, derived by the fee program
+    pub const FEE_CONFIG_SEED: &[u8] = b"fee_config";
 }
 
 /// Constants related to program accounts and authorities
@@ -35,6 +46,9 @@ pub mod accounts {
     /// Public key for the Pump.fun program
     pub const PUMPFUN: Pubkey = pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
 
+    /// Public key for the Pump.fun AMM program that completed bonding curves migrate to
+    pub const PUMPAMM: Pubkey = pubkey!("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA");
+
     /// Public key for the MPL Token Metadata program
     pub const MPL_TOKEN_METADATA: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
 
@@ -61,6 +75,242 @@ pub mod accounts {
     pub const ASSOCIATED_TOKEN_PROGRAM: Pubkey =
         pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
 
+    /// Wrapped SOL mint, the quote mint of Pump.fun AMM pools
+    pub const WSOL: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
     /// Rent Sysvar ID
     pub const RENT: Pubkey = pubkey!("SysvarRent111111111111111111111111111111111");
 }
+
+/// Anchor discriminators identifying Pump.fun events, instructions and accounts
+///
+/// Events of the Pump.fun AMM program are prefixed with `AMM_`.
+///
+/// Anchor prefixes serialized data with the first 8 bytes of
+/// `sha256("event:<Name>")`, `sha256("global:<instruction>")` or `sha256("account:<Name>")`.
+/// The `EVENTS`, `INSTRUCTIONS` and `ACCOUNTS` tables map each discriminator to its name so
+/// external decoders can identify raw data.
+pub mod discriminators {
+    /// Discriminator of `CreateEvent`
+    pub const CREATE_EVENT: [u8; 8] = [27, 114, 169, 77, 222, 235, 99, 118];
+    /// Discriminator of `TradeEvent`
+    pub const TRADE_EVENT: [u8; 8] = [189, 219, 127, 211, 78, 230, 97, 238];
+    /// Discriminator of `CompleteEvent`
+    pub const COMPLETE_EVENT: [u8; 8] = [95, 114, 97, 156, 212, 46, 152, 8];
+    /// Discriminator of `SetParamsEvent`
+    pub const SET_PARAMS_EVENT: [u8; 8] = [223, 195, 159, 246, 62, 48, 143, 131];
+    /// Discriminator of `AdminSetCreatorEvent`
+    pub const ADMIN_SET_CREATOR_EVENT: [u8; 8] = [64, 69, 192, 104, 29, 30, 25, 107];
+    /// Discriminator of `AdminSetIdlAuthorityEvent`
+    pub const ADMIN_SET_IDL_AUTHORITY_EVENT: [u8; 8] = [245, 59, 70, 34, 75, 185, 109, 92];
+    /// Discriminator of `AdminUpdateTokenIncentivesEvent`
+    pub const ADMIN_UPDATE_TOKEN_INCENTIVES_EVENT: [u8; 8] = [147, 250, 108, 120, 247, 29, 67, 222];
+    /// Discriminator of `ClaimTokenIncentivesEvent`
+    pub const CLAIM_TOKEN_INCENTIVES_EVENT: [u8; 8] = [79, 172, 246, 49, 205, 91, 206, 232];
+    /// Discriminator of `CloseUserVolumeAccumulatorEvent`
+    pub const CLOSE_USER_VOLUME_ACCUMULATOR_EVENT: [u8; 8] = [146, 159, 189, 172, 146, 88, 56, 244];
+    /// Discriminator of `CollectCreatorFeeEvent`
+    pub const COLLECT_CREATOR_FEE_EVENT: [u8; 8] = [122, 2, 127, 1, 14, 191, 12, 175];
+    /// Discriminator of `CompletePumpAmmMigrationEvent`
+    pub const COMPLETE_PUMP_AMM_MIGRATION_EVENT: [u8; 8] = [189, 233, 93, 185, 92, 148, 234, 148];
+    /// Discriminator of `ExtendAccountEvent`
+    pub const EXTEND_ACCOUNT_EVENT: [u8; 8] = [97, 97, 215, 144, 93, 146, 22, 124];
+    /// Discriminator of `InitUserVolumeAccumulatorEvent`
+    pub const INIT_USER_VOLUME_ACCUMULATOR_EVENT: [u8; 8] = [134, 36, 13, 72, 232, 101, 130, 216];
+    /// Discriminator of `SetCreatorEvent`
+    pub const SET_CREATOR_EVENT: [u8; 8] = [237, 52, 123, 37, 245, 251, 72, 210];
+    /// Discriminator of `SetMetaplexCreatorEvent`
+    pub const SET_METAPLEX_CREATOR_EVENT: [u8; 8] = [142, 203, 6, 32, 127, 105, 191, 162];
+    /// Discriminator of `SyncUserVolumeAccumulatorEvent`
+    pub const SYNC_USER_VOLUME_ACCUMULATOR_EVENT: [u8; 8] = [197, 122, 167, 124, 116, 81, 91, 255];
+    /// Discriminator of `UpdateGlobalAuthorityEvent`
+    pub const UPDATE_GLOBAL_AUTHORITY_EVENT: [u8; 8] = [182, 195, 137, 42, 35, 206, 207, 247];
+
+    /// Prefix of self-CPI instructions carrying an event emitted with Anchor's `emit_cpi!`
+    ///
+    /// The event discriminator and data follow the prefix in the instruction data.
+    pub const EVENT_IX_TAG: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+
+    /// Discriminator of the Pump.fun AMM `BuyEvent`
+    pub const AMM_BUY_EVENT: [u8; 8] = [103, 244, 82, 31, 44, 245, 119, 119];
+    /// Discriminator of the Pump.fun AMM `SellEvent`
+    pub const AMM_SELL_EVENT: [u8; 8] = [62, 47, 55, 10, 165, 3, 220, 42];
+    /// Discriminator of the Pump.fun AMM `DepositEvent`
+    pub const AMM_DEPOSIT_EVENT: [u8; 8] = [120, 248, 61, 83, 31, 142, 107, 144];
+    /// Discriminator of the Pump.fun AMM `WithdrawEvent`
+    pub const AMM_WITHDRAW_EVENT: [u8; 8] = [22, 9, 133, 26, 160, 44, 71, 192];
+    /// Discriminator of the Pump.fun AMM `CreatePoolEvent`
+    pub const AMM_CREATE_POOL_EVENT: [u8; 8] = [177, 49, 12, 210, 160, 118, 167, 116];
+    /// Discriminator of the Pump.fun AMM `CollectCoinCreatorFeeEvent`
+    pub const AMM_COLLECT_COIN_CREATOR_FEE_EVENT: [u8; 8] = [232, 245, 194, 238, 234, 218, 58, 89];
+
+    /// Discriminator of the `create` instruction
+    pub const CREATE: [u8; 8] = [24, 30, 200, 40, 5, 28, 7, 119];
+    /// Discriminator of the `buy` instruction
+    pub const BUY: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+    /// Discriminator of the `sell` instruction
+    pub const SELL: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+    /// Discriminator of the `collect_creator_fee` instruction
+    pub const COLLECT_CREATOR_FEE: [u8; 8] = [20, 22, 86, 123, 198, 28, 219, 132];
+    /// Discriminator of the `extend_account` instruction
+    pub const EXTEND_ACCOUNT: [u8; 8] = [234, 102, 194, 203, 150, 72, 62, 229];
+    /// Discriminator of the `migrate` instruction
+    pub const MIGRATE: [u8; 8] = [155, 234, 231, 146, 236, 158, 162, 30];
+    /// Discriminator of the `set_params` instruction
+    pub const SET_PARAMS: [u8; 8] = [27, 234, 178, 52, 147, 2, 187, 141];
+    /// Discriminator of the `update_global_authority` instruction
+    pub const UPDATE_GLOBAL_AUTHORITY: [u8; 8] = [227, 181, 74, 196, 208, 21, 97, 213];
+
+    /// Discriminator of the `Global` account
+    pub const GLOBAL_ACCOUNT: [u8; 8] = [167, 232, 232, 177, 200, 108, 114, 127];
+    /// Discriminator of the `BondingCurve` account
+    pub const BONDING_CURVE_ACCOUNT: [u8; 8] = [23, 183, 248, 55, 96, 216, 172, 96];
+    /// Discriminator of the `GlobalVolumeAccumulator` account
+    pub const GLOBAL_VOLUME_ACCUMULATOR_ACCOUNT: [u8; 8] = [202, 42, 246, 43, 142, 190, 30, 255];
+    /// Discriminator of the `UserVolumeAccumulator` account
+    pub const USER_VOLUME_ACCUMULATOR_ACCOUNT: [u8; 8] = [86, 255, 112, 14, 102, 53, 154, 250];
+    /// Discriminator of the fee program's `FeeConfig` account
+    pub const FEE_CONFIG_ACCOUNT: [u8; 8] = [143, 52, 146, 187, 219, 123, 76, 155];
+
+    /// Event names by discriminator
+    pub const EVENTS: &[(&str, [u8; 8])] = &[
+        ("CreateEvent", CREATE_EVENT),
+        ("TradeEvent", TRADE_EVENT),
+        ("CompleteEvent", COMPLETE_EVENT),
+        ("SetParamsEvent", SET_PARAMS_EVENT),
+        ("AdminSetCreatorEvent", ADMIN_SET_CREATOR_EVENT),
+        ("AdminSetIdlAuthorityEvent", ADMIN_SET_IDL_AUTHORITY_EVENT),
+        (
+            "AdminUpdateTokenIncentivesEvent",
+            ADMIN_UPDATE_TOKEN_INCENTIVES_EVENT,
+        ),
+        ("ClaimTokenIncentivesEvent", CLAIM_TOKEN_INCENTIVES_EVENT),
+        (
+            "CloseUserVolumeAccumulatorEvent",
+            CLOSE_USER_VOLUME_ACCUMULATOR_EVENT,
+        ),
+        ("CollectCreatorFeeEvent", COLLECT_CREATOR_FEE_EVENT),
+        (
+            "CompletePumpAmmMigrationEvent",
+            COMPLETE_PUMP_AMM_MIGRATION_EVENT,
+        ),
+        ("ExtendAccountEvent", EXTEND_ACCOUNT_EVENT),
+        (
+            "InitUserVolumeAccumulatorEvent",
+            INIT_USER_VOLUME_ACCUMULATOR_EVENT,
+        ),
+        ("SetCreatorEvent", SET_CREATOR_EVENT),
+        ("SetMetaplexCreatorEvent", SET_METAPLEX_CREATOR_EVENT),
+        (
+            "SyncUserVolumeAccumulatorEvent",
+            SYNC_USER_VOLUME_ACCUMULATOR_EVENT,
+        ),
+        ("UpdateGlobalAuthorityEvent", UPDATE_GLOBAL_AUTHORITY_EVENT),
+        ("BuyEvent", AMM_BUY_EVENT),
+        ("SellEvent", AMM_SELL_EVENT),
+        ("DepositEvent", AMM_DEPOSIT_EVENT),
+        ("WithdrawEvent", AMM_WITHDRAW_EVENT),
+        ("CreatePoolEvent", AMM_CREATE_POOL_EVENT),
+        (
+            "CollectCoinCreatorFeeEvent",
+            AMM_COLLECT_COIN_CREATOR_FEE_EVENT,
+        ),
+    ];
+
+    /// Instruction names by discriminator
+    pub const INSTRUCTIONS: &[(&str, [u8; 8])] = &[
+        ("create", CREATE),
+        ("buy", BUY),
+        ("sell", SELL),
+        ("collect_creator_fee", COLLECT_CREATOR_FEE),
+        ("extend_account", EXTEND_ACCOUNT),
+        ("migrate", MIGRATE),
+        ("set_params", SET_PARAMS),
+        ("update_global_authority", UPDATE_GLOBAL_AUTHORITY),
+    ];
+
+    /// Account names by discriminator
+    pub const ACCOUNTS: &[(&str, [u8; 8])] = &[
+        ("Global", GLOBAL_ACCOUNT),
+        ("BondingCurve", BONDING_CURVE_ACCOUNT),
+        ("GlobalVolumeAccumulator", GLOBAL_VOLUME_ACCUMULATOR_ACCOUNT),
+        ("UserVolumeAccumulator", USER_VOLUME_ACCUMULATOR_ACCOUNT),
+        ("FeeConfig", FEE_CONFIG_ACCOUNT),
+    ];
+
+    /// Looks up the name of an event from the start of its data
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Event data, starting with the 8-byte discriminator
+    ///
+    /// # Returns
+    ///
+    /// Returns the event name, or None if the discriminator is unknown
+    pub fn event_name(data: &[u8]) -> Option<&'static str> {
+        lookup(EVENTS, data)
+    }
+
+    /// Looks up the name of an instruction from the start of its data
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Instruction data, starting with the 8-byte discriminator
+    ///
+    /// # Returns
+    ///
+    /// Returns the instruction name, or None if the discriminator is unknown
+    pub fn instruction_name(data: &[u8]) -> Option<&'static str> {
+        lookup(INSTRUCTIONS, data)
+    }
+
+    /// Looks up the name of an account type from the start of its data
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Account data, starting with the 8-byte discriminator
+    ///
+    /// # Returns
+    ///
+    /// Returns the account type name, or None if the discriminator is unknown
+    pub fn account_name(data: &[u8]) -> Option<&'static str> {
+        lookup(ACCOUNTS, data)
+    }
+
+    fn lookup(table: &[(&'static str, [u8; 8])], data: &[u8]) -> Option<&'static str> {
+        let discriminator = data.get(..8)?;
+        table
+            .iter()
+            .find(|(_, value)| value == discriminator)
+            .map(|(name, _)| *name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::discriminators::*;
+
+    #[test]
+    fn test_discriminator_lookup() {
+        let mut data = TRADE_EVENT.to_vec();
+        data.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(event_name(&data), Some("TradeEvent"));
+        assert_eq!(instruction_name(&BUY), Some("buy"));
+        assert_eq!(account_name(&BONDING_CURVE_ACCOUNT), Some("BondingCurve"));
+        assert_eq!(event_name(&BUY), None);
+        assert_eq!(event_name(&[1, 2, 3]), None);
+    }
+
+    #[test]
+    fn test_discriminators_are_unique() {
+        let all: Vec<[u8; 8]> = EVENTS
+            .iter()
+            .chain(INSTRUCTIONS)
+            .chain(ACCOUNTS)
+            .map(|(_, discriminator)| *discriminator)
+            .collect();
+        for (i, discriminator) in all.iter().enumerate() {
+            assert!(!all[i + 1..].contains(discriminator));
+        }
+    }
+}
@@ -0,0 +1,426 @@
+//! Copy trading of other wallets' bonding curve trades
+//!
+//! `CopyTrader` watches the `TradeEvent`s of a set of leader wallets and mirrors them with
+//! the client's payer. Buys are sized as a fraction of the leader's SOL amount within
+//! configurable caps, and sells sell the same share of the copied position as the leader
+//! sold of theirs. Trades are built with the client's instruction builders and submitted
+//! with `PumpFun::send_instructions`, so the client's sender, retry policy, and preflight
+//! checks apply unchanged.
+
+use std::{
+    collections::{HashMap, HashSet},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::Stream;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Signature, signer::Signer};
+use spl_associated_token_account::get_associated_token_address;
+use tokio::sync::mpsc;
+
+use crate::{
+    common::{
+        stream::{
+            spawn_subscription, EventFilter, EventKind, PumpFunEvent, Subscription, TradeEvent,
+        },
+        types::PriorityFee,
+    },
+    error,
+    sender::TransactionSender,
+    PumpFun,
+};
+
+/// Sizing and limits of copied trades
+///
+/// # Fields
+///
+/// * `ratio` - Fraction of the leader's SOL amount spent on copied buys, e.g. 0.1 for 10%
+/// * `min_buy` - Copied buys below this many lamports are skipped
+/// * `max_buy` - Copied buys are capped at this many lamports, if set
+/// * `max_position` - Lamports spent on a single token across copied buys are capped at
+///   this amount, if set
+/// * `delay` - Time to wait before copying a trade
+/// * `copy_sells` - Whether leader sells are copied
+/// * `blacklist` - Mints whose trades are never copied
+/// * `slippage_basis_points` - Maximum acceptable slippage in basis points. If None, uses
+///   the client's default slippage
+/// * `priority_fee` - Priority fee of copied trades. If None, uses the cluster's priority fee
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopyTradeConfig {
+    pub ratio: f64,
+    pub min_buy: u64,
+    pub max_buy: Option<u64>,
+    pub max_position: Option<u64>,
+    pub delay: Duration,
+    pub copy_sells: bool,
+    pub blacklist: HashSet<Pubkey>,
+    pub slippage_basis_points: Option<u64>,
+    pub priority_fee: Option<PriorityFee>,
+}
+
+impl CopyTradeConfig {
+    /// Creates a configuration copying buys at `ratio` of the leader's size, and all sells,
+    /// immediately and without caps
+    pub fn new(ratio: f64) -> Self {
+        Self {
+            ratio,
+            min_buy: 0,
+            max_buy: None,
+            max_position: None,
+            delay: Duration::ZERO,
+            copy_sells: true,
+            blacklist: HashSet::new(),
+            slippage_basis_points: None,
+            priority_fee: None,
+        }
+    }
+
+    /// Returns the lamports to spend copying a buy, or None if it should be skipped
+    ///
+    /// # Arguments
+    ///
+    /// * `leader_sol` - Lamports spent by the leader
+    /// * `spent` - Lamports already spent on the token by copied buys
+    pub fn buy_amount(&self, leader_sol: u64, spent: u64) -> Option<u64> {
+        let mut amount = (leader_sol as f64 * self.ratio.max(0.0)) as u64;
+        if let Some(max_buy) = self.max_buy {
+            amount = amount.min(max_buy);
+        }
+        if let Some(max_position) = self.max_position {
+            amount = amount.min(max_position.saturating_sub(spent));
+        }
+        (amount > 0 && amount >= self.min_buy).then_some(amount)
+    }
+}
+
+/// Returns the tokens to sell copying a leader's sell, or None if nothing should be sold
+///
+/// The copied position is reduced by the share of their balance the leader sold. A sell
+/// of the leader's whole balance sells the whole copied position, signalled as `Some(None)`.
+///
+/// # Arguments
+///
+/// * `held` - Tokens held by the copying wallet
+/// * `leader_sold` - Tokens sold by the leader
+/// * `leader_remaining` - Tokens the leader holds after the sell
+fn sell_amount(held: u64, leader_sold: u64, leader_remaining: u64) -> Option<Option<u64>> {
+    if held == 0 || leader_sold == 0 {
+        return None;
+    }
+    if leader_remaining == 0 {
+        return Some(None);
+    }
+    let total = leader_sold as u128 + leader_remaining as u128;
+    let amount = (held as u128 * leader_sold as u128 / total) as u64;
+    (amount > 0).then_some(Some(amount))
+}
+
+/// Trade made by a copy trader
+///
+/// # Fields
+///
+/// * `leader` - Wallet whose trade was copied
+/// * `leader_signature` - Signature of the leader's trade
+/// * `mint` - Mint of the traded token
+/// * `is_buy` - Whether the trade bought tokens
+/// * `amount` - Lamports spent for buys, tokens sold for sells, or None when selling the
+///   whole position
+/// * `result` - Signature of the confirmed trade, or why it failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopiedTrade {
+    pub leader: Pubkey,
+    pub leader_signature: String,
+    pub mint: Pubkey,
+    pub is_buy: bool,
+    pub amount: Option<u64>,
+    pub result: Result<Signature, String>,
+}
+
+/// Stream of trades made by a running copy trader
+///
+/// The copy trader stops when the stream is dropped; trades already submitted still complete.
+pub struct CopyTradeStream {
+    receiver: mpsc::Receiver<CopiedTrade>,
+    _subscription: Subscription,
+}
+
+impl Stream for CopyTradeStream {
+    type Item = CopiedTrade;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// Mirrors the bonding curve trades of leader wallets
+///
+/// Trades are copied independently and concurrently, so a sell that follows a buy closely
+/// may find the copied buy unconfirmed and be skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use pumpfun::{
+///     common::types::{Cluster, PriorityFee},
+///     copy_trade::{CopyTradeConfig, CopyTrader},
+///     PumpFun,
+/// };
+/// use solana_sdk::{commitment_config::CommitmentConfig, native_token::sol_to_lamports, pubkey, signature::Keypair};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+/// let client = Arc::new(PumpFun::new(Arc::new(Keypair::new()), cluster));
+///
+/// let leader = pubkey!("LeaderWa11etXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX");
+/// let config = CopyTradeConfig {
+///     max_buy: Some(sol_to_lamports(0.5)),
+///     max_position: Some(sol_to_lamports(1.0)),
+///     ..CopyTradeConfig::new(0.1)
+/// };
+/// let mut trades = CopyTrader::new(client, [leader], config).start().await?;
+///
+/// while let Some(trade) = trades.next().await {
+///     println!("Copied {} of {}: {:?}", trade.leader_signature, trade.mint, trade.result);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct CopyTrader {
+    client: Arc<PumpFun>,
+    leaders: HashSet<Pubkey>,
+    config: CopyTradeConfig,
+    sender: Option<Arc<dyn TransactionSender>>,
+}
+
+impl CopyTrader {
+    /// Creates a copy trader trading with a client's payer
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Client whose payer signs and pays for copied trades
+    /// * `leaders` - Wallets whose trades are copied
+    /// * `config` - Sizing and limits of copied trades
+    pub fn new(
+        client: Arc<PumpFun>,
+        leaders: impl IntoIterator<Item = Pubkey>,
+        config: CopyTradeConfig,
+    ) -> Self {
+        Self {
+            client,
+            leaders: leaders.into_iter().collect(),
+            config,
+            sender: None,
+        }
+    }
+
+    /// Submits copied trades through the given sender instead of the client's sender
+    pub fn with_sender(mut self, sender: Arc<dyn TransactionSender>) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// Starts copying trades
+    ///
+    /// # Returns
+    ///
+    /// Returns a `CopyTradeStream` of copied trades, or a ClientError if the subscription
+    /// cannot be established
+    pub async fn start(self) -> Result<CopyTradeStream, error::ClientError> {
+        let filter = EventFilter::new()
+            .kinds([EventKind::Trade])
+            .users(self.leaders.iter().copied());
+        let (event_tx, mut event_rx) = mpsc::channel(1000);
+        let mentioned = filter.mentioned().map(|key| key.to_string());
+        let subscription = spawn_subscription(
+            self.client.cluster.clone(),
+            mentioned,
+            None,
+            filter,
+            event_tx,
+        )
+        .await?;
+
+        let (trade_tx, receiver) = mpsc::channel(100);
+        let copier = Arc::new(Copier {
+            client: self.client,
+            sender: self.sender,
+            config: self.config,
+            spent: Mutex::new(HashMap::new()),
+        });
+        tokio::spawn(async move {
+            while let Some((context, event, _)) = event_rx.recv().await {
+                let Ok(PumpFunEvent::Trade(trade)) = event else {
+                    continue;
+                };
+                if copier.config.blacklist.contains(&trade.mint)
+                    || (!trade.is_buy && !copier.config.copy_sells)
+                {
+                    continue;
+                }
+                let copier = copier.clone();
+                let trade_tx = trade_tx.clone();
+                tokio::spawn(async move {
+                    if let Some(copied) = copier.copy(context.signature, trade).await {
+                        let _ = trade_tx.send(copied).await;
+                    }
+                });
+            }
+        });
+
+        Ok(CopyTradeStream {
+            receiver,
+            _subscription: subscription,
+        })
+    }
+}
+
+/// State shared by the trades of a running copy trader
+struct Copier {
+    client: Arc<PumpFun>,
+    sender: Option<Arc<dyn TransactionSender>>,
+    config: CopyTradeConfig,
+    /// Lamports spent per token by copied buys
+    spent: Mutex<HashMap<Pubkey, u64>>,
+}
+
+impl Copier {
+    /// Copies a leader's trade, returning None if it is skipped
+    async fn copy(&self, leader_signature: String, trade: TradeEvent) -> Option<CopiedTrade> {
+        if !self.config.delay.is_zero() {
+            tokio::time::sleep(self.config.delay).await;
+        }
+        let (amount, result) = if trade.is_buy {
+            let amount = self.reserve(&trade.mint, trade.sol_amount)?;
+            let result = self.buy(&trade.mint, amount).await;
+            if result.is_err() {
+                self.release(&trade.mint, amount);
+            }
+            (Some(amount), result)
+        } else {
+            let amount = self.sell_amount(&trade).await?;
+            (amount, self.sell(&trade.mint, amount).await)
+        };
+        Some(CopiedTrade {
+            leader: trade.user,
+            leader_signature,
+            mint: trade.mint,
+            is_buy: trade.is_buy,
+            amount,
+            result,
+        })
+    }
+
+    /// Reserves the lamports of a copied buy against the token's position cap
+    fn reserve(&self, mint: &Pubkey, leader_sol: u64) -> Option<u64> {
+        let mut positions = self.lock_spent();
+        let spent = positions.entry(*mint).or_default();
+        let amount = self.config.buy_amount(leader_sol, *spent)?;
+        *spent += amount;
+        Some(amount)
+    }
+
+    fn release(&self, mint: &Pubkey, amount: u64) {
+        if let Some(spent) = self.lock_spent().get_mut(mint) {
+            *spent = spent.saturating_sub(amount);
+        }
+    }
+
+    async fn sell_amount(&self, trade: &TradeEvent) -> Option<Option<u64>> {
+        let held = self
+            .token_balance(&self.client.payer.pubkey(), &trade.mint)
+            .await;
+        // A closed token account means the leader sold everything
+        let leader_remaining = self.token_balance(&trade.user, &trade.mint).await;
+        sell_amount(held, trade.token_amount, leader_remaining)
+    }
+
+    async fn token_balance(&self, owner: &Pubkey, mint: &Pubkey) -> u64 {
+        let ata = get_associated_token_address(owner, mint);
+        self.client
+            .rpc
+            .get_token_account_balance(&ata)
+            .await
+            .ok()
+            .and_then(|balance| balance.amount.parse().ok())
+            .unwrap_or(0)
+    }
+
+    async fn buy(&self, mint: &Pubkey, amount_sol: u64) -> Result<Signature, String> {
+        let mut instructions = PumpFun::get_priority_fee_instructions(&self.priority_fee());
+        let buy = self
+            .client
+            .get_buy_instructions(*mint, amount_sol, None, self.config.slippage_basis_points)
+            .await
+            .map_err(|err| err.to_string())?;
+        instructions.extend(buy);
+        self.send(&instructions).await
+    }
+
+    async fn sell(&self, mint: &Pubkey, amount_token: Option<u64>) -> Result<Signature, String> {
+        let mut instructions = PumpFun::get_priority_fee_instructions(&self.priority_fee());
+        let sell = self
+            .client
+            .get_sell_instructions(*mint, amount_token, self.config.slippage_basis_points)
+            .await
+            .map_err(|err| err.to_string())?;
+        instructions.extend(sell);
+        self.send(&instructions).await
+    }
+
+    async fn send(&self, instructions: &[Instruction]) -> Result<Signature, String> {
+        self.client
+            .send_instructions(instructions, None, self.sender.as_deref())
+            .await
+            .map_err(|err| err.to_string())
+    }
+
+    fn priority_fee(&self) -> PriorityFee {
+        self.config
+            .priority_fee
+            .unwrap_or(self.client.cluster.priority_fee)
+    }
+
+    fn lock_spent(&self) -> std::sync::MutexGuard<'_, HashMap<Pubkey, u64>> {
+        match self.spent.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buy_amount() {
+        let config = CopyTradeConfig {
+            min_buy: 10,
+            max_buy: Some(500),
+            max_position: Some(800),
+            ..CopyTradeConfig::new(0.5)
+        };
+        assert_eq!(config.buy_amount(400, 0), Some(200));
+        // Capped per trade and per position
+        assert_eq!(config.buy_amount(2_000, 0), Some(500));
+        assert_eq!(config.buy_amount(2_000, 500), Some(300));
+        assert_eq!(config.buy_amount(2_000, 800), None);
+        // Too small to copy
+        assert_eq!(config.buy_amount(10, 0), None);
+    }
+
+    #[test]
+    fn test_sell_amount() {
+        // The leader sold a quarter of their balance
+        assert_eq!(sell_amount(1_000, 250, 750), Some(Some(250)));
+        // The leader sold everything
+        assert_eq!(sell_amount(1_000, 250, 0), Some(None));
+        // Nothing to sell
+        assert_eq!(sell_amount(0, 250, 750), None);
+        assert_eq!(sell_amount(1, 1, 1_000), None);
+    }
+}
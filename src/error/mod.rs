@@ -10,10 +10,46 @@
 //! - `BondingCurveNotFound`: The bonding curve account was not found.
 //! - `BondingCurveError`: An error occurred while interacting with the bonding curve.
 //! - `BorshError`: An error occurred while serializing or deserializing data using Borsh.
+//! - `AccountDiscriminatorMismatch`: Fetched account data is not of the expected account type.
 //! - `SolanaClientError`: An error occurred while interacting with the Solana RPC client.
 //! - `PubsubClientError`: An error occurred while interacting with the Solana Pubsub client.
 //! - `UploadMetadataError`: An error occurred while uploading metadata to IPFS.
+//! - `MetadataUploadRejected`: A metadata upload provider rejected the upload.
+//! - `InvalidImage`: A token image has an unsupported format or exceeds the size limits.
+//! - `ConfigError`: A configuration file or update could not be loaded or applied.
+//! - `RateLimited`: An RPC provider or relay throttled the request.
+//! - `SenderError`: A transaction could not be submitted or confirmed by a transaction sender.
+//! - `TransactionExpired`: A transaction's blockhash expired before it was confirmed.
+//! - `TransactionTooLarge`: A transaction exceeds the network's size limit once serialized.
+//! - `ProgramError`: An instruction failed with a known Pump.fun or Anchor error code.
+//! - `SimulationFailure`: Preflight or explicit simulation failed, with the program logs.
+//! - `InsufficientFunds`: The paying account does not hold enough SOL for the trade.
+//! - `InsufficientTokens`: The token account does not hold enough tokens for the sale.
+//! - `BondingCurveComplete`: The bonding curve has completed and can no longer be traded.
+//! - `ProgramNotInitialized`: The program's global configuration is not initialized.
+//! - `Timeout`: An operation did not finish within its per-call timeout.
+//! - `RiskRejected`: A buy would exceed the client's risk limits.
+//! - `GeyserError`: A Yellowstone gRPC connection or subscription failed (requires `geyser`).
+//! - `SinkError`: An event sink could not connect or publish an event (requires `stream`).
+//! - `StorageError`: An `EventStore` database operation failed (requires `storage`).
+//! - `ApiError`: A pump.fun frontend API request failed (requires `api`).
 //! - `OtherError`: An error occurred that is not covered by the other error types.
+//!
+//! Rate-limited responses are also counted process-wide; see `rate_limit_stats`.
+
+mod program;
+mod rate_limit;
+mod simulation;
+
+pub use program::*;
+pub use rate_limit::*;
+pub(crate) use simulation::*;
+
+use std::time::Duration;
+
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::TransactionError};
+
+use crate::common::risk::RiskRejection;
 
 #[derive(Debug)]
 #[allow(clippy::large_enum_variant)]
@@ -24,6 +60,13 @@ pub enum ClientError {
     BondingCurveError(&'static str),
     /// Error deserializing data using Borsh
     BorshError(std::io::Error),
+    /// Account data does not start with the discriminator of the expected account type
+    AccountDiscriminatorMismatch {
+        /// Discriminator of the expected account type
+        expected: [u8; 8],
+        /// First 8 bytes of the account data, zero-padded if it is shorter
+        found: [u8; 8],
+    },
     /// Error from Solana RPC client
     SolanaClientError(solana_client::client_error::ClientError),
     /// Error from Solana Pubsub client
@@ -31,6 +74,85 @@ pub enum ClientError {
     PubsubClientError(solana_client::pubsub_client::PubsubClientError),
     /// Error uploading metadata
     UploadMetadataError(Box<dyn std::error::Error>),
+    /// Metadata upload provider responded with an error status
+    MetadataUploadRejected {
+        /// HTTP status of the response
+        status: u16,
+        /// Response body
+        message: String,
+    },
+    /// Token image rejected by the image limits before upload
+    InvalidImage(String),
+    /// Error loading or applying configuration
+    ConfigError(String),
+    /// RPC provider or relay throttled the request
+    RateLimited {
+        /// Delay suggested by the provider before retrying, if it sent one
+        retry_after: Option<Duration>,
+    },
+    /// Error submitting or confirming a transaction through a sender
+    SenderError(String),
+    /// Transaction blockhash expired before the transaction was confirmed
+    TransactionExpired(Signature),
+    /// Serialized transaction exceeds the network's size limit
+    TransactionTooLarge {
+        /// Serialized size of the transaction, in bytes
+        size: usize,
+        /// Largest size accepted by the network, in bytes
+        limit: usize,
+    },
+    /// Instruction failed with a Pump.fun or Anchor custom error
+    ProgramError {
+        /// Index of the failing instruction within the transaction
+        instruction_index: u8,
+        /// Decoded program error
+        error: ProgramError,
+    },
+    /// Transaction simulation failed, either during preflight or an explicit simulation
+    SimulationFailure {
+        /// Program logs produced by the simulation
+        logs: Vec<String>,
+        /// Index of the failing instruction, if the failure came from an instruction
+        ix_index: Option<u8>,
+        /// Decoded Anchor or Pump.fun error, if one was reported
+        program_error: Option<ProgramError>,
+        /// Transaction error returned by the simulation
+        error: Option<TransactionError>,
+    },
+    /// Account does not hold enough SOL to cover the trade, fees, and rent
+    InsufficientFunds {
+        /// Lamports required
+        required: u64,
+        /// Lamports available
+        available: u64,
+    },
+    /// Token account does not hold enough tokens to sell
+    InsufficientTokens {
+        /// Token amount required, in base units
+        required: u64,
+        /// Token amount available, in base units
+        available: u64,
+    },
+    /// Bonding curve of the given mint has completed and can no longer be traded
+    BondingCurveComplete(Pubkey),
+    /// Program global configuration is not initialized
+    ProgramNotInitialized,
+    /// Operation did not finish within the given timeout
+    Timeout(Duration),
+    /// Buy rejected by the client's risk limits
+    RiskRejected(RiskRejection),
+    /// Error from a Yellowstone gRPC connection or subscription
+    #[cfg(feature = "geyser")]
+    GeyserError(String),
+    /// Error connecting to or publishing through an event sink
+    #[cfg(feature = "stream")]
+    SinkError(String),
+    /// Error reading or writing the event store database
+    #[cfg(feature = "storage")]
+    StorageError(String),
+    /// Error requesting the pump.fun frontend API
+    #[cfg(feature = "api")]
+    ApiError(String),
     /// Other error
     OtherError(String),
 }
@@ -41,15 +163,151 @@ impl std::fmt::Display for ClientError {
             Self::BondingCurveNotFound => write!(f, "Bonding curve not found"),
             Self::BondingCurveError(msg) => write!(f, "Bonding curve error: {}", msg),
             Self::BorshError(err) => write!(f, "Borsh serialization error: {}", err),
+            Self::AccountDiscriminatorMismatch { expected, found } => write!(
+                f,
+                "Account discriminator mismatch: expected {:?}, found {:?}; check the account \
+                 address",
+                expected, found
+            ),
             Self::SolanaClientError(err) => write!(f, "Solana client error: {}", err),
             #[cfg(feature = "stream")]
             Self::PubsubClientError(err) => write!(f, "Solana pubsub client error: {}", err),
             Self::UploadMetadataError(err) => write!(f, "Metadata upload error: {}", err),
+            Self::MetadataUploadRejected { status, message } => write!(
+                f,
+                "Metadata upload rejected with status {}: {}",
+                status, message
+            ),
+            Self::InvalidImage(msg) => write!(f, "Invalid image: {}", msg),
+            Self::ConfigError(msg) => write!(f, "Config error: {}", msg),
+            Self::RateLimited {
+                retry_after: Some(delay),
+            } => write!(f, "Rate limited by provider, retry after {:?}", delay),
+            Self::RateLimited { retry_after: None } => write!(f, "Rate limited by provider"),
+            Self::SenderError(msg) => write!(f, "Sender error: {}", msg),
+            Self::TransactionExpired(signature) => {
+                write!(f, "Transaction expired before confirmation: {}", signature)
+            }
+            Self::TransactionTooLarge { size, limit } => write!(
+                f,
+                "Transaction is {} bytes, over the {} byte limit; split the instructions or \
+                 use address lookup tables with the versioned-tx feature",
+                size, limit
+            ),
+            Self::ProgramError {
+                instruction_index,
+                error,
+            } => write!(
+                f,
+                "Program error in instruction {}: {}",
+                instruction_index, error
+            ),
+            Self::SimulationFailure {
+                ix_index,
+                program_error,
+                error,
+                ..
+            } => {
+                write!(f, "Simulation failed")?;
+                if let Some(index) = ix_index {
+                    write!(f, " in instruction {}", index)?;
+                }
+                match (program_error, error) {
+                    (Some(program_error), _) => write!(f, ": {}", program_error),
+                    (None, Some(error)) => write!(f, ": {}", error),
+                    (None, None) => Ok(()),
+                }
+            }
+            Self::InsufficientFunds {
+                required,
+                available,
+            } => write!(
+                f,
+                "Insufficient funds: {} lamports required, {} available",
+                required, available
+            ),
+            Self::InsufficientTokens {
+                required,
+                available,
+            } => write!(
+                f,
+                "Insufficient tokens: {} required, {} available",
+                required, available
+            ),
+            Self::BondingCurveComplete(mint) => {
+                write!(f, "Bonding curve for {} is complete", mint)
+            }
+            Self::ProgramNotInitialized => write!(f, "Program global account is not initialized"),
+            Self::Timeout(duration) => write!(f, "Operation timed out after {:?}", duration),
+            Self::RiskRejected(rejection) => write!(f, "Risk limit rejected buy: {}", rejection),
+            #[cfg(feature = "geyser")]
+            Self::GeyserError(msg) => write!(f, "Geyser gRPC error: {}", msg),
+            #[cfg(feature = "stream")]
+            Self::SinkError(msg) => write!(f, "Event sink error: {}", msg),
+            #[cfg(feature = "storage")]
+            Self::StorageError(msg) => write!(f, "Event storage error: {}", msg),
+            #[cfg(feature = "api")]
+            Self::ApiError(msg) => write!(f, "Frontend API error: {}", msg),
             Self::OtherError(msg) => write!(f, "Other error: {}", msg),
         }
     }
 }
 
+impl ClientError {
+    /// Creates a rate limited error and records it in the rate limiting counters
+    ///
+    /// # Arguments
+    ///
+    /// * `retry_after` - Delay suggested by the provider before retrying, if any
+    pub fn rate_limited(retry_after: Option<Duration>) -> Self {
+        record_rate_limited(retry_after);
+        Self::RateLimited { retry_after }
+    }
+
+    /// Returns whether this error was caused by provider rate limiting
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self, Self::RateLimited { .. })
+    }
+
+    /// Returns the delay suggested by the provider before retrying, if any
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Returns the decoded program error if an instruction failed with a known error code
+    pub fn program_error(&self) -> Option<ProgramError> {
+        match self {
+            Self::ProgramError { error, .. } => Some(*error),
+            Self::SimulationFailure { program_error, .. } => *program_error,
+            _ => None,
+        }
+    }
+
+    /// Returns whether this error means the transaction's blockhash is no longer usable
+    ///
+    /// This covers transactions that expired while awaiting confirmation and transactions
+    /// rejected because the network does not recognize their blockhash. Both can be resolved
+    /// by rebuilding the transaction with a fresh blockhash.
+    pub fn is_blockhash_expired(&self) -> bool {
+        match self {
+            Self::TransactionExpired(_) => true,
+            Self::SimulationFailure { error, .. } => {
+                matches!(error, Some(TransactionError::BlockhashNotFound))
+            }
+            Self::SolanaClientError(err) => {
+                matches!(
+                    err.get_transaction_error(),
+                    Some(TransactionError::BlockhashNotFound)
+                )
+            }
+            _ => false,
+        }
+    }
+}
+
 impl std::error::Error for ClientError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
@@ -65,10 +323,31 @@ impl std::error::Error for ClientError {
 
 impl From<solana_client::client_error::ClientError> for ClientError {
     fn from(err: solana_client::client_error::ClientError) -> Self {
+        if is_rate_limited(err.kind()) {
+            // The RPC client consumes the Retry-After header while retrying internally
+            return Self::rate_limited(None);
+        }
+        if let Some(failure) = preflight_failure(err.kind()) {
+            return failure;
+        }
+        if let Some((instruction_index, error)) =
+            err.get_transaction_error().as_ref().and_then(program_error)
+        {
+            return Self::ProgramError {
+                instruction_index,
+                error,
+            };
+        }
         Self::SolanaClientError(err)
     }
 }
 
+impl From<TransactionError> for ClientError {
+    fn from(err: TransactionError) -> Self {
+        Self::from(solana_client::client_error::ClientError::from(err))
+    }
+}
+
 #[cfg(feature = "stream")]
 impl From<solana_client::pubsub_client::PubsubClientError> for ClientError {
     fn from(err: solana_client::pubsub_client::PubsubClientError) -> Self {
@@ -0,0 +1,186 @@
+//! Typed errors returned by the Pump.fun program
+//!
+//! Failed instructions only report a numeric custom error code. This module maps the codes
+//! defined by the Pump.fun program (6000 and up) and the Anchor framework (100 to 5999) to a
+//! `ProgramError`, so callers can branch on the failure reason instead of parsing strings.
+//!
+//! Codes below 100 are left unmapped, since they are also used by non-Anchor programs such as
+//! the SPL Token program and cannot be attributed reliably.
+
+use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+
+/// Lowest custom error code assigned by the Anchor framework
+const ANCHOR_ERROR_CODE_MIN: u32 = 100;
+
+macro_rules! program_errors {
+    ($($(#[$meta:meta])* $variant:ident = $code:literal => $message:literal,)+) => {
+        /// Error returned by the Pump.fun program or the Anchor framework it is built on
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ProgramError {
+            $($(#[$meta])* $variant,)+
+            /// A custom error code with no known mapping
+            Unknown(u32),
+        }
+
+        impl ProgramError {
+            /// Maps a custom error code to a program error
+            ///
+            /// # Arguments
+            ///
+            /// * `code` - Custom error code reported by the failed instruction
+            ///
+            /// # Returns
+            ///
+            /// The matching `ProgramError`, or `ProgramError::Unknown` for unmapped codes
+            pub fn from_code(code: u32) -> Self {
+                match code {
+                    $($code => Self::$variant,)+
+                    _ => Self::Unknown(code),
+                }
+            }
+
+            /// Gets the custom error code of this error
+            pub fn code(&self) -> u32 {
+                match self {
+                    $(Self::$variant => $code,)+
+                    Self::Unknown(code) => *code,
+                }
+            }
+
+            /// Gets the human-readable message defined by the program
+            pub fn message(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $message,)+
+                    Self::Unknown(_) => "Unknown program error",
+                }
+            }
+        }
+    };
+}
+
+program_errors! {
+    // Anchor instruction errors
+    InstructionFallbackNotFound = 101 => "Fallback functions are not supported",
+    InstructionDidNotDeserialize = 102 => "The program could not deserialize the given instruction",
+
+    // Anchor constraint errors
+    ConstraintMut = 2000 => "A mut constraint was violated",
+    ConstraintHasOne = 2001 => "A has one constraint was violated",
+    ConstraintSigner = 2002 => "A signer constraint was violated",
+    ConstraintRaw = 2003 => "A raw constraint was violated",
+    ConstraintOwner = 2004 => "An owner constraint was violated",
+    ConstraintRentExempt = 2005 => "A rent exemption constraint was violated",
+    ConstraintSeeds = 2006 => "A seeds constraint was violated",
+    ConstraintAddress = 2012 => "An address constraint was violated",
+    ConstraintTokenMint = 2014 => "A token mint constraint was violated",
+    ConstraintTokenOwner = 2015 => "A token owner constraint was violated",
+
+    // Anchor account errors
+    AccountDiscriminatorNotFound = 3001 => "No 8 byte discriminator was found on the account",
+    AccountDiscriminatorMismatch = 3002 => "8 byte discriminator did not match what was expected",
+    AccountDidNotDeserialize = 3003 => "Failed to deserialize the account",
+    AccountOwnedByWrongProgram = 3007 => "The given account is owned by a different program than expected",
+    AccountNotSigner = 3010 => "The given account did not sign",
+    AccountNotInitialized = 3012 => "The program expected this account to be already initialized",
+
+    // Pump.fun program errors
+    NotAuthorized = 6000 => "The given account is not authorized to execute this instruction",
+    AlreadyInitialized = 6001 => "The program is already initialized",
+    TooMuchSolRequired = 6002 => "Slippage: Too much SOL required to buy the given amount of tokens",
+    TooLittleSolReceived = 6003 => "Slippage: Too little SOL received to sell the given amount of tokens",
+    MintDoesNotMatchBondingCurve = 6004 => "The mint does not match the bonding curve",
+    BondingCurveComplete = 6005 => "The bonding curve has completed and liquidity migrated to raydium",
+    BondingCurveNotComplete = 6006 => "The bonding curve has not completed",
+    NotInitialized = 6007 => "The program is not initialized",
+    WithdrawTooFrequent = 6008 => "Withdraw too frequent",
+    NewSizeShouldBeGreaterThanCurrentSize = 6009 => "New size should be greater than current size",
+    AccountTypeNotSupported = 6010 => "Account type not supported",
+    InitialRealTokenReservesShouldBeLessThanTokenTotalSupply = 6011 => "Initial real token reserves should be less than token total supply",
+    InitialVirtualTokenReservesShouldBeGreaterThanRealTokenReserves = 6012 => "Initial virtual token reserves should be greater than real token reserves",
+    FeeBasisPointsGreaterThanMaximum = 6013 => "Fee basis points greater than maximum",
+    AllZerosWithdrawAuthority = 6014 => "Withdraw authority cannot be set to System Program ID",
+    PoolMigrationFeeShouldBeLessThanFinalRealSolReserves = 6015 => "Pool migration fee should be less than final real sol reserves",
+    PoolMigrationFeeShouldBeGreaterThanCreatorFeePlusMaxMigrateFees = 6016 => "Pool migration fee should be greater than creator fee + MAX_MIGRATE_FEES",
+    DisabledWithdraw = 6017 => "Withdraw instruction is disabled",
+    DisabledMigrate = 6018 => "Migrate instruction is disabled",
+    InvalidCreator = 6019 => "Invalid creator pubkey",
+    BuyZeroAmount = 6020 => "Buy zero amount",
+    NotEnoughTokensToBuy = 6021 => "Not enough tokens to buy",
+    SellZeroAmount = 6022 => "Sell zero amount",
+    NotEnoughTokensToSell = 6023 => "Not enough tokens to sell",
+    Overflow = 6024 => "Overflow",
+    Truncation = 6025 => "Truncation",
+    DivisionByZero = 6026 => "Division by zero",
+    NotEnoughRemainingAccounts = 6027 => "Not enough remaining accounts",
+    AllFeeRecipientsShouldBeNonZero = 6028 => "All fee recipients should be non-zero",
+    UnsortedNotUniqueFeeRecipients = 6029 => "Unsorted or not unique fee recipients",
+    CreatorShouldNotBeZero = 6030 => "Creator should not be zero",
+}
+
+impl ProgramError {
+    /// Returns whether the error was caused by the slippage limits of a trade
+    pub fn is_slippage(&self) -> bool {
+        matches!(self, Self::TooMuchSolRequired | Self::TooLittleSolReceived)
+    }
+}
+
+impl std::fmt::Display for ProgramError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unknown(code) => write!(f, "Unknown program error {:#x}", code),
+            _ => write!(f, "{:?} ({:#x}): {}", self, self.code(), self.message()),
+        }
+    }
+}
+
+/// Extracts a typed program error from a transaction error
+///
+/// # Returns
+///
+/// The failing instruction index and program error, or None if the error is not an Anchor
+/// custom error
+pub(crate) fn program_error(err: &TransactionError) -> Option<(u8, ProgramError)> {
+    match err {
+        TransactionError::InstructionError(index, InstructionError::Custom(code))
+            if *code >= ANCHOR_ERROR_CODE_MIN =>
+        {
+            Some((*index, ProgramError::from_code(*code)))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_code() {
+        assert_eq!(
+            ProgramError::from_code(0x7d6),
+            ProgramError::ConstraintSeeds
+        );
+        assert_eq!(
+            ProgramError::from_code(6002),
+            ProgramError::TooMuchSolRequired
+        );
+        assert!(ProgramError::from_code(6003).is_slippage());
+        assert_eq!(ProgramError::BondingCurveComplete.code(), 6005);
+        assert_eq!(ProgramError::from_code(9999), ProgramError::Unknown(9999));
+        assert_eq!(ProgramError::Unknown(9999).code(), 9999);
+    }
+
+    #[test]
+    fn test_program_error() {
+        let err = TransactionError::InstructionError(2, InstructionError::Custom(6005));
+        assert_eq!(
+            program_error(&err),
+            Some((2, ProgramError::BondingCurveComplete))
+        );
+
+        // Low codes are shared with non-Anchor programs
+        let err = TransactionError::InstructionError(2, InstructionError::Custom(1));
+        assert_eq!(program_error(&err), None);
+        assert_eq!(program_error(&TransactionError::BlockhashNotFound), None);
+    }
+}
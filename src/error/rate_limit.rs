@@ -0,0 +1,128 @@
+//! Detection and accounting of provider rate limiting
+//!
+//! RPC providers and relays answer with HTTP 429 (or an equivalent JSON-RPC error) when a
+//! plan's request budget is exhausted. These responses are surfaced as
+//! `ClientError::RateLimited` and counted here, so operators can tell when their provider
+//! is the bottleneck.
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use solana_client::{client_error::ClientErrorKind, rpc_request::RpcError};
+
+/// Total number of rate-limited responses observed
+static RATE_LIMITED_TOTAL: AtomicU64 = AtomicU64::new(0);
+/// Retry delay of the most recent rate-limited response in milliseconds, `u64::MAX` if unknown
+static LAST_RETRY_AFTER_MS: AtomicU64 = AtomicU64::new(u64::MAX);
+
+/// Snapshot of rate limiting counters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitStats {
+    /// Total number of rate-limited responses observed by this process
+    pub total: u64,
+    /// Retry delay suggested by the most recent rate-limited response, if any
+    pub last_retry_after: Option<Duration>,
+}
+
+/// Returns the rate limiting counters for this process
+///
+/// # Returns
+///
+/// Returns a snapshot of how many rate-limited responses have been seen so far
+///
+/// # Examples
+///
+/// ```
+/// use pumpfun::error::rate_limit_stats;
+///
+/// let stats = rate_limit_stats();
+/// println!("Rate limited {} times", stats.total);
+/// ```
+pub fn rate_limit_stats() -> RateLimitStats {
+    let last = LAST_RETRY_AFTER_MS.load(Ordering::Relaxed);
+    RateLimitStats {
+        total: RATE_LIMITED_TOTAL.load(Ordering::Relaxed),
+        last_retry_after: (last != u64::MAX).then(|| Duration::from_millis(last)),
+    }
+}
+
+/// Records a rate-limited response
+pub(crate) fn record_rate_limited(retry_after: Option<Duration>) {
+    RATE_LIMITED_TOTAL.fetch_add(1, Ordering::Relaxed);
+    let millis = retry_after.map_or(u64::MAX, |delay| delay.as_millis() as u64);
+    LAST_RETRY_AFTER_MS.store(millis, Ordering::Relaxed);
+}
+
+/// Parses a `Retry-After` header value given in seconds
+pub(crate) fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// Returns whether a Solana client error is a throttling response
+pub(crate) fn is_rate_limited(kind: &ClientErrorKind) -> bool {
+    match kind {
+        ClientErrorKind::Reqwest(err) => err.status().map(|status| status.as_u16()) == Some(429),
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, message, .. }) => {
+            *code == 429 || *code == -32429 || is_rate_limit_message(message)
+        }
+        ClientErrorKind::RpcError(RpcError::ForUser(message))
+        | ClientErrorKind::RpcError(RpcError::RpcRequestError(message)) => {
+            is_rate_limit_message(message)
+        }
+        _ => false,
+    }
+}
+
+fn is_rate_limit_message(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("429")
+        || message.contains("too many requests")
+        || message.contains("rate limit")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_client::rpc_request::RpcResponseErrorData;
+
+    #[test]
+    fn test_is_rate_limited() {
+        let throttled = ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            code: -32429,
+            message: "rate limit exceeded".to_string(),
+            data: RpcResponseErrorData::Empty,
+        });
+        assert!(is_rate_limited(&throttled));
+
+        let throttled = ClientErrorKind::RpcError(RpcError::ForUser(
+            "HTTP status client error (429 Too Many Requests)".to_string(),
+        ));
+        assert!(is_rate_limited(&throttled));
+
+        let other = ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            code: -32002,
+            message: "Transaction simulation failed".to_string(),
+            data: RpcResponseErrorData::Empty,
+        });
+        assert!(!is_rate_limited(&other));
+        assert!(!is_rate_limited(&ClientErrorKind::Custom(
+            "boom".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("5"), Some(Duration::from_secs(5)));
+        assert_eq!(parse_retry_after(" 12 "), Some(Duration::from_secs(12)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+    }
+
+    #[test]
+    fn test_record_rate_limited() {
+        let before = rate_limit_stats().total;
+        record_rate_limited(Some(Duration::from_secs(2)));
+        assert!(rate_limit_stats().total > before);
+    }
+}
@@ -0,0 +1,112 @@
+//! Decoding of failed transaction simulations
+//!
+//! RPC nodes simulate transactions before forwarding them (preflight), and callers can
+//! simulate explicitly. When the simulation fails, the raw client error only carries an
+//! opaque message. This module extracts the program logs, the failing instruction, and the
+//! Anchor error into a `ClientError::SimulationFailure`.
+
+use solana_client::{
+    client_error::ClientErrorKind,
+    rpc_request::{RpcError, RpcResponseErrorData},
+};
+use solana_sdk::transaction::TransactionError;
+
+use super::{program_error, ClientError, ProgramError};
+
+/// Builds a simulation failure from the simulation result
+///
+/// The failing instruction and program error are taken from the transaction error when it is
+/// a custom instruction error, otherwise the Anchor error number is looked up in the logs.
+pub(crate) fn simulation_failure(err: Option<TransactionError>, logs: Vec<String>) -> ClientError {
+    let (ix_index, program_error) = match err.as_ref() {
+        Some(TransactionError::InstructionError(index, _)) => (
+            Some(*index),
+            err.as_ref()
+                .and_then(program_error)
+                .map(|(_, error)| error)
+                .or_else(|| anchor_error_from_logs(&logs)),
+        ),
+        _ => (None, anchor_error_from_logs(&logs)),
+    };
+
+    ClientError::SimulationFailure {
+        logs,
+        ix_index,
+        program_error,
+        error: err,
+    }
+}
+
+/// Extracts the simulation result from a failed preflight check
+pub(crate) fn preflight_failure(kind: &ClientErrorKind) -> Option<ClientError> {
+    match kind {
+        ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+            ..
+        }) => Some(simulation_failure(
+            result.err.clone(),
+            result.logs.clone().unwrap_or_default(),
+        )),
+        _ => None,
+    }
+}
+
+/// Finds the Anchor error reported in program logs
+///
+/// Anchor logs failures as `AnchorError ... Error Number: 6002. Error Message: ...`.
+fn anchor_error_from_logs(logs: &[String]) -> Option<ProgramError> {
+    logs.iter().rev().find_map(|line| {
+        let (_, rest) = line.split_once("Error Number: ")?;
+        let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+        digits.parse().ok().map(ProgramError::from_code)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::instruction::InstructionError;
+
+    fn get_logs() -> Vec<String> {
+        vec![
+            "Program 6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P invoke [1]".to_string(),
+            "Program log: Instruction: Buy".to_string(),
+            "Program log: AnchorError thrown in programs/pump/src/lib.rs:730. Error Code: TooMuchSolRequired. Error Number: 6002. Error Message: slippage: Too much SOL required to buy the given amount of tokens..".to_string(),
+            "Program 6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P failed: custom program error: 0x1772".to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_simulation_failure() {
+        let err = TransactionError::InstructionError(2, InstructionError::Custom(6002));
+        let failure = simulation_failure(Some(err.clone()), get_logs());
+        match failure {
+            ClientError::SimulationFailure {
+                logs,
+                ix_index,
+                program_error,
+                error,
+            } => {
+                assert_eq!(logs.len(), 4);
+                assert_eq!(ix_index, Some(2));
+                assert_eq!(program_error, Some(ProgramError::TooMuchSolRequired));
+                assert_eq!(error, Some(err));
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn test_anchor_error_from_logs() {
+        assert_eq!(
+            anchor_error_from_logs(&get_logs()),
+            Some(ProgramError::TooMuchSolRequired)
+        );
+        assert_eq!(anchor_error_from_logs(&[]), None);
+
+        // Non-Anchor failures only report the error through the transaction error
+        let err = TransactionError::InstructionError(0, InstructionError::InsufficientFunds);
+        let failure = simulation_failure(Some(err), Vec::new());
+        assert_eq!(failure.program_error(), None);
+    }
+}
@@ -0,0 +1,542 @@
+//! C-compatible interface to the SDK's offline functionality
+//!
+//! This module exposes `extern "C"` functions for the parts of the SDK that work without a
+//! network connection, so trading systems written in C, C++, or C# can embed the Pump.fun
+//! logic instead of re-implementing it:
+//!
+//! - PDA derivation (`pumpfun_get_global_pda`, `pumpfun_get_bonding_curve_pda`, ...)
+//! - Quoting from raw bonding curve account data (`pumpfun_quote_buy`, `pumpfun_quote_sell`)
+//! - Instruction building (`pumpfun_buy_instruction`, `pumpfun_sell_instruction`)
+//! - Event parsing into JSON (`pumpfun_parse_event`)
+//!
+//! Public keys are passed as pointers to 32 raw bytes. Every function returns one of the
+//! `PUMPFUN_*` status codes, except `pumpfun_parse_event`, which returns a string that must
+//! be released with `pumpfun_free_string`.
+//!
+//! Build a shared library with `cargo rustc --release --features ffi --crate-type cdylib`.
+
+use std::{
+    ffi::{c_char, CStr, CString},
+    ptr, slice,
+};
+
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+use crate::{accounts::BondingCurveAccount, common::stream, instructions, PumpFun};
+
+/// The call succeeded
+pub const PUMPFUN_OK: i32 = 0;
+/// A required pointer argument was null
+pub const PUMPFUN_ERR_NULL_POINTER: i32 = -1;
+/// An argument could not be decoded
+pub const PUMPFUN_ERR_INVALID_INPUT: i32 = -2;
+/// An output buffer is too small; the required length is still written
+pub const PUMPFUN_ERR_BUFFER_TOO_SMALL: i32 = -3;
+/// The computation failed, e.g. because the bonding curve is complete
+pub const PUMPFUN_ERR_FAILED: i32 = -4;
+
+/// Account metadata of an instruction
+///
+/// # Fields
+///
+/// * `pubkey` - Account public key
+/// * `is_signer` - 1 if the account must sign the transaction, otherwise 0
+/// * `is_writable` - 1 if the account is writable, otherwise 0
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct PumpfunAccountMeta {
+    pub pubkey: [u8; 32],
+    pub is_signer: u8,
+    pub is_writable: u8,
+}
+
+/// Writes the global state PDA
+///
+/// # Safety
+///
+/// `out` must be valid for writes of 32 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn pumpfun_get_global_pda(out: *mut u8) -> i32 {
+    write_pubkey(&PumpFun::get_global_pda(), out)
+}
+
+/// Writes the mint authority PDA
+///
+/// # Safety
+///
+/// `out` must be valid for writes of 32 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn pumpfun_get_mint_authority_pda(out: *mut u8) -> i32 {
+    write_pubkey(&PumpFun::get_mint_authority_pda(), out)
+}
+
+/// Writes the bonding curve PDA of a mint
+///
+/// # Safety
+///
+/// `mint` must be valid for reads of 32 bytes and `out` must be valid for writes of 32 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn pumpfun_get_bonding_curve_pda(mint: *const u8, out: *mut u8) -> i32 {
+    let Some(mint) = read_pubkey(mint) else {
+        return PUMPFUN_ERR_NULL_POINTER;
+    };
+    match PumpFun::get_bonding_curve_pda(&mint) {
+        Some(pda) => write_pubkey(&pda, out),
+        None => PUMPFUN_ERR_FAILED,
+    }
+}
+
+/// Writes the metadata PDA of a mint
+///
+/// # Safety
+///
+/// `mint` must be valid for reads of 32 bytes and `out` must be valid for writes of 32 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn pumpfun_get_metadata_pda(mint: *const u8, out: *mut u8) -> i32 {
+    let Some(mint) = read_pubkey(mint) else {
+        return PUMPFUN_ERR_NULL_POINTER;
+    };
+    write_pubkey(&PumpFun::get_metadata_pda(&mint), out)
+}
+
+/// Writes the creator vault PDA of a creator
+///
+/// # Safety
+///
+/// `creator` must be valid for reads of 32 bytes and `out` must be valid for writes of 32 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn pumpfun_get_creator_vault_pda(creator: *const u8, out: *mut u8) -> i32 {
+    let Some(creator) = read_pubkey(creator) else {
+        return PUMPFUN_ERR_NULL_POINTER;
+    };
+    match PumpFun::get_creator_vault_pda(&creator) {
+        Some(pda) => write_pubkey(&pda, out),
+        None => PUMPFUN_ERR_FAILED,
+    }
+}
+
+/// Quotes the tokens received for spending SOL on a bonding curve
+///
+/// # Arguments
+///
+/// * `curve_data` - Raw bonding curve account data as fetched from the network
+/// * `curve_len` - Length of `curve_data` in bytes
+/// * `amount_sol` - Lamports to spend
+/// * `out_tokens` - Receives the token amount in base units
+///
+/// # Safety
+///
+/// `curve_data` must be valid for reads of `curve_len` bytes and `out_tokens` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn pumpfun_quote_buy(
+    curve_data: *const u8,
+    curve_len: usize,
+    amount_sol: u64,
+    out_tokens: *mut u64,
+) -> i32 {
+    let Some(curve) = read_bonding_curve(curve_data, curve_len) else {
+        return PUMPFUN_ERR_INVALID_INPUT;
+    };
+    match curve.get_buy_price(amount_sol) {
+        Ok(tokens) => write_value(tokens, out_tokens),
+        Err(_) => PUMPFUN_ERR_FAILED,
+    }
+}
+
+/// Quotes the SOL received for selling tokens to a bonding curve, after fees
+///
+/// # Arguments
+///
+/// * `curve_data` - Raw bonding curve account data as fetched from the network
+/// * `curve_len` - Length of `curve_data` in bytes
+/// * `amount_token` - Tokens to sell, in base units
+/// * `fee_basis_points` - Program fee from the global account
+/// * `out_sol` - Receives the lamports received
+///
+/// # Safety
+///
+/// `curve_data` must be valid for reads of `curve_len` bytes and `out_sol` must be valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn pumpfun_quote_sell(
+    curve_data: *const u8,
+    curve_len: usize,
+    amount_token: u64,
+    fee_basis_points: u64,
+    out_sol: *mut u64,
+) -> i32 {
+    let Some(curve) = read_bonding_curve(curve_data, curve_len) else {
+        return PUMPFUN_ERR_INVALID_INPUT;
+    };
+    match curve.get_sell_price(amount_token, fee_basis_points) {
+        Ok(sol) => write_value(sol, out_sol),
+        Err(_) => PUMPFUN_ERR_FAILED,
+    }
+}
+
+/// Builds a buy instruction
+///
+/// The instruction targets the Pump.fun program. Accounts and data are written to the given
+/// buffers, and their lengths to `accounts_len` and `data_len`. If a buffer is too small,
+/// `PUMPFUN_ERR_BUFFER_TOO_SMALL` is returned with the required lengths still written.
+///
+/// # Arguments
+///
+/// * `user` - Buyer, which must sign the transaction
+/// * `mint` - Token mint to buy
+/// * `fee_recipient` - Fee recipient from the global account
+/// * `creator` - Token creator from the bonding curve account
+/// * `amount` - Tokens to buy, in base units
+/// * `max_sol_cost` - Maximum lamports to spend, including fees
+/// * `track_volume` - 1 to track volume, 0 to not track it, or -1 to leave it unset
+///
+/// # Safety
+///
+/// All public key pointers must be valid for reads of 32 bytes, `accounts` must be valid for
+/// writes of `accounts_capacity` elements, `data` must be valid for writes of `data_capacity`
+/// bytes, and `accounts_len` and `data_len` must be valid for writes.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn pumpfun_buy_instruction(
+    user: *const u8,
+    mint: *const u8,
+    fee_recipient: *const u8,
+    creator: *const u8,
+    amount: u64,
+    max_sol_cost: u64,
+    track_volume: i8,
+    accounts: *mut PumpfunAccountMeta,
+    accounts_capacity: usize,
+    accounts_len: *mut usize,
+    data: *mut u8,
+    data_capacity: usize,
+    data_len: *mut usize,
+) -> i32 {
+    let (Some(user), Some(mint), Some(fee_recipient), Some(creator)) = (
+        read_pubkey(user),
+        read_pubkey(mint),
+        read_pubkey(fee_recipient),
+        read_pubkey(creator),
+    ) else {
+        return PUMPFUN_ERR_NULL_POINTER;
+    };
+    let args = instructions::Buy {
+        amount,
+        max_sol_cost,
+        track_volume: match track_volume {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        },
+    };
+    match instructions::buy_for(&user, &mint, &fee_recipient, &creator, args) {
+        Ok(instruction) => write_instruction(
+            &instruction,
+            accounts,
+            accounts_capacity,
+            accounts_len,
+            data,
+            data_capacity,
+            data_len,
+        ),
+        Err(_) => PUMPFUN_ERR_FAILED,
+    }
+}
+
+/// Builds a sell instruction
+///
+/// Output buffers behave as in `pumpfun_buy_instruction`.
+///
+/// # Arguments
+///
+/// * `user` - Seller, which must sign the transaction
+/// * `mint` - Token mint to sell
+/// * `fee_recipient` - Fee recipient from the global account
+/// * `creator` - Token creator from the bonding curve account
+/// * `amount` - Tokens to sell, in base units
+/// * `min_sol_output` - Minimum lamports to receive
+///
+/// # Safety
+///
+/// All public key pointers must be valid for reads of 32 bytes, `accounts` must be valid for
+/// writes of `accounts_capacity` elements, `data` must be valid for writes of `data_capacity`
+/// bytes, and `accounts_len` and `data_len` must be valid for writes.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn pumpfun_sell_instruction(
+    user: *const u8,
+    mint: *const u8,
+    fee_recipient: *const u8,
+    creator: *const u8,
+    amount: u64,
+    min_sol_output: u64,
+    accounts: *mut PumpfunAccountMeta,
+    accounts_capacity: usize,
+    accounts_len: *mut usize,
+    data: *mut u8,
+    data_capacity: usize,
+    data_len: *mut usize,
+) -> i32 {
+    let (Some(user), Some(mint), Some(fee_recipient), Some(creator)) = (
+        read_pubkey(user),
+        read_pubkey(mint),
+        read_pubkey(fee_recipient),
+        read_pubkey(creator),
+    ) else {
+        return PUMPFUN_ERR_NULL_POINTER;
+    };
+    let args = instructions::Sell {
+        amount,
+        min_sol_output,
+    };
+    match instructions::sell_for(&user, &mint, &fee_recipient, &creator, args) {
+        Ok(instruction) => write_instruction(
+            &instruction,
+            accounts,
+            accounts_capacity,
+            accounts_len,
+            data,
+            data_capacity,
+            data_len,
+        ),
+        Err(_) => PUMPFUN_ERR_FAILED,
+    }
+}
+
+/// Parses base64-encoded program log data into a JSON event
+///
+/// The JSON has the same shape as the serde serialization of `PumpFunEvent`, e.g.
+/// `{"Trade":{"mint":"...","sol_amount":1000,...}}`.
+///
+/// # Arguments
+///
+/// * `data` - Null-terminated base64 data following `Program data: ` in the logs
+///
+/// # Returns
+///
+/// A null-terminated JSON string to release with `pumpfun_free_string`, or null if the data
+/// cannot be parsed
+///
+/// # Safety
+///
+/// `data` must be a valid null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn pumpfun_parse_event(data: *const c_char) -> *mut c_char {
+    if data.is_null() {
+        return ptr::null_mut();
+    }
+    let Ok(data) = CStr::from_ptr(data).to_str() else {
+        return ptr::null_mut();
+    };
+    stream::parse_event("", data)
+        .ok()
+        .and_then(|event| serde_json::to_string(&event).ok())
+        .and_then(|json| CString::new(json).ok())
+        .map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Releases a string returned by this library
+///
+/// # Safety
+///
+/// `value` must be null or a pointer returned by this library that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn pumpfun_free_string(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
+
+unsafe fn read_pubkey(value: *const u8) -> Option<Pubkey> {
+    if value.is_null() {
+        return None;
+    }
+    let mut bytes = [0u8; 32];
+    ptr::copy_nonoverlapping(value, bytes.as_mut_ptr(), 32);
+    Some(Pubkey::new_from_array(bytes))
+}
+
+unsafe fn write_pubkey(pubkey: &Pubkey, out: *mut u8) -> i32 {
+    if out.is_null() {
+        return PUMPFUN_ERR_NULL_POINTER;
+    }
+    ptr::copy_nonoverlapping(pubkey.as_ref().as_ptr(), out, 32);
+    PUMPFUN_OK
+}
+
+unsafe fn write_value<T>(value: T, out: *mut T) -> i32 {
+    if out.is_null() {
+        return PUMPFUN_ERR_NULL_POINTER;
+    }
+    out.write(value);
+    PUMPFUN_OK
+}
+
+unsafe fn read_bonding_curve(data: *const u8, len: usize) -> Option<BondingCurveAccount> {
+    if data.is_null() {
+        return None;
+    }
+    let data = slice::from_raw_parts(data, len);
+    solana_sdk::borsh1::try_from_slice_unchecked::<BondingCurveAccount>(data).ok()
+}
+
+unsafe fn write_instruction(
+    instruction: &Instruction,
+    accounts: *mut PumpfunAccountMeta,
+    accounts_capacity: usize,
+    accounts_len: *mut usize,
+    data: *mut u8,
+    data_capacity: usize,
+    data_len: *mut usize,
+) -> i32 {
+    if accounts_len.is_null() || data_len.is_null() {
+        return PUMPFUN_ERR_NULL_POINTER;
+    }
+    accounts_len.write(instruction.accounts.len());
+    data_len.write(instruction.data.len());
+
+    if instruction.accounts.len() > accounts_capacity || instruction.data.len() > data_capacity {
+        return PUMPFUN_ERR_BUFFER_TOO_SMALL;
+    }
+    if accounts.is_null() || data.is_null() {
+        return PUMPFUN_ERR_NULL_POINTER;
+    }
+
+    for (index, meta) in instruction.accounts.iter().enumerate() {
+        accounts.add(index).write(PumpfunAccountMeta {
+            pubkey: meta.pubkey.to_bytes(),
+            is_signer: u8::from(meta.is_signer),
+            is_writable: u8::from(meta.is_writable),
+        });
+    }
+    ptr::copy_nonoverlapping(instruction.data.as_ptr(), data, instruction.data.len());
+    PUMPFUN_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::BorshSerialize;
+
+    #[test]
+    fn test_pda_derivation() {
+        let mint = Pubkey::new_unique();
+        let mut out = [0u8; 32];
+        let status =
+            unsafe { pumpfun_get_bonding_curve_pda(mint.as_ref().as_ptr(), out.as_mut_ptr()) };
+        assert_eq!(status, PUMPFUN_OK);
+        assert_eq!(
+            Some(Pubkey::new_from_array(out)),
+            PumpFun::get_bonding_curve_pda(&mint)
+        );
+
+        let status = unsafe { pumpfun_get_bonding_curve_pda(ptr::null(), out.as_mut_ptr()) };
+        assert_eq!(status, PUMPFUN_ERR_NULL_POINTER);
+    }
+
+    #[test]
+    fn test_quote_buy() {
+        let curve = BondingCurveAccount::new(
+            0,
+            1_073_000_000_000_000,
+            30_000_000_000,
+            793_100_000_000_000,
+            0,
+            1_000_000_000_000_000,
+            false,
+            Pubkey::default(),
+        );
+        let mut data = Vec::new();
+        curve.serialize(&mut data).unwrap();
+
+        let mut tokens = 0u64;
+        let status =
+            unsafe { pumpfun_quote_buy(data.as_ptr(), data.len(), 1_000_000_000, &mut tokens) };
+        assert_eq!(status, PUMPFUN_OK);
+        assert_eq!(tokens, curve.get_buy_price(1_000_000_000).unwrap());
+    }
+
+    #[test]
+    fn test_buy_instruction() {
+        let keys: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let mut accounts = [PumpfunAccountMeta {
+            pubkey: [0; 32],
+            is_signer: 0,
+            is_writable: 0,
+        }; 32];
+        let mut data = [0u8; 64];
+        let (mut accounts_len, mut data_len) = (0usize, 0usize);
+
+        let status = unsafe {
+            pumpfun_buy_instruction(
+                keys[0].as_ref().as_ptr(),
+                keys[1].as_ref().as_ptr(),
+                keys[2].as_ref().as_ptr(),
+                keys[3].as_ref().as_ptr(),
+                100,
+                200,
+                -1,
+                accounts.as_mut_ptr(),
+                accounts.len(),
+                &mut accounts_len,
+                data.as_mut_ptr(),
+                data.len(),
+                &mut data_len,
+            )
+        };
+        assert_eq!(status, PUMPFUN_OK);
+
+        let expected = instructions::buy_for(
+            &keys[0],
+            &keys[1],
+            &keys[2],
+            &keys[3],
+            instructions::Buy {
+                amount: 100,
+                max_sol_cost: 200,
+                track_volume: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(accounts_len, expected.accounts.len());
+        assert_eq!(&data[..data_len], expected.data.as_slice());
+        assert_eq!(accounts[6].pubkey, keys[0].to_bytes());
+        assert_eq!(accounts[6].is_signer, 1);
+
+        // Too small buffers report the required lengths
+        let status = unsafe {
+            pumpfun_buy_instruction(
+                keys[0].as_ref().as_ptr(),
+                keys[1].as_ref().as_ptr(),
+                keys[2].as_ref().as_ptr(),
+                keys[3].as_ref().as_ptr(),
+                100,
+                200,
+                -1,
+                accounts.as_mut_ptr(),
+                1,
+                &mut accounts_len,
+                data.as_mut_ptr(),
+                data.len(),
+                &mut data_len,
+            )
+        };
+        assert_eq!(status, PUMPFUN_ERR_BUFFER_TOO_SMALL);
+        assert_eq!(accounts_len, expected.accounts.len());
+    }
+
+    #[test]
+    fn test_parse_event() {
+        let json = unsafe { pumpfun_parse_event(c"AAAAAAAAAAA=".as_ptr()) };
+        assert!(!json.is_null());
+        let value = unsafe { CStr::from_ptr(json) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        unsafe { pumpfun_free_string(json) };
+        assert!(value.starts_with("{\"Unknown\""));
+
+        assert!(unsafe { pumpfun_parse_event(c"???".as_ptr()) }.is_null());
+    }
+}
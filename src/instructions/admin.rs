@@ -0,0 +1,227 @@
+//! Administrative instructions for the Pump.fun program
+//!
+//! These instructions update the global configuration and can only be signed by the global
+//! authority. They are mainly useful to teams running a fork or a local clone of the program,
+//! e.g. on a test validator, who want to administer it through the same SDK.
+//!
+//! There is no builder for updating the fee recipients: the program has no instruction for
+//! it, and `set_params` does not take them, so the recipients stored in the global account
+//! cannot be changed through this SDK.
+
+use crate::{accounts::GlobalAccount, constants, error, PumpFun};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+/// Instruction data for updating the global parameters
+///
+/// # Fields
+///
+/// * `initial_virtual_token_reserves` - Virtual token reserves of new bonding curves
+/// * `initial_virtual_sol_reserves` - Virtual SOL reserves of new bonding curves
+/// * `initial_real_token_reserves` - Real token reserves of new bonding curves
+/// * `token_total_supply` - Total supply minted for new tokens
+/// * `fee_basis_points` - Protocol fee charged on trades, in basis points
+/// * `withdraw_authority` - Authority allowed to withdraw completed bonding curves
+/// * `enable_migrate` - Whether completed bonding curves can migrate to the AMM
+/// * `pool_migration_fee` - Lamports charged for migrating a bonding curve
+/// * `creator_fee_basis_points` - Creator fee charged on trades, in basis points
+/// * `set_creator_authority` - Authority allowed to set token creators
+/// * `admin_set_creator_authority` - Authority allowed to override token creators
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SetParams {
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub token_total_supply: u64,
+    pub fee_basis_points: u64,
+    pub withdraw_authority: Pubkey,
+    pub enable_migrate: bool,
+    pub pool_migration_fee: u64,
+    pub creator_fee_basis_points: u64,
+    pub set_creator_authority: Pubkey,
+    pub admin_set_creator_authority: Pubkey,
+}
+
+impl SetParams {
+    /// Instruction discriminator used to identify this instruction
+    pub const DISCRIMINATOR: [u8; 8] = constants::discriminators::SET_PARAMS;
+
+    /// Creates parameters matching the current global configuration
+    ///
+    /// Change the fields to update on the result, so the others keep their current values.
+    /// The global account does not store the admin set creator authority, so it has to be
+    /// passed explicitly.
+    ///
+    /// # Arguments
+    ///
+    /// * `global` - Current global configuration
+    /// * `admin_set_creator_authority` - Authority allowed to override token creators
+    pub fn from_global(global: &GlobalAccount, admin_set_creator_authority: Pubkey) -> Self {
+        Self {
+            initial_virtual_token_reserves: global.initial_virtual_token_reserves,
+            initial_virtual_sol_reserves: global.initial_virtual_sol_reserves,
+            initial_real_token_reserves: global.initial_real_token_reserves,
+            token_total_supply: global.token_total_supply,
+            fee_basis_points: global.fee_basis_points,
+            withdraw_authority: global.withdraw_authority,
+            enable_migrate: global.enable_migrate,
+            pool_migration_fee: global.pool_migration_fee,
+            creator_fee_basis_points: global.creator_fee_basis_points,
+            set_creator_authority: global.set_creator_authority,
+            admin_set_creator_authority,
+        }
+    }
+
+    /// Serializes the instruction data with the appropriate discriminator
+    ///
+    /// # Returns
+    ///
+    /// Byte vector containing the serialized instruction data, or a ClientError if
+    /// serialization fails
+    pub fn data(&self) -> Result<Vec<u8>, error::ClientError> {
+        instruction_data(&Self::DISCRIMINATOR, self)
+    }
+}
+
+/// Creates an instruction to update the global parameters
+///
+/// # Arguments
+///
+/// * `authority` - Global authority; must sign the transaction
+/// * `args` - New global parameters
+///
+/// # Returns
+///
+/// Returns a Solana instruction updating the global configuration, or a ClientError if the
+/// instruction data cannot be serialized
+///
+/// # Account Requirements
+///
+/// The instruction requires the following accounts in this order:
+/// 1. Global configuration PDA (writable)
+/// 2. Global authority (signer, writable)
+/// 3. Event authority (readonly)
+/// 4. Pump.fun program ID (readonly)
+pub fn set_params(authority: &Pubkey, args: SetParams) -> Result<Instruction, error::ClientError> {
+    Ok(Instruction::new_with_bytes(
+        constants::accounts::PUMPFUN,
+        &args.data()?,
+        vec![
+            AccountMeta::new(PumpFun::get_global_pda(), false),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(constants::accounts::EVENT_AUTHORITY, false),
+            AccountMeta::new_readonly(constants::accounts::PUMPFUN, false),
+        ],
+    ))
+}
+
+/// Creates an instruction to transfer the global authority
+///
+/// # Arguments
+///
+/// * `authority` - Current global authority; must sign the transaction
+/// * `new_authority` - Account that becomes the global authority
+///
+/// # Returns
+///
+/// Returns a Solana instruction transferring the global authority, or a ClientError if the
+/// instruction cannot be built
+///
+/// # Account Requirements
+///
+/// The instruction requires the following accounts in this order:
+/// 1. Global configuration PDA (writable)
+/// 2. Current global authority (signer)
+/// 3. New global authority (readonly)
+/// 4. Event authority (readonly)
+/// 5. Pump.fun program ID (readonly)
+pub fn update_global_authority(
+    authority: &Pubkey,
+    new_authority: &Pubkey,
+) -> Result<Instruction, error::ClientError> {
+    Ok(Instruction::new_with_bytes(
+        constants::accounts::PUMPFUN,
+        &constants::discriminators::UPDATE_GLOBAL_AUTHORITY,
+        vec![
+            AccountMeta::new(PumpFun::get_global_pda(), false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*new_authority, false),
+            AccountMeta::new_readonly(constants::accounts::EVENT_AUTHORITY, false),
+            AccountMeta::new_readonly(constants::accounts::PUMPFUN, false),
+        ],
+    ))
+}
+
+fn instruction_data(
+    discriminator: &[u8; 8],
+    args: &impl BorshSerialize,
+) -> Result<Vec<u8>, error::ClientError> {
+    let mut data = Vec::with_capacity(256);
+    data.extend_from_slice(discriminator);
+    args.serialize(&mut data)
+        .map_err(error::ClientError::BorshError)?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_admin_instructions() {
+        let authority = Pubkey::new_unique();
+        let args = SetParams {
+            initial_virtual_token_reserves: 1_073_000_000_000_000,
+            initial_virtual_sol_reserves: 30_000_000_000,
+            initial_real_token_reserves: 793_100_000_000_000,
+            token_total_supply: 1_000_000_000_000_000,
+            fee_basis_points: 95,
+            withdraw_authority: Pubkey::new_unique(),
+            enable_migrate: true,
+            pool_migration_fee: 15_000_001,
+            creator_fee_basis_points: 5,
+            set_creator_authority: Pubkey::new_unique(),
+            admin_set_creator_authority: Pubkey::new_unique(),
+        };
+        let instruction = set_params(&authority, args.clone()).unwrap();
+        assert_eq!(instruction.data[..8], SetParams::DISCRIMINATOR);
+        assert_eq!(
+            SetParams::try_from_slice(&instruction.data[8..]).unwrap(),
+            args
+        );
+        assert!(instruction.accounts[1].is_signer);
+
+        let new_authority = Pubkey::new_unique();
+        let instruction = update_global_authority(&authority, &new_authority).unwrap();
+        assert_eq!(
+            instruction.data,
+            constants::discriminators::UPDATE_GLOBAL_AUTHORITY
+        );
+        assert_eq!(instruction.accounts[2].pubkey, new_authority);
+
+        let global = GlobalAccount::new(
+            0,
+            true,
+            authority,
+            Pubkey::new_unique(),
+            1,
+            2,
+            3,
+            4,
+            95,
+            Pubkey::new_unique(),
+            true,
+            5,
+            5,
+            [Pubkey::new_unique(); 7],
+            Pubkey::new_unique(),
+        );
+        let admin = Pubkey::new_unique();
+        let params = SetParams::from_global(&global, admin);
+        assert_eq!(params.set_creator_authority, global.set_creator_authority);
+        assert_eq!(params.admin_set_creator_authority, admin);
+    }
+}
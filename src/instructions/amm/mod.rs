@@ -0,0 +1,321 @@
+//! Administrative instructions for the Pump.fun AMM program
+//!
+//! These instructions create and update the AMM's global configuration and are signed by
+//! its admin. They let localnet deployments and forks of the AMM, which completed bonding
+//! curves migrate to, be set up and managed from Rust.
+//!
+//! # Instructions
+//!
+//! - `CreateConfig`: Creates the global configuration with its fees and fee recipients.
+//! - `update_admin`: Transfers the admin role to another account.
+//! - `UpdateFeeConfig`: Updates the fees and fee recipients.
+//! - `Disable`: Toggles pool creation, deposits, withdrawals, buys, and sells.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+use crate::{constants, error};
+
+/// Seed of the AMM's global configuration PDA
+pub const GLOBAL_CONFIG_SEED: &[u8] = b"global_config";
+
+/// Seed of the AMM's event authority PDA
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Number of protocol fee recipients stored in the global configuration
+pub const PROTOCOL_FEE_RECIPIENTS: usize = 8;
+
+/// Discriminator of the `create_config` instruction
+pub const CREATE_CONFIG: [u8; 8] = [201, 207, 243, 114, 75, 111, 47, 189];
+/// Discriminator of the `update_admin` instruction
+pub const UPDATE_ADMIN: [u8; 8] = [161, 176, 40, 213, 60, 184, 179, 228];
+/// Discriminator of the `update_fee_config` instruction
+pub const UPDATE_FEE_CONFIG: [u8; 8] = [104, 184, 103, 242, 88, 151, 107, 20];
+/// Discriminator of the `disable` instruction
+pub const DISABLE: [u8; 8] = [185, 173, 187, 90, 216, 15, 238, 233];
+
+/// Returns the AMM's global configuration PDA
+pub fn get_global_config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[GLOBAL_CONFIG_SEED], &constants::accounts::PUMPAMM).0
+}
+
+/// Returns the AMM's event authority PDA
+pub fn get_event_authority_pda() -> Pubkey {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &constants::accounts::PUMPAMM).0
+}
+
+/// Instruction data for creating the AMM's global configuration
+///
+/// # Fields
+///
+/// * `lp_fee_basis_points` - Fee paid to liquidity providers on trades, in basis points
+/// * `protocol_fee_basis_points` - Fee paid to the protocol on trades, in basis points
+/// * `protocol_fee_recipients` - Accounts receiving protocol fees
+/// * `coin_creator_fee_basis_points` - Fee paid to token creators on trades, in basis points
+/// * `admin_set_coin_creator_authority` - Authority allowed to set pool coin creators
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CreateConfig {
+    pub lp_fee_basis_points: u64,
+    pub protocol_fee_basis_points: u64,
+    pub protocol_fee_recipients: [Pubkey; PROTOCOL_FEE_RECIPIENTS],
+    pub coin_creator_fee_basis_points: u64,
+    pub admin_set_coin_creator_authority: Pubkey,
+}
+
+impl CreateConfig {
+    /// Instruction discriminator used to identify this instruction
+    pub const DISCRIMINATOR: [u8; 8] = CREATE_CONFIG;
+
+    /// Serializes the instruction data with the appropriate discriminator
+    ///
+    /// # Returns
+    ///
+    /// Byte vector containing the serialized instruction data, or a ClientError if
+    /// serialization fails
+    pub fn data(&self) -> Result<Vec<u8>, error::ClientError> {
+        instruction_data(&Self::DISCRIMINATOR, self)
+    }
+}
+
+/// Instruction data for updating the AMM's fees, with the same fields as `CreateConfig`
+pub type UpdateFeeConfig = CreateConfig;
+
+/// Instruction data for disabling AMM operations
+///
+/// # Fields
+///
+/// * `disable_create_pool` - Whether new pools are rejected
+/// * `disable_deposit` - Whether liquidity deposits are rejected
+/// * `disable_withdraw` - Whether liquidity withdrawals are rejected
+/// * `disable_buy` - Whether buys are rejected
+/// * `disable_sell` - Whether sells are rejected
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Disable {
+    pub disable_create_pool: bool,
+    pub disable_deposit: bool,
+    pub disable_withdraw: bool,
+    pub disable_buy: bool,
+    pub disable_sell: bool,
+}
+
+impl Disable {
+    /// Instruction discriminator used to identify this instruction
+    pub const DISCRIMINATOR: [u8; 8] = DISABLE;
+
+    /// Creates flags disabling every operation
+    pub fn all() -> Self {
+        Self {
+            disable_create_pool: true,
+            disable_deposit: true,
+            disable_withdraw: true,
+            disable_buy: true,
+            disable_sell: true,
+        }
+    }
+
+    /// Serializes the instruction data with the appropriate discriminator
+    ///
+    /// # Returns
+    ///
+    /// Byte vector containing the serialized instruction data, or a ClientError if
+    /// serialization fails
+    pub fn data(&self) -> Result<Vec<u8>, error::ClientError> {
+        instruction_data(&Self::DISCRIMINATOR, self)
+    }
+}
+
+/// Creates an instruction to create the AMM's global configuration
+///
+/// # Arguments
+///
+/// * `admin` - Account that becomes the admin and pays for the configuration; must sign the
+///   transaction
+/// * `args` - Fees and fee recipients of the configuration
+///
+/// # Returns
+///
+/// Returns a Solana instruction creating the global configuration, or a ClientError if the
+/// instruction data cannot be serialized
+///
+/// # Account Requirements
+///
+/// The instruction requires the following accounts in this order:
+/// 1. Admin (signer, writable)
+/// 2. Global configuration PDA (writable)
+/// 3. System program (readonly)
+/// 4. Event authority (readonly)
+/// 5. Pump.fun AMM program ID (readonly)
+pub fn create_config(
+    admin: &Pubkey,
+    args: CreateConfig,
+) -> Result<Instruction, error::ClientError> {
+    Ok(Instruction::new_with_bytes(
+        constants::accounts::PUMPAMM,
+        &args.data()?,
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(get_global_config_pda(), false),
+            AccountMeta::new_readonly(constants::accounts::SYSTEM_PROGRAM, false),
+            AccountMeta::new_readonly(get_event_authority_pda(), false),
+            AccountMeta::new_readonly(constants::accounts::PUMPAMM, false),
+        ],
+    ))
+}
+
+/// Creates an instruction to transfer the AMM's admin role
+///
+/// # Arguments
+///
+/// * `admin` - Current admin; must sign the transaction
+/// * `new_admin` - Account that becomes the admin
+///
+/// # Returns
+///
+/// Returns a Solana instruction transferring the admin role
+///
+/// # Account Requirements
+///
+/// The instruction requires the following accounts in this order:
+/// 1. Current admin (signer)
+/// 2. Global configuration PDA (writable)
+/// 3. New admin (readonly)
+/// 4. Event authority (readonly)
+/// 5. Pump.fun AMM program ID (readonly)
+pub fn update_admin(admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        constants::accounts::PUMPAMM,
+        &UPDATE_ADMIN,
+        vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(get_global_config_pda(), false),
+            AccountMeta::new_readonly(*new_admin, false),
+            AccountMeta::new_readonly(get_event_authority_pda(), false),
+            AccountMeta::new_readonly(constants::accounts::PUMPAMM, false),
+        ],
+    )
+}
+
+/// Creates an instruction to update the AMM's fees and fee recipients
+///
+/// # Arguments
+///
+/// * `admin` - Admin of the AMM; must sign the transaction
+/// * `args` - New fees and fee recipients
+///
+/// # Returns
+///
+/// Returns a Solana instruction updating the fee configuration, or a ClientError if the
+/// instruction data cannot be serialized
+///
+/// # Account Requirements
+///
+/// The instruction requires the following accounts in this order:
+/// 1. Admin (signer)
+/// 2. Global configuration PDA (writable)
+/// 3. Event authority (readonly)
+/// 4. Pump.fun AMM program ID (readonly)
+pub fn update_fee_config(
+    admin: &Pubkey,
+    args: UpdateFeeConfig,
+) -> Result<Instruction, error::ClientError> {
+    Ok(Instruction::new_with_bytes(
+        constants::accounts::PUMPAMM,
+        &instruction_data(&UPDATE_FEE_CONFIG, &args)?,
+        admin_accounts(admin),
+    ))
+}
+
+/// Creates an instruction to disable or re-enable AMM operations
+///
+/// Each flag set to true disables the operation and each flag set to false enables it, so
+/// pass `Disable::default()` to re-enable everything.
+///
+/// # Arguments
+///
+/// * `admin` - Admin of the AMM; must sign the transaction
+/// * `args` - Operations to disable
+///
+/// # Returns
+///
+/// Returns a Solana instruction updating the disabled operations, or a ClientError if the
+/// instruction data cannot be serialized
+///
+/// # Account Requirements
+///
+/// The instruction requires the following accounts in this order:
+/// 1. Admin (signer)
+/// 2. Global configuration PDA (writable)
+/// 3. Event authority (readonly)
+/// 4. Pump.fun AMM program ID (readonly)
+pub fn disable(admin: &Pubkey, args: Disable) -> Result<Instruction, error::ClientError> {
+    Ok(Instruction::new_with_bytes(
+        constants::accounts::PUMPAMM,
+        &args.data()?,
+        admin_accounts(admin),
+    ))
+}
+
+fn admin_accounts(admin: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new(get_global_config_pda(), false),
+        AccountMeta::new_readonly(get_event_authority_pda(), false),
+        AccountMeta::new_readonly(constants::accounts::PUMPAMM, false),
+    ]
+}
+
+fn instruction_data(
+    discriminator: &[u8; 8],
+    args: &impl BorshSerialize,
+) -> Result<Vec<u8>, error::ClientError> {
+    let mut data = Vec::with_capacity(512);
+    data.extend_from_slice(discriminator);
+    args.serialize(&mut data)
+        .map_err(error::ClientError::BorshError)?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::pubkey;
+
+    use super::*;
+
+    #[test]
+    fn test_amm_admin_instructions() {
+        assert_eq!(
+            get_global_config_pda(),
+            pubkey!("ADyA8hdefvWN2dbGGWFotbzWxrAvLW83WG6QCVXvJKqw")
+        );
+        assert_eq!(
+            get_event_authority_pda(),
+            pubkey!("GS4CU59F31iL7aR2Q8zVS8DRrcRnXX1yjQ66TqNVQnaR")
+        );
+
+        let admin = Pubkey::new_unique();
+        let args = CreateConfig {
+            lp_fee_basis_points: 20,
+            protocol_fee_basis_points: 5,
+            protocol_fee_recipients: [Pubkey::new_unique(); PROTOCOL_FEE_RECIPIENTS],
+            coin_creator_fee_basis_points: 5,
+            admin_set_coin_creator_authority: Pubkey::new_unique(),
+        };
+        let instruction = create_config(&admin, args.clone()).unwrap();
+        assert_eq!(instruction.data[..8], CREATE_CONFIG);
+        assert_eq!(
+            CreateConfig::try_from_slice(&instruction.data[8..]).unwrap(),
+            args
+        );
+
+        let instruction = update_fee_config(&admin, args).unwrap();
+        assert_eq!(instruction.data[..8], UPDATE_FEE_CONFIG);
+        assert_eq!(instruction.accounts.len(), 4);
+
+        let instruction = disable(&admin, Disable::all()).unwrap();
+        assert_eq!(instruction.data, [&DISABLE[..], &[1; 5]].concat());
+        assert_eq!(update_admin(&admin, &admin).accounts[0].pubkey, admin);
+    }
+}
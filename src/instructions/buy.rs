@@ -3,7 +3,7 @@
 //! This module provides the functionality to buy tokens from bonding curves.
 //! It includes the instruction data structure and helper function to build the Solana instruction.
 
-use crate::{constants, PumpFun};
+use crate::{common::types::ProgramVersion, constants, error, PumpFun};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
@@ -29,18 +29,20 @@ pub struct Buy {
 
 impl Buy {
     /// Instruction discriminator used to identify this instruction
-    pub const DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+    pub const DISCRIMINATOR: [u8; 8] = constants::discriminators::BUY;
 
     /// Serializes the instruction data with the appropriate discriminator
     ///
     /// # Returns
     ///
-    /// Byte vector containing the serialized instruction data
-    pub fn data(&self) -> Vec<u8> {
+    /// Byte vector containing the serialized instruction data, or a ClientError if
+    /// serialization fails
+    pub fn data(&self) -> Result<Vec<u8>, error::ClientError> {
         let mut data = Vec::with_capacity(256);
         data.extend_from_slice(&Self::DISCRIMINATOR);
-        self.serialize(&mut data).unwrap();
-        data
+        self.serialize(&mut data)
+            .map_err(error::ClientError::BorshError)?;
+        Ok(data)
     }
 }
 
@@ -61,7 +63,8 @@ impl Buy {
 ///
 /// # Returns
 ///
-/// Returns a Solana instruction that when executed will buy tokens from the bonding curve
+/// Returns a Solana instruction that when executed will buy tokens from the bonding curve,
+/// or a ClientError if the PDAs cannot be derived or the instruction data cannot be serialized
 ///
 /// # Account Requirements
 ///
@@ -80,40 +83,147 @@ impl Buy {
 /// 12. Pump.fun program ID (readonly)
 /// 13. Global volume accumulator (writable)
 /// 14. User volume accumulator (writable)
-/// 15. Fee configuration account (readonly)
-/// 16. Fee configuration program ID (readonly)
+/// 15. Fee configuration account (readonly), omitted for `ProgramVersion::Legacy`
+/// 16. Fee configuration program ID (readonly), omitted for `ProgramVersion::Legacy`
 pub fn buy(
     payer: &Keypair,
     mint: &Pubkey,
     fee_recipient: &Pubkey,
     creator: &Pubkey,
     args: Buy,
-) -> Instruction {
-    let bonding_curve: Pubkey = PumpFun::get_bonding_curve_pda(mint).unwrap();
-    let creator_vault: Pubkey = PumpFun::get_creator_vault_pda(creator).unwrap();
-    Instruction::new_with_bytes(
-        constants::accounts::PUMPFUN,
-        &args.data(),
-        vec![
-            AccountMeta::new_readonly(PumpFun::get_global_pda(), false),
-            AccountMeta::new(*fee_recipient, false),
-            AccountMeta::new_readonly(*mint, false),
-            AccountMeta::new(bonding_curve, false),
-            AccountMeta::new(get_associated_token_address(&bonding_curve, mint), false),
-            AccountMeta::new(get_associated_token_address(&payer.pubkey(), mint), false),
-            AccountMeta::new(payer.pubkey(), true),
-            AccountMeta::new_readonly(constants::accounts::SYSTEM_PROGRAM, false),
-            AccountMeta::new_readonly(constants::accounts::TOKEN_PROGRAM, false),
-            AccountMeta::new(creator_vault, false),
-            AccountMeta::new_readonly(constants::accounts::EVENT_AUTHORITY, false),
-            AccountMeta::new_readonly(constants::accounts::PUMPFUN, false),
-            AccountMeta::new(constants::accounts::GLOBAL_VOLUME_ACCUMULATOR, false),
-            AccountMeta::new(
-                PumpFun::get_user_volume_accumulator_pda(&payer.pubkey()),
-                false,
-            ),
-            AccountMeta::new_readonly(constants::accounts::FEE_CONFIG, false),
-            AccountMeta::new_readonly(constants::accounts::FEE_CONFIG_PROGRAM, false),
-        ],
+) -> Result<Instruction, error::ClientError> {
+    buy_for(&payer.pubkey(), mint, fee_recipient, creator, args)
+}
+
+/// Creates an instruction to buy tokens for a user identified by public key
+///
+/// Same as `buy`, but takes the user's public key instead of a keypair. Use this when
+/// the instruction is signed elsewhere, such as in a multisig proposal or a PDA-signed CPI.
+///
+/// # Arguments
+///
+/// * `user` - Public key of the account that will provide the SOL to buy tokens; must sign the transaction
+/// * `mint` - Public key of the token mint to buy
+/// * `fee_recipient` - Public key of the account that will receive the transaction fee
+/// * `creator` - Public key of the token's creator
+/// * `args` - Buy instruction data
+///
+/// # Returns
+///
+/// Returns a Solana instruction with the same accounts as `buy`, or a ClientError if the
+/// PDAs cannot be derived or the instruction data cannot be serialized
+pub fn buy_for(
+    user: &Pubkey,
+    mint: &Pubkey,
+    fee_recipient: &Pubkey,
+    creator: &Pubkey,
+    args: Buy,
+) -> Result<Instruction, error::ClientError> {
+    buy_for_version(
+        user,
+        mint,
+        fee_recipient,
+        creator,
+        args,
+        ProgramVersion::default(),
     )
 }
+
+/// Creates an instruction to buy tokens for a user, targeting a program version
+///
+/// Same as `buy_for`, but only includes the fee program's accounts if the targeted version
+/// reads its fees from the fee program.
+///
+/// # Arguments
+///
+/// * `user` - Public key of the account that will provide the SOL to buy tokens; must sign the transaction
+/// * `mint` - Public key of the token mint to buy
+/// * `fee_recipient` - Public key of the account that will receive the transaction fee
+/// * `creator` - Public key of the token's creator
+/// * `args` - Buy instruction data
+/// * `version` - Version of the Pump.fun program the instruction targets
+///
+/// # Returns
+///
+/// Returns a Solana instruction with the accounts of `buy` for the version, or a
+/// ClientError if the PDAs cannot be derived or the instruction data cannot be serialized
+pub fn buy_for_version(
+    user: &Pubkey,
+    mint: &Pubkey,
+    fee_recipient: &Pubkey,
+    creator: &Pubkey,
+    args: Buy,
+    version: ProgramVersion,
+) -> Result<Instruction, error::ClientError> {
+    let bonding_curve: Pubkey = PumpFun::get_bonding_curve_pda(mint).ok_or(
+        error::ClientError::BondingCurveError("Failed to derive bonding curve PDA"),
+    )?;
+    let creator_vault: Pubkey = PumpFun::get_creator_vault_pda(creator).ok_or_else(|| {
+        error::ClientError::OtherError(format!("Failed to derive creator vault PDA: {}", creator))
+    })?;
+    let mut accounts = vec![
+        AccountMeta::new_readonly(PumpFun::get_global_pda(), false),
+        AccountMeta::new(*fee_recipient, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(bonding_curve, false),
+        AccountMeta::new(get_associated_token_address(&bonding_curve, mint), false),
+        AccountMeta::new(get_associated_token_address(user, mint), false),
+        AccountMeta::new(*user, true),
+        AccountMeta::new_readonly(constants::accounts::SYSTEM_PROGRAM, false),
+        AccountMeta::new_readonly(constants::accounts::TOKEN_PROGRAM, false),
+        AccountMeta::new(creator_vault, false),
+        AccountMeta::new_readonly(constants::accounts::EVENT_AUTHORITY, false),
+        AccountMeta::new_readonly(constants::accounts::PUMPFUN, false),
+        AccountMeta::new(constants::accounts::GLOBAL_VOLUME_ACCUMULATOR, false),
+        AccountMeta::new(PumpFun::get_user_volume_accumulator_pda(user), false),
+    ];
+    if version.uses_fee_program() {
+        accounts.push(AccountMeta::new_readonly(
+            constants::accounts::FEE_CONFIG,
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(
+            constants::accounts::FEE_CONFIG_PROGRAM,
+            false,
+        ));
+    }
+    Ok(Instruction::new_with_bytes(
+        constants::accounts::PUMPFUN,
+        &args.data()?,
+        accounts,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buy_for_version() {
+        assert_eq!(
+            PumpFun::get_fee_config_pda(),
+            constants::accounts::FEE_CONFIG
+        );
+
+        let buy = |version| {
+            buy_for_version(
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                Buy {
+                    amount: 1,
+                    max_sol_cost: 1,
+                    track_volume: None,
+                },
+                version,
+            )
+            .unwrap()
+        };
+        let accounts = buy(ProgramVersion::FeeProgram).accounts;
+        assert_eq!(accounts.len(), 16);
+        assert_eq!(accounts[14].pubkey, constants::accounts::FEE_CONFIG);
+        assert_eq!(accounts[15].pubkey, constants::accounts::FEE_CONFIG_PROGRAM);
+        assert_eq!(buy(ProgramVersion::Legacy).accounts.len(), 14);
+    }
+}
@@ -3,7 +3,7 @@
 //! This module provides the functionality to create new tokens with associated bonding curves.
 //! It includes the instruction data structure and helper function to build the Solana instruction.
 
-use crate::{constants, PumpFun};
+use crate::{constants, error, PumpFun};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
@@ -31,18 +31,20 @@ pub struct Create {
 
 impl Create {
     /// Instruction discriminator used to identify this instruction
-    pub const DISCRIMINATOR: [u8; 8] = [24, 30, 200, 40, 5, 28, 7, 119];
+    pub const DISCRIMINATOR: [u8; 8] = constants::discriminators::CREATE;
 
     /// Serializes the instruction data with the appropriate discriminator
     ///
     /// # Returns
     ///
-    /// Byte vector containing the serialized instruction data
-    pub fn data(&self) -> Vec<u8> {
+    /// Byte vector containing the serialized instruction data, or a ClientError if
+    /// serialization fails
+    pub fn data(&self) -> Result<Vec<u8>, error::ClientError> {
         let mut data = Vec::with_capacity(256);
         data.extend_from_slice(&Self::DISCRIMINATOR);
-        self.serialize(&mut data).unwrap();
-        data
+        self.serialize(&mut data)
+            .map_err(error::ClientError::BorshError)?;
+        Ok(data)
     }
 }
 
@@ -59,7 +61,8 @@ impl Create {
 ///
 /// # Returns
 ///
-/// Returns a Solana instruction that when executed will create the token and its accounts
+/// Returns a Solana instruction that when executed will create the token and its accounts,
+/// or a ClientError if the PDAs cannot be derived or the instruction data cannot be serialized
 ///
 /// # Account Requirements
 ///
@@ -78,23 +81,49 @@ impl Create {
 /// 12. Rent sysvar (readonly)
 /// 13. Event authority (readonly)
 /// 14. Pump.fun program ID (readonly)
-pub fn create(payer: &Keypair, mint: &Keypair, args: Create) -> Instruction {
-    let bonding_curve: Pubkey = PumpFun::get_bonding_curve_pda(&mint.pubkey()).unwrap();
-    Instruction::new_with_bytes(
+pub fn create(
+    payer: &Keypair,
+    mint: &Keypair,
+    args: Create,
+) -> Result<Instruction, error::ClientError> {
+    create_for(&payer.pubkey(), &mint.pubkey(), args)
+}
+
+/// Creates an instruction to create a new token for accounts identified by public key
+///
+/// Same as `create`, but takes the payer and mint public keys instead of keypairs. Use this
+/// when the instruction is signed elsewhere, such as in a multisig proposal or a PDA-signed CPI.
+///
+/// # Arguments
+///
+/// * `payer` - Public key of the account paying for account creation; must sign the transaction
+/// * `mint` - Public key of the new token mint account; must sign the transaction
+/// * `args` - Create instruction data containing token name, symbol, metadata URI, and creator
+///
+/// # Returns
+///
+/// Returns a Solana instruction with the same accounts as `create`, or a ClientError if the
+/// PDAs cannot be derived or the instruction data cannot be serialized
+pub fn create_for(
+    payer: &Pubkey,
+    mint: &Pubkey,
+    args: Create,
+) -> Result<Instruction, error::ClientError> {
+    let bonding_curve: Pubkey = PumpFun::get_bonding_curve_pda(mint).ok_or(
+        error::ClientError::BondingCurveError("Failed to derive bonding curve PDA"),
+    )?;
+    Ok(Instruction::new_with_bytes(
         constants::accounts::PUMPFUN,
-        &args.data(),
+        &args.data()?,
         vec![
-            AccountMeta::new(mint.pubkey(), true),
+            AccountMeta::new(*mint, true),
             AccountMeta::new(PumpFun::get_mint_authority_pda(), false),
             AccountMeta::new(bonding_curve, false),
-            AccountMeta::new(
-                get_associated_token_address(&bonding_curve, &mint.pubkey()),
-                false,
-            ),
+            AccountMeta::new(get_associated_token_address(&bonding_curve, mint), false),
             AccountMeta::new_readonly(PumpFun::get_global_pda(), false),
             AccountMeta::new_readonly(constants::accounts::MPL_TOKEN_METADATA, false),
-            AccountMeta::new(PumpFun::get_metadata_pda(&mint.pubkey()), false),
-            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new(PumpFun::get_metadata_pda(mint), false),
+            AccountMeta::new(*payer, true),
             AccountMeta::new_readonly(constants::accounts::SYSTEM_PROGRAM, false),
             AccountMeta::new_readonly(constants::accounts::TOKEN_PROGRAM, false),
             AccountMeta::new_readonly(constants::accounts::ASSOCIATED_TOKEN_PROGRAM, false),
@@ -102,5 +131,26 @@ pub fn create(payer: &Keypair, mint: &Keypair, args: Create) -> Instruction {
             AccountMeta::new_readonly(constants::accounts::EVENT_AUTHORITY, false),
             AccountMeta::new_readonly(constants::accounts::PUMPFUN, false),
         ],
-    )
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_for_matches_create() {
+        let payer = Keypair::new();
+        let mint = Keypair::new();
+        let args = Create {
+            name: "Test".to_string(),
+            symbol: "TEST".to_string(),
+            uri: "https://example.com".to_string(),
+            creator: payer.pubkey(),
+        };
+
+        let signed = create(&payer, &mint, args.clone()).unwrap();
+        let unsigned = create_for(&payer.pubkey(), &mint.pubkey(), args).unwrap();
+        assert_eq!(signed, unsigned);
+    }
 }
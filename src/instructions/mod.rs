@@ -7,11 +7,21 @@
 //! - `Create`: Creates a new token with an associated bonding curve.
 //! - `Buy`: Buys tokens from a bonding curve by providing SOL.
 //! - `Sell`: Sells tokens back to the bonding curve in exchange for SOL.
+//! - `SetParams`, `update_global_authority`: Administer the global configuration; signed by
+//!   the global authority.
+//!
+//! Administrative instructions of the Pump.fun AMM program live in the `amm` submodule.
+//!
+//! Each builder has a `_for` variant (`create_for`, `buy_for`, `sell_for`) that takes public
+//! keys instead of keypairs, for instructions that are signed by a multisig or another program.
 
+mod admin;
+pub mod amm;
 mod buy;
 mod create;
 mod sell;
 
+pub use admin::*;
 pub use buy::*;
 pub use create::*;
 pub use sell::*;
@@ -3,7 +3,7 @@
 //! This module provides the functionality to sell tokens back to bonding curves.
 //! It includes the instruction data structure and helper function to build the Solana instruction.
 
-use crate::{constants, PumpFun};
+use crate::{common::types::ProgramVersion, constants, error, PumpFun};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
@@ -27,18 +27,20 @@ pub struct Sell {
 
 impl Sell {
     /// Instruction discriminator used to identify this instruction
-    pub const DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+    pub const DISCRIMINATOR: [u8; 8] = constants::discriminators::SELL;
 
     /// Serializes the instruction data with the appropriate discriminator
     ///
     /// # Returns
     ///
-    /// Byte vector containing the serialized instruction data
-    pub fn data(&self) -> Vec<u8> {
+    /// Byte vector containing the serialized instruction data, or a ClientError if
+    /// serialization fails
+    pub fn data(&self) -> Result<Vec<u8>, error::ClientError> {
         let mut data = Vec::with_capacity(256);
         data.extend_from_slice(&Self::DISCRIMINATOR);
-        self.serialize(&mut data).unwrap();
-        data
+        self.serialize(&mut data)
+            .map_err(error::ClientError::BorshError)?;
+        Ok(data)
     }
 }
 
@@ -59,7 +61,8 @@ impl Sell {
 ///
 /// # Returns
 ///
-/// Returns a Solana instruction that when executed will sell tokens to the bonding curve
+/// Returns a Solana instruction that when executed will sell tokens to the bonding curve,
+/// or a ClientError if the PDAs cannot be derived or the instruction data cannot be serialized
 ///
 /// # Account Requirements
 ///
@@ -76,35 +79,111 @@ impl Sell {
 /// 10. Token program (readonly)
 /// 11. Event authority (readonly)
 /// 12. Pump.fun program ID (readonly)
-/// 13. Fee configuration account (readonly)
-/// 14. Fee configuration program ID (readonly)
+/// 13. Fee configuration account (readonly), omitted for `ProgramVersion::Legacy`
+/// 14. Fee configuration program ID (readonly), omitted for `ProgramVersion::Legacy`
 pub fn sell(
     payer: &Keypair,
     mint: &Pubkey,
     fee_recipient: &Pubkey,
     creator: &Pubkey,
     args: Sell,
-) -> Instruction {
-    let bonding_curve: Pubkey = PumpFun::get_bonding_curve_pda(mint).unwrap();
-    let creator_vault: Pubkey = PumpFun::get_creator_vault_pda(creator).unwrap();
-    Instruction::new_with_bytes(
-        constants::accounts::PUMPFUN,
-        &args.data(),
-        vec![
-            AccountMeta::new_readonly(PumpFun::get_global_pda(), false),
-            AccountMeta::new(*fee_recipient, false),
-            AccountMeta::new_readonly(*mint, false),
-            AccountMeta::new(bonding_curve, false),
-            AccountMeta::new(get_associated_token_address(&bonding_curve, mint), false),
-            AccountMeta::new(get_associated_token_address(&payer.pubkey(), mint), false),
-            AccountMeta::new(payer.pubkey(), true),
-            AccountMeta::new_readonly(constants::accounts::SYSTEM_PROGRAM, false),
-            AccountMeta::new(creator_vault, false),
-            AccountMeta::new_readonly(constants::accounts::TOKEN_PROGRAM, false),
-            AccountMeta::new_readonly(constants::accounts::EVENT_AUTHORITY, false),
-            AccountMeta::new_readonly(constants::accounts::PUMPFUN, false),
-            AccountMeta::new_readonly(constants::accounts::FEE_CONFIG, false),
-            AccountMeta::new_readonly(constants::accounts::FEE_CONFIG_PROGRAM, false),
-        ],
+) -> Result<Instruction, error::ClientError> {
+    sell_for(&payer.pubkey(), mint, fee_recipient, creator, args)
+}
+
+/// Creates an instruction to sell tokens for a user identified by public key
+///
+/// Same as `sell`, but takes the user's public key instead of a keypair. Use this when
+/// the instruction is signed elsewhere, such as in a multisig proposal or a PDA-signed CPI.
+///
+/// # Arguments
+///
+/// * `user` - Public key of the account that owns the tokens to sell; must sign the transaction
+/// * `mint` - Public key of the token mint to sell
+/// * `fee_recipient` - Public key of the account that will receive the transaction fee
+/// * `creator` - Public key of the token's creator
+/// * `args` - Sell instruction data
+///
+/// # Returns
+///
+/// Returns a Solana instruction with the same accounts as `sell`, or a ClientError if the
+/// PDAs cannot be derived or the instruction data cannot be serialized
+pub fn sell_for(
+    user: &Pubkey,
+    mint: &Pubkey,
+    fee_recipient: &Pubkey,
+    creator: &Pubkey,
+    args: Sell,
+) -> Result<Instruction, error::ClientError> {
+    sell_for_version(
+        user,
+        mint,
+        fee_recipient,
+        creator,
+        args,
+        ProgramVersion::default(),
     )
 }
+
+/// Creates an instruction to sell tokens for a user, targeting a program version
+///
+/// Same as `sell_for`, but only includes the fee program's accounts if the targeted version
+/// reads its fees from the fee program.
+///
+/// # Arguments
+///
+/// * `user` - Public key of the account that owns the tokens; must sign the transaction
+/// * `mint` - Public key of the token mint to sell
+/// * `fee_recipient` - Public key of the account that will receive the transaction fee
+/// * `creator` - Public key of the token's creator
+/// * `args` - Sell instruction data
+/// * `version` - Version of the Pump.fun program the instruction targets
+///
+/// # Returns
+///
+/// Returns a Solana instruction with the accounts of `sell` for the version, or a
+/// ClientError if the PDAs cannot be derived or the instruction data cannot be serialized
+pub fn sell_for_version(
+    user: &Pubkey,
+    mint: &Pubkey,
+    fee_recipient: &Pubkey,
+    creator: &Pubkey,
+    args: Sell,
+    version: ProgramVersion,
+) -> Result<Instruction, error::ClientError> {
+    let bonding_curve: Pubkey = PumpFun::get_bonding_curve_pda(mint).ok_or(
+        error::ClientError::BondingCurveError("Failed to derive bonding curve PDA"),
+    )?;
+    let creator_vault: Pubkey = PumpFun::get_creator_vault_pda(creator).ok_or_else(|| {
+        error::ClientError::OtherError(format!("Failed to derive creator vault PDA: {}", creator))
+    })?;
+    let mut accounts = vec![
+        AccountMeta::new_readonly(PumpFun::get_global_pda(), false),
+        AccountMeta::new(*fee_recipient, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(bonding_curve, false),
+        AccountMeta::new(get_associated_token_address(&bonding_curve, mint), false),
+        AccountMeta::new(get_associated_token_address(user, mint), false),
+        AccountMeta::new(*user, true),
+        AccountMeta::new_readonly(constants::accounts::SYSTEM_PROGRAM, false),
+        AccountMeta::new(creator_vault, false),
+        AccountMeta::new_readonly(constants::accounts::TOKEN_PROGRAM, false),
+        AccountMeta::new_readonly(constants::accounts::EVENT_AUTHORITY, false),
+        AccountMeta::new_readonly(constants::accounts::PUMPFUN, false),
+    ];
+    if version.uses_fee_program() {
+        accounts.push(AccountMeta::new_readonly(
+            constants::accounts::FEE_CONFIG,
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(
+            constants::accounts::FEE_CONFIG_PROGRAM,
+            false,
+        ));
+    }
+    Ok(Instruction::new_with_bytes(
+        constants::accounts::PUMPFUN,
+        &args.data()?,
+        accounts,
+    ))
+}
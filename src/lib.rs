@@ -1,28 +1,77 @@
 #![doc = include_str!("../RUSTDOC.md")]
+// `ClientError` embeds the Solana client error, which is large by design
+#![allow(clippy::result_large_err)]
+
+#[macro_use]
+mod macros;
 
 pub mod accounts;
+#[cfg(feature = "stream")]
+pub mod analytics;
+#[cfg(feature = "api")]
+pub mod api;
+#[cfg(feature = "stream")]
+pub mod backtest;
+pub mod builder;
 pub mod common;
 pub mod constants;
+#[cfg(feature = "stream")]
+pub mod copy_trade;
 pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod instructions;
+#[cfg(feature = "stream")]
+pub mod orders;
+#[cfg(feature = "stream")]
+pub mod portfolio;
+pub mod registry;
+pub mod sender;
+#[cfg(feature = "sniper")]
+pub mod sniper;
 pub mod utils;
+pub mod wallets;
 
-use common::types::{Cluster, PriorityFee};
-use solana_client::nonblocking::rpc_client::RpcClient;
+pub use builder::PumpFunBuilder;
+use common::{
+    risk::{RiskLimits, RiskManager, RiskTrade},
+    types::{AtaPolicy, Cluster, PriorityFee, ProgramVersion, RetryPolicy},
+};
+use sender::{TransactionHook, TransactionSender};
+use solana_client::{
+    nonblocking::rpc_client::RpcClient, rpc_request::TokenAccountsFilter,
+    rpc_response::RpcKeyedAccount,
+};
+#[cfg(feature = "versioned-tx")]
+use solana_sdk::message::AddressLookupTableAccount;
 use solana_sdk::{
     compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
     instruction::Instruction,
     pubkey::Pubkey,
     signature::{Keypair, Signature},
     signer::Signer,
+    transaction::VersionedTransaction,
 };
 use spl_associated_token_account::get_associated_token_address;
 #[cfg(feature = "create-ata")]
-use spl_associated_token_account::instruction::create_associated_token_account;
+use spl_associated_token_account::instruction::{
+    create_associated_token_account, create_associated_token_account_idempotent,
+};
 #[cfg(feature = "close-ata")]
 use spl_token::instruction::close_account;
-use std::sync::Arc;
-use utils::transaction::get_transaction;
+use std::{future::Future, sync::Arc, time::Duration};
+use utils::{
+    fees::FeeSchedule,
+    transaction::{build_transaction, get_transaction},
+    upload::MetadataUploader,
+};
+
+/// Sell instructions packed into each transaction of `PumpFun::liquidate_all`
+const SELLS_PER_TRANSACTION: usize = 4;
+
+/// Close account instructions packed into each transaction of `PumpFun::liquidate_all`
+const CLOSES_PER_TRANSACTION: usize = 16;
 
 /// Main client for interacting with the Pump.fun program
 ///
@@ -51,6 +100,32 @@ pub struct PumpFun {
     pub rpc: Arc<RpcClient>,
     /// Cluster configuration
     pub cluster: Cluster,
+    /// Sender used to submit and confirm transactions
+    pub sender: Arc<dyn TransactionSender>,
+    /// Policy for retrying transactions that expire or are rate limited
+    pub retry_policy: RetryPolicy,
+    /// Slippage applied to trades when none is given, in basis points
+    pub default_slippage_basis_points: u64,
+    /// Whether trades are validated client-side before their instructions are built
+    pub preflight_checks: bool,
+    /// Blockhash used for every transaction instead of fetching a recent one.
+    /// Set this to make built transactions reproducible, e.g. in tests
+    pub fixed_blockhash: Option<Hash>,
+    /// Address lookup tables used to compress every transaction the client builds
+    #[cfg(feature = "versioned-tx")]
+    pub lookup_tables: Vec<AddressLookupTableAccount>,
+    /// Risk limits checked before every buy, with the positions tracked against them
+    pub risk: Option<Arc<RiskManager>>,
+    /// Provider uploading the image and metadata JSON of created tokens
+    pub metadata_uploader: Arc<dyn MetadataUploader>,
+    /// How buys and sells check for and create the user's associated token account
+    pub ata_policy: AtaPolicy,
+    /// Hooks invoked around every transaction sent by the client, in order
+    pub hooks: Vec<Arc<dyn TransactionHook>>,
+    /// Volume-based fee tiers used to quote trades; empty to use the global fees
+    pub fee_schedule: FeeSchedule,
+    /// Version of the Pump.fun program that buys and sells target
+    pub program_version: ProgramVersion,
 }
 
 impl PumpFun {
@@ -76,25 +151,685 @@ impl PumpFun {
     /// use solana_sdk::{commitment_config::CommitmentConfig, signature::Keypair};
     /// use std::sync::Arc;
     ///
-    /// let payer = Arc::new(Keypair::new());
-    /// let commitment = CommitmentConfig::confirmed();
-    /// let priority_fee = PriorityFee::default();
-    /// let cluster = Cluster::devnet(commitment, priority_fee);
-    /// let client = PumpFun::new(payer, cluster);
-    /// ```
-    pub fn new(payer: Arc<Keypair>, cluster: Cluster) -> Self {
-        // Create Solana RPC Client with HTTP endpoint
-        let rpc = Arc::new(RpcClient::new_with_commitment(
-            cluster.rpc.http.clone(),
-            cluster.commitment,
-        ));
-
-        // Return configured PumpFun client
-        Self {
-            payer,
-            rpc,
-            cluster,
-        }
+    /// let payer = Arc::new(Keypair::new());
+    /// let commitment = CommitmentConfig::confirmed();
+    /// let priority_fee = PriorityFee::default();
+    /// let cluster = Cluster::devnet(commitment, priority_fee);
+    /// let client = PumpFun::new(payer, cluster);
+    /// ```
+    pub fn new(payer: Arc<Keypair>, cluster: Cluster) -> Self {
+        PumpFunBuilder::new(payer, cluster).build()
+    }
+
+    /// Creates a builder for configuring a PumpFun client
+    ///
+    /// Use the builder to share an existing RPC client, set the RPC timeout and commitment,
+    /// and configure trading defaults, the retry policy, and the sender in one place.
+    ///
+    /// # Arguments
+    ///
+    /// * `payer` - Keypair used to sign and pay for transactions
+    /// * `cluster` - Solana cluster configuration including RPC endpoints and transaction parameters
+    ///
+    /// # Returns
+    ///
+    /// Returns a `PumpFunBuilder` with the same defaults as `new`
+    pub fn builder(payer: Arc<Keypair>, cluster: Cluster) -> PumpFunBuilder {
+        PumpFunBuilder::new(payer, cluster)
+    }
+
+    /// Replaces the sender used to submit transactions
+    ///
+    /// By default transactions are sent through the client's RPC connection. Use this to route
+    /// them through a relay such as Jito, Helius Sender, or bloXroute instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `sender` - Sender used to submit and confirm transactions
+    ///
+    /// # Returns
+    ///
+    /// Returns the client configured with the new sender
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pumpfun::{
+    ///     common::types::{Cluster, PriorityFee},
+    ///     sender::{JitoSender, JITO_MAINNET_URL},
+    ///     PumpFun,
+    /// };
+    /// use solana_sdk::{commitment_config::CommitmentConfig, signature::Keypair};
+    /// use std::sync::Arc;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let payer = Arc::new(Keypair::new());
+    /// let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+    /// let client = PumpFun::new(payer, cluster);
+    ///
+    /// let jito = JitoSender::new(JITO_MAINNET_URL.to_string(), None, client.rpc.clone())?;
+    /// let client = client.with_sender(Arc::new(jito));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_sender(mut self, sender: Arc<dyn TransactionSender>) -> Self {
+        self.sender = sender;
+        self
+    }
+
+    /// Sets the policy for retrying transactions that could not be confirmed
+    ///
+    /// By default, transactions whose blockhash expires before confirmation and requests that
+    /// are rate limited are retried up to three times, rebuilding the transaction with a fresh
+    /// blockhash each time. Use `RetryPolicy::none()` to surface every failure immediately.
+    /// Expiry is detected by the sender, which reports it as `ClientError::TransactionExpired`.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry_policy` - Policy applied by every method that sends a transaction
+    ///
+    /// # Returns
+    ///
+    /// Returns the client configured with the retry policy
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Enables or disables client-side checks before trading
+    ///
+    /// When enabled, buys and sells verify that the program is initialized, that the bonding
+    /// curve is not complete, and that the user holds enough SOL or tokens, returning a
+    /// specific ClientError instead of letting the transaction fail on-chain. Buys check the
+    /// maximum SOL cost plus rent for a new token account and the base transaction fee;
+    /// priority fees are not included. The checks cost a few extra RPC requests per trade.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to run the checks
+    ///
+    /// # Returns
+    ///
+    /// Returns the client configured with the preflight setting
+    pub fn with_preflight_checks(mut self, enabled: bool) -> Self {
+        self.preflight_checks = enabled;
+        self
+    }
+
+    /// Enforces risk limits on every buy the client builds
+    ///
+    /// Buys exceeding a limit fail with `ClientError::RiskRejected` before any transaction is
+    /// sent. Positions are tracked from the buys and sells the client builds once their
+    /// transactions are confirmed; see `common::risk` for how they are counted.
+    ///
+    /// # Arguments
+    ///
+    /// * `limits` - Limits to enforce
+    ///
+    /// # Returns
+    ///
+    /// Returns the client configured with the risk limits
+    pub fn with_risk_limits(mut self, limits: RiskLimits) -> Self {
+        self.risk = Some(Arc::new(RiskManager::new(limits)));
+        self
+    }
+
+    /// Sets how buys and sells handle the user's associated token account
+    ///
+    /// The default, `AtaPolicy::Check`, fetches the account before every buy to decide
+    /// whether to create it, and before closing it after a full sell. The other policies
+    /// skip these requests to shave latency off hot paths.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - Policy applied to every trade the client builds
+    ///
+    /// # Returns
+    ///
+    /// Returns the client configured with the ATA policy
+    pub fn with_ata_policy(mut self, policy: AtaPolicy) -> Self {
+        self.ata_policy = policy;
+        self
+    }
+
+    /// Quotes trades with volume-based fee tiers
+    ///
+    /// Buys and sells account for the protocol and creator fees when computing the token
+    /// amount and minimum SOL output. By default, the flat fees of the global account are
+    /// used. With a non-empty schedule, the user's volume accumulator is fetched before each
+    /// trade to pick the tier, which costs an extra RPC request. See `utils::fees`.
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule` - Fee tiers applied to every trade the client builds
+    ///
+    /// # Returns
+    ///
+    /// Returns the client configured with the fee schedule
+    pub fn with_fee_schedule(mut self, schedule: FeeSchedule) -> Self {
+        self.fee_schedule = schedule;
+        self
+    }
+
+    /// Targets a version of the Pump.fun program with buys and sells
+    ///
+    /// The default, `ProgramVersion::FeeProgram`, matches current mainnet deployments. Use
+    /// `ProgramVersion::Legacy` for deployments and forks predating the fee program.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Version targeted by every trade the client builds
+    ///
+    /// # Returns
+    ///
+    /// Returns the client configured with the program version
+    pub fn with_program_version(mut self, version: ProgramVersion) -> Self {
+        self.program_version = version;
+        self
+    }
+
+    /// Uploads the metadata of created tokens through the given provider
+    ///
+    /// By default, metadata is uploaded through the pump.fun API. See `utils::upload` for
+    /// the available providers.
+    ///
+    /// # Arguments
+    ///
+    /// * `uploader` - Provider used by `create` and `create_and_buy`
+    ///
+    /// # Returns
+    ///
+    /// Returns the client configured with the uploader
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pumpfun::{common::types::{Cluster, PriorityFee}, utils::upload::PinataUploader, PumpFun};
+    /// use solana_sdk::{commitment_config::CommitmentConfig, signature::Keypair};
+    /// use std::sync::Arc;
+    ///
+    /// let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+    /// let client = PumpFun::new(Arc::new(Keypair::new()), cluster)
+    ///     .with_metadata_uploader(Arc::new(PinataUploader::new("PINATA_JWT")));
+    /// ```
+    pub fn with_metadata_uploader(mut self, uploader: Arc<dyn MetadataUploader>) -> Self {
+        self.metadata_uploader = uploader;
+        self
+    }
+
+    /// Registers a hook invoked around every transaction the client sends
+    ///
+    /// Hooks run in registration order before signing, after building, before each
+    /// submission, and once the outcome is known. See `TransactionHook`.
+    ///
+    /// # Arguments
+    ///
+    /// * `hook` - Hook to add after the already registered ones
+    ///
+    /// # Returns
+    ///
+    /// Returns the client with the hook registered
+    pub fn with_hook(mut self, hook: Arc<dyn TransactionHook>) -> Self {
+        self.hooks.push(hook);
+        self
+    }
+
+    /// Signs every transaction with the given blockhash instead of fetching a recent one
+    ///
+    /// Together with explicit priority fees, this makes the bytes of built transactions
+    /// reproducible across runs, which enables snapshot tests of the full build pipeline.
+    /// Transactions signed with a stale blockhash are rejected by the network, so this is
+    /// intended for testing, debugging, and durable nonce workflows.
+    ///
+    /// # Arguments
+    ///
+    /// * `blockhash` - Blockhash to sign every transaction with
+    ///
+    /// # Returns
+    ///
+    /// Returns the client configured with the fixed blockhash
+    pub fn with_fixed_blockhash(mut self, blockhash: Hash) -> Self {
+        self.fixed_blockhash = Some(blockhash);
+        self
+    }
+
+    /// Sets the address lookup tables used for every transaction the client builds
+    ///
+    /// Accounts found in these tables are referenced by index instead of by full public key,
+    /// which keeps large transactions under the size limit. This applies to `create`,
+    /// `create_and_buy`, `buy`, `sell`, and `send_instructions`.
+    ///
+    /// # Arguments
+    ///
+    /// * `lookup_tables` - Address lookup table accounts to compile transactions against
+    ///
+    /// # Returns
+    ///
+    /// Returns the client configured with the lookup tables
+    #[cfg(feature = "versioned-tx")]
+    pub fn with_lookup_tables(mut self, lookup_tables: Vec<AddressLookupTableAccount>) -> Self {
+        self.lookup_tables = lookup_tables;
+        self
+    }
+
+    /// Signs instructions into a transaction without submitting it
+    ///
+    /// Uses the fixed blockhash if one is configured, otherwise fetches a recent blockhash.
+    /// With the "versioned-tx" feature, the client's lookup tables are applied.
+    ///
+    /// # Arguments
+    ///
+    /// * `instructions` - Instructions to include in the transaction
+    /// * `additional_signers` - Optional signers in addition to the payer
+    ///
+    /// # Returns
+    ///
+    /// Returns the signed transaction if successful, or a ClientError if the operation fails
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Failed to retrieve the recent blockhash from the network
+    /// - Transaction creation or signing fails
+    pub async fn build_transaction(
+        &self,
+        instructions: &[Instruction],
+        additional_signers: Option<&[&Keypair]>,
+    ) -> Result<VersionedTransaction, error::ClientError> {
+        match self.fixed_blockhash {
+            Some(blockhash) => build_transaction(
+                &self.payer,
+                instructions,
+                additional_signers,
+                blockhash,
+                #[cfg(feature = "versioned-tx")]
+                Some(&self.lookup_tables),
+            ),
+            None => {
+                get_transaction(
+                    self.rpc.clone(),
+                    self.payer.clone(),
+                    instructions,
+                    additional_signers,
+                    #[cfg(feature = "versioned-tx")]
+                    Some(&self.lookup_tables),
+                )
+                .await
+            }
+        }
+    }
+
+    /// Signs instructions into a transaction and submits it
+    ///
+    /// The transaction is signed by the payer and any additional signers, then submitted
+    /// through the given sender, or the client's sender if none is provided. Failed attempts
+    /// are retried according to the client's retry policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `instructions` - Instructions to include in the transaction
+    /// * `additional_signers` - Optional signers in addition to the payer
+    /// * `sender` - Optional sender to use for this call instead of the client's sender
+    ///
+    /// # Returns
+    ///
+    /// Returns the transaction signature if successful, or a ClientError if the operation fails
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Transaction creation fails
+    /// - The sender fails to submit or confirm the transaction and the retry policy is exhausted
+    /// - Re-simulation shows the rebuilt transaction would fail
+    pub async fn send_instructions(
+        &self,
+        instructions: &[Instruction],
+        additional_signers: Option<&[&Keypair]>,
+        sender: Option<&dyn TransactionSender>,
+    ) -> Result<Signature, error::ClientError> {
+        self.send_instructions_with_timeout(instructions, additional_signers, sender, None)
+            .await
+    }
+
+    /// Signs instructions into a transaction and submits it, giving up after a timeout
+    ///
+    /// Same as `send_instructions`, but the whole operation, including confirmation and any
+    /// retries, is bounded by the given timeout instead of the RPC client's defaults. When the
+    /// timeout elapses the transaction may still land, so check its status before resending.
+    ///
+    /// # Arguments
+    ///
+    /// * `instructions` - Instructions to include in the transaction
+    /// * `additional_signers` - Optional signers in addition to the payer
+    /// * `sender` - Optional sender to use for this call instead of the client's sender
+    /// * `timeout` - Optional maximum duration of the call. If None, no timeout is applied
+    ///
+    /// # Returns
+    ///
+    /// Returns the transaction signature if successful, or a ClientError if the operation fails
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClientError::Timeout` if the timeout elapses, or any error of `send_instructions`
+    pub async fn send_instructions_with_timeout(
+        &self,
+        instructions: &[Instruction],
+        additional_signers: Option<&[&Keypair]>,
+        sender: Option<&dyn TransactionSender>,
+        timeout: Option<Duration>,
+    ) -> Result<Signature, error::ClientError> {
+        Self::with_timeout(
+            timeout,
+            self.send_with_retries(instructions, additional_signers, sender),
+        )
+        .await
+    }
+
+    /// Starts a transaction mixing the SDK's trade instructions with custom instructions
+    ///
+    /// # Returns
+    ///
+    /// Returns an empty `TransactionBuilder` using the client's priority fee, sender, and
+    /// retry policy
+    pub fn transaction(&self) -> utils::transaction::TransactionBuilder<'_> {
+        utils::transaction::TransactionBuilder::new(self)
+    }
+
+    /// Runs the hooks around signing, submitting, and retrying a transaction
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(instructions = instructions.len()))
+    )]
+    async fn send_with_retries(
+        &self,
+        instructions: &[Instruction],
+        additional_signers: Option<&[&Keypair]>,
+        sender: Option<&dyn TransactionSender>,
+    ) -> Result<Signature, error::ClientError> {
+        // Let hooks adjust the instructions before anything is signed
+        let mut hooked;
+        let instructions = if self.hooks.is_empty() {
+            instructions
+        } else {
+            hooked = instructions.to_vec();
+            for hook in &self.hooks {
+                hook.on_instructions(&mut hooked);
+            }
+            hooked.as_slice()
+        };
+
+        let result = self
+            .send_hooked(instructions, additional_signers, sender)
+            .await;
+        // Trades built by the client count towards its risk limits once they land
+        if let Some(risk) = &self.risk {
+            risk.settle(instructions, result.is_ok());
+        }
+        for hook in &self.hooks {
+            hook.on_confirm(&result);
+        }
+        result
+    }
+
+    /// Sends a transaction with the retry policy, notifying hooks of builds and submissions
+    async fn send_hooked(
+        &self,
+        instructions: &[Instruction],
+        additional_signers: Option<&[&Keypair]>,
+        sender: Option<&dyn TransactionSender>,
+    ) -> Result<Signature, error::ClientError> {
+        let sender = sender.unwrap_or(self.sender.as_ref());
+        let policy = self.retry_policy;
+        #[cfg(feature = "tracing")]
+        let started = std::time::Instant::now();
+
+        // Create and sign transaction
+        let mut transaction = self
+            .build_transaction(instructions, additional_signers)
+            .await?;
+        for hook in &self.hooks {
+            hook.on_build(&transaction);
+        }
+
+        let mut attempt = 1;
+        loop {
+            if let Some(signature) = transaction.signatures.first() {
+                for hook in &self.hooks {
+                    hook.on_send(signature);
+                }
+            }
+
+            // Send and confirm transaction, returning unless the failure is retryable
+            let (delay, expired) = match sender.send_transaction(&transaction).await {
+                Ok(signature) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        signature = %signature,
+                        attempt,
+                        latency_ms = started.elapsed().as_millis() as u64,
+                        "transaction confirmed"
+                    );
+                    return Ok(signature);
+                }
+                Err(err) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        signature = ?transaction.signatures.first(),
+                        attempt,
+                        latency_ms = started.elapsed().as_millis() as u64,
+                        error = %err,
+                        "transaction failed"
+                    );
+                    match policy.retry_delay(attempt, &err) {
+                        Some(delay) => (delay, err.is_blockhash_expired()),
+                        None => return Err(err),
+                    }
+                }
+            };
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+
+            if expired && policy.refresh_blockhash {
+                transaction = self
+                    .build_transaction(instructions, additional_signers)
+                    .await?;
+                for hook in &self.hooks {
+                    hook.on_build(&transaction);
+                }
+            }
+
+            if policy.resimulate {
+                let simulation = self
+                    .rpc
+                    .simulate_transaction(&transaction)
+                    .await
+                    .map_err(error::ClientError::from)?;
+                if let Some(err) = simulation.value.err {
+                    return Err(error::simulation_failure(
+                        Some(err),
+                        simulation.value.logs.unwrap_or_default(),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Builds an unsigned transaction for signing outside of this process
+    ///
+    /// Uses the fixed blockhash if one is configured, otherwise fetches a recent blockhash.
+    /// With the "versioned-tx" feature, the client's lookup tables are applied. Signatures are
+    /// left as placeholders; use `sender::encode_transaction` to export it as base64.
+    ///
+    /// # Arguments
+    ///
+    /// * `fee_payer` - Account that pays the transaction fees
+    /// * `instructions` - Instructions to include in the transaction
+    ///
+    /// # Returns
+    ///
+    /// Returns the unsigned transaction if successful, or a ClientError if the operation fails
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Failed to retrieve the recent blockhash from the network
+    /// - Transaction message compilation fails
+    pub async fn build_unsigned_transaction(
+        &self,
+        fee_payer: &Pubkey,
+        instructions: &[Instruction],
+    ) -> Result<VersionedTransaction, error::ClientError> {
+        let recent_blockhash = match self.fixed_blockhash {
+            Some(blockhash) => blockhash,
+            None => self.rpc.get_latest_blockhash().await?,
+        };
+
+        utils::transaction::build_unsigned_transaction(
+            fee_payer,
+            instructions,
+            recent_blockhash,
+            #[cfg(feature = "versioned-tx")]
+            Some(&self.lookup_tables),
+        )
+    }
+
+    /// Builds an unsigned buy transaction for an external signer
+    ///
+    /// Produces the same instructions as `buy`, but for an arbitrary user and without signing,
+    /// so the transaction can be signed by an external wallet, a multisig, or a backend
+    /// signing service.
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - Account buying the tokens; must sign the transaction
+    /// * `fee_payer` - Optional account paying transaction fees and token account rent.
+    ///   If None, the user pays
+    /// * `mint` - Public key of the token mint to buy
+    /// * `amount_sol` - Amount of SOL to spend, in lamports (1 SOL = 1,000,000,000 lamports)
+    /// * `track_volume` - Optional flag to track this purchase in volume accumulators
+    /// * `slippage_basis_points` - Optional maximum acceptable slippage in basis points (1 bp = 0.01%).
+    ///   If None, uses the client's default slippage (500, i.e. 5%, unless configured)
+    /// * `priority_fee` - Optional priority fee configuration for compute units. If None, uses the
+    ///   default from the cluster configuration
+    ///
+    /// # Returns
+    ///
+    /// Returns the unsigned transaction if successful, or a ClientError if the operation fails
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The global account or bonding curve account cannot be fetched
+    /// - The buy price calculation fails
+    /// - Transaction message compilation fails
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use pumpfun::{PumpFun, common::types::{Cluster, PriorityFee}, sender::encode_transaction};
+    /// # use solana_sdk::{commitment_config::CommitmentConfig, native_token::sol_to_lamports, pubkey, signature::Keypair};
+    /// # use std::sync::Arc;
+    /// #
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let payer = Arc::new(Keypair::new());
+    /// # let cluster = Cluster::devnet(CommitmentConfig::confirmed(), PriorityFee::default());
+    /// # let client = PumpFun::new(payer, cluster);
+    /// let user = pubkey!("UserWa11etXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX");
+    /// let mint = pubkey!("SoMeTokenM1ntAddr3ssXXXXXXXXXXXXXXXXXXXXXXX");
+    ///
+    /// let transaction = client
+    ///     .build_buy_transaction(user, None, mint, sol_to_lamports(0.01), None, None, None)
+    ///     .await?;
+    /// let base64 = encode_transaction(&transaction)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub async fn build_buy_transaction(
+        &self,
+        user: Pubkey,
+        fee_payer: Option<Pubkey>,
+        mint: Pubkey,
+        amount_sol: u64,
+        track_volume: Option<bool>,
+        slippage_basis_points: Option<u64>,
+        priority_fee: Option<PriorityFee>,
+    ) -> Result<VersionedTransaction, error::ClientError> {
+        let fee_payer = fee_payer.unwrap_or(user);
+
+        // Add priority fee if provided or default to cluster priority fee
+        let priority_fee = priority_fee.unwrap_or(self.cluster.priority_fee);
+        let mut instructions = Self::get_priority_fee_instructions(&priority_fee);
+
+        // Add buy instructions for the user
+        let buy_ix = self
+            .buy_instructions_for(
+                &user,
+                &fee_payer,
+                mint,
+                amount_sol,
+                track_volume,
+                slippage_basis_points,
+            )
+            .await?;
+        instructions.extend(buy_ix.instructions);
+
+        self.build_unsigned_transaction(&fee_payer, &instructions)
+            .await
+    }
+
+    /// Builds an unsigned sell transaction for an external signer
+    ///
+    /// Produces the same instructions as `sell`, but for an arbitrary user and without signing,
+    /// so the transaction can be signed by an external wallet, a multisig, or a backend
+    /// signing service.
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - Account selling the tokens; must sign the transaction
+    /// * `fee_payer` - Optional account paying transaction fees. If None, the user pays
+    /// * `mint` - Public key of the token mint to sell
+    /// * `amount_token` - Optional amount of tokens to sell in base units. If None, sells the entire balance
+    /// * `slippage_basis_points` - Optional maximum acceptable slippage in basis points (1 bp = 0.01%).
+    ///   If None, uses the client's default slippage (500, i.e. 5%, unless configured)
+    /// * `priority_fee` - Optional priority fee configuration for compute units. If None, uses the
+    ///   default from the cluster configuration
+    ///
+    /// # Returns
+    ///
+    /// Returns the unsigned transaction if successful, or a ClientError if the operation fails
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The token account or token balance cannot be fetched
+    /// - The global account or bonding curve account cannot be fetched
+    /// - The sell price calculation fails
+    /// - Transaction message compilation fails
+    pub async fn build_sell_transaction(
+        &self,
+        user: Pubkey,
+        fee_payer: Option<Pubkey>,
+        mint: Pubkey,
+        amount_token: Option<u64>,
+        slippage_basis_points: Option<u64>,
+        priority_fee: Option<PriorityFee>,
+    ) -> Result<VersionedTransaction, error::ClientError> {
+        let fee_payer = fee_payer.unwrap_or(user);
+
+        // Add priority fee if provided or default to cluster priority fee
+        let priority_fee = priority_fee.unwrap_or(self.cluster.priority_fee);
+        let mut instructions = Self::get_priority_fee_instructions(&priority_fee);
+
+        // Add sell instructions for the user
+        let sell_ix = self
+            .sell_instructions_for(&user, mint, amount_token, slippage_basis_points)
+            .await?;
+        instructions.extend(sell_ix.instructions);
+
+        self.build_unsigned_transaction(&fee_payer, &instructions)
+            .await
     }
 
     /// Creates a new token with metadata by uploading metadata to IPFS and initializing on-chain accounts
@@ -140,10 +875,14 @@ impl PumpFun {
     ///     name: "My Token".to_string(),
     ///     symbol: "MYTKN".to_string(),
     ///     description: "A test token created with Pump.fun".to_string(),
-    ///     file: "path/to/image.png".to_string(),
+    ///     file: "path/to/image.png".into(),
     ///     twitter: None,
     ///     telegram: None,
     ///     website: Some("https://example.com".to_string()),
+    ///     banner: None,
+    ///     video: None,
+    ///     discord: None,
+    ///     extra: Default::default(),
     /// };
     ///
     /// let signature = client.create(mint, metadata, None).await?;
@@ -151,6 +890,10 @@ impl PumpFun {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(mint = %mint.pubkey()))
+    )]
     pub async fn create(
         &self,
         mint: Keypair,
@@ -158,37 +901,19 @@ impl PumpFun {
         priority_fee: Option<PriorityFee>,
     ) -> Result<Signature, error::ClientError> {
         // First upload metadata and image to IPFS
-        let ipfs: utils::TokenMetadataResponse = utils::create_token_metadata(metadata)
-            .await
-            .map_err(error::ClientError::UploadMetadataError)?;
+        let ipfs: utils::TokenMetadataResponse = self.metadata_uploader.upload(metadata).await?;
 
         // Add priority fee if provided or default to cluster priority fee
         let priority_fee = priority_fee.unwrap_or(self.cluster.priority_fee);
         let mut instructions = Self::get_priority_fee_instructions(&priority_fee);
 
         // Add create token instruction
-        let create_ix = self.get_create_instruction(&mint, ipfs);
+        let create_ix = self.get_create_instruction(&mint, ipfs)?;
         instructions.push(create_ix);
 
-        // Create and sign transaction
-        let transaction = get_transaction(
-            self.rpc.clone(),
-            self.payer.clone(),
-            &instructions,
-            Some(&[&mint]),
-            #[cfg(feature = "versioned-tx")]
-            None,
-        )
-        .await?;
-
-        // Send and confirm transaction
-        let signature = self
-            .rpc
-            .send_and_confirm_transaction(&transaction)
+        // Sign and send transaction
+        self.send_instructions(&instructions, Some(&[&mint]), None)
             .await
-            .map_err(error::ClientError::SolanaClientError)?;
-
-        Ok(signature)
     }
 
     /// Creates a new token and immediately buys an initial amount in a single atomic transaction
@@ -207,7 +932,7 @@ impl PumpFun {
     /// * `metadata` - Token metadata including name, symbol, description and image file
     /// * `amount_sol` - Amount of SOL to spend on the initial buy, in lamports (1 SOL = 1,000,000,000 lamports)
     /// * `slippage_basis_points` - Optional maximum acceptable slippage in basis points (1 bp = 0.01%).
-    ///   If None, defaults to 500 (5%)
+    ///   If None, uses the client's default slippage (500, i.e. 5%, unless configured)
     /// * `priority_fee` - Optional priority fee configuration for compute units. If None, uses the
     ///   default from the cluster configuration
     ///
@@ -240,10 +965,14 @@ impl PumpFun {
     ///     name: "My Token".to_string(),
     ///     symbol: "MYTKN".to_string(),
     ///     description: "A test token created with Pump.fun".to_string(),
-    ///     file: "path/to/image.png".to_string(),
+    ///     file: "path/to/image.png".into(),
     ///     twitter: None,
     ///     telegram: None,
     ///     website: Some("https://example.com".to_string()),
+    ///     banner: None,
+    ///     video: None,
+    ///     discord: None,
+    ///     extra: Default::default(),
     /// };
     ///
     /// // Create token and buy 0.1 SOL worth with 5% slippage tolerance
@@ -256,6 +985,10 @@ impl PumpFun {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(mint = %mint.pubkey(), amount_sol))
+    )]
     pub async fn create_and_buy(
         &self,
         mint: Keypair,
@@ -266,16 +999,14 @@ impl PumpFun {
         priority_fee: Option<PriorityFee>,
     ) -> Result<Signature, error::ClientError> {
         // Upload metadata to IPFS first
-        let ipfs: utils::TokenMetadataResponse = utils::create_token_metadata(metadata)
-            .await
-            .map_err(error::ClientError::UploadMetadataError)?;
+        let ipfs: utils::TokenMetadataResponse = self.metadata_uploader.upload(metadata).await?;
 
         // Add priority fee if provided or default to cluster priority fee
         let priority_fee = priority_fee.unwrap_or(self.cluster.priority_fee);
         let mut instructions = Self::get_priority_fee_instructions(&priority_fee);
 
         // Add create token instruction
-        let create_ix = self.get_create_instruction(&mint, ipfs);
+        let create_ix = self.get_create_instruction(&mint, ipfs)?;
         instructions.push(create_ix);
 
         // Add buy instruction
@@ -289,25 +1020,9 @@ impl PumpFun {
             .await?;
         instructions.extend(buy_ix);
 
-        // Create and sign transaction
-        let transaction = get_transaction(
-            self.rpc.clone(),
-            self.payer.clone(),
-            &instructions,
-            Some(&[&mint]),
-            #[cfg(feature = "versioned-tx")]
-            None,
-        )
-        .await?;
-
-        // Send and confirm transaction
-        let signature = self
-            .rpc
-            .send_and_confirm_transaction(&transaction)
+        // Sign and send transaction
+        self.send_instructions(&instructions, Some(&[&mint]), None)
             .await
-            .map_err(error::ClientError::SolanaClientError)?;
-
-        Ok(signature)
     }
 
     /// Buys tokens from a bonding curve by spending SOL
@@ -328,7 +1043,7 @@ impl PumpFun {
     /// * `mint` - Public key of the token mint to buy
     /// * `amount_sol` - Amount of SOL to spend, in lamports (1 SOL = 1,000,000,000 lamports)
     /// * `slippage_basis_points` - Optional maximum acceptable slippage in basis points (1 bp = 0.01%).
-    ///   If None, defaults to 500 (5%)
+    ///   If None, uses the client's default slippage (500, i.e. 5%, unless configured)
     /// * `priority_fee` - Optional priority fee configuration for compute units. If None, uses the
     ///   default from the cluster configuration
     ///
@@ -368,6 +1083,10 @@ impl PumpFun {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(mint = %mint, amount_sol))
+    )]
     pub async fn buy(
         &self,
         mint: Pubkey,
@@ -386,25 +1105,8 @@ impl PumpFun {
             .await?;
         instructions.extend(buy_ix);
 
-        // Create and sign transaction
-        let transaction = get_transaction(
-            self.rpc.clone(),
-            self.payer.clone(),
-            &instructions,
-            None,
-            #[cfg(feature = "versioned-tx")]
-            None,
-        )
-        .await?;
-
-        // Send and confirm transaction
-        let signature = self
-            .rpc
-            .send_and_confirm_transaction(&transaction)
-            .await
-            .map_err(error::ClientError::SolanaClientError)?;
-
-        Ok(signature)
+        // Sign and send transaction
+        self.send_instructions(&instructions, None, None).await
     }
 
     /// Sells tokens back to the bonding curve in exchange for SOL
@@ -425,7 +1127,7 @@ impl PumpFun {
     /// * `mint` - Public key of the token mint to sell
     /// * `amount_token` - Optional amount of tokens to sell in base units. If None, sells the entire balance
     /// * `slippage_basis_points` - Optional maximum acceptable slippage in basis points (1 bp = 0.01%).
-    ///   If None, defaults to 500 (5%)
+    ///   If None, uses the client's default slippage (500, i.e. 5%, unless configured)
     /// * `priority_fee` - Optional priority fee configuration for compute units. If None, uses the
     ///   default from the cluster configuration
     ///
@@ -469,6 +1171,10 @@ impl PumpFun {
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(mint = %mint, amount_token = ?amount_token))
+    )]
     pub async fn sell(
         &self,
         mint: Pubkey,
@@ -486,25 +1192,232 @@ impl PumpFun {
             .await?;
         instructions.extend(sell_ix);
 
-        // Create and sign transaction
-        let transaction = get_transaction(
-            self.rpc.clone(),
-            self.payer.clone(),
-            &instructions,
-            None,
-            #[cfg(feature = "versioned-tx")]
-            None,
-        )
-        .await?;
+        // Sign and send transaction
+        self.send_instructions(&instructions, None, None).await
+    }
+
+    /// Updates the program's global parameters
+    ///
+    /// The client's payer must be the global authority. Start from
+    /// `SetParams::from_global(&client.get_global_account().await?, admin_set_creator_authority)`
+    /// to change only some of the parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - New global parameters
+    /// * `priority_fee` - Optional priority fee configuration for compute units. If None, uses the
+    ///   default from the cluster configuration
+    ///
+    /// # Returns
+    ///
+    /// Returns the transaction signature if successful, or a ClientError if the operation fails
+    pub async fn set_params(
+        &self,
+        params: instructions::SetParams,
+        priority_fee: Option<PriorityFee>,
+    ) -> Result<Signature, error::ClientError> {
+        let instruction = instructions::set_params(&self.payer.pubkey(), params)?;
+        self.send_admin_instruction(instruction, priority_fee).await
+    }
+
+    /// Transfers the program's global authority to another account
+    ///
+    /// The client's payer must be the current global authority.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_authority` - Account that becomes the global authority
+    /// * `priority_fee` - Optional priority fee configuration for compute units. If None, uses the
+    ///   default from the cluster configuration
+    ///
+    /// # Returns
+    ///
+    /// Returns the transaction signature if successful, or a ClientError if the operation fails
+    pub async fn update_global_authority(
+        &self,
+        new_authority: &Pubkey,
+        priority_fee: Option<PriorityFee>,
+    ) -> Result<Signature, error::ClientError> {
+        let instruction =
+            instructions::update_global_authority(&self.payer.pubkey(), new_authority)?;
+        self.send_admin_instruction(instruction, priority_fee).await
+    }
+
+    /// Sends an administrative instruction signed by the payer, after the priority fee
+    async fn send_admin_instruction(
+        &self,
+        instruction: Instruction,
+        priority_fee: Option<PriorityFee>,
+    ) -> Result<Signature, error::ClientError> {
+        let priority_fee = priority_fee.unwrap_or(self.cluster.priority_fee);
+        let mut instructions = Self::get_priority_fee_instructions(&priority_fee);
+        instructions.push(instruction);
+        self.send_instructions(&instructions, None, None).await
+    }
 
-        // Send and confirm transaction
-        let signature = self
+    /// Sells every bonding curve token held by the payer and closes its empty token accounts
+    ///
+    /// Enumerates the payer's SPL token accounts, keeps those whose mint has a Pump.fun bonding
+    /// curve, and sells their whole balance, packing up to four sells per transaction. Tokens
+    /// whose curve has completed trade on the Pump.fun AMM, which the client cannot sell, and
+    /// are reported instead. Once the sells are sent, the empty token accounts of Pump.fun
+    /// tokens are closed and their rent returned to the payer, also packing several per
+    /// transaction. A failed transaction does not stop the remaining ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `slippage_basis_points` - Optional maximum acceptable slippage in basis points (1 bp = 0.01%).
+    ///   If None, uses the client's default slippage
+    /// * `priority_fee` - Optional priority fee configuration for compute units. If None, uses the
+    ///   default from the cluster configuration
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Liquidation` listing the sent transactions and the migrated tokens, or a
+    /// ClientError if the token or bonding curve accounts cannot be fetched
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use pumpfun::{PumpFun, common::types::{Cluster, PriorityFee}};
+    /// # use solana_sdk::{commitment_config::CommitmentConfig, signature::Keypair};
+    /// # use std::sync::Arc;
+    /// #
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let payer = Arc::new(Keypair::new());
+    /// # let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+    /// # let client = PumpFun::new(payer, cluster);
+    /// #
+    /// // Sell everything with up to 20% slippage
+    /// let liquidation = client.liquidate_all(Some(2_000), None).await?;
+    /// for batch in &liquidation.sells {
+    ///     println!("Sold {:?}: {:?}", batch.mints, batch.result);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn liquidate_all(
+        &self,
+        slippage_basis_points: Option<u64>,
+        priority_fee: Option<PriorityFee>,
+    ) -> Result<common::types::Liquidation, error::ClientError> {
+        let priority_fee = priority_fee.unwrap_or(self.cluster.priority_fee);
+        let payer = self.payer.pubkey();
+        let holdings: Vec<(Pubkey, Pubkey, u64)> = self
             .rpc
-            .send_and_confirm_transaction(&transaction)
-            .await
-            .map_err(error::ClientError::SolanaClientError)?;
+            .get_token_accounts_by_owner(
+                &payer,
+                TokenAccountsFilter::ProgramId(constants::accounts::TOKEN_PROGRAM),
+            )
+            .await?
+            .iter()
+            .filter_map(parse_token_account)
+            .collect();
+
+        // Only tokens launched on Pump.fun have a bonding curve
+        let mut curves = Vec::with_capacity(holdings.len());
+        for chunk in holdings.chunks(100) {
+            let pdas: Vec<Pubkey> = chunk
+                .iter()
+                .map(|(_, mint, _)| Self::get_bonding_curve_pda(mint).unwrap_or_default())
+                .collect();
+            curves.extend(self.rpc.get_multiple_accounts(&pdas).await?);
+        }
+
+        let mut liquidation = common::types::Liquidation::default();
+        let mut sells = Vec::new();
+        let mut empty = Vec::new();
+        for ((account, mint, amount), curve) in holdings.into_iter().zip(curves) {
+            let Some(curve) = curve
+                .filter(|curve| curve.owner == constants::accounts::PUMPFUN)
+                .and_then(|curve| {
+                    accounts::try_from_versioned_account_data::<accounts::BondingCurveAccount>(
+                        &curve.data,
+                    )
+                    .ok()
+                })
+            else {
+                continue;
+            };
+            if amount == 0 {
+                empty.push((account, mint));
+            } else if curve.complete {
+                liquidation.migrated.push(mint);
+            } else {
+                sells.push((account, mint, amount));
+            }
+        }
 
-        Ok(signature)
+        for chunk in sells.chunks(SELLS_PER_TRANSACTION) {
+            let mut instructions = Self::get_priority_fee_instructions(&priority_fee);
+            let mut mints = Vec::new();
+            for (_, mint, amount) in chunk {
+                match self
+                    .get_sell_instructions(*mint, Some(*amount), slippage_basis_points)
+                    .await
+                {
+                    Ok(sell) => {
+                        instructions.extend(sell);
+                        mints.push(*mint);
+                    }
+                    Err(err) => liquidation.sells.push(common::types::TokenBatch {
+                        mints: vec![*mint],
+                        result: Err(err.to_string()),
+                    }),
+                }
+            }
+            if mints.is_empty() {
+                continue;
+            }
+            let result = self
+                .send_instructions(&instructions, None, None)
+                .await
+                .map_err(|err| err.to_string());
+            // Sells of the whole balance close their account when close-ata is enabled
+            if result.is_ok() && !cfg!(feature = "close-ata") {
+                empty.extend(
+                    chunk
+                        .iter()
+                        .filter(|(_, mint, _)| mints.contains(mint))
+                        .map(|(account, mint, _)| (*account, *mint)),
+                );
+            }
+            liquidation
+                .sells
+                .push(common::types::TokenBatch { mints, result });
+        }
+
+        for chunk in empty.chunks(CLOSES_PER_TRANSACTION) {
+            let mut instructions = Self::get_priority_fee_instructions(&priority_fee);
+            for (account, _) in chunk {
+                instructions.push(
+                    spl_token::instruction::close_account(
+                        &constants::accounts::TOKEN_PROGRAM,
+                        account,
+                        &payer,
+                        &payer,
+                        &[&payer],
+                    )
+                    .map_err(|err| {
+                        error::ClientError::OtherError(format!(
+                            "Failed to create close account instruction: pubkey={}: {}",
+                            account, err
+                        ))
+                    })?,
+                );
+            }
+            let result = self
+                .send_instructions(&instructions, None, None)
+                .await
+                .map_err(|err| err.to_string());
+            liquidation.closes.push(common::types::TokenBatch {
+                mints: chunk.iter().map(|(_, mint)| *mint).collect(),
+                result,
+            });
+        }
+
+        Ok(liquidation)
     }
 
     /// Subscribes to real-time events from the Pump.fun program
@@ -581,29 +1494,204 @@ impl PumpFun {
     ///     }
     /// }).await?;
     ///
-    /// // Keep the subscription active
-    /// // When no longer needed, drop the subscription to unsubscribe
+    /// // Keep the subscription active
+    /// // When no longer needed, drop the subscription to unsubscribe
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "stream")]
+    pub async fn subscribe<F>(
+        &self,
+        mentioned: Option<String>,
+        commitment: Option<solana_sdk::commitment_config::CommitmentConfig>,
+        callback: F,
+    ) -> Result<common::stream::Subscription, error::ClientError>
+    where
+        F: Fn(
+                String,
+                Option<common::stream::PumpFunEvent>,
+                Option<Box<dyn std::error::Error + Send + Sync>>,
+                solana_client::rpc_response::Response<solana_client::rpc_response::RpcLogsResponse>,
+            ) + Send
+            + Sync
+            + 'static,
+    {
+        common::stream::subscribe(self.cluster.clone(), mentioned, commitment, callback).await
+    }
+
+    /// Subscribes to real-time events from the Pump.fun program along with their context
+    ///
+    /// Works like `subscribe`, but passes the callback an `EventContext` with the slot,
+    /// block time, and index of each event instead of the bare transaction signature. See
+    /// `common::stream::subscribe_with_context` for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `mentioned` - Optional public key to filter events by mentions. If None, subscribes to all Pump.fun events
+    /// * `commitment` - Optional commitment level for the subscription. If None, uses the
+    ///   default from the cluster configuration
+    /// * `callback` - A function that will be called for each event, with the event context
+    ///   in place of the signature passed by `subscribe`
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Subscription` object that manages the lifecycle of the subscription, or a
+    /// ClientError if the subscription cannot be established
+    #[cfg(feature = "stream")]
+    pub async fn subscribe_with_context<F>(
+        &self,
+        mentioned: Option<String>,
+        commitment: Option<solana_sdk::commitment_config::CommitmentConfig>,
+        callback: F,
+    ) -> Result<common::stream::Subscription, error::ClientError>
+    where
+        F: Fn(
+                common::stream::EventContext,
+                Option<common::stream::PumpFunEvent>,
+                Option<common::stream::ParseError>,
+                solana_client::rpc_response::Response<solana_client::rpc_response::RpcLogsResponse>,
+            ) + Send
+            + Sync
+            + 'static,
+    {
+        common::stream::subscribe_with_context(
+            self.cluster.clone(),
+            mentioned,
+            commitment,
+            callback,
+        )
+        .await
+    }
+
+    /// Subscribes to real-time events from the Pump.fun program matching a filter
+    ///
+    /// Works like `subscribe`, but only invokes the callback for events matching `filter`,
+    /// e.g. only token creations or only trades of a set of mints. See
+    /// `common::stream::subscribe_with_filter` for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - Event kinds and accounts to deliver events for
+    /// * `commitment` - Optional commitment level for the subscription. If None, uses the
+    ///   default from the cluster configuration
+    /// * `callback` - A function that will be called for each matching event, with the same
+    ///   parameters as in `subscribe`
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Subscription` object that manages the lifecycle of the subscription, or a
+    /// ClientError if the subscription cannot be established
+    #[cfg(feature = "stream")]
+    pub async fn subscribe_with_filter<F>(
+        &self,
+        filter: common::stream::EventFilter,
+        commitment: Option<solana_sdk::commitment_config::CommitmentConfig>,
+        callback: F,
+    ) -> Result<common::stream::Subscription, error::ClientError>
+    where
+        F: Fn(
+                String,
+                Option<common::stream::PumpFunEvent>,
+                Option<common::stream::ParseError>,
+                solana_client::rpc_response::Response<solana_client::rpc_response::RpcLogsResponse>,
+            ) + Send
+            + Sync
+            + 'static,
+    {
+        common::stream::subscribe_with_filter(self.cluster.clone(), filter, commitment, callback)
+            .await
+    }
+
+    /// Subscribes to real-time events from the Pump.fun program as a stream
+    ///
+    /// Works like `subscribe`, but yields events from an `EventStream` instead of invoking
+    /// a callback. Dropping the stream terminates the subscription.
+    ///
+    /// # Arguments
+    ///
+    /// * `mentioned` - Optional public key to filter events by mentions. If None, subscribes to all Pump.fun events
+    /// * `commitment` - Optional commitment level for the subscription. If None, uses the
+    ///   default from the cluster configuration
+    ///
+    /// # Returns
+    ///
+    /// Returns an `EventStream` of event contexts, parse results, and logs responses, or a
+    /// ClientError if the subscription cannot be established
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use futures::StreamExt;
+    /// # use pumpfun::{PumpFun, common::types::{Cluster, PriorityFee}};
+    /// # use solana_sdk::{commitment_config::CommitmentConfig, signature::Keypair};
+    /// # use std::{sync::Arc, error::Error};
+    /// #
+    /// # async fn example() -> Result<(), Box<dyn Error>> {
+    /// # let payer = Arc::new(Keypair::new());
+    /// # let cluster = Cluster::devnet(CommitmentConfig::confirmed(), PriorityFee::default());
+    /// # let client = PumpFun::new(payer, cluster);
+    /// #
+    /// let mut stream = client.subscribe_stream(None, None).await?;
+    /// while let Some((context, Ok(event), _)) = stream.next().await {
+    ///     println!("Event in tx {}: {:?}", context.signature, event);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "stream")]
+    pub async fn subscribe_stream(
+        &self,
+        mentioned: Option<String>,
+        commitment: Option<solana_sdk::commitment_config::CommitmentConfig>,
+    ) -> Result<common::stream::EventStream, error::ClientError> {
+        common::stream::subscribe_stream(self.cluster.clone(), mentioned, commitment).await
+    }
+
+    /// Watches a token's bonding curve account for changes
+    ///
+    /// Subscribes to the bonding curve PDA through `accountSubscribe` and yields the decoded
+    /// account on every change, so reserves can be tracked per slot without parsing trade
+    /// logs. See `common::watch::watch_bonding_curve` for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - Public key of the token mint
+    /// * `commitment` - Optional commitment level for the subscription. If None, uses the
+    ///   default from the cluster configuration
+    ///
+    /// # Returns
+    ///
+    /// Returns a `BondingCurveStream` of account updates, or a ClientError if the
+    /// subscription cannot be established
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use futures::StreamExt;
+    /// # use pumpfun::{PumpFun, common::types::{Cluster, PriorityFee}};
+    /// # use solana_sdk::{commitment_config::CommitmentConfig, pubkey, signature::Keypair};
+    /// # use std::{sync::Arc, error::Error};
+    /// #
+    /// # async fn example() -> Result<(), Box<dyn Error>> {
+    /// # let payer = Arc::new(Keypair::new());
+    /// # let cluster = Cluster::devnet(CommitmentConfig::confirmed(), PriorityFee::default());
+    /// # let client = PumpFun::new(payer, cluster);
+    /// #
+    /// let mint = pubkey!("TokenM1ntPubk3yXXXXXXXXXXXXXXXXXXXXXXXXXXXX");
+    /// let mut updates = client.watch_bonding_curve(&mint, None).await?;
+    /// while let Some(update) = updates.next().await {
+    ///     println!("Real SOL reserves at slot {}: {}", update.slot, update.account.real_sol_reserves);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
     #[cfg(feature = "stream")]
-    pub async fn subscribe<F>(
+    pub async fn watch_bonding_curve(
         &self,
-        mentioned: Option<String>,
+        mint: &Pubkey,
         commitment: Option<solana_sdk::commitment_config::CommitmentConfig>,
-        callback: F,
-    ) -> Result<common::stream::Subscription, error::ClientError>
-    where
-        F: Fn(
-                String,
-                Option<common::stream::PumpFunEvent>,
-                Option<Box<dyn std::error::Error + Send + Sync>>,
-                solana_client::rpc_response::Response<solana_client::rpc_response::RpcLogsResponse>,
-            ) + Send
-            + Sync
-            + 'static,
-    {
-        common::stream::subscribe(self.cluster.clone(), mentioned, commitment, callback).await
+    ) -> Result<common::watch::BondingCurveStream, error::ClientError> {
+        common::watch::watch_bonding_curve(self.cluster.clone(), mint, commitment).await
     }
 
     /// Creates compute budget instructions for priority fees
@@ -685,14 +1773,18 @@ impl PumpFun {
     ///         name: "Example Token".to_string(),
     ///         symbol: "EXTKN".to_string(),
     ///         description: "An example token".to_string(),
-    ///         file: "path/to/image.png".to_string(),
+    ///         file: "path/to/image.png".into(),
     ///         twitter: None,
     ///         telegram: None,
     ///         website: None,
+    ///         banner: None,
+    ///         video: None,
+    ///         discord: None,
+    ///         extra: Default::default(),
     ///     }
     /// ).await?;
     ///
-    /// let create_instruction = client.get_create_instruction(&mint, metadata_response);
+    /// let create_instruction = client.get_create_instruction(&mint, metadata_response)?;
     /// # Ok(())
     /// # }
     /// ```
@@ -700,7 +1792,7 @@ impl PumpFun {
         &self,
         mint: &Keypair,
         ipfs: utils::TokenMetadataResponse,
-    ) -> Instruction {
+    ) -> Result<Instruction, error::ClientError> {
         instructions::create(
             &self.payer,
             mint,
@@ -724,7 +1816,7 @@ impl PumpFun {
     /// * `mint` - Public key of the token mint to buy
     /// * `amount_sol` - Amount of SOL to spend, in lamports (1 SOL = 1,000,000,000 lamports)
     /// * `slippage_basis_points` - Optional maximum acceptable slippage in basis points (1 bp = 0.01%).
-    ///   If None, defaults to 500 (5%)
+    ///   If None, uses the client's default slippage (500, i.e. 5%, unless configured)
     ///
     /// # Returns
     ///
@@ -766,56 +1858,147 @@ impl PumpFun {
         track_volume: Option<bool>,
         slippage_basis_points: Option<u64>,
     ) -> Result<Vec<Instruction>, error::ClientError> {
+        let payer = self.payer.pubkey();
+        let buy = self
+            .buy_instructions_for(
+                &payer,
+                &payer,
+                mint,
+                amount_sol,
+                track_volume,
+                slippage_basis_points,
+            )
+            .await?;
+        Ok(buy.held_by(self.risk.as_deref()))
+    }
+
+    /// Generates buy instructions for a user identified by public key
+    ///
+    /// `funding` pays for the associated token account if one has to be created.
+    async fn buy_instructions_for(
+        &self,
+        user: &Pubkey,
+        funding: &Pubkey,
+        mint: Pubkey,
+        amount_sol: u64,
+        track_volume: Option<bool>,
+        slippage_basis_points: Option<u64>,
+    ) -> Result<TradeInstructions, error::ClientError> {
         // Get accounts and calculate buy amounts
         let global_account = self.get_global_account().await?;
+        // The fees are charged on top of the SOL paid into the curve
+        let sol_into_curve = self
+            .get_fees(user, &global_account)
+            .await?
+            .sol_before_fees(amount_sol);
         let mut bonding_curve_account: Option<accounts::BondingCurveAccount> = None;
         let buy_amount = {
             let bonding_curve_pda = Self::get_bonding_curve_pda(&mint)
                 .ok_or(error::ClientError::BondingCurveNotFound)?;
-            if self.rpc.get_account(&bonding_curve_pda).await.is_err() {
-                global_account.get_initial_buy_price(amount_sol)
+            // Tokens created in the same transaction have no bonding curve yet
+            if let Ok(account) = self.rpc.get_account(&bonding_curve_pda).await {
+                let account: accounts::BondingCurveAccount =
+                    accounts::try_from_versioned_account_data(&account.data)?;
+                let price = account
+                    .get_buy_price(sol_into_curve)
+                    .map_err(error::ClientError::BondingCurveError)?;
+                bonding_curve_account = Some(account);
+                price
             } else {
-                bonding_curve_account = self.get_bonding_curve_account(&mint).await.ok();
-                bonding_curve_account
-                    .as_ref()
-                    .unwrap()
-                    .get_buy_price(amount_sol)
-                    .map_err(error::ClientError::BondingCurveError)?
+                global_account.get_initial_buy_price(sol_into_curve)
             }
         };
-        let buy_amount_with_slippage =
-            utils::calculate_with_slippage_buy(amount_sol, slippage_basis_points.unwrap_or(500));
+        let buy_amount_with_slippage = utils::calculate_with_slippage_buy(
+            amount_sol,
+            slippage_basis_points.unwrap_or(self.default_slippage_basis_points),
+        );
 
         let mut instructions = Vec::new();
 
         // Create Associated Token Account if needed
+        #[cfg_attr(not(feature = "create-ata"), allow(unused_mut))]
+        let mut creates_ata = false;
         #[cfg(feature = "create-ata")]
-        {
-            let ata: Pubkey = get_associated_token_address(&self.payer.pubkey(), &mint);
-            if self.rpc.get_account(&ata).await.is_err() {
-                instructions.push(create_associated_token_account(
-                    &self.payer.pubkey(),
-                    &self.payer.pubkey(),
+        match self.ata_policy {
+            AtaPolicy::Check => {
+                let ata: Pubkey = get_associated_token_address(user, &mint);
+                if self.rpc.get_account(&ata).await.is_err() {
+                    instructions.push(create_associated_token_account(
+                        funding,
+                        user,
+                        &mint,
+                        &constants::accounts::TOKEN_PROGRAM,
+                    ));
+                    creates_ata = true;
+                }
+            }
+            AtaPolicy::AlwaysCreateIdempotent => {
+                instructions.push(create_associated_token_account_idempotent(
+                    funding,
+                    user,
                     &mint,
                     &constants::accounts::TOKEN_PROGRAM,
                 ));
             }
+            AtaPolicy::AssumeExists => {}
+        }
+
+        // Validate the trade before building the buy instruction
+        if self.preflight_checks {
+            utils::preflight::check_global(&global_account)?;
+            if let Some(account) = &bonding_curve_account {
+                utils::preflight::check_bonding_curve(&mint, account)?;
+            }
+
+            let mut required = buy_amount_with_slippage;
+            if funding == user {
+                required = required.saturating_add(utils::preflight::LAMPORTS_PER_SIGNATURE);
+                if creates_ata {
+                    let rent = self
+                        .rpc
+                        .get_minimum_balance_for_rent_exemption(
+                            utils::preflight::TOKEN_ACCOUNT_SIZE,
+                        )
+                        .await?;
+                    required = required.saturating_add(rent);
+                }
+            }
+            let available = self.rpc.get_balance(user).await?;
+            utils::preflight::check_sol_balance(required, available)?;
         }
 
         // Add buy instruction
-        instructions.push(instructions::buy(
-            &self.payer,
+        let creator = bonding_curve_account.map_or(*user, |bc| bc.creator);
+        let buy = instructions::buy_for_version(
+            user,
             &mint,
             &global_account.fee_recipient,
-            &bonding_curve_account.map_or(self.payer.pubkey(), |bc| bc.creator),
+            &creator,
             instructions::Buy {
                 amount: buy_amount,
                 max_sol_cost: buy_amount_with_slippage,
                 track_volume,
             },
-        ));
+            self.program_version,
+        )?;
+
+        // The buy only counts towards the risk limits once it lands
+        if let Some(risk) = &self.risk {
+            risk.check_buy(&mint, &creator, amount_sol)
+                .map_err(error::ClientError::RiskRejected)?;
+        }
+        instructions.push(buy);
 
-        Ok(instructions)
+        Ok(TradeInstructions {
+            index: instructions.len() - 1,
+            instructions,
+            trade: RiskTrade::Buy {
+                mint,
+                creator,
+                amount_sol,
+                tokens: buy_amount,
+            },
+        })
     }
 
     /// Generates instructions for selling tokens back to a bonding curve
@@ -830,7 +2013,7 @@ impl PumpFun {
     /// * `mint` - Public key of the token mint to sell
     /// * `amount_token` - Optional amount of tokens to sell in base units. If None, sells the entire balance
     /// * `slippage_basis_points` - Optional maximum acceptable slippage in basis points (1 bp = 0.01%).
-    ///   If None, defaults to 500 (5%)
+    ///   If None, uses the client's default slippage (500, i.e. 5%, unless configured)
     ///
     /// # Returns
     ///
@@ -874,37 +2057,82 @@ impl PumpFun {
         amount_token: Option<u64>,
         slippage_basis_points: Option<u64>,
     ) -> Result<Vec<Instruction>, error::ClientError> {
+        let sell = self
+            .sell_instructions_for(
+                &self.payer.pubkey(),
+                mint,
+                amount_token,
+                slippage_basis_points,
+            )
+            .await?;
+        Ok(sell.held_by(self.risk.as_deref()))
+    }
+
+    /// Generates sell instructions for a user identified by public key
+    async fn sell_instructions_for(
+        &self,
+        user: &Pubkey,
+        mint: Pubkey,
+        amount_token: Option<u64>,
+        slippage_basis_points: Option<u64>,
+    ) -> Result<TradeInstructions, error::ClientError> {
         // Get ATA
-        let ata: Pubkey = get_associated_token_address(&self.payer.pubkey(), &mint);
+        let ata: Pubkey = get_associated_token_address(user, &mint);
 
         // Get token balance
-        let token_balance = if amount_token.is_none() || cfg!(feature = "close-ata") {
-            // We need the balance if amount_token is None OR if the close-ata feature is enabled
-            let balance = self.rpc.get_token_account_balance(&ata).await?;
-            Some(balance.amount.parse::<u64>().unwrap())
-        } else {
-            None
-        };
+        let token_balance =
+            if amount_token.is_none() || cfg!(feature = "close-ata") || self.preflight_checks {
+                // We need the balance if amount_token is None, if the close-ata feature is enabled,
+                // or to validate the amount when preflight checks are enabled
+                let balance = self.rpc.get_token_account_balance(&ata).await?;
+                let amount = balance.amount.parse::<u64>().map_err(|err| {
+                    error::ClientError::OtherError(format!(
+                        "Invalid token balance for {}: {}: {}",
+                        ata, balance.amount, err
+                    ))
+                })?;
+                Some(amount)
+            } else {
+                None
+            };
 
         // Determine amount to sell
-        let amount = amount_token.unwrap_or_else(|| token_balance.unwrap());
+        let amount = match (amount_token, token_balance) {
+            (Some(amount), _) | (None, Some(amount)) => amount,
+            (None, None) => {
+                return Err(error::ClientError::OtherError(format!(
+                    "Token balance unavailable for {}",
+                    ata
+                )))
+            }
+        };
 
         // Calculate min sol output
         let global_account = self.get_global_account().await?;
         let bonding_curve_account = self.get_bonding_curve_account(&mint).await?;
-        let min_sol_output = bonding_curve_account
-            .get_sell_price(amount, global_account.fee_basis_points)
+
+        // Validate the trade before building the sell instruction
+        if self.preflight_checks {
+            utils::preflight::check_global(&global_account)?;
+            utils::preflight::check_bonding_curve(&mint, &bonding_curve_account)?;
+            if let Some(balance) = token_balance {
+                utils::preflight::check_token_balance(amount, balance)?;
+            }
+        }
+
+        let fees = self.get_fees(user, &global_account).await?;
+        let sol_output = bonding_curve_account
+            .get_sell_price(amount, fees.total_basis_points())
             .map_err(error::ClientError::BondingCurveError)?;
         let min_sol_output = utils::calculate_with_slippage_sell(
-            min_sol_output,
-            slippage_basis_points.unwrap_or(500),
+            sol_output,
+            slippage_basis_points.unwrap_or(self.default_slippage_basis_points),
         );
 
-        let mut instructions = Vec::new();
-
         // Add sell instruction
-        instructions.push(instructions::sell(
-            &self.payer,
+        #[cfg_attr(not(feature = "close-ata"), allow(unused_mut))]
+        let mut instructions = vec![instructions::sell_for_version(
+            user,
             &mint,
             &global_account.fee_recipient,
             &bonding_curve_account.creator,
@@ -912,7 +2140,8 @@ impl PumpFun {
                 amount,
                 min_sol_output,
             },
-        ));
+            self.program_version,
+        )?];
 
         // Close account if balance equals amount
         #[cfg(feature = "close-ata")]
@@ -925,39 +2154,42 @@ impl PumpFun {
                     let token_program = constants::accounts::TOKEN_PROGRAM;
 
                     // Verify the token account exists before attempting to close it
-                    if self.rpc.get_account(&ata).await.is_ok() {
+                    if self.ata_policy != AtaPolicy::Check
+                        || self.rpc.get_account(&ata).await.is_ok()
+                    {
                         // Create instruction to close the ATA
-                        let close_instruction = close_account(
-                            &token_program,
-                            &ata,
-                            &self.payer.pubkey(),
-                            &self.payer.pubkey(),
-                            &[&self.payer.pubkey()],
-                        )
-                        .map_err(|err| {
-                            error::ClientError::OtherError(format!(
-                                "Failed to create close account instruction: pubkey={}: {}",
-                                ata, err
-                            ))
-                        })?;
+                        let close_instruction =
+                            close_account(&token_program, &ata, user, user, &[user]).map_err(
+                                |err| {
+                                    error::ClientError::OtherError(format!(
+                                        "Failed to create close account instruction: pubkey={}: {}",
+                                        ata, err
+                                    ))
+                                },
+                            )?;
 
                         instructions.push(close_instruction);
                     } else {
                         // Log warning but don't fail the transaction if account doesn't exist
-                        eprintln!(
-                            "Warning: Cannot close token account {}, it doesn't exist",
-                            ata
-                        );
+                        log_warn!("Cannot close token account {}, it doesn't exist", ata);
                     }
                 }
             } else {
                 // This case should not occur due to our balance fetch logic,
                 // but handle it gracefully just in case
-                eprintln!("Warning: Token balance unavailable, not closing account");
+                log_warn!("Token balance unavailable, not closing account");
             }
         }
 
-        Ok(instructions)
+        Ok(TradeInstructions {
+            instructions,
+            index: 0,
+            trade: RiskTrade::Sell {
+                mint,
+                tokens: amount,
+                proceeds: sol_output,
+            },
+        })
     }
 
     /// Gets the Program Derived Address (PDA) for the global state account
@@ -1093,6 +2325,8 @@ impl PumpFun {
     ///
     /// Returns an error if:
     /// - The account cannot be found on-chain
+    /// - The account data does not start with the expected discriminator
+    ///   (`ClientError::AccountDiscriminatorMismatch`)
     /// - The account data cannot be properly deserialized
     ///
     /// # Examples
@@ -1113,16 +2347,48 @@ impl PumpFun {
     /// # }
     /// ```
     pub async fn get_global_account(&self) -> Result<accounts::GlobalAccount, error::ClientError> {
+        self.get_global_account_with_timeout(None).await
+    }
+
+    /// Gets the global state account data, giving up after a timeout
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Optional maximum duration of the request. If None, the RPC client's
+    ///   default timeout applies
+    ///
+    /// # Returns
+    ///
+    /// Returns the deserialized GlobalAccount if successful, or a ClientError if the operation fails
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClientError::Timeout` if the timeout elapses, or any error of `get_global_account`
+    pub async fn get_global_account_with_timeout(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<accounts::GlobalAccount, error::ClientError> {
         let global: Pubkey = Self::get_global_pda();
 
-        let account = self
-            .rpc
-            .get_account(&global)
-            .await
-            .map_err(error::ClientError::SolanaClientError)?;
+        #[cfg(feature = "tracing")]
+        let started = std::time::Instant::now();
+        let account = Self::with_timeout(timeout, async {
+            self.rpc
+                .get_account(&global)
+                .await
+                .map_err(error::ClientError::from)
+        })
+        .await;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            account = %global,
+            latency_ms = started.elapsed().as_millis() as u64,
+            ok = account.is_ok(),
+            "fetched global account"
+        );
+        let account = account?;
 
-        solana_sdk::borsh1::try_from_slice_unchecked::<accounts::GlobalAccount>(&account.data)
-            .map_err(error::ClientError::BorshError)
+        accounts::try_from_versioned_account_data(&account.data)
     }
 
     /// Gets a token's bonding curve account data containing pricing parameters
@@ -1147,6 +2413,8 @@ impl PumpFun {
     /// Returns an error if:
     /// - The bonding curve PDA cannot be derived
     /// - The account cannot be found on-chain
+    /// - The account data does not start with the expected discriminator
+    ///   (`ClientError::AccountDiscriminatorMismatch`)
     /// - The account data cannot be properly deserialized
     ///
     /// # Examples
@@ -1169,18 +2437,116 @@ impl PumpFun {
     pub async fn get_bonding_curve_account(
         &self,
         mint: &Pubkey,
+    ) -> Result<accounts::BondingCurveAccount, error::ClientError> {
+        self.get_bonding_curve_account_with_timeout(mint, None)
+            .await
+    }
+
+    /// Gets a token's bonding curve account data, giving up after a timeout
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - Public key of the token mint
+    /// * `timeout` - Optional maximum duration of the request. If None, the RPC client's
+    ///   default timeout applies
+    ///
+    /// # Returns
+    ///
+    /// Returns the deserialized BondingCurveAccount if successful, or a ClientError if the operation fails
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClientError::Timeout` if the timeout elapses, or any error of
+    /// `get_bonding_curve_account`
+    pub async fn get_bonding_curve_account_with_timeout(
+        &self,
+        mint: &Pubkey,
+        timeout: Option<Duration>,
     ) -> Result<accounts::BondingCurveAccount, error::ClientError> {
         let bonding_curve_pda =
             Self::get_bonding_curve_pda(mint).ok_or(error::ClientError::BondingCurveNotFound)?;
 
+        #[cfg(feature = "tracing")]
+        let started = std::time::Instant::now();
+        let account = Self::with_timeout(timeout, async {
+            self.rpc
+                .get_account(&bonding_curve_pda)
+                .await
+                .map_err(error::ClientError::from)
+        })
+        .await;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            mint = %mint,
+            account = %bonding_curve_pda,
+            latency_ms = started.elapsed().as_millis() as u64,
+            ok = account.is_ok(),
+            "fetched bonding curve account"
+        );
+        let account = account?;
+
+        accounts::try_from_versioned_account_data(&account.data)
+    }
+
+    /// Gets a token's Metaplex metadata, optionally with its off-chain metadata JSON
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - Public key of the token mint
+    /// * `fetch_offchain` - Whether to also fetch and parse the JSON at the metadata URI,
+    ///   which holds the token's image and social links
+    ///
+    /// # Returns
+    ///
+    /// Returns the deserialized metadata account and, if requested, the off-chain metadata
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The metadata account cannot be found on-chain
+    /// - The account data cannot be properly deserialized
+    /// - The off-chain metadata was requested and cannot be fetched or parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use pumpfun::{PumpFun, common::types::{Cluster, PriorityFee}};
+    /// # use solana_sdk::{commitment_config::CommitmentConfig, signature::Keypair, pubkey};
+    /// # use std::sync::Arc;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let payer = Arc::new(Keypair::new());
+    /// # let commitment = CommitmentConfig::confirmed();
+    /// # let cluster = Cluster::devnet(commitment, PriorityFee::default());
+    /// # let client = PumpFun::new(payer, cluster);
+    /// let mint = pubkey!("TokenM1ntPubk3yXXXXXXXXXXXXXXXXXXXXXXXXXXXX");
+    /// let metadata = client.get_token_metadata(&mint, true).await?;
+    /// println!("{} ({})", metadata.account.name(), metadata.account.symbol());
+    /// if let Some(offchain) = metadata.offchain {
+    ///     println!("Twitter: {:?}", offchain.twitter);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_token_metadata(
+        &self,
+        mint: &Pubkey,
+        fetch_offchain: bool,
+    ) -> Result<common::types::TokenMetadataInfo, error::ClientError> {
         let account = self
             .rpc
-            .get_account(&bonding_curve_pda)
+            .get_account(&Self::get_metadata_pda(mint))
             .await
-            .map_err(error::ClientError::SolanaClientError)?;
+            .map_err(error::ClientError::from)?;
+        let account = solana_sdk::borsh1::try_from_slice_unchecked::<accounts::MetadataAccount>(
+            &account.data,
+        )
+        .map_err(error::ClientError::BorshError)?;
 
-        solana_sdk::borsh1::try_from_slice_unchecked::<accounts::BondingCurveAccount>(&account.data)
-            .map_err(error::ClientError::BorshError)
+        let offchain = match fetch_offchain {
+            true => Some(utils::fetch_offchain_metadata(account.uri()).await?),
+            false => None,
+        };
+        Ok(common::types::TokenMetadataInfo { account, offchain })
     }
 
     /// Gets the creator vault address (for claiming pump creator fees)
@@ -1214,18 +2580,240 @@ impl PumpFun {
         pda.map(|pubkey| pubkey.0)
     }
 
-    /// Returns the PDA of a user volume accumulator account.
+    /// Gets the Program Derived Address (PDA) for a user's volume accumulator
+    ///
+    /// Derives the account that tracks a user's trading volume using the program ID, a
+    /// constant seed, and the user's address.
     ///
     /// # Arguments
-    /// * `user` - Public key of the user.
+    ///
+    /// * `user` - Public key of the user
     ///
     /// # Returns
-    /// PDA of the corresponding user volume accumulator account.
+    ///
+    /// Returns the PDA public key derived from the USER_VOLUME_ACCUMULATOR_SEED
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pumpfun::PumpFun;
+    /// # use solana_sdk::pubkey::Pubkey;
+    /// #
+    /// let user = Pubkey::new_unique();
+    /// let accumulator: Pubkey = PumpFun::get_user_volume_accumulator_pda(&user);
+    /// println!("User volume accumulator: {}", accumulator);
+    /// ```
     pub fn get_user_volume_accumulator_pda(user: &Pubkey) -> Pubkey {
         let (user_volume_accumulator, _bump) = Pubkey::find_program_address(
-            &[b"user_volume_accumulator", user.as_ref()],
+            &[
+                constants::seeds::USER_VOLUME_ACCUMULATOR_SEED,
+                user.as_ref(),
+            ],
             &constants::accounts::PUMPFUN,
         );
         user_volume_accumulator
     }
+
+    /// Gets the Program Derived Address (PDA) for the global volume accumulator
+    ///
+    /// Derives the account that tracks the program-wide trading volume using the program ID
+    /// and a constant seed.
+    ///
+    /// # Returns
+    ///
+    /// Returns the PDA public key derived from the GLOBAL_VOLUME_ACCUMULATOR_SEED, equal to
+    /// `constants::accounts::GLOBAL_VOLUME_ACCUMULATOR`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pumpfun::{constants, PumpFun};
+    /// #
+    /// let accumulator = PumpFun::get_global_volume_accumulator_pda();
+    /// assert_eq!(accumulator, constants::accounts::GLOBAL_VOLUME_ACCUMULATOR);
+    /// ```
+    pub fn get_global_volume_accumulator_pda() -> Pubkey {
+        Pubkey::find_program_address(
+            &[constants::seeds::GLOBAL_VOLUME_ACCUMULATOR_SEED],
+            &constants::accounts::PUMPFUN,
+        )
+        .0
+    }
+
+    /// Gets the Program Derived Address (PDA) for the fee configuration account
+    ///
+    /// Derives the fee configuration of the Pump.fun program, which is owned by the fee
+    /// program, using the fee program ID, a constant seed, and the Pump.fun program ID.
+    ///
+    /// # Returns
+    ///
+    /// Returns the PDA public key derived from the FEE_CONFIG_SEED, equal to
+    /// `constants::accounts::FEE_CONFIG`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pumpfun::{constants, PumpFun};
+    /// #
+    /// let fee_config = PumpFun::get_fee_config_pda();
+    /// assert_eq!(fee_config, constants::accounts::FEE_CONFIG);
+    /// ```
+    pub fn get_fee_config_pda() -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                constants::seeds::FEE_CONFIG_SEED,
+                constants::accounts::PUMPFUN.as_ref(),
+            ],
+            &constants::accounts::FEE_CONFIG_PROGRAM,
+        )
+        .0
+    }
+
+    /// Fetches the fee configuration, with the flat fees and market cap fee tiers
+    ///
+    /// # Returns
+    ///
+    /// Returns the deserialized fee configuration if successful, or a ClientError if the
+    /// operation fails
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the account cannot be fetched or deserialized, e.g. on deployments
+    /// predating the fee program
+    pub async fn get_fee_config_account(
+        &self,
+    ) -> Result<accounts::FeeConfigAccount, error::ClientError> {
+        let account = self
+            .rpc
+            .get_account(&Self::get_fee_config_pda())
+            .await
+            .map_err(error::ClientError::from)?;
+        accounts::try_from_account_data(
+            &account.data,
+            &constants::discriminators::FEE_CONFIG_ACCOUNT,
+        )
+    }
+
+    /// Fetches the global volume accumulator, with the program's daily trading volume
+    ///
+    /// # Returns
+    ///
+    /// Returns the deserialized global volume accumulator if successful, or a ClientError if
+    /// the operation fails
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the account cannot be fetched or deserialized
+    pub async fn get_global_volume_accumulator(
+        &self,
+    ) -> Result<accounts::GlobalVolumeAccumulator, error::ClientError> {
+        let account = self
+            .rpc
+            .get_account(&Self::get_global_volume_accumulator_pda())
+            .await
+            .map_err(error::ClientError::from)?;
+        accounts::try_from_account_data(
+            &account.data,
+            &constants::discriminators::GLOBAL_VOLUME_ACCUMULATOR_ACCOUNT,
+        )
+    }
+
+    /// Fetches the volume accumulator of a user
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - Public key of the user
+    ///
+    /// # Returns
+    ///
+    /// Returns the deserialized user volume accumulator, or None if the user has never
+    /// traded with volume tracking enabled
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the account cannot be fetched or deserialized
+    pub async fn get_user_volume_accumulator(
+        &self,
+        user: &Pubkey,
+    ) -> Result<Option<accounts::UserVolumeAccumulator>, error::ClientError> {
+        let account = self
+            .rpc
+            .get_account_with_commitment(
+                &Self::get_user_volume_accumulator_pda(user),
+                self.cluster.commitment,
+            )
+            .await
+            .map_err(error::ClientError::from)?
+            .value;
+        account
+            .map(|account| {
+                accounts::try_from_account_data(
+                    &account.data,
+                    &constants::discriminators::USER_VOLUME_ACCUMULATOR_ACCOUNT,
+                )
+            })
+            .transpose()
+    }
+
+    /// Returns the fees charged to a user by the client's fee schedule
+    ///
+    /// The user's volume accumulator is only fetched when the schedule has tiers.
+    async fn get_fees(
+        &self,
+        user: &Pubkey,
+        global_account: &accounts::GlobalAccount,
+    ) -> Result<utils::fees::Fees, error::ClientError> {
+        let user_volume_accumulator = if self.fee_schedule.is_empty() {
+            None
+        } else {
+            self.get_user_volume_accumulator(user).await?
+        };
+        Ok(utils::fees::compute_fee_tier(
+            user_volume_accumulator.as_ref(),
+            global_account,
+            &self.fee_schedule,
+        ))
+    }
+
+    /// Runs a future, failing with `ClientError::Timeout` if it does not finish in time
+    async fn with_timeout<T>(
+        timeout: Option<Duration>,
+        future: impl Future<Output = Result<T, error::ClientError>>,
+    ) -> Result<T, error::ClientError> {
+        match timeout {
+            Some(duration) => tokio::time::timeout(duration, future)
+                .await
+                .map_err(|_| error::ClientError::Timeout(duration))?,
+            None => future.await,
+        }
+    }
+}
+
+/// Reads the address, mint, and balance of a JSON-parsed SPL token account
+fn parse_token_account(keyed: &RpcKeyedAccount) -> Option<(Pubkey, Pubkey, u64)> {
+    let account = serde_json::to_value(&keyed.account).ok()?;
+    let info = &account["data"]["parsed"]["info"];
+    Some((
+        keyed.pubkey.parse().ok()?,
+        info["mint"].as_str()?.parse().ok()?,
+        info["tokenAmount"]["amount"].as_str()?.parse().ok()?,
+    ))
+}
+
+/// Instructions of a buy or sell, with the trade they make
+struct TradeInstructions {
+    instructions: Vec<Instruction>,
+    /// Index of the buy or sell instruction
+    index: usize,
+    trade: RiskTrade,
+}
+
+impl TradeInstructions {
+    /// Returns the instructions, holding the trade in the risk manager until they are sent
+    fn held_by(self, risk: Option<&RiskManager>) -> Vec<Instruction> {
+        if let (Some(risk), Some(instruction)) = (risk, self.instructions.get(self.index)) {
+            risk.hold(instruction, self.trade);
+        }
+        self.instructions
+    }
 }
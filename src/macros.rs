@@ -0,0 +1,14 @@
+//! Internal logging macros
+//!
+//! With the `tracing` feature, warnings are emitted as `tracing` events so they reach the
+//! application's subscriber. Without it, they are printed to stderr as before.
+
+/// Logs a warning that the SDK recovered from
+macro_rules! log_warn {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        tracing::warn!($($arg)+);
+        #[cfg(not(feature = "tracing"))]
+        eprintln!("Warning: {}", format_args!($($arg)+));
+    }};
+}
@@ -0,0 +1,414 @@
+//! Recurring buys and sells of a fixed SOL amount
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::BuildHasher,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use tokio::{
+    sync::{mpsc, watch},
+    task::JoinHandle,
+};
+
+use crate::{
+    accounts::BondingCurveAccount,
+    common::{trades::TradeSide, types::PriorityFee},
+    sender::TransactionSender,
+    PumpFun,
+};
+
+/// Schedule and guards of a DCA plan
+///
+/// # Fields
+///
+/// * `side` - Whether the plan buys or sells
+/// * `amount_sol` - Lamports bought per execution, or lamports worth of tokens sold per
+///   execution, quoted from the bonding curve before fees
+/// * `interval` - Time between executions
+/// * `jitter` - Maximum random delay added to each interval
+/// * `price_limit` - Executions are skipped while the price is above this limit for buys,
+///   or below it for sells, in lamports per token base unit, if set
+/// * `executions` - Number of executions after which the plan ends, counting skipped
+///   executions. If None, runs until stopped or the bonding curve completes
+/// * `slippage_basis_points` - Maximum acceptable slippage in basis points. If None, uses
+///   the client's default slippage
+/// * `priority_fee` - Priority fee of the trades. If None, uses the cluster's priority fee
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DcaConfig {
+    pub side: TradeSide,
+    pub amount_sol: u64,
+    pub interval: Duration,
+    pub jitter: Duration,
+    pub price_limit: Option<f64>,
+    pub executions: Option<u32>,
+    pub slippage_basis_points: Option<u64>,
+    pub priority_fee: Option<PriorityFee>,
+}
+
+impl DcaConfig {
+    /// Creates an unbounded plan without jitter or price limit
+    pub fn new(side: TradeSide, amount_sol: u64, interval: Duration) -> Self {
+        Self {
+            side,
+            amount_sol,
+            interval,
+            jitter: Duration::ZERO,
+            price_limit: None,
+            executions: None,
+            slippage_basis_points: None,
+            priority_fee: None,
+        }
+    }
+
+    /// Returns whether the price limit allows trading at a price
+    pub fn allows(&self, price: f64) -> bool {
+        self.price_limit.is_none_or(|limit| match self.side {
+            TradeSide::Buy => price <= limit,
+            TradeSide::Sell => price >= limit,
+        })
+    }
+
+    /// Returns the delay before the next execution, including a random jitter
+    fn next_delay(&self) -> Duration {
+        if self.jitter.is_zero() {
+            return self.interval;
+        }
+        let random = RandomState::new().hash_one(std::time::SystemTime::now());
+        let jitter = self.jitter.as_nanos() as u64;
+        self.interval + Duration::from_nanos(random % (jitter + 1))
+    }
+}
+
+/// Outcome of a scheduled execution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DcaOutcome {
+    /// The trade confirmed with the given signature
+    Executed(Signature),
+    /// The price was outside the price limit
+    Skipped,
+    /// The trade failed for the given reason
+    Failed(String),
+}
+
+/// Scheduled execution of a DCA plan
+///
+/// # Fields
+///
+/// * `index` - Number of the execution, starting at zero
+/// * `mint` - Mint of the token
+/// * `price` - Price of the bonding curve, in lamports per token base unit, if it was read
+/// * `amount` - Lamports spent for buys, tokens sold for sells
+/// * `outcome` - Outcome of the execution
+#[derive(Debug, Clone, PartialEq)]
+pub struct DcaExecution {
+    pub index: u32,
+    pub mint: Pubkey,
+    pub price: Option<f64>,
+    pub amount: u64,
+    pub outcome: DcaOutcome,
+}
+
+/// Handle of a running DCA plan, streaming its executions
+///
+/// The stream ends once the plan has run its executions or the bonding curve completes.
+/// Dropping the handle stops the plan.
+pub struct DcaHandle {
+    receiver: mpsc::Receiver<DcaExecution>,
+    paused: watch::Sender<bool>,
+    task: JoinHandle<()>,
+}
+
+impl DcaHandle {
+    /// Pauses the plan. The scheduled execution due while paused runs once it is resumed
+    pub fn pause(&self) {
+        self.paused.send_replace(true);
+    }
+
+    /// Resumes a paused plan
+    pub fn resume(&self) {
+        self.paused.send_replace(false);
+    }
+
+    /// Returns whether the plan is paused
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+}
+
+impl Stream for DcaHandle {
+    type Item = DcaExecution;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl Drop for DcaHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Runs recurring buys or sells of a token on its bonding curve
+///
+/// The first execution runs as soon as the plan starts. Each execution reads the bonding
+/// curve, skips the trade if the price is outside the price limit, and otherwise trades
+/// `amount_sol` through the client's payer. Each execution uses the configuration current
+/// when it runs, so a plan following a `HotConfig` picks up updates without restarting.
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use pumpfun::{
+///     common::{trades::TradeSide, types::{Cluster, PriorityFee}},
+///     orders::{DcaConfig, DcaScheduler},
+///     PumpFun,
+/// };
+/// use solana_sdk::{commitment_config::CommitmentConfig, native_token::sol_to_lamports, pubkey, signature::Keypair};
+/// use std::{sync::Arc, time::Duration};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+/// let client = Arc::new(PumpFun::new(Arc::new(Keypair::new()), cluster));
+/// let mint = pubkey!("TokenM1ntPubk3yXXXXXXXXXXXXXXXXXXXXXXXXXXXX");
+///
+/// let config = DcaConfig {
+///     jitter: Duration::from_secs(30),
+///     price_limit: Some(0.000_000_05),
+///     executions: Some(24),
+///     ..DcaConfig::new(TradeSide::Buy, sol_to_lamports(0.05), Duration::from_secs(3600))
+/// };
+/// let mut plan = DcaScheduler::new(client, mint, config).start();
+///
+/// while let Some(execution) = plan.next().await {
+///     println!("Execution {}: {:?}", execution.index, execution.outcome);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct DcaScheduler {
+    client: Arc<PumpFun>,
+    mint: Pubkey,
+    config: watch::Receiver<DcaConfig>,
+    sender: Option<Arc<dyn TransactionSender>>,
+}
+
+impl DcaScheduler {
+    /// Creates a DCA plan trading with a client's payer
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Client whose payer signs and pays for the trades
+    /// * `mint` - Mint of the token
+    /// * `config` - Schedule and guards of the plan
+    pub fn new(client: Arc<PumpFun>, mint: Pubkey, config: DcaConfig) -> Self {
+        Self {
+            client,
+            mint,
+            config: watch::channel(config).1,
+            sender: None,
+        }
+    }
+
+    /// Reads the configuration from a watch channel, such as `HotConfig::subscribe`,
+    /// instead of the configuration passed to `new`
+    pub fn with_config_updates(mut self, config: watch::Receiver<DcaConfig>) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Submits trades through the given sender instead of the client's sender
+    pub fn with_sender(mut self, sender: Arc<dyn TransactionSender>) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// Starts the plan
+    pub fn start(self) -> DcaHandle {
+        let (execution_tx, receiver) = mpsc::channel(100);
+        let (paused, mut paused_rx) = watch::channel(false);
+        let task = tokio::spawn(async move {
+            let mut index = 0;
+            while self
+                .config()
+                .executions
+                .is_none_or(|executions| index < executions)
+            {
+                if index > 0 {
+                    tokio::time::sleep(self.config().next_delay()).await;
+                }
+                if paused_rx.wait_for(|paused| !paused).await.is_err() {
+                    return;
+                }
+                // The plan may have been shortened while waiting
+                let config = self.config();
+                if config
+                    .executions
+                    .is_some_and(|executions| index >= executions)
+                {
+                    return;
+                }
+                let Some(execution) = self.execute(&config, index).await else {
+                    return;
+                };
+                if execution_tx.send(execution).await.is_err() {
+                    return;
+                }
+                index += 1;
+            }
+        });
+        DcaHandle {
+            receiver,
+            paused,
+            task,
+        }
+    }
+
+    /// Returns the current configuration of the plan
+    fn config(&self) -> DcaConfig {
+        *self.config.borrow()
+    }
+
+    /// Runs an execution, returning None once the bonding curve has completed
+    async fn execute(&self, config: &DcaConfig, index: u32) -> Option<DcaExecution> {
+        let mut execution = DcaExecution {
+            index,
+            mint: self.mint,
+            price: None,
+            amount: 0,
+            outcome: DcaOutcome::Skipped,
+        };
+        let curve = match self.client.get_bonding_curve_account(&self.mint).await {
+            Ok(curve) => curve,
+            Err(err) => {
+                execution.outcome = DcaOutcome::Failed(err.to_string());
+                return Some(execution);
+            }
+        };
+        if curve.complete {
+            return None;
+        }
+        let price = curve.virtual_sol_reserves as f64 / curve.virtual_token_reserves.max(1) as f64;
+        execution.price = Some(price);
+        if !config.allows(price) {
+            return Some(execution);
+        }
+        execution.amount = match config.side {
+            TradeSide::Buy => config.amount_sol,
+            TradeSide::Sell => sell_amount(&curve, config.amount_sol),
+        };
+        execution.outcome = match self.trade(config, execution.amount).await {
+            Ok(signature) => DcaOutcome::Executed(signature),
+            Err(err) => DcaOutcome::Failed(err),
+        };
+        Some(execution)
+    }
+
+    async fn trade(&self, config: &DcaConfig, amount: u64) -> Result<Signature, String> {
+        let priority_fee = config
+            .priority_fee
+            .unwrap_or(self.client.cluster.priority_fee);
+        let mut instructions = PumpFun::get_priority_fee_instructions(&priority_fee);
+        let slippage = config.slippage_basis_points;
+        let trade = match config.side {
+            TradeSide::Buy => {
+                self.client
+                    .get_buy_instructions(self.mint, amount, None, slippage)
+                    .await
+            }
+            TradeSide::Sell => {
+                self.client
+                    .get_sell_instructions(self.mint, Some(amount), slippage)
+                    .await
+            }
+        }
+        .map_err(|err| err.to_string())?;
+        instructions.extend(trade);
+        self.client
+            .send_instructions(&instructions, None, self.sender.as_deref())
+            .await
+            .map_err(|err| err.to_string())
+    }
+}
+
+/// Returns the tokens worth `amount_sol` lamports on a bonding curve, before fees
+fn sell_amount(curve: &BondingCurveAccount, amount_sol: u64) -> u64 {
+    curve.get_buy_price(amount_sol).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{config::HotConfig, types::Cluster};
+    use serde_json::json;
+    use solana_sdk::{commitment_config::CommitmentConfig, signature::Keypair};
+
+    #[test]
+    fn test_dca_config() {
+        let config = DcaConfig {
+            price_limit: Some(2.0),
+            jitter: Duration::from_millis(10),
+            ..DcaConfig::new(TradeSide::Buy, 1_000, Duration::from_secs(1))
+        };
+        assert!(config.allows(1.5));
+        assert!(!config.allows(2.5));
+        let sell = DcaConfig {
+            side: TradeSide::Sell,
+            ..config
+        };
+        assert!(sell.allows(2.5));
+        assert!(!sell.allows(1.5));
+
+        for _ in 0..10 {
+            let delay = config.next_delay();
+            assert!(delay >= Duration::from_secs(1) && delay <= Duration::from_millis(1_010));
+        }
+    }
+
+    #[test]
+    fn test_sell_amount() {
+        let curve = BondingCurveAccount::new(
+            0,
+            1_000_000,
+            1_000,
+            800_000,
+            0,
+            1_000_000,
+            false,
+            Pubkey::new_unique(),
+        );
+        // Selling the tokens worth 1 lamport at the spot price of 1000 tokens per lamport
+        assert_eq!(sell_amount(&curve, 1), 999);
+    }
+
+    fn client() -> Arc<PumpFun> {
+        let cluster = Cluster::localnet(CommitmentConfig::processed(), PriorityFee::default());
+        Arc::new(PumpFun::new(Arc::new(Keypair::new()), cluster))
+    }
+
+    #[test]
+    fn test_config_updates() {
+        let config = DcaConfig {
+            price_limit: Some(2.0),
+            ..DcaConfig::new(TradeSide::Buy, 1_000, Duration::from_secs(1))
+        };
+        let hot = HotConfig::new(config);
+        let scheduler = DcaScheduler::new(client(), Pubkey::new_unique(), config)
+            .with_config_updates(hot.subscribe());
+        assert!(!scheduler.config().allows(2.5));
+
+        hot.apply_patch(json!({ "price_limit": 3.0, "executions": 5 }))
+            .unwrap();
+        let config = scheduler.config();
+        assert!(config.allows(2.5));
+        assert_eq!(config.executions, Some(5));
+    }
+}
@@ -0,0 +1,459 @@
+//! Limit orders executed on the bonding curve
+//!
+//! `LimitOrderBook` holds buy orders executing once a token's price falls to a limit and
+//! sell orders executing once it rises to one. Prices are taken from the virtual reserves
+//! of every `TradeEvent` of the program, so an order triggers on the first trade crossing
+//! its limit. Orders are executed with the client's instruction builders and submitted with
+//! `PumpFun::send_instructions`.
+//!
+//! # Persistence
+//!
+//! An `OrderStore` receives every placed and removed order and returns the open orders
+//! when the book is restored, so orders survive restarts of the process.
+//!
+//! # Scheduled orders
+//!
+//! `DcaScheduler` buys or sells a fixed SOL amount of a token at a regular interval, with
+//! jitter, a price limit, and pause and resume controls. `execute_twap` splits a large
+//! order into slices spread over time, each capped by its price impact.
+
+mod dca;
+mod twap;
+
+pub use dca::*;
+pub use twap::*;
+
+use std::{
+    collections::BTreeMap,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+};
+
+use async_trait::async_trait;
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use tokio::sync::mpsc;
+
+use crate::{
+    common::{
+        stream::{spawn_subscription, EventFilter, EventKind, PumpFunEvent, Subscription},
+        trades::TradeSide,
+        types::PriorityFee,
+    },
+    error,
+    sender::TransactionSender,
+    PumpFun,
+};
+
+/// Order waiting for a token's price to reach a limit
+///
+/// # Fields
+///
+/// * `id` - Identifier assigned by the order book
+/// * `mint` - Mint of the token
+/// * `side` - Buys execute once the price is at or below the limit, sells once it is at or
+///   above it
+/// * `amount` - Lamports to spend for buys, tokens to sell for sells
+/// * `limit_price` - Limit price in lamports per token base unit
+/// * `slippage_basis_points` - Maximum acceptable slippage in basis points. If None, uses
+///   the client's default slippage
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LimitOrder {
+    pub id: u64,
+    pub mint: Pubkey,
+    pub side: TradeSide,
+    pub amount: u64,
+    pub limit_price: f64,
+    pub slippage_basis_points: Option<u64>,
+}
+
+impl LimitOrder {
+    /// Returns whether the order executes at a price
+    pub fn is_triggered(&self, price: f64) -> bool {
+        match self.side {
+            TradeSide::Buy => price <= self.limit_price,
+            TradeSide::Sell => price >= self.limit_price,
+        }
+    }
+}
+
+/// Persistence of the open orders of a book
+///
+/// Errors are returned to the caller placing or cancelling the order; an order executed by
+/// the running book whose removal fails stays in the store and is restored again.
+#[async_trait]
+pub trait OrderStore: Send + Sync {
+    /// Saves a placed order
+    async fn save(&self, order: &LimitOrder) -> Result<(), error::ClientError>;
+
+    /// Removes an executed or cancelled order
+    async fn remove(&self, id: u64) -> Result<(), error::ClientError>;
+
+    /// Loads the open orders
+    async fn load(&self) -> Result<Vec<LimitOrder>, error::ClientError>;
+}
+
+/// Execution of a triggered order
+///
+/// # Fields
+///
+/// * `order` - Executed order
+/// * `price` - Price that triggered the order, in lamports per token base unit
+/// * `slot` - Slot of the trade that triggered the order
+/// * `result` - Signature of the confirmed trade, or why it failed
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderExecution {
+    pub order: LimitOrder,
+    pub price: f64,
+    pub slot: u64,
+    pub result: Result<Signature, String>,
+}
+
+/// Stream of executions of a running order book
+///
+/// The book stops watching prices when the stream is dropped; trades already submitted
+/// still complete.
+pub struct OrderStream {
+    receiver: mpsc::Receiver<OrderExecution>,
+    _subscription: Subscription,
+}
+
+impl Stream for OrderStream {
+    type Item = OrderExecution;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// Book of limit orders of the client's payer
+///
+/// An order is removed from the book when it triggers and executed once, whether the
+/// trade succeeds or fails, so a trade that lands despite an error is never repeated.
+/// Failed orders can be placed again.
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use pumpfun::{
+///     common::{trades::TradeSide, types::{Cluster, PriorityFee}},
+///     orders::LimitOrderBook,
+///     PumpFun,
+/// };
+/// use solana_sdk::{commitment_config::CommitmentConfig, native_token::sol_to_lamports, pubkey, signature::Keypair};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+/// let client = Arc::new(PumpFun::new(Arc::new(Keypair::new()), cluster));
+/// let mint = pubkey!("TokenM1ntPubk3yXXXXXXXXXXXXXXXXXXXXXXXXXXXX");
+///
+/// let book = Arc::new(LimitOrderBook::new(client));
+/// book.place(mint, TradeSide::Buy, sol_to_lamports(0.1), 0.000_000_025, None).await?;
+///
+/// let mut executions = book.clone().start().await?;
+/// while let Some(execution) = executions.next().await {
+///     println!("Order {} executed: {:?}", execution.order.id, execution.result);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct LimitOrderBook {
+    client: Arc<PumpFun>,
+    store: Option<Arc<dyn OrderStore>>,
+    sender: Option<Arc<dyn TransactionSender>>,
+    priority_fee: Option<PriorityFee>,
+    orders: Mutex<BTreeMap<u64, LimitOrder>>,
+    next_id: AtomicU64,
+}
+
+impl LimitOrderBook {
+    /// Creates an empty order book trading with a client's payer
+    pub fn new(client: Arc<PumpFun>) -> Self {
+        Self {
+            client,
+            store: None,
+            sender: None,
+            priority_fee: None,
+            orders: Mutex::new(BTreeMap::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Persists orders in the given store
+    pub fn with_store(mut self, store: Arc<dyn OrderStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Submits trades through the given sender instead of the client's sender
+    pub fn with_sender(mut self, sender: Arc<dyn TransactionSender>) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// Sets the priority fee of executed orders. If not set, uses the cluster's priority fee
+    pub fn with_priority_fee(mut self, priority_fee: PriorityFee) -> Self {
+        self.priority_fee = Some(priority_fee);
+        self
+    }
+
+    /// Loads the open orders from the store into the book
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of restored orders, or a ClientError if the store fails
+    pub async fn restore(&self) -> Result<usize, error::ClientError> {
+        let Some(store) = &self.store else {
+            return Ok(0);
+        };
+        let restored = store.load().await?;
+        let count = restored.len();
+        let mut orders = self.lock_orders();
+        for order in restored {
+            self.next_id.fetch_max(order.id + 1, Ordering::Relaxed);
+            orders.insert(order.id, order);
+        }
+        Ok(count)
+    }
+
+    /// Places an order
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - Mint of the token
+    /// * `side` - Whether to buy at or below the limit, or sell at or above it
+    /// * `amount` - Lamports to spend for buys, tokens to sell for sells
+    /// * `limit_price` - Limit price in lamports per token base unit
+    /// * `slippage_basis_points` - Maximum acceptable slippage in basis points. If None,
+    ///   uses the client's default slippage
+    ///
+    /// # Returns
+    ///
+    /// Returns the identifier of the order, or a ClientError if the store fails to save it
+    pub async fn place(
+        &self,
+        mint: Pubkey,
+        side: TradeSide,
+        amount: u64,
+        limit_price: f64,
+        slippage_basis_points: Option<u64>,
+    ) -> Result<u64, error::ClientError> {
+        let order = LimitOrder {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            mint,
+            side,
+            amount,
+            limit_price,
+            slippage_basis_points,
+        };
+        if let Some(store) = &self.store {
+            store.save(&order).await?;
+        }
+        self.lock_orders().insert(order.id, order);
+        Ok(order.id)
+    }
+
+    /// Cancels an order
+    ///
+    /// # Returns
+    ///
+    /// Returns whether the order was open, or a ClientError if the store fails to remove it
+    pub async fn cancel(&self, id: u64) -> Result<bool, error::ClientError> {
+        if self.lock_orders().remove(&id).is_none() {
+            return Ok(false);
+        }
+        if let Some(store) = &self.store {
+            store.remove(id).await?;
+        }
+        Ok(true)
+    }
+
+    /// Returns the open orders, ordered by identifier
+    pub fn orders(&self) -> Vec<LimitOrder> {
+        self.lock_orders().values().copied().collect()
+    }
+
+    /// Starts watching prices and executing triggered orders
+    ///
+    /// # Returns
+    ///
+    /// Returns an `OrderStream` of executions, or a ClientError if the subscription cannot
+    /// be established
+    pub async fn start(self: Arc<Self>) -> Result<OrderStream, error::ClientError> {
+        let (event_tx, mut event_rx) = mpsc::channel(1000);
+        let subscription = spawn_subscription(
+            self.client.cluster.clone(),
+            None,
+            None,
+            EventFilter::new().kinds([EventKind::Trade]),
+            event_tx,
+        )
+        .await?;
+
+        let (execution_tx, receiver) = mpsc::channel(100);
+        tokio::spawn(async move {
+            while let Some((context, event, _)) = event_rx.recv().await {
+                let Ok(PumpFunEvent::Trade(trade)) = event else {
+                    continue;
+                };
+                if trade.virtual_token_reserves == 0 {
+                    continue;
+                }
+                let price = trade.virtual_sol_reserves as f64 / trade.virtual_token_reserves as f64;
+                for order in self.take_triggered(&trade.mint, price) {
+                    let book = self.clone();
+                    let execution_tx = execution_tx.clone();
+                    let slot = context.slot;
+                    tokio::spawn(async move {
+                        let result = book.execute(&order).await;
+                        if let Some(store) = &book.store {
+                            let _ = store.remove(order.id).await;
+                        }
+                        let _ = execution_tx
+                            .send(OrderExecution {
+                                order,
+                                price,
+                                slot,
+                                result,
+                            })
+                            .await;
+                    });
+                }
+            }
+        });
+
+        Ok(OrderStream {
+            receiver,
+            _subscription: subscription,
+        })
+    }
+
+    /// Removes and returns the orders of a mint triggered by a price
+    fn take_triggered(&self, mint: &Pubkey, price: f64) -> Vec<LimitOrder> {
+        let mut orders = self.lock_orders();
+        let triggered: Vec<LimitOrder> = orders
+            .values()
+            .filter(|order| order.mint == *mint && order.is_triggered(price))
+            .copied()
+            .collect();
+        for order in &triggered {
+            orders.remove(&order.id);
+        }
+        triggered
+    }
+
+    async fn execute(&self, order: &LimitOrder) -> Result<Signature, String> {
+        let priority_fee = self
+            .priority_fee
+            .unwrap_or(self.client.cluster.priority_fee);
+        let mut instructions = PumpFun::get_priority_fee_instructions(&priority_fee);
+        let trade = match order.side {
+            TradeSide::Buy => {
+                self.client
+                    .get_buy_instructions(
+                        order.mint,
+                        order.amount,
+                        None,
+                        order.slippage_basis_points,
+                    )
+                    .await
+            }
+            TradeSide::Sell => {
+                self.client
+                    .get_sell_instructions(
+                        order.mint,
+                        Some(order.amount),
+                        order.slippage_basis_points,
+                    )
+                    .await
+            }
+        }
+        .map_err(|err| err.to_string())?;
+        instructions.extend(trade);
+        self.client
+            .send_instructions(&instructions, None, self.sender.as_deref())
+            .await
+            .map_err(|err| err.to_string())
+    }
+
+    fn lock_orders(&self) -> std::sync::MutexGuard<'_, BTreeMap<u64, LimitOrder>> {
+        match self.orders.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::Cluster;
+    use solana_sdk::{commitment_config::CommitmentConfig, signature::Keypair};
+
+    struct MemoryStore(Mutex<Vec<LimitOrder>>);
+
+    #[async_trait]
+    impl OrderStore for MemoryStore {
+        async fn save(&self, order: &LimitOrder) -> Result<(), error::ClientError> {
+            self.0.lock().unwrap().push(*order);
+            Ok(())
+        }
+
+        async fn remove(&self, id: u64) -> Result<(), error::ClientError> {
+            self.0.lock().unwrap().retain(|order| order.id != id);
+            Ok(())
+        }
+
+        async fn load(&self) -> Result<Vec<LimitOrder>, error::ClientError> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+    }
+
+    fn client() -> Arc<PumpFun> {
+        let cluster = Cluster::localnet(CommitmentConfig::processed(), PriorityFee::default());
+        Arc::new(PumpFun::new(Arc::new(Keypair::new()), cluster))
+    }
+
+    #[tokio::test]
+    async fn test_order_book() {
+        let mint = Pubkey::new_unique();
+        let store = Arc::new(MemoryStore(Mutex::new(Vec::new())));
+        let book = LimitOrderBook::new(client()).with_store(store.clone());
+        let buy = book
+            .place(mint, TradeSide::Buy, 1_000, 2.0, None)
+            .await
+            .unwrap();
+        let sell = book
+            .place(mint, TradeSide::Sell, 1_000, 4.0, None)
+            .await
+            .unwrap();
+
+        assert!(book.take_triggered(&Pubkey::new_unique(), 1.0).is_empty());
+        assert!(book.take_triggered(&mint, 3.0).is_empty());
+        let triggered = book.take_triggered(&mint, 1.5);
+        assert_eq!(triggered.len(), 1);
+        assert_eq!(triggered[0].id, buy);
+        // Triggered orders leave the book and never execute twice
+        assert!(book.take_triggered(&mint, 1.5).is_empty());
+
+        // Orders survive a restart through the store, with fresh identifiers after them
+        let restored = LimitOrderBook::new(client()).with_store(store.clone());
+        assert_eq!(restored.restore().await.unwrap(), 2);
+        assert!(restored.cancel(buy).await.unwrap());
+        assert!(!restored.cancel(buy).await.unwrap());
+        assert_eq!(restored.orders().len(), 1);
+        assert_eq!(store.load().await.unwrap()[0].id, sell);
+        let next = restored
+            .place(mint, TradeSide::Sell, 1, 5.0, None)
+            .await
+            .unwrap();
+        assert!(next > sell);
+    }
+}
@@ -0,0 +1,240 @@
+//! Time-weighted execution of large orders
+
+use std::time::Duration;
+
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use tokio::time::Instant;
+
+use crate::{accounts::BondingCurveAccount, common::trades::TradeSide, error, PumpFun};
+
+/// Shortest time between two slices of a TWAP order
+const MIN_SLICE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Number of slices a TWAP order is split into when its duration allows it
+const TARGET_SLICES: u32 = 20;
+
+/// Trade of a single slice of a TWAP order
+///
+/// # Fields
+///
+/// * `amount_sol` - Lamports bought, or lamports worth of tokens sold, by the slice
+/// * `amount` - Lamports spent for buys, tokens sold for sells
+/// * `price` - Price of the bonding curve before the slice, in lamports per token base unit
+/// * `result` - Signature of the confirmed slice, or why it failed
+#[derive(Debug, Clone, PartialEq)]
+pub struct TwapSlice {
+    pub amount_sol: u64,
+    pub amount: u64,
+    pub price: f64,
+    pub result: Result<Signature, String>,
+}
+
+/// Outcome of a TWAP order
+///
+/// # Fields
+///
+/// * `filled_sol` - Lamports traded by confirmed slices
+/// * `remaining_sol` - Lamports left untraded when the order ended
+/// * `slices` - Slices submitted, in order
+#[derive(Debug, Clone, PartialEq)]
+pub struct TwapReport {
+    pub filled_sol: u64,
+    pub remaining_sol: u64,
+    pub slices: Vec<TwapSlice>,
+}
+
+/// Buys or sells a large amount of a token in slices spread over a duration
+///
+/// The order is split into up to 20 slices at least two seconds apart. Each slice reads the
+/// bonding curve and trades an equal share of the remaining amount over the remaining
+/// slices, capped so that the slice moves the price by at most `max_impact_bps`. Failed
+/// slices leave their amount to the following slices. The order ends after its last slice,
+/// when the amount is filled, or when the bonding curve completes, and any untraded amount
+/// is reported rather than forced through the curve.
+///
+/// # Arguments
+///
+/// * `client` - Client whose payer trades
+/// * `side` - Whether to buy or sell
+/// * `mint` - Mint of the token
+/// * `total_sol` - Lamports to buy, or lamports worth of tokens to sell
+/// * `duration` - Time over which the slices are spread
+/// * `max_impact_bps` - Maximum price impact of a single slice, in basis points
+///
+/// # Returns
+///
+/// Returns a `TwapReport` of the slices, or a ClientError if the bonding curve cannot be
+/// read before the first slice
+///
+/// # Examples
+///
+/// ```no_run
+/// use pumpfun::{
+///     common::{trades::TradeSide, types::{Cluster, PriorityFee}},
+///     orders::execute_twap,
+///     PumpFun,
+/// };
+/// use solana_sdk::{commitment_config::CommitmentConfig, native_token::sol_to_lamports, pubkey, signature::Keypair};
+/// use std::{sync::Arc, time::Duration};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+/// let client = PumpFun::new(Arc::new(Keypair::new()), cluster);
+/// let mint = pubkey!("TokenM1ntPubk3yXXXXXXXXXXXXXXXXXXXXXXXXXXXX");
+///
+/// // Buy 10 SOL over ten minutes, moving the price at most 1% per slice
+/// let report = execute_twap(
+///     &client,
+///     TradeSide::Buy,
+///     mint,
+///     sol_to_lamports(10.0),
+///     Duration::from_secs(600),
+///     100,
+/// )
+/// .await?;
+/// println!("Filled {} lamports, {} left", report.filled_sol, report.remaining_sol);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn execute_twap(
+    client: &PumpFun,
+    side: TradeSide,
+    mint: Pubkey,
+    total_sol: u64,
+    duration: Duration,
+    max_impact_bps: u64,
+) -> Result<TwapReport, error::ClientError> {
+    let slices = slice_count(duration);
+    let interval = duration / slices;
+    let start = Instant::now();
+    let mut report = TwapReport {
+        filled_sol: 0,
+        remaining_sol: total_sol,
+        slices: Vec::new(),
+    };
+
+    for index in 0..slices {
+        if report.remaining_sol == 0 {
+            break;
+        }
+        tokio::time::sleep_until(start + interval * index).await;
+        let curve = match client.get_bonding_curve_account(&mint).await {
+            Ok(curve) => curve,
+            Err(err) if index == 0 => return Err(err),
+            Err(_) => continue,
+        };
+        if curve.complete {
+            break;
+        }
+
+        let target = report.remaining_sol.div_ceil((slices - index) as u64);
+        let amount_sol = target.min(max_slice(&curve, side, max_impact_bps));
+        if amount_sol == 0 {
+            continue;
+        }
+        let amount = match side {
+            TradeSide::Buy => amount_sol,
+            TradeSide::Sell => tokens_for_sol(&curve, amount_sol),
+        };
+        let result = trade(client, side, mint, amount).await;
+        if result.is_ok() {
+            report.filled_sol += amount_sol;
+            report.remaining_sol -= amount_sol;
+        }
+        report.slices.push(TwapSlice {
+            amount_sol,
+            amount,
+            price: curve.virtual_sol_reserves as f64 / curve.virtual_token_reserves.max(1) as f64,
+            result,
+        });
+    }
+    Ok(report)
+}
+
+/// Returns the number of slices of an order spread over a duration
+fn slice_count(duration: Duration) -> u32 {
+    let slices = duration.as_secs() / MIN_SLICE_INTERVAL.as_secs();
+    slices.clamp(1, TARGET_SLICES as u64) as u32
+}
+
+/// Returns the largest slice in lamports moving the curve's price by at most `max_impact_bps`
+///
+/// On the constant-product curve, adding `x` lamports to the virtual SOL reserves `s`
+/// multiplies the price by `(1 + x / s)^2`, and removing them by `(1 - x / s)^2`.
+fn max_slice(curve: &BondingCurveAccount, side: TradeSide, max_impact_bps: u64) -> u64 {
+    let impact = max_impact_bps.min(10_000) as f64 / 10_000.0;
+    let fraction = match side {
+        TradeSide::Buy => (1.0 + impact).sqrt() - 1.0,
+        TradeSide::Sell => 1.0 - (1.0 - impact).sqrt(),
+    };
+    (curve.virtual_sol_reserves as f64 * fraction) as u64
+}
+
+/// Returns the tokens to sell on a curve to receive `amount_sol` lamports, before fees
+fn tokens_for_sol(curve: &BondingCurveAccount, amount_sol: u64) -> u64 {
+    let sol = curve.virtual_sol_reserves as u128;
+    let tokens = curve.virtual_token_reserves as u128;
+    if amount_sol as u128 >= sol {
+        return 0;
+    }
+    (sol * tokens / (sol - amount_sol as u128)).saturating_sub(tokens) as u64
+}
+
+async fn trade(
+    client: &PumpFun,
+    side: TradeSide,
+    mint: Pubkey,
+    amount: u64,
+) -> Result<Signature, String> {
+    let mut instructions = PumpFun::get_priority_fee_instructions(&client.cluster.priority_fee);
+    let trade = match side {
+        TradeSide::Buy => client.get_buy_instructions(mint, amount, None, None).await,
+        TradeSide::Sell => client.get_sell_instructions(mint, Some(amount), None).await,
+    }
+    .map_err(|err| err.to_string())?;
+    instructions.extend(trade);
+    client
+        .send_instructions(&instructions, None, None)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve() -> BondingCurveAccount {
+        BondingCurveAccount::new(
+            0,
+            1_000_000_000,
+            100_000,
+            800_000_000,
+            0,
+            1_000_000_000,
+            false,
+            Pubkey::new_unique(),
+        )
+    }
+
+    #[test]
+    fn test_slice_count() {
+        assert_eq!(slice_count(Duration::ZERO), 1);
+        assert_eq!(slice_count(Duration::from_secs(10)), 5);
+        assert_eq!(slice_count(Duration::from_secs(3600)), TARGET_SLICES);
+    }
+
+    #[test]
+    fn test_slice_sizing() {
+        let curve = curve();
+        // A 1% price move on 100000 lamports of virtual reserves takes about 500 lamports
+        let buy = max_slice(&curve, TradeSide::Buy, 100);
+        assert_eq!(buy, 498);
+        let sell = max_slice(&curve, TradeSide::Sell, 100);
+        assert_eq!(sell, 501);
+
+        // Selling the quoted tokens returns the requested SOL from the curve
+        let tokens = tokens_for_sol(&curve, 1_000);
+        assert_eq!(tokens, 10_101_010);
+        assert_eq!(tokens_for_sol(&curve, 100_000), 0);
+    }
+}
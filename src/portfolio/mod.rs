@@ -0,0 +1,457 @@
+//! Position and PnL bookkeeping for a trading wallet
+//!
+//! `PositionManager` records the fills of a wallet and keeps one position per mint with
+//! its token balance, cost basis, and realized PnL, valued in SOL and optionally USD.
+//!
+//! # Accounting
+//!
+//! Positions use average cost: buys add their SOL amount plus fees to the cost basis,
+//! and sells realize their proceeds after fees minus the average cost of the tokens sold.
+//! Unrealized PnL values the remaining tokens at the mint's mark price, which is updated
+//! from the reserves of every trade of the mint seen by the manager, or set explicitly.
+//! USD values use the SOL/USD rate current when the position is read.
+//!
+//! Fills can be recorded directly, from parsed `TradeEvent`s, or from the signature of a
+//! transaction sent by the client, which is fetched and parsed. `TpSlMonitor` exits a
+//! position automatically once its price reaches a take-profit or stop-loss threshold.
+
+mod tpsl;
+
+pub use tpsl::*;
+
+use std::{collections::HashMap, sync::Mutex};
+
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+
+use crate::{
+    common::{
+        stream::{fetch_transaction_events, PumpFunEvent, TradeEvent},
+        trades::TradeSide,
+    },
+    error,
+};
+
+/// Executed trade of the managed wallet
+///
+/// # Fields
+///
+/// * `mint` - Mint of the traded token
+/// * `side` - Whether tokens were bought or sold
+/// * `token_amount` - Tokens exchanged, in base units
+/// * `sol_amount` - Lamports exchanged, excluding fees
+/// * `fee` - Protocol and creator fees paid, in lamports
+/// * `timestamp` - Unix timestamp of the trade
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fill {
+    pub mint: Pubkey,
+    pub side: TradeSide,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+impl From<&TradeEvent> for Fill {
+    fn from(trade: &TradeEvent) -> Self {
+        Self {
+            mint: trade.mint,
+            side: if trade.is_buy {
+                TradeSide::Buy
+            } else {
+                TradeSide::Sell
+            },
+            token_amount: trade.token_amount,
+            sol_amount: trade.sol_amount,
+            fee: trade.fee.saturating_add(trade.creator_fee),
+            timestamp: trade.timestamp,
+        }
+    }
+}
+
+/// Snapshot of a position
+///
+/// # Fields
+///
+/// * `mint` - Mint of the token
+/// * `tokens` - Tokens held, in base units
+/// * `cost_basis` - Lamports paid for the tokens held, including fees
+/// * `average_entry` - Cost basis per token base unit, in lamports, or None without tokens
+/// * `realized_pnl` - Lamports realized by sells
+/// * `fees_paid` - Lamports paid in fees across all fills
+/// * `mark_price` - Latest price in lamports per token base unit, if known
+/// * `unrealized_pnl` - Value of the tokens held at the mark price minus their cost basis,
+///   in lamports, if the mark price is known
+/// * `realized_pnl_usd` - Realized PnL in USD, if a SOL/USD rate is set
+/// * `unrealized_pnl_usd` - Unrealized PnL in USD, if a SOL/USD rate and the mark price are known
+/// * `fills` - Number of recorded fills
+/// * `last_fill` - Unix timestamp of the latest fill
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    pub mint: Pubkey,
+    pub tokens: u64,
+    pub cost_basis: u64,
+    pub average_entry: Option<f64>,
+    pub realized_pnl: i64,
+    pub fees_paid: u64,
+    pub mark_price: Option<f64>,
+    pub unrealized_pnl: Option<f64>,
+    pub realized_pnl_usd: Option<f64>,
+    pub unrealized_pnl_usd: Option<f64>,
+    pub fills: u64,
+    pub last_fill: i64,
+}
+
+/// Position bookkeeping of a single mint
+#[derive(Debug, Clone, Default)]
+struct Book {
+    tokens: u64,
+    cost_basis: u64,
+    realized: i64,
+    fees: u64,
+    fills: u64,
+    last_fill: i64,
+}
+
+impl Book {
+    fn apply(&mut self, fill: &Fill) {
+        self.fees = self.fees.saturating_add(fill.fee);
+        self.fills += 1;
+        self.last_fill = self.last_fill.max(fill.timestamp);
+        match fill.side {
+            TradeSide::Buy => {
+                self.tokens = self.tokens.saturating_add(fill.token_amount);
+                self.cost_basis = self
+                    .cost_basis
+                    .saturating_add(fill.sol_amount.saturating_add(fill.fee));
+            }
+            TradeSide::Sell => {
+                // Tokens sold beyond the recorded balance were acquired at no recorded cost
+                let sold = fill.token_amount.min(self.tokens);
+                let cost = match self.tokens {
+                    0 => 0,
+                    tokens => (self.cost_basis as u128 * sold as u128 / tokens as u128) as u64,
+                };
+                let proceeds = fill.sol_amount.saturating_sub(fill.fee);
+                self.realized = self
+                    .realized
+                    .saturating_add(proceeds as i64)
+                    .saturating_sub(cost as i64);
+                self.tokens -= sold;
+                self.cost_basis -= cost;
+            }
+        }
+    }
+}
+
+/// Tracks the positions and PnL of a wallet
+///
+/// # Examples
+///
+/// ```
+/// # use pumpfun::{common::trades::TradeSide, portfolio::{Fill, PositionManager}};
+/// # use solana_sdk::pubkey::Pubkey;
+/// #
+/// let owner = Pubkey::new_unique();
+/// let mint = Pubkey::new_unique();
+/// let positions = PositionManager::new(owner);
+/// positions.set_sol_usd_price(Some(150.0));
+///
+/// positions.record_fill(Fill {
+///     mint,
+///     side: TradeSide::Buy,
+///     token_amount: 1_000_000,
+///     sol_amount: 10_000_000,
+///     fee: 100_000,
+///     timestamp: 1_700_000_000,
+/// });
+/// positions.set_price(&mint, 12.0);
+///
+/// let position = positions.position(&mint).unwrap();
+/// println!("Unrealized PnL: {:?} lamports", position.unrealized_pnl);
+/// ```
+pub struct PositionManager {
+    owner: Pubkey,
+    books: Mutex<HashMap<Pubkey, Book>>,
+    marks: Mutex<HashMap<Pubkey, f64>>,
+    sol_usd: Mutex<Option<f64>>,
+}
+
+impl PositionManager {
+    /// Creates a position manager for a wallet
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - Wallet whose trades are recorded from events and transactions
+    pub fn new(owner: Pubkey) -> Self {
+        Self {
+            owner,
+            books: Mutex::new(HashMap::new()),
+            marks: Mutex::new(HashMap::new()),
+            sol_usd: Mutex::new(None),
+        }
+    }
+
+    /// Records a fill of the managed wallet
+    pub fn record_fill(&self, fill: Fill) {
+        let mut books = match self.books.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        books.entry(fill.mint).or_default().apply(&fill);
+    }
+
+    /// Records a parsed program event
+    ///
+    /// Trades of the managed wallet are recorded as fills, and every trade updates the
+    /// mark price of its mint from the bonding curve's virtual reserves.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - Event received from the Pump.fun event stream or a parsed transaction
+    ///
+    /// # Returns
+    ///
+    /// Returns true if the event was recorded as a fill
+    pub fn record_event(&self, event: &PumpFunEvent) -> bool {
+        let PumpFunEvent::Trade(trade) = event else {
+            return false;
+        };
+        if trade.virtual_token_reserves > 0 {
+            self.set_price(
+                &trade.mint,
+                trade.virtual_sol_reserves as f64 / trade.virtual_token_reserves as f64,
+            );
+        }
+        if trade.user != self.owner {
+            return false;
+        }
+        self.record_fill(Fill::from(trade));
+        true
+    }
+
+    /// Fetches a confirmed transaction and records the trades of the managed wallet in it
+    ///
+    /// # Arguments
+    ///
+    /// * `rpc` - RPC client used to fetch the transaction
+    /// * `signature` - Signature of the transaction, e.g. as returned by `PumpFun::buy`
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of recorded fills, or a ClientError if the transaction cannot be
+    /// fetched
+    pub async fn record_transaction(
+        &self,
+        rpc: &RpcClient,
+        signature: &str,
+    ) -> Result<usize, error::ClientError> {
+        let events = fetch_transaction_events(rpc, signature).await?;
+        Ok(events
+            .iter()
+            .filter(|event| self.record_event(event))
+            .count())
+    }
+
+    /// Sets the mark price of a mint
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - Token mint
+    /// * `price` - Price in lamports per token base unit
+    pub fn set_price(&self, mint: &Pubkey, price: f64) {
+        let mut marks = match self.marks.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        marks.insert(*mint, price);
+    }
+
+    /// Sets the SOL/USD rate used to value positions in USD
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - USD per SOL, or None to stop valuing positions in USD
+    pub fn set_sol_usd_price(&self, price: Option<f64>) {
+        let mut sol_usd = match self.sol_usd.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        *sol_usd = price;
+    }
+
+    /// Gets the position of a mint
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - Token mint to query
+    ///
+    /// # Returns
+    ///
+    /// The position, including closed positions with realized PnL, or None if the mint
+    /// has no recorded fills
+    pub fn position(&self, mint: &Pubkey) -> Option<Position> {
+        let book = {
+            let books = match self.books.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            books.get(mint)?.clone()
+        };
+        Some(self.snapshot(mint, &book))
+    }
+
+    /// Gets all positions with tokens held, ordered by mint
+    pub fn positions(&self) -> Vec<Position> {
+        let books: Vec<(Pubkey, Book)> = {
+            let books = match self.books.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            books
+                .iter()
+                .filter(|(_, book)| book.tokens > 0)
+                .map(|(mint, book)| (*mint, book.clone()))
+                .collect()
+        };
+        let mut positions: Vec<Position> = books
+            .iter()
+            .map(|(mint, book)| self.snapshot(mint, book))
+            .collect();
+        positions.sort_by_key(|position| position.mint);
+        positions
+    }
+
+    /// Returns the realized PnL across all mints, in lamports
+    pub fn total_realized_pnl(&self) -> i64 {
+        let books = match self.books.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        books
+            .values()
+            .fold(0i64, |total, book| total.saturating_add(book.realized))
+    }
+
+    fn snapshot(&self, mint: &Pubkey, book: &Book) -> Position {
+        let mark_price = {
+            let marks = match self.marks.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            marks.get(mint).copied()
+        };
+        let sol_usd = match self.sol_usd.lock() {
+            Ok(guard) => *guard,
+            Err(poisoned) => *poisoned.into_inner(),
+        };
+        let to_usd = |lamports: f64| sol_usd.map(|rate| lamports * rate / LAMPORTS_PER_SOL as f64);
+
+        let unrealized_pnl =
+            mark_price.map(|price| price * book.tokens as f64 - book.cost_basis as f64);
+        Position {
+            mint: *mint,
+            tokens: book.tokens,
+            cost_basis: book.cost_basis,
+            average_entry: (book.tokens > 0).then(|| book.cost_basis as f64 / book.tokens as f64),
+            realized_pnl: book.realized,
+            fees_paid: book.fees,
+            mark_price,
+            unrealized_pnl,
+            realized_pnl_usd: to_usd(book.realized as f64),
+            unrealized_pnl_usd: unrealized_pnl.and_then(to_usd),
+            fills: book.fills,
+            last_fill: book.last_fill,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(mint: Pubkey, side: TradeSide, token_amount: u64, sol_amount: u64, fee: u64) -> Fill {
+        Fill {
+            mint,
+            side,
+            token_amount,
+            sol_amount,
+            fee,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_position_pnl() {
+        let mint = Pubkey::new_unique();
+        let positions = PositionManager::new(Pubkey::new_unique());
+        positions.record_fill(fill(mint, TradeSide::Buy, 1_000, 990, 10));
+        positions.record_fill(fill(mint, TradeSide::Buy, 1_000, 2_990, 10));
+
+        let position = positions.position(&mint).unwrap();
+        assert_eq!(position.cost_basis, 4_000);
+        assert_eq!(position.average_entry, Some(2.0));
+        assert!(position.unrealized_pnl.is_none());
+
+        // Selling half realizes the proceeds after fees minus half the cost basis
+        positions.record_fill(fill(mint, TradeSide::Sell, 1_000, 3_010, 10));
+        positions.set_price(&mint, 3.0);
+        positions.set_sol_usd_price(Some(100.0));
+        let position = positions.position(&mint).unwrap();
+        assert_eq!(position.tokens, 1_000);
+        assert_eq!(position.cost_basis, 2_000);
+        assert_eq!(position.realized_pnl, 1_000);
+        assert_eq!(position.fees_paid, 30);
+        assert_eq!(position.unrealized_pnl, Some(1_000.0));
+        let usd = position.realized_pnl_usd.unwrap();
+        assert!((usd - 1e-4).abs() < 1e-12);
+
+        // Closed positions keep their realized PnL but are not listed as open
+        positions.record_fill(fill(mint, TradeSide::Sell, 1_000, 1_010, 10));
+        assert!(positions.positions().is_empty());
+        assert_eq!(positions.position(&mint).unwrap().realized_pnl, 0);
+        assert_eq!(positions.total_realized_pnl(), 0);
+    }
+
+    #[test]
+    fn test_record_event() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let positions = PositionManager::new(owner);
+        let trade = |user: Pubkey| TradeEvent {
+            mint,
+            sol_amount: 100,
+            token_amount: 1_000,
+            is_buy: true,
+            user,
+            timestamp: 5,
+            virtual_sol_reserves: 30,
+            virtual_token_reserves: 100,
+            real_sol_reserves: 0,
+            real_token_reserves: 0,
+            fee_recipient: Pubkey::new_unique(),
+            fee_basis_points: 95,
+            fee: 1,
+            creator: Pubkey::new_unique(),
+            creator_fee_basis_points: 5,
+            creator_fee: 1,
+            track_volume: false,
+            total_unclaimed_tokens: 0,
+            total_claimed_tokens: 0,
+            current_sol_volume: 0,
+            last_update_timestamp: 0,
+        };
+
+        assert!(positions.record_event(&PumpFunEvent::Trade(trade(owner))));
+        // Trades of other wallets only move the mark price
+        assert!(!positions.record_event(&PumpFunEvent::Trade(trade(Pubkey::new_unique()))));
+
+        let open = positions.positions();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].fills, 1);
+        assert_eq!(open[0].cost_basis, 102);
+        assert_eq!(open[0].last_fill, 5);
+        assert_eq!(open[0].mark_price, Some(0.3));
+    }
+}
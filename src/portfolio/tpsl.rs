@@ -0,0 +1,353 @@
+//! Take-profit and stop-loss exits of a position
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use tokio::{
+    sync::{oneshot, watch},
+    task::JoinHandle,
+};
+
+use super::Position;
+use crate::{
+    common::{price_feed::price_feed, types::PriorityFee},
+    error,
+    sender::TransactionSender,
+    PumpFun,
+};
+
+/// Slippage of market sells, accepting any SOL output
+const MARKET_SLIPPAGE_BASIS_POINTS: u64 = 10_000;
+
+/// Threshold that triggered an exit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Trigger {
+    TakeProfit,
+    StopLoss,
+}
+
+/// How an exit sells its tokens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExitOrder {
+    /// Sells without a minimum SOL output
+    Market,
+    /// Sells with the given maximum slippage in basis points
+    Slippage(u64),
+}
+
+/// Thresholds and execution of take-profit and stop-loss exits
+///
+/// Thresholds are fractions of the entry price: a `take_profit` of 0.5 sells once the price
+/// is 50% above the entry, and a `stop_loss` of 0.2 sells once it is 20% below.
+///
+/// # Fields
+///
+/// * `take_profit` - Gain over the entry price triggering a sell, if set
+/// * `stop_loss` - Loss under the entry price triggering a sell, if set
+/// * `order` - How the exit sells its tokens
+/// * `priority_fee` - Priority fee of the exit. If None, uses the cluster's priority fee
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TpSlConfig {
+    pub take_profit: Option<f64>,
+    pub stop_loss: Option<f64>,
+    pub order: ExitOrder,
+    pub priority_fee: Option<PriorityFee>,
+}
+
+impl TpSlConfig {
+    /// Creates a configuration with the given thresholds selling at market
+    pub fn new(take_profit: Option<f64>, stop_loss: Option<f64>) -> Self {
+        Self {
+            take_profit,
+            stop_loss,
+            order: ExitOrder::Market,
+            priority_fee: None,
+        }
+    }
+
+    /// Returns the threshold triggered by a price, if any
+    ///
+    /// # Arguments
+    ///
+    /// * `entry_price` - Entry price of the position, in lamports per token base unit
+    /// * `price` - Current price, in lamports per token base unit
+    pub fn check(&self, entry_price: f64, price: f64) -> Option<Trigger> {
+        if self
+            .stop_loss
+            .is_some_and(|loss| price <= entry_price * (1.0 - loss))
+        {
+            return Some(Trigger::StopLoss);
+        }
+        self.take_profit
+            .is_some_and(|gain| price >= entry_price * (1.0 + gain))
+            .then_some(Trigger::TakeProfit)
+    }
+
+    fn slippage_basis_points(&self) -> u64 {
+        match self.order {
+            ExitOrder::Market => MARKET_SLIPPAGE_BASIS_POINTS,
+            ExitOrder::Slippage(basis_points) => basis_points,
+        }
+    }
+}
+
+/// Sell made by a triggered monitor
+///
+/// # Fields
+///
+/// * `mint` - Mint of the sold token
+/// * `trigger` - Threshold that triggered the sell
+/// * `price` - Price that triggered the sell, in lamports per token base unit
+/// * `slot` - Slot the triggering price was observed in
+/// * `amount` - Tokens sold, or None when selling the whole balance
+/// * `result` - Signature of the confirmed sell, or why it failed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exit {
+    pub mint: Pubkey,
+    pub trigger: Trigger,
+    pub price: f64,
+    pub slot: u64,
+    pub amount: Option<u64>,
+    pub result: Result<Signature, String>,
+}
+
+/// Handle of a running monitor, resolving to its exit
+///
+/// Resolves to None if the price feed ends before a threshold triggers. Dropping the
+/// handle stops the monitor.
+pub struct TpSlHandle {
+    receiver: oneshot::Receiver<Exit>,
+    task: JoinHandle<()>,
+}
+
+impl TpSlHandle {
+    /// Stops the monitor without selling
+    pub fn cancel(self) {}
+}
+
+impl Future for TpSlHandle {
+    type Output = Option<Exit>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.receiver).poll(cx).map(Result::ok)
+    }
+}
+
+impl Drop for TpSlHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Sells a position once its price reaches a take-profit or stop-loss threshold
+///
+/// The monitor follows the token's `price_feed` and submits at most one sell: once a
+/// threshold triggers, later prices are ignored and the monitor ends with the outcome of
+/// that sell, whether it succeeded or not, so a sell that failed after landing is never
+/// repeated. Sells go through the bonding curve, so they fail once the token has migrated.
+/// Each price is checked against the configuration current when it arrives, so a monitor
+/// following a `HotConfig` picks up new thresholds without restarting.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pumpfun::{
+///     common::types::{Cluster, PriorityFee},
+///     portfolio::{ExitOrder, TpSlConfig, TpSlMonitor},
+///     PumpFun,
+/// };
+/// use solana_sdk::{commitment_config::CommitmentConfig, pubkey, signature::Keypair};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+/// let client = Arc::new(PumpFun::new(Arc::new(Keypair::new()), cluster));
+/// let mint = pubkey!("TokenM1ntPubk3yXXXXXXXXXXXXXXXXXXXXXXXXXXXX");
+///
+/// let config = TpSlConfig {
+///     order: ExitOrder::Slippage(500),
+///     ..TpSlConfig::new(Some(1.0), Some(0.3))
+/// };
+/// let exit = TpSlMonitor::new(client, mint, 0.000_000_03, None, config)
+///     .start()
+///     .await?
+///     .await;
+/// println!("Exit: {:?}", exit);
+/// # Ok(())
+/// # }
+/// ```
+pub struct TpSlMonitor {
+    client: Arc<PumpFun>,
+    mint: Pubkey,
+    entry_price: f64,
+    amount: Option<u64>,
+    config: watch::Receiver<TpSlConfig>,
+    sender: Option<Arc<dyn TransactionSender>>,
+    commitment: Option<CommitmentConfig>,
+}
+
+impl TpSlMonitor {
+    /// Creates a monitor for a position
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Client whose payer holds the tokens and signs the sell
+    /// * `mint` - Mint of the token
+    /// * `entry_price` - Entry price of the position, in lamports per token base unit
+    /// * `amount` - Tokens to sell on exit. If None, sells the whole balance
+    /// * `config` - Thresholds and execution of the exit
+    pub fn new(
+        client: Arc<PumpFun>,
+        mint: Pubkey,
+        entry_price: f64,
+        amount: Option<u64>,
+        config: TpSlConfig,
+    ) -> Self {
+        Self {
+            client,
+            mint,
+            entry_price,
+            amount,
+            config: watch::channel(config).1,
+            sender: None,
+            commitment: None,
+        }
+    }
+
+    /// Reads the configuration from a watch channel, such as `HotConfig::subscribe`,
+    /// instead of the configuration passed to `new`
+    pub fn with_config_updates(mut self, config: watch::Receiver<TpSlConfig>) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Creates a monitor selling the tokens of a tracked position at its average entry
+    ///
+    /// # Errors
+    ///
+    /// Returns a ConfigError if the position holds no tokens
+    pub fn from_position(
+        client: Arc<PumpFun>,
+        position: &Position,
+        config: TpSlConfig,
+    ) -> Result<Self, error::ClientError> {
+        let entry_price = position.average_entry.ok_or_else(|| {
+            error::ClientError::ConfigError(format!("No tokens held of {}", position.mint))
+        })?;
+        Ok(Self::new(
+            client,
+            position.mint,
+            entry_price,
+            Some(position.tokens),
+            config,
+        ))
+    }
+
+    /// Submits the sell through the given sender instead of the client's sender
+    pub fn with_sender(mut self, sender: Arc<dyn TransactionSender>) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// Sets the commitment of the price feed. If not set, uses the cluster's commitment
+    pub fn with_commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.commitment = Some(commitment);
+        self
+    }
+
+    /// Starts monitoring the price
+    ///
+    /// # Returns
+    ///
+    /// Returns a `TpSlHandle` resolving to the exit, or a ClientError if the price feed
+    /// cannot be established
+    pub async fn start(self) -> Result<TpSlHandle, error::ClientError> {
+        let mut prices =
+            price_feed(self.client.cluster.clone(), &self.mint, self.commitment).await?;
+        let (exit_tx, receiver) = oneshot::channel();
+        let task = tokio::spawn(async move {
+            while let Some(update) = prices.next().await {
+                let config = *self.config.borrow();
+                let Some(trigger) = config.check(self.entry_price, update.price) else {
+                    continue;
+                };
+                let result = self.sell(&config).await;
+                let _ = exit_tx.send(Exit {
+                    mint: self.mint,
+                    trigger,
+                    price: update.price,
+                    slot: update.slot,
+                    amount: self.amount,
+                    result,
+                });
+                return;
+            }
+        });
+        Ok(TpSlHandle { receiver, task })
+    }
+
+    async fn sell(&self, config: &TpSlConfig) -> Result<Signature, String> {
+        let priority_fee = config
+            .priority_fee
+            .unwrap_or(self.client.cluster.priority_fee);
+        let mut instructions = PumpFun::get_priority_fee_instructions(&priority_fee);
+        let sell = self
+            .client
+            .get_sell_instructions(self.mint, self.amount, Some(config.slippage_basis_points()))
+            .await
+            .map_err(|err| err.to_string())?;
+        instructions.extend(sell);
+        self.client
+            .send_instructions(&instructions, None, self.sender.as_deref())
+            .await
+            .map_err(|err| err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{config::HotConfig, types::Cluster};
+    use serde_json::json;
+    use solana_sdk::signature::Keypair;
+
+    #[test]
+    fn test_check() {
+        let config = TpSlConfig::new(Some(0.5), Some(0.2));
+        assert_eq!(config.check(100.0, 120.0), None);
+        assert_eq!(config.check(100.0, 150.0), Some(Trigger::TakeProfit));
+        assert_eq!(config.check(100.0, 80.0), Some(Trigger::StopLoss));
+        assert_eq!(config.check(100.0, 81.0), None);
+
+        let config = TpSlConfig::new(None, Some(0.2));
+        assert_eq!(config.check(100.0, 1_000.0), None);
+        assert_eq!(config.slippage_basis_points(), MARKET_SLIPPAGE_BASIS_POINTS);
+    }
+
+    fn client() -> Arc<PumpFun> {
+        let cluster = Cluster::localnet(CommitmentConfig::processed(), PriorityFee::default());
+        Arc::new(PumpFun::new(Arc::new(Keypair::new()), cluster))
+    }
+
+    #[test]
+    fn test_config_updates() {
+        let config = TpSlConfig::new(Some(0.5), Some(0.2));
+        let hot = HotConfig::new(config);
+        let monitor = TpSlMonitor::new(client(), Pubkey::new_unique(), 100.0, None, config)
+            .with_config_updates(hot.subscribe());
+        assert_eq!(monitor.config.borrow().check(100.0, 130.0), None);
+
+        hot.apply_patch(json!({ "take_profit": 0.25, "order": { "Slippage": 500 } }))
+            .unwrap();
+        let config = *monitor.config.borrow();
+        assert_eq!(config.check(100.0, 130.0), Some(Trigger::TakeProfit));
+        assert_eq!(config.slippage_basis_points(), 500);
+    }
+}
@@ -0,0 +1,354 @@
+//! Registry of known discriminators and PDAs
+//!
+//! This module gathers every instruction, event, and account discriminator known to the
+//! crate, for both the Pump.fun and Pump.fun AMM programs, together with the PDAs the client
+//! derives, in tables that can be queried by name or by bytes. Indexers can classify
+//! arbitrary transactions and accounts with it instead of hard-coding byte arrays.
+//!
+//! The discriminators themselves are defined in `constants::discriminators`; the PDAs are
+//! derived by the same functions the client uses.
+//!
+//! # Examples
+//!
+//! ```
+//! use pumpfun::{constants, registry::{self, DiscriminatorKind}};
+//!
+//! let entry = registry::classify_instruction(
+//!     &constants::accounts::PUMPFUN,
+//!     &constants::discriminators::BUY,
+//! ).unwrap();
+//! assert_eq!(entry.name, "buy");
+//! assert_eq!(entry.kind, DiscriminatorKind::Instruction);
+//!
+//! let global = registry::find_pda("global").unwrap();
+//! assert_eq!(global.derive(None), Some(pumpfun::PumpFun::get_global_pda()));
+//! ```
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    constants::{accounts, discriminators},
+    instructions::amm,
+    PumpFun,
+};
+
+/// Kind of data a discriminator identifies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiscriminatorKind {
+    Instruction,
+    Event,
+    Account,
+}
+
+/// Known discriminator
+///
+/// # Fields
+///
+/// * `name` - Name in the program's IDL, e.g. `buy`, `TradeEvent`, or `BondingCurve`
+/// * `kind` - Whether the discriminator identifies an instruction, an event, or an account
+/// * `program_id` - Program that defines the instruction, event, or account
+/// * `discriminator` - First 8 bytes of the serialized data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiscriminatorEntry {
+    pub name: &'static str,
+    pub kind: DiscriminatorKind,
+    pub program_id: Pubkey,
+    pub discriminator: [u8; 8],
+}
+
+/// Events of the Pump.fun AMM program, which share the events table
+const AMM_EVENTS: [[u8; 8]; 6] = [
+    discriminators::AMM_BUY_EVENT,
+    discriminators::AMM_SELL_EVENT,
+    discriminators::AMM_DEPOSIT_EVENT,
+    discriminators::AMM_WITHDRAW_EVENT,
+    discriminators::AMM_CREATE_POOL_EVENT,
+    discriminators::AMM_COLLECT_COIN_CREATOR_FEE_EVENT,
+];
+
+/// Instructions of the Pump.fun AMM program
+const AMM_INSTRUCTIONS: &[(&str, [u8; 8])] = &[
+    ("create_config", amm::CREATE_CONFIG),
+    ("update_admin", amm::UPDATE_ADMIN),
+    ("update_fee_config", amm::UPDATE_FEE_CONFIG),
+    ("disable", amm::DISABLE),
+];
+
+/// Returns every known discriminator
+///
+/// The fee program's `FeeConfig` account is attributed to the fee program.
+pub fn discriminators() -> impl Iterator<Item = DiscriminatorEntry> {
+    let instructions = discriminators::INSTRUCTIONS
+        .iter()
+        .map(|ix| entry(ix, DiscriminatorKind::Instruction, accounts::PUMPFUN))
+        .chain(
+            AMM_INSTRUCTIONS
+                .iter()
+                .map(|ix| entry(ix, DiscriminatorKind::Instruction, accounts::PUMPAMM)),
+        );
+    let events = discriminators::EVENTS.iter().map(|event| {
+        let program_id = if AMM_EVENTS.contains(&event.1) {
+            accounts::PUMPAMM
+        } else {
+            accounts::PUMPFUN
+        };
+        entry(event, DiscriminatorKind::Event, program_id)
+    });
+    let accounts = discriminators::ACCOUNTS.iter().map(|account| {
+        let program_id = if account.1 == discriminators::FEE_CONFIG_ACCOUNT {
+            accounts::FEE_CONFIG_PROGRAM
+        } else {
+            accounts::PUMPFUN
+        };
+        entry(account, DiscriminatorKind::Account, program_id)
+    });
+    instructions.chain(events).chain(accounts)
+}
+
+fn entry(
+    (name, discriminator): &(&'static str, [u8; 8]),
+    kind: DiscriminatorKind,
+    program_id: Pubkey,
+) -> DiscriminatorEntry {
+    DiscriminatorEntry {
+        name,
+        kind,
+        program_id,
+        discriminator: *discriminator,
+    }
+}
+
+/// Looks up a discriminator by name
+///
+/// # Arguments
+///
+/// * `name` - Name in the program's IDL, e.g. `sell` or `CompleteEvent`
+///
+/// # Returns
+///
+/// Returns the entry, or None if the name is unknown
+pub fn find_by_name(name: &str) -> Option<DiscriminatorEntry> {
+    discriminators().find(|entry| entry.name == name)
+}
+
+/// Looks up a discriminator from the start of raw data
+///
+/// # Arguments
+///
+/// * `data` - Instruction, event, or account data, starting with the 8-byte discriminator
+///
+/// # Returns
+///
+/// Returns the entry, or None if the discriminator is unknown
+pub fn find_by_discriminator(data: &[u8]) -> Option<DiscriminatorEntry> {
+    let discriminator = data.get(..8)?;
+    discriminators().find(|entry| entry.discriminator == discriminator)
+}
+
+/// Classifies the data of an instruction sent to a program
+///
+/// Events emitted with Anchor's `emit_cpi!` are sent as self-CPI instructions prefixed with
+/// `EVENT_IX_TAG`; these are classified as the event they carry.
+///
+/// # Arguments
+///
+/// * `program_id` - Program the instruction is sent to
+/// * `data` - Instruction data
+///
+/// # Returns
+///
+/// Returns the instruction or event entry, or None if the program or discriminator is
+/// unknown
+pub fn classify_instruction(program_id: &Pubkey, data: &[u8]) -> Option<DiscriminatorEntry> {
+    let (kind, data) = match data.strip_prefix(&discriminators::EVENT_IX_TAG) {
+        Some(event) => (DiscriminatorKind::Event, event),
+        None => (DiscriminatorKind::Instruction, data),
+    };
+    let discriminator = data.get(..8)?;
+    discriminators().find(|entry| {
+        entry.kind == kind
+            && entry.program_id == *program_id
+            && entry.discriminator == discriminator
+    })
+}
+
+/// How a PDA is derived
+#[derive(Debug, Clone, Copy)]
+enum Derivation {
+    Fixed(fn() -> Pubkey),
+    Keyed(fn(&Pubkey) -> Option<Pubkey>),
+}
+
+/// Known PDA
+///
+/// # Fields
+///
+/// * `name` - Name of the PDA, e.g. `bonding_curve`
+/// * `program_id` - Program the PDA is derived from
+/// * `key` - Name of the public key the PDA is derived for, e.g. `mint`, or None if there
+///   is a single PDA
+#[derive(Debug, Clone, Copy)]
+pub struct PdaEntry {
+    pub name: &'static str,
+    pub program_id: Pubkey,
+    pub key: Option<&'static str>,
+    derivation: Derivation,
+}
+
+impl PdaEntry {
+    /// Derives the PDA
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Public key the PDA is derived for, required when `self.key` is set and
+    ///   ignored otherwise
+    ///
+    /// # Returns
+    ///
+    /// Returns the PDA, or None if a required key is missing or no address can be derived
+    pub fn derive(&self, key: Option<&Pubkey>) -> Option<Pubkey> {
+        match self.derivation {
+            Derivation::Fixed(derive) => Some(derive()),
+            Derivation::Keyed(derive) => derive(key?),
+        }
+    }
+}
+
+const PDAS: &[PdaEntry] = &[
+    PdaEntry {
+        name: "global",
+        program_id: accounts::PUMPFUN,
+        key: None,
+        derivation: Derivation::Fixed(PumpFun::get_global_pda),
+    },
+    PdaEntry {
+        name: "mint_authority",
+        program_id: accounts::PUMPFUN,
+        key: None,
+        derivation: Derivation::Fixed(PumpFun::get_mint_authority_pda),
+    },
+    PdaEntry {
+        name: "bonding_curve",
+        program_id: accounts::PUMPFUN,
+        key: Some("mint"),
+        derivation: Derivation::Keyed(PumpFun::get_bonding_curve_pda),
+    },
+    PdaEntry {
+        name: "metadata",
+        program_id: accounts::MPL_TOKEN_METADATA,
+        key: Some("mint"),
+        derivation: Derivation::Keyed(|mint| Some(PumpFun::get_metadata_pda(mint))),
+    },
+    PdaEntry {
+        name: "creator_vault",
+        program_id: accounts::PUMPFUN,
+        key: Some("creator"),
+        derivation: Derivation::Keyed(PumpFun::get_creator_vault_pda),
+    },
+    PdaEntry {
+        name: "global_volume_accumulator",
+        program_id: accounts::PUMPFUN,
+        key: None,
+        derivation: Derivation::Fixed(PumpFun::get_global_volume_accumulator_pda),
+    },
+    PdaEntry {
+        name: "user_volume_accumulator",
+        program_id: accounts::PUMPFUN,
+        key: Some("user"),
+        derivation: Derivation::Keyed(|user| Some(PumpFun::get_user_volume_accumulator_pda(user))),
+    },
+    PdaEntry {
+        name: "fee_config",
+        program_id: accounts::FEE_CONFIG_PROGRAM,
+        key: None,
+        derivation: Derivation::Fixed(PumpFun::get_fee_config_pda),
+    },
+    PdaEntry {
+        name: "amm_global_config",
+        program_id: accounts::PUMPAMM,
+        key: None,
+        derivation: Derivation::Fixed(amm::get_global_config_pda),
+    },
+    PdaEntry {
+        name: "amm_event_authority",
+        program_id: accounts::PUMPAMM,
+        key: None,
+        derivation: Derivation::Fixed(amm::get_event_authority_pda),
+    },
+];
+
+/// Returns every known PDA
+pub fn pdas() -> &'static [PdaEntry] {
+    PDAS
+}
+
+/// Looks up a PDA by name
+///
+/// # Arguments
+///
+/// * `name` - Name of the PDA, e.g. `creator_vault`
+///
+/// # Returns
+///
+/// Returns the entry, or None if the name is unknown
+pub fn find_pda(name: &str) -> Option<&'static PdaEntry> {
+    PDAS.iter().find(|pda| pda.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let count = discriminators::INSTRUCTIONS.len()
+            + AMM_INSTRUCTIONS.len()
+            + discriminators::EVENTS.len()
+            + discriminators::ACCOUNTS.len();
+        assert_eq!(discriminators().count(), count);
+
+        let trade = find_by_name("TradeEvent").unwrap();
+        assert_eq!(trade.kind, DiscriminatorKind::Event);
+        assert_eq!(trade.program_id, accounts::PUMPFUN);
+        assert_eq!(
+            find_by_name("BuyEvent").unwrap().program_id,
+            accounts::PUMPAMM
+        );
+        assert_eq!(
+            find_by_discriminator(&discriminators::FEE_CONFIG_ACCOUNT)
+                .unwrap()
+                .program_id,
+            accounts::FEE_CONFIG_PROGRAM
+        );
+
+        // Self-CPI events are classified as the event they carry
+        let mut data = discriminators::EVENT_IX_TAG.to_vec();
+        data.extend_from_slice(&discriminators::TRADE_EVENT);
+        assert_eq!(classify_instruction(&accounts::PUMPFUN, &data), Some(trade));
+        assert_eq!(
+            classify_instruction(&accounts::PUMPAMM, &amm::DISABLE)
+                .unwrap()
+                .name,
+            "disable"
+        );
+        assert_eq!(
+            classify_instruction(&accounts::PUMPAMM, &discriminators::BUY),
+            None
+        );
+        assert_eq!(classify_instruction(&accounts::PUMPFUN, &[1, 2]), None);
+
+        let mint = Pubkey::new_unique();
+        let bonding_curve = find_pda("bonding_curve").unwrap();
+        assert_eq!(bonding_curve.key, Some("mint"));
+        assert_eq!(bonding_curve.derive(None), None);
+        assert_eq!(
+            bonding_curve.derive(Some(&mint)),
+            PumpFun::get_bonding_curve_pda(&mint)
+        );
+        assert_eq!(
+            find_pda("fee_config").unwrap().derive(None),
+            Some(accounts::FEE_CONFIG)
+        );
+        assert!(pdas().iter().all(|pda| pda.derive(Some(&mint)).is_some()));
+    }
+}
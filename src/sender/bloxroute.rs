@@ -0,0 +1,89 @@
+//! Sender that submits transactions through the bloXroute trader API
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::Value;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{signature::Signature, transaction::VersionedTransaction};
+
+use super::TransactionSender;
+use crate::{
+    error,
+    utils::http::{self, HttpClient},
+};
+
+/// Default bloXroute submit endpoint
+pub const BLOXROUTE_SUBMIT_URL: &str = "https://ny.solana.dex.blxrbdn.com/api/v2/submit";
+
+/// Sends transactions through the bloXroute trader API and confirms them over RPC
+pub struct BloxrouteSender {
+    /// bloXroute submit endpoint
+    pub url: String,
+    /// bloXroute authorization header value
+    pub auth_header: String,
+    /// RPC client used to confirm transactions
+    pub rpc: Arc<RpcClient>,
+    http: Arc<dyn HttpClient>,
+}
+
+impl BloxrouteSender {
+    /// Creates a new bloXroute sender
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - bloXroute submit endpoint, e.g. `BLOXROUTE_SUBMIT_URL`
+    /// * `auth_header` - bloXroute authorization header value
+    /// * `rpc` - RPC client used to confirm transactions
+    ///
+    /// # Errors
+    ///
+    /// Never fails; the `Result` is kept for compatibility with earlier releases, which
+    /// created the HTTP client here
+    pub fn new(
+        url: String,
+        auth_header: String,
+        rpc: Arc<RpcClient>,
+    ) -> Result<Self, error::ClientError> {
+        Ok(Self {
+            url,
+            auth_header,
+            rpc,
+            http: http::default_client(),
+        })
+    }
+
+    /// Sends requests through the given HTTP client instead of the default backend
+    pub fn with_http_client(mut self, http: Arc<dyn HttpClient>) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+#[async_trait]
+impl TransactionSender for BloxrouteSender {
+    async fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Result<Signature, error::ClientError> {
+        let encoded = super::encode_transaction(transaction)?;
+        let body = serde_json::json!({
+            "transaction": { "content": encoded },
+            "skipPreFlight": true,
+        });
+        let request = super::json_request(
+            &self.url,
+            &[("Authorization", self.auth_header.as_str())],
+            &body,
+        );
+
+        super::submit_and_confirm(
+            self.http.as_ref(),
+            &self.rpc,
+            request,
+            transaction,
+            |response| response.get("signature").and_then(Value::as_str),
+        )
+        .await
+    }
+}
@@ -0,0 +1,95 @@
+//! Sender that submits transactions through Helius Sender
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{signature::Signature, transaction::VersionedTransaction};
+
+use super::TransactionSender;
+use crate::{
+    error,
+    utils::http::{self, HttpClient},
+};
+
+/// Default Helius Sender endpoint
+pub const HELIUS_SENDER_URL: &str = "https://sender.helius-rpc.com/fast";
+
+/// Sends transactions through Helius Sender and confirms them over RPC
+///
+/// Helius Sender skips preflight and requires a tip and a priority fee on every transaction.
+pub struct HeliusSender {
+    /// Helius Sender endpoint
+    pub url: String,
+    /// Optional Helius API key
+    pub api_key: Option<String>,
+    /// RPC client used to confirm transactions
+    pub rpc: Arc<RpcClient>,
+    http: Arc<dyn HttpClient>,
+}
+
+impl HeliusSender {
+    /// Creates a new Helius sender
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Helius Sender endpoint, e.g. `HELIUS_SENDER_URL`
+    /// * `api_key` - Optional Helius API key
+    /// * `rpc` - RPC client used to confirm transactions
+    ///
+    /// # Errors
+    ///
+    /// Never fails; the `Result` is kept for compatibility with earlier releases, which
+    /// created the HTTP client here
+    pub fn new(
+        url: String,
+        api_key: Option<String>,
+        rpc: Arc<RpcClient>,
+    ) -> Result<Self, error::ClientError> {
+        Ok(Self {
+            url,
+            api_key,
+            rpc,
+            http: http::default_client(),
+        })
+    }
+
+    /// Sends requests through the given HTTP client instead of the default backend
+    pub fn with_http_client(mut self, http: Arc<dyn HttpClient>) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+#[async_trait]
+impl TransactionSender for HeliusSender {
+    async fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Result<Signature, error::ClientError> {
+        let encoded = super::encode_transaction(transaction)?;
+        let body = super::send_transaction_body(
+            &encoded,
+            serde_json::json!({
+                "encoding": "base64",
+                "skipPreflight": true,
+                "maxRetries": 0,
+            }),
+        );
+
+        let url = match &self.api_key {
+            Some(key) => format!("{}?api-key={}", self.url, key),
+            None => self.url.clone(),
+        };
+        let request = super::json_request(&url, &[], &body);
+
+        super::submit_and_confirm(
+            self.http.as_ref(),
+            &self.rpc,
+            request,
+            transaction,
+            super::json_rpc_signature,
+        )
+        .await
+    }
+}
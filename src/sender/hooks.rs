@@ -0,0 +1,154 @@
+//! Hooks around the client's transaction send path
+
+use solana_sdk::{
+    instruction::Instruction, signature::Signature, transaction::VersionedTransaction,
+};
+
+use crate::error;
+
+/// Callbacks invoked by the client around every transaction it sends
+///
+/// Hooks are registered with `PumpFun::with_hook` or `PumpFunBuilder::hook` and run in
+/// registration order for every transaction sent through `send_instructions`, which
+/// includes the client's own trades. Every method defaults to doing nothing, so
+/// implementations only override the stages they care about. Hooks run inline on the send
+/// path and should return quickly; hand slow work such as database writes off to a task.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::sync::Arc;
+///
+/// use pumpfun::{error::ClientError, sender::TransactionHook, PumpFun};
+/// use solana_sdk::signature::Signature;
+///
+/// struct Logger;
+///
+/// impl TransactionHook for Logger {
+///     fn on_send(&self, signature: &Signature) {
+///         println!("Sent {}", signature);
+///     }
+///
+///     fn on_confirm(&self, result: &Result<Signature, ClientError>) {
+///         println!("Finished: {:?}", result.as_ref().map_err(|err| err.to_string()));
+///     }
+/// }
+///
+/// # fn example(client: PumpFun) {
+/// let client = client.with_hook(Arc::new(Logger));
+/// # }
+/// ```
+pub trait TransactionHook: Send + Sync {
+    /// Called with the instructions of a transaction before it is signed
+    ///
+    /// Instructions can be added, removed, or reordered, e.g. to insert a tip transfer.
+    /// Signers required by added instructions must already be among the signers given to
+    /// `send_instructions`.
+    fn on_instructions(&self, _instructions: &mut Vec<Instruction>) {}
+
+    /// Called with the signed transaction, each time it is built or rebuilt
+    fn on_build(&self, _transaction: &VersionedTransaction) {}
+
+    /// Called with the signature of the transaction before each submission attempt
+    fn on_send(&self, _signature: &Signature) {}
+
+    /// Called once with the final outcome, after confirmation or when the retries give up
+    fn on_confirm(&self, _result: &Result<Signature, error::ClientError>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use solana_client::nonblocking::rpc_client::RpcClient;
+    use solana_sdk::{
+        commitment_config::CommitmentConfig, hash::Hash, pubkey::Pubkey, signature::Keypair,
+        signer::Signer,
+    };
+    use solana_system_interface::instruction::transfer;
+
+    use super::*;
+    use crate::{
+        common::types::{Cluster, PriorityFee},
+        sender::TransactionSender,
+        PumpFun,
+    };
+
+    struct Recorder {
+        tip: Instruction,
+        events: Mutex<Vec<String>>,
+    }
+
+    impl Recorder {
+        fn record(&self, event: String) {
+            match self.events.lock() {
+                Ok(mut events) => events.push(event),
+                Err(poisoned) => poisoned.into_inner().push(event),
+            }
+        }
+    }
+
+    impl TransactionHook for Recorder {
+        fn on_instructions(&self, instructions: &mut Vec<Instruction>) {
+            instructions.push(self.tip.clone());
+        }
+
+        fn on_build(&self, transaction: &VersionedTransaction) {
+            self.record(format!(
+                "build {}",
+                transaction.message.instructions().len()
+            ));
+        }
+
+        fn on_send(&self, signature: &Signature) {
+            self.record(format!("send {}", signature));
+        }
+
+        fn on_confirm(&self, result: &Result<Signature, error::ClientError>) {
+            self.record(format!("confirm {}", result.is_ok()));
+        }
+    }
+
+    struct Confirmed;
+
+    #[async_trait]
+    impl TransactionSender for Confirmed {
+        async fn send_transaction(
+            &self,
+            transaction: &VersionedTransaction,
+        ) -> Result<Signature, error::ClientError> {
+            Ok(transaction.signatures[0])
+        }
+    }
+
+    #[test]
+    fn test_hooks() {
+        let payer = Arc::new(Keypair::new_from_array([7; 32]));
+        let cluster = Cluster::localnet(CommitmentConfig::confirmed(), PriorityFee::default());
+        let recorder = Arc::new(Recorder {
+            tip: transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000),
+            events: Mutex::new(Vec::new()),
+        });
+        let client = PumpFun::builder(payer.clone(), cluster)
+            .rpc_client(Arc::new(RpcClient::new_mock("succeeds".to_string())))
+            .sender(Arc::new(Confirmed))
+            .hook(recorder.clone())
+            .build()
+            .with_fixed_blockhash(Hash::default());
+
+        let instructions = [transfer(&payer.pubkey(), &payer.pubkey(), 1)];
+        let signature =
+            tokio_test::block_on(client.send_instructions(&instructions, None, None)).unwrap();
+
+        let events = recorder.events.lock().unwrap().clone();
+        assert_eq!(
+            events,
+            vec![
+                "build 2".to_string(),
+                format!("send {}", signature),
+                "confirm true".to_string(),
+            ]
+        );
+    }
+}
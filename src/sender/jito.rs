@@ -0,0 +1,122 @@
+//! Sender that submits transactions through a Jito block engine
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::Instruction, pubkey, pubkey::Pubkey, signature::Signature,
+    transaction::VersionedTransaction,
+};
+use solana_system_interface::instruction as system_instruction;
+
+use super::TransactionSender;
+use crate::{
+    error,
+    utils::http::{self, HttpClient},
+};
+
+/// Default Jito block engine transactions endpoint
+pub const JITO_MAINNET_URL: &str = "https://mainnet.block-engine.jito.wtf/api/v1/transactions";
+
+/// Jito tip accounts; one of these must receive a tip for the transaction to be forwarded
+pub const JITO_TIP_ACCOUNTS: [Pubkey; 8] = [
+    pubkey!("96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5"),
+    pubkey!("HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe"),
+    pubkey!("Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY"),
+    pubkey!("ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49"),
+    pubkey!("DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh"),
+    pubkey!("ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt"),
+    pubkey!("DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL"),
+    pubkey!("3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT"),
+];
+
+/// Creates an instruction that tips a Jito tip account
+///
+/// # Arguments
+///
+/// * `payer` - Account paying the tip
+/// * `lamports` - Tip amount in lamports
+///
+/// # Returns
+///
+/// Returns a system transfer instruction to one of the Jito tip accounts
+pub fn jito_tip_instruction(payer: &Pubkey, lamports: u64) -> Instruction {
+    // Spread tips across accounts to reduce write-lock contention
+    let index = (payer.to_bytes()[0] as usize) % JITO_TIP_ACCOUNTS.len();
+    system_instruction::transfer(payer, &JITO_TIP_ACCOUNTS[index], lamports)
+}
+
+/// Sends transactions through a Jito block engine and confirms them over RPC
+///
+/// The transaction must include a tip (see `jito_tip_instruction`) to be forwarded.
+pub struct JitoSender {
+    /// Block engine transactions endpoint
+    pub url: String,
+    /// Optional Jito authentication UUID
+    pub uuid: Option<String>,
+    /// RPC client used to confirm transactions
+    pub rpc: Arc<RpcClient>,
+    http: Arc<dyn HttpClient>,
+}
+
+impl JitoSender {
+    /// Creates a new Jito sender
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Block engine transactions endpoint, e.g. `JITO_MAINNET_URL`
+    /// * `uuid` - Optional Jito authentication UUID
+    /// * `rpc` - RPC client used to confirm transactions
+    ///
+    /// # Errors
+    ///
+    /// Never fails; the `Result` is kept for compatibility with earlier releases, which
+    /// created the HTTP client here
+    pub fn new(
+        url: String,
+        uuid: Option<String>,
+        rpc: Arc<RpcClient>,
+    ) -> Result<Self, error::ClientError> {
+        Ok(Self {
+            url,
+            uuid,
+            rpc,
+            http: http::default_client(),
+        })
+    }
+
+    /// Sends requests through the given HTTP client instead of the default backend
+    pub fn with_http_client(mut self, http: Arc<dyn HttpClient>) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+#[async_trait]
+impl TransactionSender for JitoSender {
+    async fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Result<Signature, error::ClientError> {
+        let encoded = super::encode_transaction(transaction)?;
+        let body =
+            super::send_transaction_body(&encoded, serde_json::json!({"encoding": "base64"}));
+
+        let headers: Vec<(&str, &str)> = self
+            .uuid
+            .as_deref()
+            .map(|uuid| vec![("x-jito-auth", uuid)])
+            .unwrap_or_default();
+        let request = super::json_request(&self.url, &headers, &body);
+
+        super::submit_and_confirm(
+            self.http.as_ref(),
+            &self.rpc,
+            request,
+            transaction,
+            super::json_rpc_signature,
+        )
+        .await
+    }
+}
@@ -0,0 +1,358 @@
+//! Transaction senders for submitting signed transactions
+//!
+//! This module defines the `TransactionSender` trait used by the `PumpFun` client to
+//! submit and confirm transactions, along with implementations for common submission paths.
+//!
+//! # Senders
+//!
+//! - `RpcSender`: Sends through the standard RPC `sendTransaction` and waits for confirmation.
+//!   This is the default sender.
+//! - `JitoSender`: Sends through a Jito block engine.
+//! - `HeliusSender`: Sends through Helius Sender.
+//! - `BloxrouteSender`: Sends through the bloXroute trader API.
+//! - `MultiRpcSender`: Broadcasts to several RPC endpoints and resolves on the first confirmation.
+//! - `TpuSender`: Sends directly to leader TPUs over QUIC, falling back to RPC
+//!   (requires the `tpu-client` feature).
+//! - `TxTracker`: Sends through RPC and tracks the signature until it lands, fails, or expires,
+//!   resubmitting in the meantime.
+//!
+//! Independently of the sender, `TransactionHook`s registered on the client observe or adjust
+//! every transaction it sends, e.g. for logging, metrics, tip insertion, or persistence.
+//!
+//! Relay senders submit the transaction through their own endpoint and then confirm it
+//! using a regular RPC client. Their requests go through a `utils::http::HttpClient`, the
+//! default backend unless one is passed with `with_http_client`. Most relays only forward
+//! transactions that pay a tip to one of their tip accounts, so include a tip instruction
+//! when building the transaction.
+
+mod bloxroute;
+mod helius;
+mod hooks;
+mod jito;
+mod multi_rpc;
+mod rpc;
+#[cfg(feature = "tpu-client")]
+mod tpu;
+mod tracker;
+
+pub use bloxroute::*;
+pub use helius::*;
+pub use hooks::*;
+pub use jito::*;
+pub use multi_rpc::*;
+pub use rpc::*;
+#[cfg(feature = "tpu-client")]
+pub use tpu::*;
+pub use tracker::*;
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use base64::Engine;
+use serde_json::Value;
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_client::SerializableTransaction};
+use solana_sdk::{
+    commitment_config::CommitmentConfig, hash::Hash, signature::Signature,
+    transaction::VersionedTransaction,
+};
+
+use crate::{
+    error,
+    utils::http::{HttpClient, HttpRequest},
+};
+
+/// Submits signed transactions to the network
+///
+/// Implementations return once the transaction has been confirmed, or with an error if
+/// it could not be submitted or failed on-chain. A transaction whose blockhash expired
+/// before it was confirmed should be reported as `ClientError::TransactionExpired`, as
+/// `confirm_transaction` does, so the client's retry policy can rebuild it.
+#[async_trait]
+pub trait TransactionSender: Send + Sync {
+    /// Submits a signed transaction and waits for it to be confirmed
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - Fully signed transaction to submit
+    ///
+    /// # Returns
+    ///
+    /// Returns the transaction signature if successful, or a ClientError if the operation fails
+    async fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Result<Signature, error::ClientError>;
+}
+
+#[async_trait]
+impl<T: TransactionSender + ?Sized> TransactionSender for Arc<T> {
+    async fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Result<Signature, error::ClientError> {
+        (**self).send_transaction(transaction).await
+    }
+}
+
+/// Serializes a transaction into the base64 wire format accepted by RPC endpoints
+///
+/// # Arguments
+///
+/// * `transaction` - Transaction to encode
+///
+/// # Returns
+///
+/// Returns the base64-encoded transaction, or a ClientError if serialization fails
+pub fn encode_transaction(
+    transaction: &VersionedTransaction,
+) -> Result<String, error::ClientError> {
+    let bytes = bincode::serialize(transaction).map_err(|err| {
+        error::ClientError::OtherError(format!("Failed to serialize transaction: {}", err))
+    })?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+}
+
+/// Waits for a submitted transaction to be confirmed
+///
+/// Polls the signature status until the transaction is confirmed at the client's commitment,
+/// fails on-chain, or its blockhash expires.
+///
+/// # Arguments
+///
+/// * `rpc` - RPC client used to poll the signature status
+/// * `signature` - Signature of the submitted transaction
+/// * `recent_blockhash` - Blockhash the transaction was signed with
+///
+/// # Returns
+///
+/// Returns the signature once confirmed, or a ClientError if the transaction failed or expired
+pub async fn confirm_transaction(
+    rpc: &RpcClient,
+    signature: &Signature,
+    recent_blockhash: &Hash,
+) -> Result<Signature, error::ClientError> {
+    Ok(await_confirmation(rpc, signature, recent_blockhash).await?)
+}
+
+/// Reason a transaction was not confirmed, kept `Send` so it can leave spawned tasks
+pub(crate) enum Unconfirmed {
+    Expired(Signature),
+    Rpc(solana_client::client_error::ClientError),
+}
+
+impl From<Unconfirmed> for error::ClientError {
+    fn from(err: Unconfirmed) -> Self {
+        match err {
+            Unconfirmed::Expired(signature) => Self::TransactionExpired(signature),
+            Unconfirmed::Rpc(err) => err.into(),
+        }
+    }
+}
+
+/// Polls the signature status like `confirm_transaction`
+pub(crate) async fn await_confirmation(
+    rpc: &RpcClient,
+    signature: &Signature,
+    recent_blockhash: &Hash,
+) -> Result<Signature, Unconfirmed> {
+    loop {
+        match rpc
+            .get_signature_status(signature)
+            .await
+            .map_err(Unconfirmed::Rpc)?
+        {
+            Some(Ok(())) => return Ok(*signature),
+            Some(Err(err)) => return Err(Unconfirmed::Rpc(err.into())),
+            None => {
+                if !rpc
+                    .is_blockhash_valid(recent_blockhash, CommitmentConfig::processed())
+                    .await
+                    .map_err(Unconfirmed::Rpc)?
+                {
+                    return Err(Unconfirmed::Expired(*signature));
+                }
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            }
+        }
+    }
+}
+
+/// Submits a transaction to a relay endpoint and waits for RPC confirmation
+pub(crate) async fn submit_and_confirm(
+    http: &dyn HttpClient,
+    rpc: &RpcClient,
+    request: HttpRequest,
+    transaction: &VersionedTransaction,
+    extract_signature: fn(&Value) -> Option<&str>,
+) -> Result<Signature, error::ClientError> {
+    let response = post_json(http, request).await?;
+
+    // Prefer the signature reported by the relay, but fall back to the one we signed
+    let signature = extract_signature(&response)
+        .and_then(|sig| sig.parse::<Signature>().ok())
+        .unwrap_or(*transaction.get_signature());
+
+    confirm_transaction(rpc, &signature, transaction.get_recent_blockhash()).await
+}
+
+/// Sends a JSON request and returns the parsed response body
+pub(crate) async fn post_json(
+    http: &dyn HttpClient,
+    request: HttpRequest,
+) -> Result<Value, error::ClientError> {
+    let uri = request.url.clone();
+    let response = http.send(request).await.map_err(|err| {
+        error::ClientError::SenderError(format!("Request to {} failed: {}", uri, err))
+    })?;
+
+    if response.status == 429 {
+        let retry_after = response
+            .header("Retry-After")
+            .and_then(error::parse_retry_after);
+        return Err(error::ClientError::rate_limited(retry_after));
+    }
+
+    let text = response.text();
+    if !response.is_success() {
+        return Err(error::ClientError::SenderError(format!(
+            "{} responded with {}: {}",
+            uri, response.status, text
+        )));
+    }
+
+    let json: Value = serde_json::from_str(&text).map_err(|err| {
+        error::ClientError::SenderError(format!("Invalid response from {}: {}", uri, err))
+    })?;
+
+    if let Some(err) = json.get("error") {
+        return Err(error::ClientError::SenderError(format!(
+            "{} rejected transaction: {}",
+            uri, err
+        )));
+    }
+
+    Ok(json)
+}
+
+/// Builds a JSON POST request
+pub(crate) fn json_request(uri: &str, headers: &[(&str, &str)], body: &Value) -> HttpRequest {
+    headers.iter().fold(
+        HttpRequest::post(uri, body.to_string().into_bytes())
+            .header("Content-Type", "application/json"),
+        |request, (name, value)| request.header(*name, *value),
+    )
+}
+
+/// Builds a JSON-RPC `sendTransaction` request body
+pub(crate) fn send_transaction_body(encoded: &str, config: Value) -> Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "sendTransaction",
+        "params": [encoded, config],
+    })
+}
+
+/// Extracts the signature from a JSON-RPC response
+pub(crate) fn json_rpc_signature(response: &Value) -> Option<&str> {
+    response.get("result").and_then(Value::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        message::Message, signature::Keypair, signer::Signer, transaction::Transaction,
+    };
+    use solana_system_interface::instruction as system_instruction;
+
+    use crate::utils::http::{HttpError, HttpMethod, HttpResponse};
+
+    fn get_transaction() -> VersionedTransaction {
+        let payer = Keypair::new();
+        let ix = system_instruction::transfer(&payer.pubkey(), &payer.pubkey(), 1);
+        let message = Message::new(&[ix], Some(&payer.pubkey()));
+        Transaction::new(&[&payer], message, Hash::default()).into()
+    }
+
+    #[test]
+    fn test_encode_transaction() {
+        let transaction = get_transaction();
+        let encoded = encode_transaction(&transaction).unwrap();
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .unwrap();
+        let roundtrip: VersionedTransaction = bincode::deserialize(&decoded).unwrap();
+        assert_eq!(roundtrip, transaction);
+    }
+
+    #[test]
+    fn test_send_transaction_body() {
+        let body = send_transaction_body("abc", serde_json::json!({"encoding": "base64"}));
+        assert_eq!(body["method"], "sendTransaction");
+        assert_eq!(body["params"][0], "abc");
+        assert_eq!(body["params"][1]["encoding"], "base64");
+
+        let response = serde_json::json!({"jsonrpc": "2.0", "result": "sig", "id": 1});
+        assert_eq!(json_rpc_signature(&response), Some("sig"));
+    }
+
+    /// Replies to every request with a fixed response, recording the requests
+    struct StaticClient {
+        response: HttpResponse,
+        requests: std::sync::Mutex<Vec<HttpRequest>>,
+    }
+
+    #[async_trait]
+    impl HttpClient for StaticClient {
+        async fn send(&self, request: HttpRequest) -> Result<HttpResponse, HttpError> {
+            self.requests.lock().unwrap().push(request);
+            Ok(self.response.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_post_json() {
+        let client = |status, headers: Vec<(&str, &str)>, body: &str| StaticClient {
+            response: HttpResponse {
+                status,
+                headers: headers
+                    .into_iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect(),
+                body: body.as_bytes().to_vec(),
+            },
+            requests: std::sync::Mutex::new(Vec::new()),
+        };
+        let request = || {
+            json_request(
+                "https://relay.example.com",
+                &[("x-auth", "key")],
+                &serde_json::json!({"id": 1}),
+            )
+        };
+
+        let http = client(200, vec![], r#"{"result": "sig"}"#);
+        let response = post_json(&http, request()).await.unwrap();
+        assert_eq!(json_rpc_signature(&response), Some("sig"));
+        let sent = http.requests.lock().unwrap().remove(0);
+        assert_eq!(sent.method, HttpMethod::Post);
+        assert_eq!(
+            sent.headers,
+            vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("x-auth".to_string(), "key".to_string()),
+            ]
+        );
+
+        let http = client(429, vec![("retry-after", "3")], "");
+        let err = post_json(&http, request()).await.unwrap_err();
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(3)));
+
+        let http = client(200, vec![], r#"{"error": "bad tip"}"#);
+        assert!(matches!(
+            post_json(&http, request()).await,
+            Err(error::ClientError::SenderError(_))
+        ));
+    }
+}
@@ -0,0 +1,157 @@
+//! Sender that broadcasts transactions to several RPC endpoints at once
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_client::SerializableTransaction};
+use solana_sdk::{
+    commitment_config::CommitmentConfig, signature::Signature, transaction::VersionedTransaction,
+};
+use tokio::task::JoinSet;
+
+use super::{await_confirmation, TransactionSender, Unconfirmed};
+use crate::error;
+
+/// Sends the same transaction to multiple RPC endpoints concurrently
+///
+/// Every endpoint submits and confirms the transaction independently, and the sender resolves
+/// as soon as the first one confirms it. If none does, the error of the last endpoint is
+/// returned, e.g. `ClientError::TransactionExpired` once the blockhash has expired. Broadcasting to several providers improves land rates
+/// during congestion since a slow or overloaded node no longer delays the transaction.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pumpfun::sender::MultiRpcSender;
+/// use solana_sdk::commitment_config::CommitmentConfig;
+///
+/// let sender = MultiRpcSender::new(
+///     &[
+///         "https://api.mainnet-beta.solana.com".to_string(),
+///         "https://my-provider.example.com".to_string(),
+///     ],
+///     CommitmentConfig::confirmed(),
+/// );
+/// ```
+pub struct MultiRpcSender {
+    /// RPC clients the transaction is broadcast to
+    pub rpcs: Vec<Arc<RpcClient>>,
+}
+
+impl MultiRpcSender {
+    /// Creates a new multi-RPC sender from a list of HTTP endpoints
+    ///
+    /// # Arguments
+    ///
+    /// * `urls` - HTTP RPC endpoints to broadcast transactions to
+    /// * `commitment` - Commitment level used when confirming transactions
+    ///
+    /// # Returns
+    ///
+    /// Returns a sender with one RPC client per endpoint
+    pub fn new(urls: &[String], commitment: CommitmentConfig) -> Self {
+        let rpcs = urls
+            .iter()
+            .map(|url| Arc::new(RpcClient::new_with_commitment(url.clone(), commitment)))
+            .collect();
+        Self { rpcs }
+    }
+
+    /// Creates a new multi-RPC sender from existing RPC clients
+    ///
+    /// # Arguments
+    ///
+    /// * `rpcs` - RPC clients to broadcast transactions to
+    pub fn from_clients(rpcs: Vec<Arc<RpcClient>>) -> Self {
+        Self { rpcs }
+    }
+}
+
+#[async_trait]
+impl TransactionSender for MultiRpcSender {
+    async fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Result<Signature, error::ClientError> {
+        if self.rpcs.is_empty() {
+            return Err(error::ClientError::SenderError(
+                "No RPC endpoints configured".to_string(),
+            ));
+        }
+
+        let mut tasks = JoinSet::new();
+        for rpc in &self.rpcs {
+            let rpc = rpc.clone();
+            let transaction = transaction.clone();
+            tasks.spawn(async move {
+                let signature = rpc
+                    .send_transaction(&transaction)
+                    .await
+                    .map_err(Unconfirmed::Rpc)?;
+                await_confirmation(&rpc, &signature, transaction.get_recent_blockhash()).await
+            });
+        }
+
+        // Resolve on the first confirmation; remaining tasks are aborted when the set is dropped
+        let mut last_error = None;
+        while let Some(result) = tasks.join_next().await {
+            match result {
+                Ok(Ok(signature)) => return Ok(signature),
+                Ok(Err(err)) => last_error = Some(err),
+                // A panicked or cancelled task counts as a failed endpoint
+                Err(_) => {}
+            }
+        }
+
+        Err(match last_error {
+            Some(err) => err.into(),
+            None => error::ClientError::SenderError("All RPC endpoints failed".to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_sdk::{
+        hash::Hash, message::Message, signature::Keypair, signer::Signer, transaction::Transaction,
+    };
+    use solana_system_interface::instruction as system_instruction;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn test_multi_rpc_sender_requires_endpoints() {
+        let sender = MultiRpcSender::new(&[], CommitmentConfig::confirmed());
+        let transaction = VersionedTransaction::default();
+        let result = sender.send_transaction(&transaction).await;
+        assert!(matches!(result, Err(error::ClientError::SenderError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_multi_rpc_sender_reports_expiry() {
+        let rpcs = (0..2)
+            .map(|_| {
+                let mocks = HashMap::from([(
+                    RpcRequest::IsBlockhashValid,
+                    json!({"context": {"slot": 1}, "value": false}),
+                )]);
+                Arc::new(RpcClient::new_mock_with_mocks(
+                    "sig_not_found".to_string(),
+                    mocks,
+                ))
+            })
+            .collect();
+        let sender = MultiRpcSender::from_clients(rpcs);
+
+        let payer = Keypair::new();
+        let ix = system_instruction::transfer(&payer.pubkey(), &payer.pubkey(), 1);
+        let message = Message::new(&[ix], Some(&payer.pubkey()));
+        let transaction: VersionedTransaction =
+            Transaction::new(&[&payer], message, Hash::default()).into();
+
+        let err = sender.send_transaction(&transaction).await.unwrap_err();
+        assert!(err.is_blockhash_expired());
+    }
+}
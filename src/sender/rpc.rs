@@ -0,0 +1,81 @@
+//! Default sender that submits transactions through a Solana RPC node
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_client::SerializableTransaction};
+use solana_sdk::{signature::Signature, transaction::VersionedTransaction};
+
+use super::TransactionSender;
+use crate::error;
+
+/// Sends transactions with RPC `sendTransaction` and waits for confirmation
+///
+/// This is the sender used by `PumpFun` unless another one is configured. Transactions
+/// whose blockhash expires before they are confirmed fail with
+/// `ClientError::TransactionExpired`, so the client's retry policy can rebuild them.
+pub struct RpcSender {
+    /// RPC client used to submit and confirm transactions
+    pub rpc: Arc<RpcClient>,
+}
+
+impl RpcSender {
+    /// Creates a new RPC sender
+    ///
+    /// # Arguments
+    ///
+    /// * `rpc` - RPC client used to submit and confirm transactions
+    pub fn new(rpc: Arc<RpcClient>) -> Self {
+        Self { rpc }
+    }
+}
+
+#[async_trait]
+impl TransactionSender for RpcSender {
+    async fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Result<Signature, error::ClientError> {
+        let signature = self.rpc.send_transaction(transaction).await?;
+        super::confirm_transaction(&self.rpc, &signature, transaction.get_recent_blockhash()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::RetryPolicy;
+    use serde_json::json;
+    use solana_client::rpc_request::RpcRequest;
+    use solana_sdk::{
+        hash::Hash, message::Message, signature::Keypair, signer::Signer, transaction::Transaction,
+    };
+    use solana_system_interface::instruction as system_instruction;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn test_expired_transaction_is_retried() {
+        // The transaction never lands and its blockhash is no longer valid
+        let mocks = HashMap::from([(
+            RpcRequest::IsBlockhashValid,
+            json!({"context": {"slot": 1}, "value": false}),
+        )]);
+        let rpc = RpcClient::new_mock_with_mocks("sig_not_found".to_string(), mocks);
+        let sender = RpcSender::new(Arc::new(rpc));
+
+        let payer = Keypair::new();
+        let ix = system_instruction::transfer(&payer.pubkey(), &payer.pubkey(), 1);
+        let message = Message::new(&[ix], Some(&payer.pubkey()));
+        let transaction: VersionedTransaction =
+            Transaction::new(&[&payer], message, Hash::default()).into();
+
+        let err = sender.send_transaction(&transaction).await.unwrap_err();
+        assert!(matches!(
+            err,
+            error::ClientError::TransactionExpired(signature)
+                if signature == *transaction.get_signature()
+        ));
+        assert!(err.is_blockhash_expired());
+        assert!(RetryPolicy::default().retry_delay(1, &err).is_some());
+    }
+}
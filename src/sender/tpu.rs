@@ -0,0 +1,116 @@
+//! Sender that submits transactions directly to leader TPUs over QUIC
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use solana_client::{
+    nonblocking::{rpc_client::RpcClient, tpu_client::TpuClient},
+    rpc_client::SerializableTransaction,
+    tpu_client::TpuClientConfig,
+};
+use solana_quic_client::{QuicConfig, QuicConnectionManager, QuicPool};
+use solana_sdk::{signature::Signature, transaction::VersionedTransaction};
+
+use super::{RpcSender, TransactionSender};
+use crate::error;
+
+/// Sends transactions straight to the current and upcoming leaders over QUIC
+///
+/// Leader schedule and contact info are tracked through RPC and WebSocket subscriptions.
+/// Transactions bypass RPC `sendTransaction` and are confirmed over RPC afterwards. If the
+/// transaction cannot be delivered to any leader, it is sent through RPC instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pumpfun::{common::types::{Cluster, PriorityFee}, sender::TpuSender, PumpFun};
+/// use solana_sdk::{commitment_config::CommitmentConfig, signature::Keypair};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let payer = Arc::new(Keypair::new());
+/// let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+/// let client = PumpFun::new(payer, cluster.clone());
+///
+/// let tpu = TpuSender::new(client.rpc.clone(), &cluster.rpc.ws).await?;
+/// let client = client.with_sender(Arc::new(tpu));
+/// # Ok(())
+/// # }
+/// ```
+pub struct TpuSender {
+    /// QUIC client sending to leader TPUs
+    pub tpu: TpuClient<QuicPool, QuicConnectionManager, QuicConfig>,
+    /// RPC client used for confirmation and fallback submission
+    pub rpc: Arc<RpcClient>,
+    fallback: RpcSender,
+}
+
+impl TpuSender {
+    /// Creates a new TPU sender with the default fanout
+    ///
+    /// # Arguments
+    ///
+    /// * `rpc` - RPC client used for leader discovery, confirmation and fallback submission
+    /// * `ws` - WebSocket endpoint used to track slot updates
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the leader schedule or cluster nodes cannot be fetched
+    pub async fn new(rpc: Arc<RpcClient>, ws: &str) -> Result<Self, error::ClientError> {
+        Self::new_with_config(rpc, ws, TpuClientConfig::default()).await
+    }
+
+    /// Creates a new TPU sender with a custom configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `rpc` - RPC client used for leader discovery, confirmation and fallback submission
+    /// * `ws` - WebSocket endpoint used to track slot updates
+    /// * `config` - TPU client configuration, such as the number of leaders to fan out to
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the leader schedule or cluster nodes cannot be fetched
+    pub async fn new_with_config(
+        rpc: Arc<RpcClient>,
+        ws: &str,
+        config: TpuClientConfig,
+    ) -> Result<Self, error::ClientError> {
+        let tpu = TpuClient::new("pumpfun-tpu", rpc.clone(), ws, config)
+            .await
+            .map_err(|err| {
+                error::ClientError::SenderError(format!("Failed to create TPU client: {}", err))
+            })?;
+
+        Ok(Self {
+            tpu,
+            fallback: RpcSender::new(rpc.clone()),
+            rpc,
+        })
+    }
+}
+
+#[async_trait]
+impl TransactionSender for TpuSender {
+    async fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Result<Signature, error::ClientError> {
+        let wire = bincode::serialize(transaction).map_err(|err| {
+            error::ClientError::OtherError(format!("Failed to serialize transaction: {}", err))
+        })?;
+
+        match self.tpu.try_send_wire_transaction(wire).await {
+            Ok(()) => {
+                super::confirm_transaction(
+                    &self.rpc,
+                    transaction.get_signature(),
+                    transaction.get_recent_blockhash(),
+                )
+                .await
+            }
+            // No leader accepted the transaction, so fall back to RPC submission
+            Err(_) => self.fallback.send_transaction(transaction).await,
+        }
+    }
+}
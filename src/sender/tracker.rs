@@ -0,0 +1,435 @@
+//! Signature status tracking for submitted transactions
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, Weak},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient, rpc_client::SerializableTransaction,
+    rpc_config::RpcSendTransactionConfig,
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    signature::Signature,
+    transaction::{TransactionError, VersionedTransaction},
+};
+use tokio::sync::{broadcast, oneshot};
+
+use super::TransactionSender;
+use crate::error;
+
+/// Maximum number of signatures accepted by a single `getSignatureStatuses` request
+const MAX_STATUSES_PER_REQUEST: usize = 256;
+
+/// Final outcome of a tracked transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxOutcome {
+    /// The transaction was confirmed at the RPC client's commitment and succeeded
+    Landed {
+        /// Transaction signature
+        signature: Signature,
+        /// Slot the transaction was processed in
+        slot: u64,
+    },
+    /// The transaction's blockhash expired before it was confirmed
+    Expired {
+        /// Transaction signature
+        signature: Signature,
+    },
+    /// The transaction was confirmed but failed on-chain
+    Failed {
+        /// Transaction signature
+        signature: Signature,
+        /// Slot the transaction was processed in
+        slot: u64,
+        /// Error returned by the runtime or program
+        error: TransactionError,
+    },
+}
+
+impl TxOutcome {
+    /// Gets the signature of the tracked transaction
+    pub fn signature(&self) -> &Signature {
+        match self {
+            Self::Landed { signature, .. }
+            | Self::Expired { signature }
+            | Self::Failed { signature, .. } => signature,
+        }
+    }
+
+    /// Converts the outcome into the result returned by a `TransactionSender`
+    ///
+    /// # Returns
+    ///
+    /// The signature if the transaction landed, otherwise the matching ClientError
+    pub fn into_result(self) -> Result<Signature, error::ClientError> {
+        match self {
+            Self::Landed { signature, .. } => Ok(signature),
+            Self::Expired { signature } => Err(error::ClientError::TransactionExpired(signature)),
+            Self::Failed { error, .. } => Err(error.into()),
+        }
+    }
+}
+
+/// Configuration for a `TxTracker`
+///
+/// # Fields
+///
+/// * `poll_interval` - Delay between signature status polls
+/// * `resubmit_interval` - Delay between resubmissions of a pending transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxTrackerConfig {
+    pub poll_interval: Duration,
+    pub resubmit_interval: Duration,
+}
+
+impl Default for TxTrackerConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_millis(500),
+            resubmit_interval: Duration::from_secs(2),
+        }
+    }
+}
+
+struct PendingTx {
+    transaction: VersionedTransaction,
+    last_valid_block_height: Option<u64>,
+    last_sent: Instant,
+    waiters: Vec<oneshot::Sender<TxOutcome>>,
+}
+
+/// Tracks submitted transactions until they land, fail, or expire
+///
+/// Pending transactions are polled in batches with `getSignatureStatuses` and resubmitted
+/// periodically until their blockhash expires, which greatly improves land rates during
+/// congestion. Each transaction resolves to a typed `TxOutcome`, delivered both to the
+/// caller that registered it and to every subscriber.
+///
+/// The tracker also implements `TransactionSender`, submitting through its RPC client, so it
+/// can be used as the sender of a `PumpFun` client to track every transaction it sends.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pumpfun::{
+///     common::types::{Cluster, PriorityFee},
+///     sender::{TxTracker, TxTrackerConfig},
+///     PumpFun,
+/// };
+/// use solana_sdk::{commitment_config::CommitmentConfig, signature::Keypair};
+/// use std::sync::Arc;
+///
+/// # async fn example() {
+/// let payer = Arc::new(Keypair::new());
+/// let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+/// let client = PumpFun::new(payer, cluster);
+///
+/// let tracker = TxTracker::new(client.rpc.clone(), TxTrackerConfig::default());
+/// let mut outcomes = tracker.subscribe();
+/// let client = client.with_sender(tracker);
+///
+/// tokio::spawn(async move {
+///     while let Ok(outcome) = outcomes.recv().await {
+///         println!("{:?}", outcome);
+///     }
+/// });
+/// # }
+/// ```
+pub struct TxTracker {
+    /// RPC client used to submit transactions and poll their statuses
+    pub rpc: Arc<RpcClient>,
+    config: TxTrackerConfig,
+    pending: Mutex<HashMap<Signature, PendingTx>>,
+    outcomes: broadcast::Sender<TxOutcome>,
+}
+
+impl TxTracker {
+    /// Creates a new tracker and starts polling in the background
+    ///
+    /// Must be called from within a Tokio runtime. The background task stops once every
+    /// reference to the tracker has been dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `rpc` - RPC client used to submit transactions and poll their statuses; its
+    ///   commitment determines when a transaction counts as landed
+    /// * `config` - Polling and resubmission intervals
+    ///
+    /// # Returns
+    ///
+    /// A shared handle to the tracker
+    pub fn new(rpc: Arc<RpcClient>, config: TxTrackerConfig) -> Arc<Self> {
+        let (outcomes, _) = broadcast::channel(256);
+        let tracker = Arc::new(Self {
+            rpc,
+            config,
+            pending: Mutex::new(HashMap::new()),
+            outcomes,
+        });
+
+        let weak: Weak<Self> = Arc::downgrade(&tracker);
+        tokio::spawn(async move {
+            while let Some(tracker) = weak.upgrade() {
+                if let Err(err) = tracker.poll().await {
+                    log_warn!("Failed to poll transaction statuses: {}", err);
+                }
+                let interval = tracker.config.poll_interval;
+                drop(tracker);
+                tokio::time::sleep(interval).await;
+            }
+        });
+
+        tracker
+    }
+
+    /// Registers a submitted transaction for tracking
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - Signed transaction that has already been submitted
+    /// * `last_valid_block_height` - Last block height at which the transaction's blockhash is
+    ///   valid. If None, expiry is detected by checking whether the blockhash is still valid
+    ///
+    /// # Returns
+    ///
+    /// A receiver that resolves with the transaction's final outcome
+    pub fn track(
+        &self,
+        transaction: VersionedTransaction,
+        last_valid_block_height: Option<u64>,
+    ) -> oneshot::Receiver<TxOutcome> {
+        let (tx, rx) = oneshot::channel();
+        let mut pending = self.lock_pending();
+        pending
+            .entry(*transaction.get_signature())
+            .or_insert_with(|| PendingTx {
+                transaction,
+                last_valid_block_height,
+                last_sent: Instant::now(),
+                waiters: Vec::new(),
+            })
+            .waiters
+            .push(tx);
+        rx
+    }
+
+    /// Subscribes to the outcomes of all tracked transactions
+    ///
+    /// # Returns
+    ///
+    /// A broadcast receiver yielding every final outcome
+    pub fn subscribe(&self) -> broadcast::Receiver<TxOutcome> {
+        self.outcomes.subscribe()
+    }
+
+    /// Gets the number of transactions still awaiting a final outcome
+    pub fn pending(&self) -> usize {
+        self.lock_pending().len()
+    }
+
+    /// Runs a single round of resubmission and status polling
+    ///
+    /// This is called automatically by the background task, but can be called directly to
+    /// resolve pending transactions immediately.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signature statuses or block height cannot be fetched
+    pub async fn poll(&self) -> Result<(), error::ClientError> {
+        // Snapshot the pending set so the lock is not held across requests
+        let mut resubmit = Vec::new();
+        let mut tracked: Vec<(Signature, Hash, Option<u64>)> = Vec::new();
+        {
+            let mut pending = self.lock_pending();
+            for (signature, entry) in pending.iter_mut() {
+                if entry.last_sent.elapsed() >= self.config.resubmit_interval {
+                    entry.last_sent = Instant::now();
+                    resubmit.push(entry.transaction.clone());
+                }
+                tracked.push((
+                    *signature,
+                    *entry.transaction.get_recent_blockhash(),
+                    entry.last_valid_block_height,
+                ));
+            }
+        }
+
+        if tracked.is_empty() {
+            return Ok(());
+        }
+
+        let config = RpcSendTransactionConfig {
+            skip_preflight: true,
+            max_retries: Some(0),
+            ..RpcSendTransactionConfig::default()
+        };
+        for transaction in &resubmit {
+            // Failed resubmissions are harmless; the status poll decides the outcome
+            let _ = self
+                .rpc
+                .send_transaction_with_config(transaction, config)
+                .await;
+        }
+
+        let signatures: Vec<Signature> = tracked.iter().map(|(signature, ..)| *signature).collect();
+        let mut statuses = Vec::with_capacity(signatures.len());
+        for chunk in signatures.chunks(MAX_STATUSES_PER_REQUEST) {
+            statuses.extend(self.rpc.get_signature_statuses(chunk).await?.value);
+        }
+
+        let block_height = if tracked.iter().any(|(.., height)| height.is_some()) {
+            Some(self.rpc.get_block_height().await?)
+        } else {
+            None
+        };
+
+        let commitment = self.rpc.commitment();
+        for ((signature, blockhash, last_valid_block_height), status) in
+            tracked.into_iter().zip(statuses)
+        {
+            let outcome = match status {
+                Some(status) if status.satisfies_commitment(commitment) => match status.err {
+                    None => TxOutcome::Landed {
+                        signature,
+                        slot: status.slot,
+                    },
+                    Some(error) => TxOutcome::Failed {
+                        signature,
+                        slot: status.slot,
+                        error,
+                    },
+                },
+                _ => {
+                    let expired = match (last_valid_block_height, block_height) {
+                        (Some(last_valid), Some(current)) => current > last_valid,
+                        _ => {
+                            !self
+                                .rpc
+                                .is_blockhash_valid(&blockhash, CommitmentConfig::processed())
+                                .await?
+                        }
+                    };
+                    if !expired {
+                        continue;
+                    }
+                    TxOutcome::Expired { signature }
+                }
+            };
+
+            self.resolve(outcome);
+        }
+
+        Ok(())
+    }
+
+    fn resolve(&self, outcome: TxOutcome) {
+        let entry = self.lock_pending().remove(outcome.signature());
+        if let Some(entry) = entry {
+            for waiter in entry.waiters {
+                // The caller may have stopped waiting
+                let _ = waiter.send(outcome.clone());
+            }
+        }
+        // Sending only fails when nobody is subscribed
+        let _ = self.outcomes.send(outcome);
+    }
+
+    fn lock_pending(&self) -> std::sync::MutexGuard<'_, HashMap<Signature, PendingTx>> {
+        match self.pending.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+#[async_trait]
+impl TransactionSender for TxTracker {
+    async fn send_transaction(
+        &self,
+        transaction: &VersionedTransaction,
+    ) -> Result<Signature, error::ClientError> {
+        self.rpc
+            .send_transaction(transaction)
+            .await
+            .map_err(error::ClientError::from)?;
+
+        let outcome = self.track(transaction.clone(), None);
+        match outcome.await {
+            Ok(outcome) => outcome.into_result(),
+            Err(_) => Err(error::ClientError::SenderError(
+                "Transaction tracker stopped before the transaction resolved".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{
+        instruction::InstructionError, message::Message, signature::Keypair, signer::Signer,
+        transaction::Transaction,
+    };
+    use solana_system_interface::instruction as system_instruction;
+
+    fn get_transaction() -> VersionedTransaction {
+        let payer = Keypair::new();
+        let ix = system_instruction::transfer(&payer.pubkey(), &payer.pubkey(), 1);
+        let message = Message::new(&[ix], Some(&payer.pubkey()));
+        Transaction::new(&[&payer], message, Hash::default()).into()
+    }
+
+    #[test]
+    fn test_outcome_into_result() {
+        let signature = Signature::default();
+        let landed = TxOutcome::Landed { signature, slot: 1 };
+        assert_eq!(landed.into_result().unwrap(), signature);
+
+        let expired = TxOutcome::Expired { signature };
+        assert!(expired.into_result().unwrap_err().is_blockhash_expired());
+
+        let failed = TxOutcome::Failed {
+            signature,
+            slot: 1,
+            error: TransactionError::InsufficientFundsForFee,
+        };
+        assert!(matches!(
+            failed.into_result(),
+            Err(error::ClientError::SolanaClientError(_))
+        ));
+
+        let failed = TxOutcome::Failed {
+            signature,
+            slot: 1,
+            error: TransactionError::InstructionError(3, InstructionError::Custom(6002)),
+        };
+        assert_eq!(
+            failed.into_result().unwrap_err().program_error(),
+            Some(error::ProgramError::TooMuchSolRequired)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tracker_resolves_landed() {
+        let rpc = Arc::new(RpcClient::new_mock("succeeds".to_string()));
+        let tracker = TxTracker::new(rpc, TxTrackerConfig::default());
+        let mut outcomes = tracker.subscribe();
+
+        let transaction = get_transaction();
+        let signature = *transaction.get_signature();
+        let receiver = tracker.track(transaction, None);
+        assert_eq!(tracker.pending(), 1);
+
+        tracker.poll().await.unwrap();
+        let outcome = receiver.await.unwrap();
+        assert!(matches!(outcome, TxOutcome::Landed { .. }));
+        assert_eq!(*outcome.signature(), signature);
+        assert_eq!(outcomes.recv().await.unwrap(), outcome);
+        assert_eq!(tracker.pending(), 0);
+    }
+}
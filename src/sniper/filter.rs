@@ -0,0 +1,341 @@
+//! Filter rules deciding which launches the sniper buys
+
+use std::collections::HashSet;
+
+use regex::Regex;
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    accounts::BondingCurveAccount,
+    common::stream::{CreateEvent, TradeEvent},
+    error,
+};
+
+/// Token launch observed by the sniper
+///
+/// # Fields
+///
+/// * `signature` - Signature of the creation transaction
+/// * `create` - Creation event of the token
+/// * `dev_buy` - Buy of the token's creator in the creation transaction, if any
+#[derive(Debug)]
+pub struct Launch {
+    pub signature: String,
+    pub create: CreateEvent,
+    pub dev_buy: Option<TradeEvent>,
+}
+
+impl Launch {
+    /// Returns the SOL spent by the creator in the creation transaction, in lamports
+    pub fn dev_buy_sol(&self) -> u64 {
+        self.dev_buy.as_ref().map_or(0, |buy| buy.sol_amount)
+    }
+
+    /// Returns the bonding curve state after the creation transaction
+    pub fn bonding_curve(&self) -> BondingCurveAccount {
+        let create = &self.create;
+        match &self.dev_buy {
+            Some(buy) => BondingCurveAccount::new(
+                0,
+                buy.virtual_token_reserves,
+                buy.virtual_sol_reserves,
+                buy.real_token_reserves,
+                buy.real_sol_reserves,
+                create.token_total_supply,
+                false,
+                create.creator,
+            ),
+            None => BondingCurveAccount::new(
+                0,
+                create.virtual_token_reserves,
+                create.virtual_sol_reserves,
+                create.real_token_reserves,
+                0,
+                create.token_total_supply,
+                false,
+                create.creator,
+            ),
+        }
+    }
+}
+
+/// Social link of a token's metadata
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Social {
+    Twitter,
+    Telegram,
+    Website,
+}
+
+/// Social links read from a token's metadata JSON
+///
+/// # Fields
+///
+/// * `twitter` - Twitter link or handle
+/// * `telegram` - Telegram link or handle
+/// * `website` - Website URL
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct TokenLinks {
+    pub twitter: Option<String>,
+    pub telegram: Option<String>,
+    pub website: Option<String>,
+}
+
+impl TokenLinks {
+    /// Returns whether the given link is set and not blank
+    pub fn has(&self, social: Social) -> bool {
+        let link = match social {
+            Social::Twitter => &self.twitter,
+            Social::Telegram => &self.telegram,
+            Social::Website => &self.website,
+        };
+        link.as_deref().is_some_and(|link| !link.trim().is_empty())
+    }
+}
+
+/// Rules a launch must satisfy to be sniped
+///
+/// An empty filter matches every launch. Each configured rule must pass:
+///
+/// - Name and symbol patterns must match the token's name and symbol
+/// - The creator must be on the allowlist, if one is set, and not on the denylist
+/// - The creator's buy in the creation transaction must be within the dev-buy bounds,
+///   where a launch without a dev buy counts as zero
+/// - The metadata must contain every required social link, which requires fetching the
+///   metadata JSON from the token's URI
+///
+/// # Examples
+///
+/// ```
+/// # use pumpfun::sniper::{Social, SniperFilter};
+/// # use solana_sdk::native_token::sol_to_lamports;
+/// #
+/// # fn example() -> Result<(), pumpfun::error::ClientError> {
+/// let filter = SniperFilter::new()
+///     .symbol("^[A-Z]{3,6}$")?
+///     .min_dev_buy(sol_to_lamports(0.5))
+///     .max_dev_buy(sol_to_lamports(5.0))
+///     .require_socials([Social::Twitter, Social::Website]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SniperFilter {
+    name: Option<Regex>,
+    symbol: Option<Regex>,
+    allowed_creators: HashSet<Pubkey>,
+    denied_creators: HashSet<Pubkey>,
+    min_dev_buy: Option<u64>,
+    max_dev_buy: Option<u64>,
+    socials: HashSet<Social>,
+}
+
+impl SniperFilter {
+    /// Creates a filter matching every launch
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires the token name to match a regular expression
+    ///
+    /// # Errors
+    ///
+    /// Returns a ConfigError if the pattern is not a valid regular expression
+    pub fn name(mut self, pattern: &str) -> Result<Self, error::ClientError> {
+        self.name = Some(compile(pattern)?);
+        Ok(self)
+    }
+
+    /// Requires the token symbol to match a regular expression
+    ///
+    /// # Errors
+    ///
+    /// Returns a ConfigError if the pattern is not a valid regular expression
+    pub fn symbol(mut self, pattern: &str) -> Result<Self, error::ClientError> {
+        self.symbol = Some(compile(pattern)?);
+        Ok(self)
+    }
+
+    /// Only matches launches by the given creators
+    pub fn allow_creators(mut self, creators: impl IntoIterator<Item = Pubkey>) -> Self {
+        self.allowed_creators.extend(creators);
+        self
+    }
+
+    /// Never matches launches by the given creators
+    pub fn deny_creators(mut self, creators: impl IntoIterator<Item = Pubkey>) -> Self {
+        self.denied_creators.extend(creators);
+        self
+    }
+
+    /// Requires the creator to buy at least this many lamports in the creation transaction
+    pub fn min_dev_buy(mut self, lamports: u64) -> Self {
+        self.min_dev_buy = Some(lamports);
+        self
+    }
+
+    /// Requires the creator to buy at most this many lamports in the creation transaction
+    pub fn max_dev_buy(mut self, lamports: u64) -> Self {
+        self.max_dev_buy = Some(lamports);
+        self
+    }
+
+    /// Requires the token metadata to contain the given social links
+    pub fn require_socials(mut self, socials: impl IntoIterator<Item = Social>) -> Self {
+        self.socials.extend(socials);
+        self
+    }
+
+    /// Returns whether a launch passes every rule that does not need its metadata
+    pub fn matches(&self, launch: &Launch) -> bool {
+        let create = &launch.create;
+        if self
+            .name
+            .as_ref()
+            .is_some_and(|name| !name.is_match(&create.name))
+        {
+            return false;
+        }
+        if self
+            .symbol
+            .as_ref()
+            .is_some_and(|symbol| !symbol.is_match(&create.symbol))
+        {
+            return false;
+        }
+        if !self.allowed_creators.is_empty() && !self.allowed_creators.contains(&create.creator) {
+            return false;
+        }
+        if self.denied_creators.contains(&create.creator) {
+            return false;
+        }
+        let dev_buy = launch.dev_buy_sol();
+        self.min_dev_buy.is_none_or(|min| dev_buy >= min)
+            && self.max_dev_buy.is_none_or(|max| dev_buy <= max)
+    }
+
+    /// Returns whether the metadata must be fetched to apply the filter
+    pub fn requires_metadata(&self) -> bool {
+        !self.socials.is_empty()
+    }
+
+    /// Returns whether the social links of a token's metadata pass the filter
+    pub fn matches_links(&self, links: &TokenLinks) -> bool {
+        self.socials.iter().all(|social| links.has(*social))
+    }
+}
+
+fn compile(pattern: &str) -> Result<Regex, error::ClientError> {
+    Regex::new(pattern).map_err(|err| {
+        error::ClientError::ConfigError(format!("Invalid pattern {:?}: {}", pattern, err))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launch(symbol: &str, creator: Pubkey, dev_buy: Option<u64>) -> Launch {
+        let create = CreateEvent {
+            name: "Token".to_string(),
+            symbol: symbol.to_string(),
+            uri: String::new(),
+            mint: Pubkey::new_unique(),
+            bonding_curve: Pubkey::new_unique(),
+            user: creator,
+            creator,
+            timestamp: 0,
+            virtual_token_reserves: 1_073_000_000_000_000,
+            virtual_sol_reserves: 30_000_000_000,
+            real_token_reserves: 793_100_000_000_000,
+            token_total_supply: 1_000_000_000_000_000,
+        };
+        let dev_buy = dev_buy.map(|sol_amount| TradeEvent {
+            mint: create.mint,
+            sol_amount,
+            token_amount: 0,
+            is_buy: true,
+            user: creator,
+            timestamp: 0,
+            virtual_sol_reserves: 30_000_000_000 + sol_amount,
+            virtual_token_reserves: 1_000_000_000_000_000,
+            real_sol_reserves: sol_amount,
+            real_token_reserves: 720_100_000_000_000,
+            fee_recipient: Pubkey::new_unique(),
+            fee_basis_points: 0,
+            fee: 0,
+            creator,
+            creator_fee_basis_points: 0,
+            creator_fee: 0,
+            track_volume: false,
+            total_unclaimed_tokens: 0,
+            total_claimed_tokens: 0,
+            current_sol_volume: 0,
+            last_update_timestamp: 0,
+        });
+        Launch {
+            signature: "sig".to_string(),
+            create,
+            dev_buy,
+        }
+    }
+
+    #[test]
+    fn test_filter() {
+        let creator = Pubkey::new_unique();
+        let denied = Pubkey::new_unique();
+        assert!(SniperFilter::new().matches(&launch("ABC", creator, None)));
+
+        let filter = SniperFilter::new()
+            .symbol("^[A-Z]+$")
+            .unwrap()
+            .deny_creators([denied])
+            .min_dev_buy(100)
+            .max_dev_buy(1_000);
+        assert!(filter.matches(&launch("ABC", creator, Some(500))));
+        assert!(!filter.matches(&launch("abc", creator, Some(500))));
+        assert!(!filter.matches(&launch("ABC", denied, Some(500))));
+        // A launch without a dev buy counts as a zero dev buy
+        assert!(!filter.matches(&launch("ABC", creator, None)));
+        assert!(!filter.matches(&launch("ABC", creator, Some(2_000))));
+
+        let filter = SniperFilter::new().allow_creators([creator]);
+        assert!(filter.matches(&launch("ABC", creator, None)));
+        assert!(!filter.matches(&launch("ABC", Pubkey::new_unique(), None)));
+
+        assert!(SniperFilter::new().name("(").is_err());
+    }
+
+    #[test]
+    fn test_filter_socials() {
+        let filter = SniperFilter::new().require_socials([Social::Twitter, Social::Website]);
+        assert!(filter.requires_metadata());
+
+        let links: TokenLinks = serde_json::from_str(
+            r#"{"name":"Token","twitter":"https://x.com/token","website":"https://token.fun"}"#,
+        )
+        .unwrap();
+        assert!(filter.matches_links(&links));
+
+        let links = TokenLinks {
+            website: Some(" ".to_string()),
+            ..links
+        };
+        assert!(!filter.matches_links(&links));
+    }
+
+    #[test]
+    fn test_launch_bonding_curve() {
+        let creator = Pubkey::new_unique();
+        let curve = launch("ABC", creator, None).bonding_curve();
+        assert_eq!(curve.virtual_sol_reserves, 30_000_000_000);
+        assert_eq!(curve.creator, creator);
+
+        // The creator's buy moves the curve the sniper buys from
+        let curve = launch("ABC", creator, Some(1_000_000_000)).bonding_curve();
+        assert_eq!(curve.virtual_sol_reserves, 31_000_000_000);
+        assert_eq!(curve.real_token_reserves, 720_100_000_000_000);
+    }
+}
@@ -0,0 +1,630 @@
+//! Sniper engine buying newly created tokens
+//!
+//! `Sniper` subscribes to `CreateEvent`s, checks every launch against a `SniperFilter`,
+//! and buys matching tokens through a `TransactionSender` within the limits of its
+//! `SniperConfig`.
+//!
+//! # Latency
+//!
+//! Buy transactions are built without any request on the hot path: the global account
+//! is fetched once when the sniper starts, a recent blockhash is kept refreshed in the
+//! background, and the buy amount is quoted from the reserves in the creation transaction,
+//! including the creator's own buy. Only filters requiring social links wait for the
+//! token's metadata to be fetched.
+
+mod filter;
+
+pub use filter::*;
+
+use std::{
+    collections::HashSet,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::Stream;
+use solana_sdk::{
+    commitment_config::CommitmentConfig, hash::Hash, instruction::Instruction, pubkey::Pubkey,
+    signature::Signature, signer::Signer,
+};
+#[cfg(feature = "create-ata")]
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use tokio::sync::{mpsc, watch};
+
+use crate::{
+    accounts::GlobalAccount,
+    common::{
+        risk::RiskTrade,
+        stream::{
+            log_events, spawn_subscription, EventFilter, EventKind, PumpFunEvent, Subscription,
+        },
+        types::PriorityFee,
+    },
+    constants, error, instructions,
+    sender::TransactionSender,
+    utils::{self, transaction::build_transaction},
+    PumpFun,
+};
+
+/// Interval at which the cached blockhash is refreshed
+const BLOCKHASH_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Buy size and budget of a sniper
+///
+/// # Fields
+///
+/// * `buy_amount` - Lamports spent on each sniped token, excluding slippage and fees
+/// * `slippage_basis_points` - Maximum acceptable slippage in basis points. If None, uses
+///   the client's default slippage
+/// * `priority_fee` - Priority fee of buy transactions. If None, uses the cluster's
+///   priority fee
+/// * `max_total` - Maximum lamports spent across all snipes, if limited
+/// * `max_buys` - Maximum number of tokens sniped, if limited
+/// * `metadata_timeout` - Time allowed for fetching token metadata. Launches whose
+///   metadata cannot be fetched in time fail social link rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SniperConfig {
+    pub buy_amount: u64,
+    pub slippage_basis_points: Option<u64>,
+    pub priority_fee: Option<PriorityFee>,
+    pub max_total: Option<u64>,
+    pub max_buys: Option<usize>,
+    pub metadata_timeout: Duration,
+}
+
+impl SniperConfig {
+    /// Creates a configuration buying each matching token for `buy_amount` lamports,
+    /// without a total budget
+    pub fn new(buy_amount: u64) -> Self {
+        Self {
+            buy_amount,
+            slippage_basis_points: None,
+            priority_fee: None,
+            max_total: None,
+            max_buys: None,
+            metadata_timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Buy attempted by a sniper
+///
+/// # Fields
+///
+/// * `mint` - Mint of the sniped token
+/// * `name` - Token name
+/// * `symbol` - Token symbol
+/// * `launch_signature` - Signature of the token's creation transaction
+/// * `amount_sol` - Lamports spent on the buy, excluding slippage and fees
+/// * `result` - Signature of the confirmed buy, or why it failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snipe {
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub launch_signature: String,
+    pub amount_sol: u64,
+    pub result: Result<Signature, String>,
+}
+
+/// Stream of buys attempted by a running sniper
+///
+/// The sniper stops when the stream is dropped; buys already submitted still complete.
+pub struct SnipeStream {
+    receiver: mpsc::Receiver<Snipe>,
+    _subscription: Subscription,
+}
+
+impl Stream for SnipeStream {
+    type Item = Snipe;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// Sniper buying newly created tokens that match a filter
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use pumpfun::{
+///     common::types::{Cluster, PriorityFee},
+///     sniper::{Sniper, SniperConfig, SniperFilter},
+///     PumpFun,
+/// };
+/// use solana_sdk::{commitment_config::CommitmentConfig, native_token::sol_to_lamports, signature::Keypair};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let cluster = Cluster::mainnet(CommitmentConfig::processed(), PriorityFee::default());
+/// let client = Arc::new(PumpFun::new(Arc::new(Keypair::new()), cluster));
+///
+/// let filter = SniperFilter::new()
+///     .name("(?i)cat")?
+///     .min_dev_buy(sol_to_lamports(1.0));
+/// let config = SniperConfig {
+///     max_total: Some(sol_to_lamports(1.0)),
+///     ..SniperConfig::new(sol_to_lamports(0.1))
+/// };
+/// let mut snipes = Sniper::new(client, filter, config).start().await?;
+///
+/// while let Some(snipe) = snipes.next().await {
+///     println!("{} ({}): {:?}", snipe.symbol, snipe.mint, snipe.result);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Sniper {
+    client: Arc<PumpFun>,
+    filter: watch::Receiver<SniperFilter>,
+    config: SniperConfig,
+    sender: Option<Arc<dyn TransactionSender>>,
+    commitment: Option<CommitmentConfig>,
+}
+
+impl Sniper {
+    /// Creates a sniper buying through a client with its payer and sender
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Client whose payer signs and pays for the buys
+    /// * `filter` - Rules launches must satisfy to be sniped
+    /// * `config` - Buy size and budget
+    pub fn new(client: Arc<PumpFun>, filter: SniperFilter, config: SniperConfig) -> Self {
+        Self {
+            client,
+            filter: watch::channel(filter).1,
+            config,
+            sender: None,
+            commitment: None,
+        }
+    }
+
+    /// Reads the filter from a watch channel instead of the filter passed to `new`
+    ///
+    /// Each launch is checked against the latest filter, so updates apply to the following
+    /// launches without restarting the sniper.
+    pub fn with_filter_updates(mut self, filter: watch::Receiver<SniperFilter>) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Submits buys through the given sender instead of the client's sender
+    pub fn with_sender(mut self, sender: Arc<dyn TransactionSender>) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// Subscribes to creations at the given commitment instead of the cluster's
+    pub fn with_commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.commitment = Some(commitment);
+        self
+    }
+
+    /// Starts the sniper
+    ///
+    /// Each matching token is bought at most once. Buys are submitted concurrently and
+    /// reported on the returned stream once they are confirmed or fail; the budget spent
+    /// on failed buys is released for later launches.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `SnipeStream` of attempted buys, or a ClientError if the global account,
+    /// a recent blockhash, or the subscription cannot be obtained
+    pub async fn start(self) -> Result<SnipeStream, error::ClientError> {
+        let global = self.client.get_global_account().await?;
+        let blockhash = match self.client.fixed_blockhash {
+            Some(blockhash) => blockhash,
+            None => self.client.rpc.get_latest_blockhash().await?,
+        };
+        let (blockhash_tx, blockhash_rx) = watch::channel(blockhash);
+        if self.client.fixed_blockhash.is_none() {
+            let rpc = self.client.rpc.clone();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(BLOCKHASH_REFRESH_INTERVAL);
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => {}
+                        _ = blockhash_tx.closed() => return,
+                    }
+                    if let Ok(blockhash) = rpc.get_latest_blockhash().await {
+                        let _ = blockhash_tx.send(blockhash);
+                    }
+                }
+            });
+        }
+
+        let (event_tx, mut event_rx) = mpsc::channel(1000);
+        let subscription = spawn_subscription(
+            self.client.cluster.clone(),
+            None,
+            self.commitment,
+            EventFilter::new().kinds([EventKind::Create]),
+            event_tx,
+        )
+        .await?;
+
+        let (snipe_tx, receiver) = mpsc::channel(100);
+        let engine = Arc::new(Engine {
+            sender: self.sender.unwrap_or_else(|| self.client.sender.clone()),
+            client: self.client,
+            filter: self.filter,
+            config: self.config,
+            global,
+            blockhash: blockhash_rx,
+            budget: Mutex::new(Budget::new(&self.config)),
+        });
+        tokio::spawn(async move {
+            while let Some((context, event, response)) = event_rx.recv().await {
+                let Ok(PumpFunEvent::Create(create)) = event else {
+                    continue;
+                };
+                // The creator's buy is emitted in the same transaction as the creation
+                let dev_buy = log_events(&context.signature, &response.value.logs)
+                    .into_iter()
+                    .find_map(|event| match event {
+                        Ok(PumpFunEvent::Trade(trade))
+                            if trade.is_buy
+                                && trade.mint == create.mint
+                                && trade.user == create.user =>
+                        {
+                            Some(trade)
+                        }
+                        _ => None,
+                    });
+                let launch = Launch {
+                    signature: context.signature,
+                    create,
+                    dev_buy,
+                };
+                if !engine.filter.borrow().matches(&launch) {
+                    continue;
+                }
+                let engine = engine.clone();
+                let snipe_tx = snipe_tx.clone();
+                tokio::spawn(async move {
+                    if let Some(snipe) = engine.snipe(launch).await {
+                        let _ = snipe_tx.send(snipe).await;
+                    }
+                });
+            }
+        });
+
+        Ok(SnipeStream {
+            receiver,
+            _subscription: subscription,
+        })
+    }
+}
+
+/// State shared by the buys of a running sniper
+struct Engine {
+    client: Arc<PumpFun>,
+    sender: Arc<dyn TransactionSender>,
+    filter: watch::Receiver<SniperFilter>,
+    config: SniperConfig,
+    global: GlobalAccount,
+    blockhash: watch::Receiver<Hash>,
+    budget: Mutex<Budget>,
+}
+
+impl Engine {
+    /// Buys a launch that passed the filter's immediate rules
+    ///
+    /// Returns None if the launch fails its social link rules or exceeds the budget.
+    async fn snipe(&self, launch: Launch) -> Option<Snipe> {
+        let requires_metadata = self.filter.borrow().requires_metadata();
+        if requires_metadata {
+            let links = tokio::time::timeout(
+                self.config.metadata_timeout,
+                fetch_links(&launch.create.uri),
+            )
+            .await
+            .ok()
+            .flatten()?;
+            if !self.filter.borrow().matches_links(&links) {
+                return None;
+            }
+        }
+
+        let mint = launch.create.mint;
+        let amount_sol = self.lock_budget().reserve(&mint)?;
+        let result = self.buy(&launch, amount_sol).await;
+        if result.is_err() {
+            self.lock_budget().release(amount_sol);
+        }
+        Some(Snipe {
+            mint,
+            name: launch.create.name,
+            symbol: launch.create.symbol,
+            launch_signature: launch.signature,
+            amount_sol,
+            result,
+        })
+    }
+
+    async fn buy(&self, launch: &Launch, amount_sol: u64) -> Result<Signature, String> {
+        let instructions = buy_instructions(
+            &self.client.payer.pubkey(),
+            &self.global,
+            launch,
+            amount_sol,
+            self.config
+                .slippage_basis_points
+                .unwrap_or(self.client.default_slippage_basis_points),
+            &self
+                .config
+                .priority_fee
+                .unwrap_or(self.client.cluster.priority_fee),
+        )
+        .map_err(|err| err.to_string())?;
+        // The client's risk limits apply to snipes like to every other buy
+        let create = &launch.create;
+        if let Some(risk) = &self.client.risk {
+            risk.check_buy(&create.mint, &create.creator, amount_sol)
+                .map_err(|rejection| error::ClientError::RiskRejected(rejection).to_string())?;
+        }
+        let blockhash = *self.blockhash.borrow();
+        let transaction = build_transaction(
+            &self.client.payer,
+            &instructions,
+            None,
+            blockhash,
+            #[cfg(feature = "versioned-tx")]
+            Some(&self.client.lookup_tables),
+        )
+        .map_err(|err| err.to_string())?;
+        let signature = self
+            .sender
+            .send_transaction(&transaction)
+            .await
+            .map_err(|err| err.to_string())?;
+        if let Some(risk) = &self.client.risk {
+            risk.record(&RiskTrade::Buy {
+                mint: create.mint,
+                creator: create.creator,
+                amount_sol,
+                tokens: launch
+                    .bonding_curve()
+                    .get_buy_price(amount_sol)
+                    .unwrap_or_default(),
+            });
+        }
+        Ok(signature)
+    }
+
+    fn lock_budget(&self) -> std::sync::MutexGuard<'_, Budget> {
+        match self.budget.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// Remaining budget of a sniper
+struct Budget {
+    buy_amount: u64,
+    remaining: Option<u64>,
+    buys_left: Option<usize>,
+    sniped: HashSet<Pubkey>,
+}
+
+impl Budget {
+    fn new(config: &SniperConfig) -> Self {
+        Self {
+            buy_amount: config.buy_amount,
+            remaining: config.max_total,
+            buys_left: config.max_buys,
+            sniped: HashSet::new(),
+        }
+    }
+
+    /// Reserves the buy amount for a token, returning None if the token was already
+    /// sniped or the budget is exhausted
+    fn reserve(&mut self, mint: &Pubkey) -> Option<u64> {
+        if self.sniped.contains(mint)
+            || self
+                .remaining
+                .is_some_and(|remaining| remaining < self.buy_amount)
+            || self.buys_left == Some(0)
+        {
+            return None;
+        }
+        self.sniped.insert(*mint);
+        if let Some(remaining) = &mut self.remaining {
+            *remaining -= self.buy_amount;
+        }
+        if let Some(buys_left) = &mut self.buys_left {
+            *buys_left -= 1;
+        }
+        Some(self.buy_amount)
+    }
+
+    /// Returns the budget reserved for a failed buy; the token is not bought again
+    fn release(&mut self, amount: u64) {
+        if let Some(remaining) = &mut self.remaining {
+            *remaining += amount;
+        }
+        if let Some(buys_left) = &mut self.buys_left {
+            *buys_left += 1;
+        }
+    }
+}
+
+/// Builds the instructions buying a launch without any network requests
+fn buy_instructions(
+    user: &Pubkey,
+    global: &GlobalAccount,
+    launch: &Launch,
+    amount_sol: u64,
+    slippage_basis_points: u64,
+    priority_fee: &PriorityFee,
+) -> Result<Vec<Instruction>, error::ClientError> {
+    let create = &launch.create;
+    let amount = launch
+        .bonding_curve()
+        .get_buy_price(amount_sol)
+        .map_err(error::ClientError::BondingCurveError)?;
+
+    let mut instructions = PumpFun::get_priority_fee_instructions(priority_fee);
+    // The token account of a token created moments ago cannot exist yet
+    #[cfg(feature = "create-ata")]
+    instructions.push(create_associated_token_account_idempotent(
+        user,
+        user,
+        &create.mint,
+        &constants::accounts::TOKEN_PROGRAM,
+    ));
+    instructions.push(instructions::buy_for(
+        user,
+        &create.mint,
+        &global.fee_recipient,
+        &create.creator,
+        instructions::Buy {
+            amount,
+            max_sol_cost: utils::calculate_with_slippage_buy(amount_sol, slippage_basis_points),
+            track_volume: None,
+        },
+    )?);
+    Ok(instructions)
+}
+
+/// Fetches the social links from a token's metadata JSON
+async fn fetch_links(uri: &str) -> Option<TokenLinks> {
+    let metadata = utils::fetch_offchain_metadata(uri).await.ok()?;
+    Some(TokenLinks {
+        twitter: metadata.twitter,
+        telegram: metadata.telegram,
+        website: metadata.website,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{stream::CreateEvent, types::Cluster};
+    use solana_sdk::signature::Keypair;
+
+    #[test]
+    fn test_budget() {
+        let config = SniperConfig {
+            max_total: Some(250),
+            max_buys: Some(3),
+            ..SniperConfig::new(100)
+        };
+        let mut budget = Budget::new(&config);
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        assert_eq!(budget.reserve(&a), Some(100));
+        // Each token is bought at most once
+        assert_eq!(budget.reserve(&a), None);
+        assert_eq!(budget.reserve(&b), Some(100));
+        assert_eq!(budget.reserve(&c), None);
+
+        // Failed buys return their budget
+        budget.release(100);
+        assert_eq!(budget.reserve(&c), Some(100));
+        assert_eq!(budget.remaining, Some(50));
+    }
+
+    #[test]
+    fn test_buy_instructions() {
+        let mint = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let launch = Launch {
+            signature: "sig".to_string(),
+            create: CreateEvent {
+                name: "Token".to_string(),
+                symbol: "TKN".to_string(),
+                uri: String::new(),
+                mint,
+                bonding_curve: PumpFun::get_bonding_curve_pda(&mint).unwrap(),
+                user: creator,
+                creator,
+                timestamp: 0,
+                virtual_token_reserves: 1_073_000_000_000_000,
+                virtual_sol_reserves: 30_000_000_000,
+                real_token_reserves: 793_100_000_000_000,
+                token_total_supply: 1_000_000_000_000_000,
+            },
+            dev_buy: None,
+        };
+        let user = Pubkey::new_unique();
+        let global = GlobalAccount::new(
+            1,
+            true,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            1_073_000_000_000_000,
+            30_000_000_000,
+            793_100_000_000_000,
+            1_000_000_000_000_000,
+            95,
+            Pubkey::new_unique(),
+            true,
+            0,
+            5,
+            [Pubkey::new_unique(); 7],
+            Pubkey::new_unique(),
+        );
+        let instructions = buy_instructions(
+            &user,
+            &global,
+            &launch,
+            1_000_000_000,
+            500,
+            &PriorityFee::default(),
+        )
+        .unwrap();
+
+        let buy = instructions.last().unwrap();
+        assert_eq!(buy.program_id, constants::accounts::PUMPFUN);
+        let args = instructions::Buy {
+            amount: launch.bonding_curve().get_buy_price(1_000_000_000).unwrap(),
+            max_sol_cost: 1_050_000_000,
+            track_volume: None,
+        };
+        assert_eq!(buy.data, args.data().unwrap());
+        #[cfg(feature = "create-ata")]
+        assert_eq!(instructions.len(), 2);
+    }
+
+    #[test]
+    fn test_filter_updates() {
+        let creator = Pubkey::new_unique();
+        let launch = Launch {
+            signature: "sig".to_string(),
+            create: CreateEvent {
+                name: "Token".to_string(),
+                symbol: "TKN".to_string(),
+                uri: String::new(),
+                mint: Pubkey::new_unique(),
+                bonding_curve: Pubkey::new_unique(),
+                user: creator,
+                creator,
+                timestamp: 0,
+                virtual_token_reserves: 1_073_000_000_000_000,
+                virtual_sol_reserves: 30_000_000_000,
+                real_token_reserves: 793_100_000_000_000,
+                token_total_supply: 1_000_000_000_000_000,
+            },
+            dev_buy: None,
+        };
+        let cluster = Cluster::localnet(CommitmentConfig::processed(), PriorityFee::default());
+        let client = Arc::new(PumpFun::new(Arc::new(Keypair::new()), cluster));
+        let (filter_tx, filter_rx) = watch::channel(SniperFilter::new());
+        let sniper = Sniper::new(client, SniperFilter::new(), SniperConfig::new(100))
+            .with_filter_updates(filter_rx);
+        assert!(sniper.filter.borrow().matches(&launch));
+
+        filter_tx.send_replace(SniperFilter::new().deny_creators([creator]));
+        assert!(!sniper.filter.borrow().matches(&launch));
+    }
+}
@@ -0,0 +1,320 @@
+//! Address lookup table helpers
+//!
+//! Versioned transactions can reference accounts through address lookup tables (ALTs), which
+//! replaces each 32-byte public key with a 1-byte index. This module provides helpers to
+//! create a lookup table holding the accounts shared by every Pump.fun transaction, extend it
+//! with user accounts, fetch it for use in transactions, and close it to reclaim rent.
+//!
+//! Closing a table is a two-step process: the table must be deactivated first and can only be
+//! closed once the deactivation slot is no longer in the slot hashes sysvar (about 513 slots).
+
+use solana_address_lookup_table_interface::{instruction, state::AddressLookupTable};
+use solana_sdk::{
+    commitment_config::CommitmentConfig, instruction::Instruction,
+    message::AddressLookupTableAccount, pubkey::Pubkey, signature::Signature, signer::Signer,
+};
+
+use crate::{constants, error, PumpFun};
+
+/// Maximum number of addresses added by a single extend instruction so that the
+/// instruction fits in a transaction alongside compute budget instructions
+pub const MAX_ADDRESSES_PER_EXTEND: usize = 30;
+
+/// Returns the accounts shared by Pump.fun transactions
+///
+/// This includes the program, its global PDAs, and the system, token, metadata and fee
+/// programs referenced by the create, buy and sell instructions.
+///
+/// # Arguments
+///
+/// * `fee_recipients` - Fee recipient accounts to include, e.g. from the global account
+///
+/// # Returns
+///
+/// Returns the deduplicated list of addresses
+pub fn pump_addresses(fee_recipients: &[Pubkey]) -> Vec<Pubkey> {
+    let mut addresses = vec![
+        constants::accounts::PUMPFUN,
+        PumpFun::get_global_pda(),
+        PumpFun::get_mint_authority_pda(),
+        constants::accounts::EVENT_AUTHORITY,
+        constants::accounts::GLOBAL_VOLUME_ACCUMULATOR,
+        constants::accounts::FEE_CONFIG,
+        constants::accounts::FEE_CONFIG_PROGRAM,
+        constants::accounts::MPL_TOKEN_METADATA,
+        constants::accounts::SYSTEM_PROGRAM,
+        constants::accounts::TOKEN_PROGRAM,
+        constants::accounts::ASSOCIATED_TOKEN_PROGRAM,
+        constants::accounts::RENT,
+    ];
+
+    for recipient in fee_recipients {
+        if !addresses.contains(recipient) {
+            addresses.push(*recipient);
+        }
+    }
+
+    addresses
+}
+
+/// Creates the instructions that create a lookup table and fill it with addresses
+///
+/// The instructions are grouped into batches, each of which should be sent in its own
+/// transaction, in order. The first batch creates the table.
+///
+/// # Arguments
+///
+/// * `authority` - Account that controls the lookup table
+/// * `payer` - Account paying for the lookup table rent
+/// * `recent_slot` - Recent slot used to derive the table address
+/// * `addresses` - Addresses to store in the table
+///
+/// # Returns
+///
+/// Returns the lookup table address and the instruction batches
+pub fn create_lookup_table_instructions(
+    authority: &Pubkey,
+    payer: &Pubkey,
+    recent_slot: u64,
+    addresses: &[Pubkey],
+) -> (Pubkey, Vec<Vec<Instruction>>) {
+    let (create_ix, table) = instruction::create_lookup_table(*authority, *payer, recent_slot);
+
+    let mut batches: Vec<Vec<Instruction>> =
+        extend_lookup_table_instructions(&table, authority, payer, addresses)
+            .into_iter()
+            .map(|ix| vec![ix])
+            .collect();
+
+    match batches.first_mut() {
+        Some(first) => first.insert(0, create_ix),
+        None => batches.push(vec![create_ix]),
+    }
+
+    (table, batches)
+}
+
+/// Creates the instructions that add addresses to an existing lookup table
+///
+/// Addresses are split into chunks of `MAX_ADDRESSES_PER_EXTEND`, one instruction per chunk.
+/// Each instruction should be sent in its own transaction.
+///
+/// # Arguments
+///
+/// * `table` - Lookup table address
+/// * `authority` - Account that controls the lookup table
+/// * `payer` - Account paying for the additional rent
+/// * `addresses` - Addresses to add to the table
+///
+/// # Returns
+///
+/// Returns one extend instruction per chunk of addresses
+pub fn extend_lookup_table_instructions(
+    table: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    addresses: &[Pubkey],
+) -> Vec<Instruction> {
+    addresses
+        .chunks(MAX_ADDRESSES_PER_EXTEND)
+        .map(|chunk| {
+            instruction::extend_lookup_table(*table, *authority, Some(*payer), chunk.to_vec())
+        })
+        .collect()
+}
+
+/// Creates a lookup table owned by the client's payer and fills it with addresses
+///
+/// # Arguments
+///
+/// * `client` - Client used to sign and send the transactions
+/// * `addresses` - Addresses to store in the table, e.g. from `pump_addresses`
+///
+/// # Returns
+///
+/// Returns the lookup table address if successful, or a ClientError if the operation fails
+///
+/// # Errors
+///
+/// Returns an error if fetching the current slot or sending any of the transactions fails
+///
+/// # Examples
+///
+/// ```no_run
+/// # use pumpfun::{common::types::{Cluster, PriorityFee}, utils::alt, PumpFun};
+/// # use solana_sdk::{commitment_config::CommitmentConfig, signature::Keypair};
+/// # use std::sync::Arc;
+/// #
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let payer = Arc::new(Keypair::new());
+/// # let cluster = Cluster::devnet(CommitmentConfig::confirmed(), PriorityFee::default());
+/// let client = PumpFun::new(payer, cluster);
+///
+/// let global = client.get_global_account().await?;
+/// let table = alt::create_lookup_table(&client, &alt::pump_addresses(&global.fee_recipients)).await?;
+///
+/// let lookup_table = alt::get_lookup_table(&client, &table).await?;
+/// let client = client.with_lookup_tables(vec![lookup_table]);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn create_lookup_table(
+    client: &PumpFun,
+    addresses: &[Pubkey],
+) -> Result<Pubkey, error::ClientError> {
+    // The derivation slot must be present in the slot hashes sysvar
+    let recent_slot = client
+        .rpc
+        .get_slot_with_commitment(CommitmentConfig::finalized())
+        .await?;
+
+    let authority = client.payer.pubkey();
+    let (table, batches) =
+        create_lookup_table_instructions(&authority, &authority, recent_slot, addresses);
+
+    for batch in batches {
+        client.send_instructions(&batch, None, None).await?;
+    }
+
+    Ok(table)
+}
+
+/// Adds addresses to a lookup table owned by the client's payer
+///
+/// # Arguments
+///
+/// * `client` - Client used to sign and send the transactions
+/// * `table` - Lookup table address
+/// * `addresses` - Addresses to add, e.g. user token accounts
+///
+/// # Returns
+///
+/// Returns the signatures of the extend transactions, or a ClientError if the operation fails
+///
+/// # Errors
+///
+/// Returns an error if sending any of the transactions fails
+pub async fn extend_lookup_table(
+    client: &PumpFun,
+    table: &Pubkey,
+    addresses: &[Pubkey],
+) -> Result<Vec<Signature>, error::ClientError> {
+    let authority = client.payer.pubkey();
+    let mut signatures = Vec::new();
+
+    for ix in extend_lookup_table_instructions(table, &authority, &authority, addresses) {
+        signatures.push(client.send_instructions(&[ix], None, None).await?);
+    }
+
+    Ok(signatures)
+}
+
+/// Deactivates a lookup table owned by the client's payer so it can later be closed
+///
+/// # Arguments
+///
+/// * `client` - Client used to sign and send the transaction
+/// * `table` - Lookup table address
+///
+/// # Returns
+///
+/// Returns the transaction signature if successful, or a ClientError if the operation fails
+pub async fn deactivate_lookup_table(
+    client: &PumpFun,
+    table: &Pubkey,
+) -> Result<Signature, error::ClientError> {
+    let ix = instruction::deactivate_lookup_table(*table, client.payer.pubkey());
+    client.send_instructions(&[ix], None, None).await
+}
+
+/// Closes a deactivated lookup table and returns its rent to the client's payer
+///
+/// # Arguments
+///
+/// * `client` - Client used to sign and send the transaction
+/// * `table` - Lookup table address
+///
+/// # Returns
+///
+/// Returns the transaction signature if successful, or a ClientError if the operation fails
+///
+/// # Errors
+///
+/// Returns an error if the table has not been deactivated or is still cooling down
+pub async fn close_lookup_table(
+    client: &PumpFun,
+    table: &Pubkey,
+) -> Result<Signature, error::ClientError> {
+    let authority = client.payer.pubkey();
+    let ix = instruction::close_lookup_table(*table, authority, authority);
+    client.send_instructions(&[ix], None, None).await
+}
+
+/// Fetches a lookup table so it can be used when compiling transactions
+///
+/// # Arguments
+///
+/// * `client` - Client used to fetch the account
+/// * `table` - Lookup table address
+///
+/// # Returns
+///
+/// Returns the lookup table account if successful, or a ClientError if the operation fails
+///
+/// # Errors
+///
+/// Returns an error if the account cannot be fetched or is not a lookup table
+pub async fn get_lookup_table(
+    client: &PumpFun,
+    table: &Pubkey,
+) -> Result<AddressLookupTableAccount, error::ClientError> {
+    let account = client.rpc.get_account(table).await?;
+    let lookup_table = AddressLookupTable::deserialize(&account.data).map_err(|err| {
+        error::ClientError::OtherError(format!("Invalid lookup table {}: {}", table, err))
+    })?;
+
+    Ok(AddressLookupTableAccount {
+        key: *table,
+        addresses: lookup_table.addresses.to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pump_addresses() {
+        let recipient = Pubkey::new_unique();
+        let addresses = pump_addresses(&[recipient, recipient, constants::accounts::PUMPFUN]);
+
+        assert!(addresses.contains(&recipient));
+        assert!(addresses.contains(&PumpFun::get_global_pda()));
+        assert_eq!(addresses.iter().filter(|key| **key == recipient).count(), 1);
+        assert_eq!(
+            addresses
+                .iter()
+                .filter(|key| **key == constants::accounts::PUMPFUN)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_create_lookup_table_instructions() {
+        let authority = Pubkey::new_unique();
+        let addresses: Vec<Pubkey> = (0..70).map(|_| Pubkey::new_unique()).collect();
+
+        let (table, batches) =
+            create_lookup_table_instructions(&authority, &authority, 100, &addresses);
+
+        assert_eq!(batches.len(), 3);
+        assert_eq!(batches[0].len(), 2);
+        assert_eq!(batches[1].len(), 1);
+        assert_eq!(batches[0][1].accounts[0].pubkey, table);
+
+        let (_, batches) = create_lookup_table_instructions(&authority, &authority, 100, &[]);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].len(), 1);
+    }
+}
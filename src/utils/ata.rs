@@ -0,0 +1,386 @@
+//! Bulk associated token account helpers
+//!
+//! Buying from many wallets or mints at once creates one associated token account (ATA) per
+//! wallet and mint, and selling out leaves them empty but still holding rent. This module
+//! creates missing ATAs in batched transactions ahead of time, for example before sending a
+//! bundle whose buys should not each pay for an account, and closes empty ATAs to reclaim
+//! their rent.
+//!
+//! Wrapped SOL (WSOL) proceeds left in a wallet's WSOL account can be unwrapped back
+//! to native SOL with `unwrap_wsol`, which closes the account.
+//!
+//! The instruction builders can be used on their own to add the batches to other
+//! transactions; the async helpers fetch the accounts and send the batches through a client.
+
+use solana_client::rpc_request::TokenAccountsFilter;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+
+use crate::{constants, error, PumpFun};
+
+/// Maximum number of ATAs created by a single transaction
+pub const MAX_CREATES_PER_TRANSACTION: usize = 6;
+
+/// Maximum number of ATAs closed by a single transaction
+pub const MAX_CLOSES_PER_TRANSACTION: usize = 20;
+
+/// Result of closing the empty ATAs of a wallet
+///
+/// # Fields
+///
+/// * `closed` - Token accounts closed
+/// * `lamports` - Rent returned to the owner, in lamports
+/// * `signatures` - Signatures of the close transactions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AtaCleanup {
+    pub closed: Vec<Pubkey>,
+    pub lamports: u64,
+    pub signatures: Vec<Signature>,
+}
+
+/// Creates the instructions that create ATAs, grouped into transactions
+///
+/// The instructions are idempotent, so batches can be resent or race with other
+/// transactions creating the same accounts.
+///
+/// # Arguments
+///
+/// * `payer` - Account paying for the rent of the new accounts
+/// * `accounts` - Owner and mint of each ATA to create
+///
+/// # Returns
+///
+/// Returns batches of at most `MAX_CREATES_PER_TRANSACTION` instructions, each of which
+/// should be sent in its own transaction
+pub fn create_ata_instructions(
+    payer: &Pubkey,
+    accounts: &[(Pubkey, Pubkey)],
+) -> Vec<Vec<Instruction>> {
+    accounts
+        .chunks(MAX_CREATES_PER_TRANSACTION)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|(owner, mint)| {
+                    create_associated_token_account_idempotent(
+                        payer,
+                        owner,
+                        mint,
+                        &constants::accounts::TOKEN_PROGRAM,
+                    )
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Creates the instructions that close empty token accounts, grouped into transactions
+///
+/// # Arguments
+///
+/// * `owner` - Owner of the token accounts, which must sign the transactions
+/// * `destination` - Account receiving the reclaimed rent
+/// * `accounts` - Token accounts to close, which must hold no tokens
+///
+/// # Returns
+///
+/// Returns batches of at most `MAX_CLOSES_PER_TRANSACTION` instructions, each of which
+/// should be sent in its own transaction
+///
+/// # Errors
+///
+/// Returns an error if a close instruction cannot be built
+pub fn close_ata_instructions(
+    owner: &Pubkey,
+    destination: &Pubkey,
+    accounts: &[Pubkey],
+) -> Result<Vec<Vec<Instruction>>, error::ClientError> {
+    accounts
+        .chunks(MAX_CLOSES_PER_TRANSACTION)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|account| {
+                    spl_token::instruction::close_account(
+                        &constants::accounts::TOKEN_PROGRAM,
+                        account,
+                        destination,
+                        owner,
+                        &[],
+                    )
+                    .map_err(|err| error::ClientError::OtherError(err.to_string()))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Creates the instruction that unwraps a wallet's WSOL by closing its WSOL ATA
+///
+/// Closing the account returns both its wrapped SOL and its rent to the owner as native
+/// SOL. Append it after instructions that leave proceeds in the WSOL ATA, such as AMM sells.
+///
+/// # Arguments
+///
+/// * `owner` - Owner of the WSOL ATA, which must sign the transaction
+///
+/// # Errors
+///
+/// Returns an error if the close instruction cannot be built
+pub fn unwrap_wsol_instruction(owner: &Pubkey) -> Result<Instruction, error::ClientError> {
+    let wsol_ata = get_associated_token_address(owner, &constants::accounts::WSOL);
+    spl_token::instruction::close_account(
+        &constants::accounts::TOKEN_PROGRAM,
+        &wsol_ata,
+        owner,
+        owner,
+        &[],
+    )
+    .map_err(|err| error::ClientError::OtherError(err.to_string()))
+}
+
+/// Unwraps a wallet's WSOL into native SOL by closing its WSOL ATA
+///
+/// The client's payer pays the transaction fee, so the owner can be any wallet.
+///
+/// # Arguments
+///
+/// * `client` - Client used to fetch the account and sign and send the transaction
+/// * `owner` - Wallet holding the WSOL
+///
+/// # Returns
+///
+/// Returns the signature of the close transaction, or None if the wallet has no WSOL ATA
+///
+/// # Errors
+///
+/// Returns an error if fetching the account or sending the transaction fails
+pub async fn unwrap_wsol(
+    client: &PumpFun,
+    owner: &Keypair,
+) -> Result<Option<Signature>, error::ClientError> {
+    let wsol_ata = get_associated_token_address(&owner.pubkey(), &constants::accounts::WSOL);
+    let account = client
+        .rpc
+        .get_account_with_commitment(&wsol_ata, client.cluster.commitment)
+        .await?
+        .value;
+    if account.is_none() {
+        return Ok(None);
+    }
+
+    let mut instructions = PumpFun::get_priority_fee_instructions(&client.cluster.priority_fee);
+    instructions.push(unwrap_wsol_instruction(&owner.pubkey())?);
+    let signers = [owner];
+    let additional_signers = (owner.pubkey() != client.payer.pubkey()).then_some(&signers[..]);
+    client
+        .send_instructions(&instructions, additional_signers, None)
+        .await
+        .map(Some)
+}
+
+/// Finds the ATAs that do not exist yet among every owner and mint pair
+///
+/// # Arguments
+///
+/// * `client` - Client used to fetch the accounts
+/// * `owners` - Wallets that should hold the ATAs
+/// * `mints` - Mints of the ATAs
+///
+/// # Returns
+///
+/// Returns the owner and mint of each missing ATA
+pub async fn missing_atas(
+    client: &PumpFun,
+    owners: &[Pubkey],
+    mints: &[Pubkey],
+) -> Result<Vec<(Pubkey, Pubkey)>, error::ClientError> {
+    let pairs: Vec<(Pubkey, Pubkey)> = owners
+        .iter()
+        .flat_map(|owner| mints.iter().map(move |mint| (*owner, *mint)))
+        .collect();
+
+    let mut missing = Vec::new();
+    for chunk in pairs.chunks(100) {
+        let atas: Vec<Pubkey> = chunk
+            .iter()
+            .map(|(owner, mint)| get_associated_token_address(owner, mint))
+            .collect();
+        let accounts = client.rpc.get_multiple_accounts(&atas).await?;
+        missing.extend(
+            chunk
+                .iter()
+                .zip(accounts)
+                .filter(|(_, account)| account.is_none())
+                .map(|(pair, _)| *pair),
+        );
+    }
+    Ok(missing)
+}
+
+/// Creates the missing ATAs of every owner and mint pair, paid by the client's payer
+///
+/// # Arguments
+///
+/// * `client` - Client used to fetch the accounts and sign and send the transactions
+/// * `owners` - Wallets that should hold the ATAs
+/// * `mints` - Mints of the ATAs
+///
+/// # Returns
+///
+/// Returns the signatures of the create transactions; none are sent if every ATA exists
+///
+/// # Errors
+///
+/// Returns an error if fetching the accounts or sending any of the transactions fails
+///
+/// # Examples
+///
+/// ```no_run
+/// # use pumpfun::{common::types::{Cluster, PriorityFee}, utils::ata, PumpFun};
+/// # use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Keypair};
+/// # use std::sync::Arc;
+/// #
+/// # async fn example(wallets: Vec<Pubkey>, mint: Pubkey) -> Result<(), Box<dyn std::error::Error>> {
+/// # let payer = Arc::new(Keypair::new());
+/// # let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+/// let client = PumpFun::new(payer, cluster);
+///
+/// let signatures = ata::ensure_atas(&client, &wallets, &[mint]).await?;
+/// println!("Created ATAs in {} transactions", signatures.len());
+/// # Ok(())
+/// # }
+/// ```
+pub async fn ensure_atas(
+    client: &PumpFun,
+    owners: &[Pubkey],
+    mints: &[Pubkey],
+) -> Result<Vec<Signature>, error::ClientError> {
+    let missing = missing_atas(client, owners, mints).await?;
+    let mut signatures = Vec::new();
+    for batch in create_ata_instructions(&client.payer.pubkey(), &missing) {
+        let mut instructions = PumpFun::get_priority_fee_instructions(&client.cluster.priority_fee);
+        instructions.extend(batch);
+        signatures.push(client.send_instructions(&instructions, None, None).await?);
+    }
+    Ok(signatures)
+}
+
+/// Closes the empty token accounts of a wallet, returning their rent to it
+///
+/// The client's payer pays the transaction fees, so the owner can be any wallet.
+///
+/// # Arguments
+///
+/// * `client` - Client used to fetch the accounts and sign and send the transactions
+/// * `owner` - Wallet whose empty token accounts are closed
+///
+/// # Returns
+///
+/// Returns the closed accounts, the rent reclaimed, and the transaction signatures
+///
+/// # Errors
+///
+/// Returns an error if fetching the accounts or sending any of the transactions fails;
+/// accounts closed by earlier transactions stay closed
+pub async fn close_empty_atas(
+    client: &PumpFun,
+    owner: &Keypair,
+) -> Result<AtaCleanup, error::ClientError> {
+    let accounts = client
+        .rpc
+        .get_token_accounts_by_owner(
+            &owner.pubkey(),
+            TokenAccountsFilter::ProgramId(constants::accounts::TOKEN_PROGRAM),
+        )
+        .await?;
+    let empty: Vec<(Pubkey, u64)> = accounts
+        .iter()
+        .filter_map(|keyed| {
+            let (account, _, amount) = crate::parse_token_account(keyed)?;
+            (amount == 0).then_some((account, keyed.account.lamports))
+        })
+        .collect();
+
+    // The payer signs every transaction already
+    let signers = [owner];
+    let additional_signers = (owner.pubkey() != client.payer.pubkey()).then_some(&signers[..]);
+
+    let mut cleanup = AtaCleanup::default();
+    for chunk in empty.chunks(MAX_CLOSES_PER_TRANSACTION) {
+        let closed: Vec<Pubkey> = chunk.iter().map(|(account, _)| *account).collect();
+        let mut instructions = PumpFun::get_priority_fee_instructions(&client.cluster.priority_fee);
+        for batch in close_ata_instructions(&owner.pubkey(), &owner.pubkey(), &closed)? {
+            instructions.extend(batch);
+        }
+        cleanup.signatures.push(
+            client
+                .send_instructions(&instructions, additional_signers, None)
+                .await?,
+        );
+        cleanup.lamports += chunk.iter().map(|(_, lamports)| lamports).sum::<u64>();
+        cleanup.closed.extend(closed);
+    }
+    Ok(cleanup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_ata_instructions() {
+        let payer = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let accounts: Vec<(Pubkey, Pubkey)> =
+            (0..13).map(|_| (Pubkey::new_unique(), mint)).collect();
+
+        let batches = create_ata_instructions(&payer, &accounts);
+        assert_eq!(
+            batches.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![6, 6, 1]
+        );
+        let first = &batches[0][0];
+        assert_eq!(first.program_id, spl_associated_token_account::id());
+        assert_eq!(first.accounts[0].pubkey, payer);
+        assert_eq!(
+            first.accounts[1].pubkey,
+            get_associated_token_address(&accounts[0].0, &mint)
+        );
+        assert!(create_ata_instructions(&payer, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_unwrap_wsol_instruction() {
+        let owner = Pubkey::new_unique();
+        let instruction = unwrap_wsol_instruction(&owner).unwrap();
+
+        assert_eq!(instruction.program_id, constants::accounts::TOKEN_PROGRAM);
+        assert_eq!(
+            instruction.accounts[0].pubkey,
+            get_associated_token_address(&owner, &constants::accounts::WSOL)
+        );
+        assert_eq!(instruction.accounts[1].pubkey, owner);
+        assert_eq!(instruction.accounts[2].pubkey, owner);
+        assert!(instruction.accounts[2].is_signer);
+    }
+
+    #[test]
+    fn test_close_ata_instructions() {
+        let owner = Pubkey::new_unique();
+        let accounts: Vec<Pubkey> = (0..21).map(|_| Pubkey::new_unique()).collect();
+
+        let batches = close_ata_instructions(&owner, &owner, &accounts).unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].len(), MAX_CLOSES_PER_TRANSACTION);
+        assert_eq!(batches[1][0].accounts[0].pubkey, accounts[20]);
+        assert_eq!(batches[1][0].accounts[1].pubkey, owner);
+    }
+}
@@ -0,0 +1,227 @@
+//! Trading fee calculation for bonding curve quotes
+//!
+//! The program charges a protocol fee and a creator fee on the SOL side of every trade: on
+//! top of the SOL paid into the curve for buys, and out of the SOL received for sells. Quotes
+//! built by the client account for both, so `max_sol_cost` and `min_sol_output` match what
+//! the program actually charges instead of relying on slippage to absorb the fees.
+//!
+//! By default the fees are the flat rates of the global account. Deployments charging
+//! volume-based tiers can describe them with a `FeeSchedule`, set on the client with
+//! `PumpFun::with_fee_schedule`; the tier is then picked from the user's volume accumulator.
+
+use crate::accounts::{GlobalAccount, UserVolumeAccumulator};
+
+/// Basis points in 100%
+const BASIS_POINTS: u64 = 10_000;
+
+/// Fees charged on a trade
+///
+/// # Fields
+///
+/// * `protocol_fee_basis_points` - Fee paid to the protocol's fee recipient, in basis points
+/// * `creator_fee_basis_points` - Fee paid to the token creator's vault, in basis points
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Fees {
+    pub protocol_fee_basis_points: u64,
+    pub creator_fee_basis_points: u64,
+}
+
+impl Fees {
+    /// Returns the flat fees of the global account
+    pub fn from_global(global: &GlobalAccount) -> Self {
+        Self {
+            protocol_fee_basis_points: global.fee_basis_points,
+            creator_fee_basis_points: global.creator_fee_basis_points,
+        }
+    }
+
+    /// Returns the sum of the protocol and creator fees, in basis points
+    pub fn total_basis_points(&self) -> u64 {
+        self.protocol_fee_basis_points + self.creator_fee_basis_points
+    }
+
+    /// Returns the fees charged on a SOL amount, rounding each fee up like the program
+    pub fn fee(&self, sol_amount: u64) -> u64 {
+        fee(sol_amount, self.protocol_fee_basis_points)
+            .saturating_add(fee(sol_amount, self.creator_fee_basis_points))
+    }
+
+    /// Returns the largest SOL amount that can be paid into the curve within a budget
+    ///
+    /// # Arguments
+    ///
+    /// * `budget` - Lamports available for the buy, including fees
+    ///
+    /// # Returns
+    ///
+    /// The amount that, once the fees are added, costs at most `budget`
+    pub fn sol_before_fees(&self, budget: u64) -> u64 {
+        let total = self.total_basis_points() as u128;
+        let mut amount =
+            ((budget as u128 * BASIS_POINTS as u128) / (BASIS_POINTS as u128 + total)) as u64;
+        // Rounding the fees up can push the cost over the budget by a few lamports
+        while amount > 0 && amount.saturating_add(self.fee(amount)) > budget {
+            amount -= 1;
+        }
+        amount
+    }
+}
+
+/// Fees charged to users whose volume reaches a threshold
+///
+/// # Fields
+///
+/// * `min_sol_volume` - Volume from which the tier applies, in lamports
+/// * `fees` - Fees charged in the tier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeTier {
+    pub min_sol_volume: u64,
+    pub fees: Fees,
+}
+
+/// Volume-based fee tiers
+///
+/// An empty schedule, the default, charges the flat fees of the global account.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeeSchedule {
+    tiers: Vec<FeeTier>,
+}
+
+impl FeeSchedule {
+    /// Creates a schedule from its tiers, in any order
+    pub fn new(mut tiers: Vec<FeeTier>) -> Self {
+        tiers.sort_by_key(|tier| tier.min_sol_volume);
+        Self { tiers }
+    }
+
+    /// Returns the tiers, sorted by increasing volume threshold
+    pub fn tiers(&self) -> &[FeeTier] {
+        &self.tiers
+    }
+
+    /// Returns whether the schedule has no tiers and charges the global fees
+    pub fn is_empty(&self) -> bool {
+        self.tiers.is_empty()
+    }
+}
+
+/// Returns the fees charged to a user
+///
+/// # Arguments
+///
+/// * `user_volume_accumulator` - Volume accumulator of the user, or None if it does not exist
+/// * `global` - Global account, whose flat fees apply below the first tier
+/// * `schedule` - Volume-based fee tiers
+///
+/// # Returns
+///
+/// The fees of the highest tier whose threshold the user's volume reaches, or the global
+/// fees if there is none
+pub fn compute_fee_tier(
+    user_volume_accumulator: Option<&UserVolumeAccumulator>,
+    global: &GlobalAccount,
+    schedule: &FeeSchedule,
+) -> Fees {
+    let volume = user_volume_accumulator.map_or(0, |account| account.current_sol_volume);
+    schedule
+        .tiers
+        .iter()
+        .rev()
+        .find(|tier| volume >= tier.min_sol_volume)
+        .map_or_else(|| Fees::from_global(global), |tier| tier.fees)
+}
+
+fn fee(amount: u64, basis_points: u64) -> u64 {
+    (amount as u128 * basis_points as u128).div_ceil(BASIS_POINTS as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::pubkey::Pubkey;
+
+    use super::*;
+
+    fn global() -> GlobalAccount {
+        GlobalAccount::new(
+            1,
+            true,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            1_073_000_000_000_000,
+            30_000_000_000,
+            793_100_000_000_000,
+            1_000_000_000_000_000,
+            95,
+            Pubkey::new_unique(),
+            true,
+            15_000_001,
+            5,
+            [Pubkey::new_unique(); 7],
+            Pubkey::new_unique(),
+        )
+    }
+
+    #[test]
+    fn test_fees() {
+        let fees = Fees::from_global(&global());
+        assert_eq!(fees.total_basis_points(), 100);
+        assert_eq!(fees.fee(1_000_000_000), 10_000_000);
+        assert_eq!(fees.fee(1), 2);
+
+        for budget in [0, 1, 101, 1_000_000_000, 1_234_567_891] {
+            let amount = fees.sol_before_fees(budget);
+            assert!(amount + fees.fee(amount) <= budget);
+            assert!(amount + 1 + fees.fee(amount + 1) > budget);
+        }
+    }
+
+    #[test]
+    fn test_compute_fee_tier() {
+        let global = global();
+        let discounted = Fees {
+            protocol_fee_basis_points: 50,
+            creator_fee_basis_points: 5,
+        };
+        let schedule = FeeSchedule::new(vec![
+            FeeTier {
+                min_sol_volume: 100_000_000_000,
+                fees: discounted,
+            },
+            FeeTier {
+                min_sol_volume: 10_000_000_000,
+                fees: Fees {
+                    protocol_fee_basis_points: 80,
+                    creator_fee_basis_points: 5,
+                },
+            },
+        ]);
+        let mut user = UserVolumeAccumulator {
+            discriminator: 0,
+            user: Pubkey::new_unique(),
+            needs_claim: false,
+            total_unclaimed_tokens: 0,
+            total_claimed_tokens: 0,
+            current_sol_volume: 1_000_000_000,
+            last_update_timestamp: 0,
+            has_total_claimed_tokens: true,
+        };
+
+        assert_eq!(
+            compute_fee_tier(None, &global, &schedule),
+            Fees::from_global(&global)
+        );
+        assert_eq!(
+            compute_fee_tier(Some(&user), &global, &schedule),
+            Fees::from_global(&global)
+        );
+        user.current_sol_volume = 150_000_000_000;
+        assert_eq!(
+            compute_fee_tier(Some(&user), &global, &schedule),
+            discounted
+        );
+        assert_eq!(
+            compute_fee_tier(Some(&user), &global, &FeeSchedule::default()),
+            Fees::from_global(&global)
+        );
+    }
+}
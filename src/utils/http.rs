@@ -0,0 +1,339 @@
+//! Pluggable HTTP backend for metadata uploads, the frontend API, and relay senders
+//!
+//! `HttpClient` abstracts how the crate sends its HTTP requests: uploading token metadata,
+//! downloading images and off-chain metadata, querying the pump.fun frontend API, and
+//! submitting transactions through the Jito, Helius, and bloXroute senders. Pass
+//! your own implementation, or a configured `ReqwestClient` or `IsahcClient`, to reuse a
+//! connection pool, route requests through a proxy, or keep a single TLS stack.
+//!
+//! # Backends
+//!
+//! - `ReqwestClient`: Backed by `reqwest`, the default; requires the `reqwest` feature
+//! - `IsahcClient`: Backed by `isahc`, used when the `reqwest` feature is disabled;
+//!   requires the `isahc` feature
+//!
+//! With neither feature enabled, the default client fails every request, so an
+//! implementation has to be passed to each component sending requests.
+
+use std::{fmt, sync::Arc};
+
+use async_trait::async_trait;
+
+/// Method of an HTTP request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpMethod {
+    Get,
+    Post,
+}
+
+/// HTTP request sent through an `HttpClient`
+///
+/// # Fields
+///
+/// * `method` - Request method
+/// * `url` - Absolute URL of the request
+/// * `headers` - Header names and values, in order
+/// * `body` - Request body, empty for GET requests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: HttpMethod,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// Creates a GET request
+    pub fn get(url: impl Into<String>) -> Self {
+        Self {
+            method: HttpMethod::Get,
+            url: url.into(),
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    /// Creates a POST request with the given body
+    pub fn post(url: impl Into<String>, body: Vec<u8>) -> Self {
+        Self {
+            method: HttpMethod::Post,
+            url: url.into(),
+            headers: Vec::new(),
+            body,
+        }
+    }
+
+    /// Adds a header to the request
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// HTTP response returned by an `HttpClient`
+///
+/// # Fields
+///
+/// * `status` - Status code
+/// * `headers` - Header names and values, in order
+/// * `body` - Response body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Returns whether the status code is in the 2xx range
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Returns the first value of a header, compared case-insensitively
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the body as text, replacing invalid UTF-8
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// Error returned when a request could not be sent or its response could not be read
+///
+/// Responses with error statuses are not errors; they are returned as an `HttpResponse`.
+/// Uploads failing with an `HttpError` are retried following their `UploadRetryPolicy`.
+#[derive(Debug)]
+pub struct HttpError(Box<dyn std::error::Error + Send + Sync>);
+
+impl HttpError {
+    /// Wraps the error of an HTTP backend
+    pub fn new(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self(err.into())
+    }
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HTTP request failed: {}", self.0)
+    }
+}
+
+impl std::error::Error for HttpError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.0.as_ref())
+    }
+}
+
+/// Sends HTTP requests
+#[async_trait]
+pub trait HttpClient: Send + Sync {
+    /// Sends a request and reads the whole response
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - Request to send
+    ///
+    /// # Returns
+    ///
+    /// Returns the response, whatever its status, or an HttpError if the request could not
+    /// be sent or the response could not be read
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, HttpError>;
+}
+
+/// Returns a client for the default backend
+///
+/// The client is backed by `reqwest` if the `reqwest` feature is enabled, by `isahc` if only
+/// the `isahc` feature is, and fails every request if neither is.
+pub fn default_client() -> Arc<dyn HttpClient> {
+    #[cfg(feature = "reqwest")]
+    {
+        Arc::new(ReqwestClient::new())
+    }
+    #[cfg(all(not(feature = "reqwest"), feature = "isahc"))]
+    {
+        Arc::new(IsahcClient::new())
+    }
+    #[cfg(not(any(feature = "reqwest", feature = "isahc")))]
+    {
+        Arc::new(NoBackend)
+    }
+}
+
+/// Default client when no HTTP backend feature is enabled
+#[cfg(not(any(feature = "reqwest", feature = "isahc")))]
+struct NoBackend;
+
+#[cfg(not(any(feature = "reqwest", feature = "isahc")))]
+#[async_trait]
+impl HttpClient for NoBackend {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, HttpError> {
+        Err(HttpError::new(format!(
+            "no HTTP backend enabled for {}; enable the `reqwest` or `isahc` feature",
+            request.url
+        )))
+    }
+}
+
+/// HTTP client backed by `reqwest`
+#[cfg(feature = "reqwest")]
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestClient {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "reqwest")]
+impl ReqwestClient {
+    /// Creates a client with the default `reqwest` settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl From<reqwest::Client> for ReqwestClient {
+    fn from(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+#[async_trait]
+impl HttpClient for ReqwestClient {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, HttpError> {
+        let method = match request.method {
+            HttpMethod::Get => reqwest::Method::GET,
+            HttpMethod::Post => reqwest::Method::POST,
+        };
+        let mut builder = self.client.request(method, &request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        let response = builder
+            .body(request.body)
+            .send()
+            .await
+            .map_err(HttpError::new)?;
+
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let body = response.bytes().await.map_err(HttpError::new)?.to_vec();
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+/// HTTP client backed by `isahc`
+#[cfg(feature = "isahc")]
+#[derive(Debug, Clone)]
+pub struct IsahcClient {
+    client: Option<isahc::HttpClient>,
+}
+
+#[cfg(feature = "isahc")]
+impl IsahcClient {
+    /// Creates a client with the default `isahc` settings
+    ///
+    /// If the client cannot be created, requests fail with the creation error.
+    pub fn new() -> Self {
+        Self {
+            client: isahc::HttpClient::new().ok(),
+        }
+    }
+}
+
+#[cfg(feature = "isahc")]
+impl Default for IsahcClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "isahc")]
+impl From<isahc::HttpClient> for IsahcClient {
+    fn from(client: isahc::HttpClient) -> Self {
+        Self {
+            client: Some(client),
+        }
+    }
+}
+
+#[cfg(feature = "isahc")]
+#[async_trait]
+impl HttpClient for IsahcClient {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, HttpError> {
+        use isahc::AsyncReadResponseExt;
+
+        let client = match &self.client {
+            Some(client) => client.clone(),
+            None => isahc::HttpClient::new().map_err(HttpError::new)?,
+        };
+        let method = match request.method {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+        };
+        let mut builder = isahc::Request::builder().method(method).uri(&request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        let request = builder.body(request.body).map_err(HttpError::new)?;
+        let mut response = client.send_async(request).await.map_err(HttpError::new)?;
+
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let body = response.bytes().await.map_err(HttpError::new)?;
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_and_response() {
+        let request = HttpRequest::post("https://example.com/upload", b"body".to_vec())
+            .header("Content-Type", "application/json");
+        assert_eq!(request.method, HttpMethod::Post);
+        assert_eq!(
+            request.headers,
+            vec![("Content-Type".to_string(), "application/json".to_string())]
+        );
+        assert!(HttpRequest::get("https://example.com").body.is_empty());
+
+        let response = HttpResponse {
+            status: 201,
+            headers: vec![("retry-after".to_string(), "5".to_string())],
+            body: b"{}".to_vec(),
+        };
+        assert!(response.is_success());
+        assert_eq!(response.header("Retry-After"), Some("5"));
+        assert_eq!(response.header("Content-Type"), None);
+        assert_eq!(response.text(), "{}");
+        assert!(!HttpResponse {
+            status: 404,
+            ..response
+        }
+        .is_success());
+    }
+}
@@ -0,0 +1,568 @@
+//! Image sources for token metadata uploads
+//!
+//! `ImageSource` describes where the image of a new token is read from: a file, bytes
+//! already in memory, an async reader such as an HTTP request body, or a URL to download.
+//! The image is loaded when the metadata is uploaded, and its content type is taken from
+//! the server for URLs, or detected from the image's leading bytes and the file extension.
+//!
+//! # Validation
+//!
+//! Uploaders check loaded images against `ImageLimits` before uploading anything, so an
+//! unsupported format or oversized file fails with `ClientError::InvalidImage` up front
+//! rather than as a rejection from the provider. The default limits match pump.fun's: PNG,
+//! JPEG, GIF, and WebP images up to 15 MB, and MP4 videos up to 30 MB.
+//!
+//! With the `image` feature, `ImageLimits::downscale` shrinks and re-encodes PNG, JPEG, and
+//! WebP images that are too large instead of rejecting them, so a token can still be created
+//! from an oversized picture.
+
+use std::{fmt, path::PathBuf, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt},
+    sync::Mutex,
+};
+
+use super::http::{self, HttpClient, HttpRequest};
+use crate::error;
+
+/// Content type sent for images whose format is not recognized
+const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Largest image accepted by pump.fun, in bytes
+pub const PUMP_FUN_MAX_IMAGE_BYTES: usize = 15 * 1024 * 1024;
+
+/// Largest video accepted by pump.fun, in bytes
+pub const PUMP_FUN_MAX_VIDEO_BYTES: usize = 30 * 1024 * 1024;
+
+/// Image formats accepted by pump.fun
+const PUMP_FUN_IMAGE_TYPES: [&str; 4] = ["image/png", "image/jpeg", "image/gif", "image/webp"];
+
+/// Async reader of an image
+pub type ImageReader = Box<dyn AsyncRead + Send + Unpin>;
+
+/// Source of a token image
+///
+/// Paths convert into sources with `into()`, so `file: "path/to/image.png".into()` reads
+/// the image from a file.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum ImageSource {
+    /// Image file on disk
+    Path(PathBuf),
+    /// Image bytes held in memory
+    Bytes(Vec<u8>),
+    /// Async reader producing the image bytes, read to the end on upload
+    ///
+    /// The reader is consumed by the first upload, and cannot be serialized.
+    #[serde(skip)]
+    Reader(Arc<Mutex<Option<ImageReader>>>),
+    /// URL the image is downloaded from
+    Url(String),
+}
+
+impl ImageSource {
+    /// Creates a source reading the image from an async reader
+    pub fn reader(reader: impl AsyncRead + Send + Unpin + 'static) -> Self {
+        Self::Reader(Arc::new(Mutex::new(Some(Box::new(reader)))))
+    }
+
+    /// Loads the image, returning its bytes and content type
+    ///
+    /// URLs are downloaded with the default HTTP client; use `load_with` to choose one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file, reader, or URL cannot be read, or if a reader has
+    /// already been consumed
+    pub async fn load(&self) -> Result<(Vec<u8>, String), Box<dyn std::error::Error>> {
+        self.load_with(http::default_client().as_ref()).await
+    }
+
+    /// Loads the image, downloading URLs with the given HTTP client
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file, reader, or URL cannot be read, or if a reader has
+    /// already been consumed
+    pub async fn load_with(
+        &self,
+        http: &dyn HttpClient,
+    ) -> Result<(Vec<u8>, String), Box<dyn std::error::Error>> {
+        let (bytes, content_type) = match self {
+            Self::Path(path) => {
+                let bytes = std::fs::read(path)?;
+                let content_type = detect_content_type(&bytes).or_else(|| {
+                    path.extension()?
+                        .to_str()
+                        .and_then(content_type_of_extension)
+                });
+                (bytes, content_type.map(str::to_string))
+            }
+            Self::Bytes(bytes) => (
+                bytes.clone(),
+                detect_content_type(bytes).map(str::to_string),
+            ),
+            Self::Reader(reader) => {
+                let mut reader = reader
+                    .lock()
+                    .await
+                    .take()
+                    .ok_or("Image reader has already been consumed")?;
+                let mut bytes = Vec::new();
+                reader.read_to_end(&mut bytes).await?;
+                let content_type = detect_content_type(&bytes).map(str::to_string);
+                (bytes, content_type)
+            }
+            Self::Url(url) => {
+                let response = http.send(HttpRequest::get(url)).await?;
+                if !response.is_success() {
+                    return Err(format!("{} responded with {}", url, response.status).into());
+                }
+                let served = response
+                    .header("Content-Type")
+                    .filter(|value| value.starts_with("image/") || value.starts_with("video/"))
+                    .map(str::to_string);
+                let bytes = response.body;
+                let content_type =
+                    served.or_else(|| detect_content_type(&bytes).map(str::to_string));
+                (bytes, content_type)
+            }
+        };
+        Ok((
+            bytes,
+            content_type.unwrap_or_else(|| DEFAULT_CONTENT_TYPE.to_string()),
+        ))
+    }
+}
+
+/// Format and size limits checked before images are uploaded
+///
+/// # Fields
+///
+/// * `max_image_bytes` - Largest accepted image, in bytes
+/// * `max_video_bytes` - Largest accepted MP4 video, in bytes. If None, videos are rejected
+/// * `downscale` - How oversized PNG, JPEG, and WebP images are shrunk before they are
+///   checked. If None, they are rejected (requires the `image` feature)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageLimits {
+    pub max_image_bytes: usize,
+    pub max_video_bytes: Option<usize>,
+    #[cfg(feature = "image")]
+    pub downscale: Option<Downscale>,
+}
+
+/// Options for shrinking images that exceed the limits
+///
+/// Images wider or taller than `max_dimension` are resized to fit, keeping their aspect
+/// ratio, and re-encoded in their original format. Images that are still larger than
+/// `ImageLimits::max_image_bytes` are scaled down further until they fit.
+///
+/// # Fields
+///
+/// * `max_dimension` - Largest width and height of the resized image, in pixels
+/// * `jpeg_quality` - Quality JPEG images are re-encoded with, from 1 to 100
+#[cfg(feature = "image")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Downscale {
+    pub max_dimension: u32,
+    pub jpeg_quality: u8,
+}
+
+#[cfg(feature = "image")]
+impl Default for Downscale {
+    fn default() -> Self {
+        Self {
+            max_dimension: 1_000,
+            jpeg_quality: 85,
+        }
+    }
+}
+
+impl Default for ImageLimits {
+    fn default() -> Self {
+        Self::pump_fun()
+    }
+}
+
+impl ImageLimits {
+    /// Returns the limits enforced by pump.fun
+    pub fn pump_fun() -> Self {
+        Self {
+            max_image_bytes: PUMP_FUN_MAX_IMAGE_BYTES,
+            max_video_bytes: Some(PUMP_FUN_MAX_VIDEO_BYTES),
+            #[cfg(feature = "image")]
+            downscale: None,
+        }
+    }
+
+    /// Prepares a loaded image for upload, shrinking it if needed and checking the limits
+    ///
+    /// Without `downscale`, or for formats that cannot be resized, this only runs
+    /// `validate`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Image bytes
+    /// * `content_type` - Content type returned by `ImageSource::load`
+    ///
+    /// # Returns
+    ///
+    /// The image bytes to upload, resized and re-encoded if they exceeded the limits
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClientError::InvalidImage` if the image cannot be decoded for resizing, the
+    /// format is not accepted, or the image is still too large
+    pub fn prepare(
+        &self,
+        bytes: Vec<u8>,
+        content_type: &str,
+    ) -> Result<Vec<u8>, error::ClientError> {
+        #[cfg(feature = "image")]
+        let bytes = match &self.downscale {
+            Some(downscale) => downscale.apply(bytes, content_type, self.max_image_bytes)?,
+            None => bytes,
+        };
+        self.validate(&bytes, content_type)?;
+        Ok(bytes)
+    }
+
+    /// Checks a loaded image against the limits
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Image bytes
+    /// * `content_type` - Content type returned by `ImageSource::load`
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClientError::InvalidImage` if the format is not accepted or the image is too
+    /// large
+    pub fn validate(&self, bytes: &[u8], content_type: &str) -> Result<(), error::ClientError> {
+        let max = if PUMP_FUN_IMAGE_TYPES.contains(&content_type) {
+            self.max_image_bytes
+        } else if content_type == "video/mp4" {
+            self.max_video_bytes.ok_or_else(|| {
+                error::ClientError::InvalidImage("videos are not accepted".to_string())
+            })?
+        } else {
+            return Err(error::ClientError::InvalidImage(format!(
+                "unsupported format {}, expected PNG, JPEG, GIF, WebP, or MP4",
+                content_type
+            )));
+        };
+        if bytes.is_empty() {
+            return Err(error::ClientError::InvalidImage(
+                "image is empty".to_string(),
+            ));
+        }
+        if bytes.len() > max {
+            return Err(error::ClientError::InvalidImage(format!(
+                "{} bytes exceed the limit of {} bytes",
+                bytes.len(),
+                max
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "image")]
+impl Downscale {
+    /// Smallest width and height images are scaled down to while trying to fit the limit
+    const MIN_DIMENSION: u32 = 64;
+
+    /// Resizes and re-encodes an image if it is too large, returning other images unchanged
+    fn apply(
+        &self,
+        bytes: Vec<u8>,
+        content_type: &str,
+        max_bytes: usize,
+    ) -> Result<Vec<u8>, error::ClientError> {
+        use image::{imageops::FilterType, ImageFormat, ImageReader};
+
+        let format = match content_type {
+            "image/png" => ImageFormat::Png,
+            "image/jpeg" => ImageFormat::Jpeg,
+            "image/webp" => ImageFormat::WebP,
+            _ => return Ok(bytes),
+        };
+        let invalid = |err: image::ImageError| {
+            error::ClientError::InvalidImage(format!("failed to resize image: {}", err))
+        };
+        let (width, height) = ImageReader::with_format(std::io::Cursor::new(&bytes), format)
+            .into_dimensions()
+            .map_err(invalid)?;
+        if bytes.len() <= max_bytes && width.max(height) <= self.max_dimension {
+            return Ok(bytes);
+        }
+
+        let mut image = image::load_from_memory_with_format(&bytes, format).map_err(invalid)?;
+        if width.max(height) > self.max_dimension {
+            image = image.resize(self.max_dimension, self.max_dimension, FilterType::Lanczos3);
+        }
+        loop {
+            let encoded = self.encode(&image, format).map_err(invalid)?;
+            let side = image.width().max(image.height());
+            if encoded.len() <= max_bytes || side <= Self::MIN_DIMENSION {
+                return Ok(encoded);
+            }
+            let side = (side * 3 / 4).max(Self::MIN_DIMENSION);
+            image = image.resize(side, side, FilterType::Lanczos3);
+        }
+    }
+
+    fn encode(
+        &self,
+        image: &image::DynamicImage,
+        format: image::ImageFormat,
+    ) -> image::ImageResult<Vec<u8>> {
+        use image::codecs::{
+            jpeg::JpegEncoder,
+            png::{CompressionType, FilterType, PngEncoder},
+            webp::WebPEncoder,
+        };
+
+        let mut encoded = Vec::new();
+        match format {
+            image::ImageFormat::Jpeg => {
+                // JPEG has no alpha channel
+                let encoder = JpegEncoder::new_with_quality(&mut encoded, self.jpeg_quality);
+                image::DynamicImage::ImageRgb8(image.to_rgb8()).write_with_encoder(encoder)?;
+            }
+            image::ImageFormat::WebP => {
+                image.write_with_encoder(WebPEncoder::new_lossless(&mut encoded))?
+            }
+            _ => image.write_with_encoder(PngEncoder::new_with_quality(
+                &mut encoded,
+                CompressionType::Best,
+                FilterType::Adaptive,
+            ))?,
+        }
+        Ok(encoded)
+    }
+}
+
+/// Loads an image, preparing it for upload with the given limits if any
+pub(crate) async fn load_checked(
+    http: &dyn HttpClient,
+    source: &ImageSource,
+    limits: Option<&ImageLimits>,
+) -> Result<(Vec<u8>, String), error::ClientError> {
+    let (bytes, content_type) = source
+        .load_with(http)
+        .await
+        .map_err(error::ClientError::UploadMetadataError)?;
+    let bytes = match limits {
+        Some(limits) => limits.prepare(bytes, &content_type)?,
+        None => bytes,
+    };
+    Ok((bytes, content_type))
+}
+
+impl fmt::Debug for ImageSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Path(path) => f.debug_tuple("Path").field(path).finish(),
+            Self::Bytes(bytes) => write!(f, "Bytes({} bytes)", bytes.len()),
+            Self::Reader(_) => write!(f, "Reader"),
+            Self::Url(url) => f.debug_tuple("Url").field(url).finish(),
+        }
+    }
+}
+
+impl From<PathBuf> for ImageSource {
+    fn from(path: PathBuf) -> Self {
+        Self::Path(path)
+    }
+}
+
+impl From<&str> for ImageSource {
+    fn from(path: &str) -> Self {
+        Self::Path(path.into())
+    }
+}
+
+impl From<String> for ImageSource {
+    fn from(path: String) -> Self {
+        Self::Path(path.into())
+    }
+}
+
+impl From<Vec<u8>> for ImageSource {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Bytes(bytes)
+    }
+}
+
+/// Returns the file extension matching a content type, used to name the uploaded file
+pub(crate) fn extension_of_content_type(content_type: &str) -> &'static str {
+    match content_type {
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/svg+xml" => "svg",
+        "video/mp4" => "mp4",
+        _ => "bin",
+    }
+}
+
+/// Detects the content type of an image from its leading bytes
+fn detect_content_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else if bytes.len() >= 12 && &bytes[4..8] == b"ftyp" {
+        Some("video/mp4")
+    } else {
+        let start = String::from_utf8_lossy(&bytes[..bytes.len().min(256)]);
+        let start = start.trim_start();
+        (start.starts_with("<svg") || (start.starts_with("<?xml") && start.contains("<svg")))
+            .then_some("image/svg+xml")
+    }
+}
+
+fn content_type_of_extension(extension: &str) -> Option<&'static str> {
+    match extension.to_ascii_lowercase().as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        "svg" => Some("image/svg+xml"),
+        "mp4" => Some("video/mp4"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_content_type() {
+        assert_eq!(
+            detect_content_type(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"),
+            Some("image/png")
+        );
+        assert_eq!(
+            detect_content_type(&[0xFF, 0xD8, 0xFF, 0xE0]),
+            Some("image/jpeg")
+        );
+        assert_eq!(detect_content_type(b"GIF89a\x01\0"), Some("image/gif"));
+        assert_eq!(
+            detect_content_type(b"RIFF\x24\0\0\0WEBPVP8 "),
+            Some("image/webp")
+        );
+        assert_eq!(
+            detect_content_type(b"  <?xml version=\"1.0\"?><svg></svg>"),
+            Some("image/svg+xml")
+        );
+        assert_eq!(
+            detect_content_type(b"\0\0\0\x20ftypisom\0\0\x02\0"),
+            Some("video/mp4")
+        );
+        assert_eq!(detect_content_type(b"fake image data"), None);
+        assert_eq!(extension_of_content_type("image/jpeg"), "jpg");
+    }
+
+    #[tokio::test]
+    async fn test_load() {
+        let png = b"\x89PNG\r\n\x1a\nrest".to_vec();
+        let (bytes, content_type) = ImageSource::from(png.clone()).load().await.unwrap();
+        assert_eq!((bytes, content_type.as_str()), (png.clone(), "image/png"));
+
+        // Readers are consumed by the first load
+        let source = ImageSource::reader(std::io::Cursor::new(b"GIF89a".to_vec()));
+        let (_, content_type) = source.clone().load().await.unwrap();
+        assert_eq!(content_type, "image/gif");
+        assert!(source.load().await.is_err());
+
+        // Unrecognized files fall back to their extension
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("image.WEBP");
+        std::fs::write(&path, b"fake image data").unwrap();
+        let (_, content_type) = ImageSource::from(path).load().await.unwrap();
+        assert_eq!(content_type, "image/webp");
+        let path = dir.path().join("image");
+        std::fs::write(&path, b"fake image data").unwrap();
+        let (_, content_type) = ImageSource::from(path).load().await.unwrap();
+        assert_eq!(content_type, DEFAULT_CONTENT_TYPE);
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "image"), allow(clippy::needless_update))]
+    fn test_image_limits() {
+        let limits = ImageLimits {
+            max_image_bytes: 4,
+            max_video_bytes: None,
+            ..ImageLimits::pump_fun()
+        };
+        limits.validate(b"png!", "image/png").unwrap();
+        assert!(matches!(
+            limits.validate(b"png!!", "image/png"),
+            Err(error::ClientError::InvalidImage(_))
+        ));
+        assert!(limits.validate(b"", "image/png").is_err());
+        assert!(limits.validate(b"<svg/>", "image/svg+xml").is_err());
+        assert!(limits.validate(b"mp4", "video/mp4").is_err());
+        ImageLimits::pump_fun()
+            .validate(b"mp4", "video/mp4")
+            .unwrap();
+        assert_eq!(
+            limits.prepare(b"png!".to_vec(), "image/png").unwrap(),
+            b"png!"
+        );
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_downscale() {
+        let mut noise = 7u32;
+        let pixels = image::RgbImage::from_fn(1_200, 600, |_, _| {
+            noise = noise.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            image::Rgb([(noise >> 16) as u8, (noise >> 8) as u8, noise as u8])
+        });
+        let encode = |format| {
+            let mut bytes = std::io::Cursor::new(Vec::new());
+            pixels.write_to(&mut bytes, format).unwrap();
+            bytes.into_inner()
+        };
+        let png = encode(image::ImageFormat::Png);
+        let limits = ImageLimits {
+            max_image_bytes: 256 * 1024,
+            downscale: Some(Downscale::default()),
+            ..ImageLimits::pump_fun()
+        };
+
+        // Rejected without downscaling, resized to fit with it
+        assert!(ImageLimits {
+            downscale: None,
+            ..limits
+        }
+        .prepare(png.clone(), "image/png")
+        .is_err());
+        let resized = limits.prepare(png, "image/png").unwrap();
+        assert!(resized.len() <= limits.max_image_bytes);
+        let resized = image::load_from_memory(&resized).unwrap();
+        assert!(resized.width() <= 1_000);
+        assert!(resized.width().abs_diff(resized.height() * 2) <= 2);
+
+        let jpeg = encode(image::ImageFormat::Jpeg);
+        let resized = limits.prepare(jpeg, "image/jpeg").unwrap();
+        assert_eq!(detect_content_type(&resized), Some("image/jpeg"));
+
+        // Small images and other formats are left untouched
+        let small = b"GIF89a".to_vec();
+        assert_eq!(limits.prepare(small.clone(), "image/gif").unwrap(), small);
+        assert!(limits
+            .prepare(b"\x89PNG\r\n\x1a\nbroken".to_vec(), "image/png")
+            .is_err());
+    }
+}
@@ -3,11 +3,25 @@
 //! This module provides functionality for creating and managing token metadata,
 //! including uploading image and metadata to IPFS via the Pump.fun API.
 
+#[cfg(feature = "versioned-tx")]
+pub mod alt;
+pub mod ata;
+pub mod fees;
+pub mod http;
+pub mod image;
+pub mod preflight;
 pub mod transaction;
+pub mod upload;
+pub mod vanity;
+
+use std::collections::BTreeMap;
 
-use isahc::AsyncReadResponseExt;
 use serde::{Deserialize, Serialize};
-use std::{fs::File, io::Read};
+
+use http::{HttpClient, HttpRequest};
+use image::ImageSource;
+
+use crate::{common::types::UploadRetryPolicy, error};
 
 /// Metadata structure for a token, matching the format expected by Pump.fun.
 #[derive(Debug, Serialize, Deserialize)]
@@ -31,6 +45,18 @@ pub struct TokenMetadata {
     pub telegram: Option<String>,
     /// Website URL
     pub website: Option<String>,
+    /// IPFS URL of the token's banner image
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub banner: Option<String>,
+    /// IPFS URL of the token's video
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub video: Option<String>,
+    /// Discord invite link
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discord: Option<String>,
+    /// Any other fields of the metadata JSON
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
 }
 
 /// Response received after successfully uploading token metadata.
@@ -52,14 +78,22 @@ pub struct CreateTokenMetadata {
     pub symbol: String,
     /// Description of the token
     pub description: String,
-    /// Path to the token's image file
-    pub file: String,
+    /// Source of the token's image: a file path, bytes, async reader, or URL
+    pub file: ImageSource,
     /// Optional Twitter handle
     pub twitter: Option<String>,
     /// Optional Telegram group
     pub telegram: Option<String>,
     /// Optional website URL
     pub website: Option<String>,
+    /// Optional banner image, shown at the top of the token's page
+    pub banner: Option<ImageSource>,
+    /// Optional MP4 video, shown in place of the image on the token's page
+    pub video: Option<ImageSource>,
+    /// Optional Discord invite link
+    pub discord: Option<String>,
+    /// Additional text fields sent with the upload and stored in the metadata JSON
+    pub extra: BTreeMap<String, String>,
 }
 
 /// Creates and uploads token metadata to IPFS via the Pump.fun API.
@@ -74,8 +108,22 @@ pub struct CreateTokenMetadata {
 ///
 /// # Returns
 ///
-/// Returns a `Result` containing the `TokenMetadataResponse` with IPFS locations on success,
-/// or an error if the upload fails.
+/// Returns the `TokenMetadataResponse` with IPFS locations on success, or a ClientError if
+/// the upload fails.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The image cannot be loaded
+/// - The image is not a PNG, JPEG, GIF, WebP, or MP4 within pump.fun's size limits, as
+///   `ClientError::InvalidImage`
+/// - The API rejects the upload, as `ClientError::MetadataUploadRejected`
+/// - The API still rate limits the upload after retrying, as `ClientError::RateLimited`
+/// - The response does not contain the uploaded metadata and its URI
+///
+/// Rate limited uploads, server errors, and network failures are retried with the default
+/// `UploadRetryPolicy`, and requests are sent with the default HTTP client; use
+/// `upload::PumpFunUploader` to configure the retries or the client.
 ///
 /// # Examples
 ///
@@ -87,10 +135,14 @@ pub struct CreateTokenMetadata {
 ///     name: "My Token".to_string(),
 ///     symbol: "MT".to_string(),
 ///     description: "A test token".to_string(),
-///     file: "path/to/image.png".to_string(),
+///     file: "path/to/image.png".into(),
 ///     twitter: None,
 ///     telegram: None,
 ///     website: Some("https://example.com".to_string()),
+///     banner: None,
+///     video: None,
+///     discord: None,
+///     extra: Default::default(),
 /// };
 ///
 /// let response = create_token_metadata(metadata).await?;
@@ -100,7 +152,35 @@ pub struct CreateTokenMetadata {
 /// ```
 pub async fn create_token_metadata(
     metadata: CreateTokenMetadata,
-) -> Result<TokenMetadataResponse, Box<dyn std::error::Error>> {
+) -> Result<TokenMetadataResponse, error::ClientError> {
+    create_token_metadata_at(
+        http::default_client().as_ref(),
+        metadata,
+        upload::PUMP_FUN_IPFS_URL,
+        &UploadRetryPolicy::default(),
+        Some(&image::ImageLimits::pump_fun()),
+    )
+    .await
+}
+
+/// Uploads token metadata to a Pump.fun compatible IPFS endpoint
+pub(crate) async fn create_token_metadata_at(
+    http: &dyn HttpClient,
+    metadata: CreateTokenMetadata,
+    url: &str,
+    retry_policy: &UploadRetryPolicy,
+    image_limits: Option<&image::ImageLimits>,
+) -> Result<TokenMetadataResponse, error::ClientError> {
+    // Load and check the files before building the request
+    let (image, content_type) = image::load_checked(http, &metadata.file, image_limits).await?;
+    let mut files = vec![("file", image, content_type)];
+    for (name, source) in [("banner", &metadata.banner), ("video", &metadata.video)] {
+        if let Some(source) = source {
+            let (bytes, content_type) = image::load_checked(http, source, image_limits).await?;
+            files.push((name, bytes, content_type));
+        }
+    }
+
     let boundary = "------------------------f4d9c2e8b7a5310f";
     let mut body = Vec::new();
 
@@ -129,43 +209,100 @@ pub async fn create_token_metadata(
     if let Some(website) = metadata.website {
         append_text_field(&mut body, boundary, "website", &website);
     }
+    if let Some(discord) = metadata.discord {
+        append_text_field(&mut body, boundary, "discord", &discord);
+    }
+    for (name, value) in &metadata.extra {
+        append_text_field(&mut body, boundary, name, value);
+    }
     append_text_field(&mut body, boundary, "showName", "true");
 
-    // Append file part
-    body.extend_from_slice(b"--");
-    body.extend_from_slice(boundary.as_bytes());
-    body.extend_from_slice(b"\r\n");
-    body.extend_from_slice(b"Content-Disposition: form-data; name=\"file\"; filename=\"file\"\r\n");
-    body.extend_from_slice(b"Content-Type: application/octet-stream\r\n\r\n");
-
-    // Read the file contents
-    let mut file = File::open(&metadata.file)?;
-    let mut file_contents = Vec::new();
-    file.read_to_end(&mut file_contents)?;
-    body.extend_from_slice(&file_contents);
+    // Append file parts
+    for (name, bytes, content_type) in files {
+        body.extend_from_slice(b"--");
+        body.extend_from_slice(boundary.as_bytes());
+        body.extend_from_slice(b"\r\n");
+        body.extend_from_slice(
+            format!(
+                "Content-Disposition: form-data; name=\"{}\"; filename=\"{}.{}\"\r\n",
+                name,
+                if name == "file" { "image" } else { name },
+                image::extension_of_content_type(&content_type)
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(format!("Content-Type: {}\r\n\r\n", content_type).as_bytes());
+        body.extend_from_slice(&bytes);
+        body.extend_from_slice(b"\r\n");
+    }
 
     // Close the boundary
-    body.extend_from_slice(b"\r\n--");
+    body.extend_from_slice(b"--");
     body.extend_from_slice(boundary.as_bytes());
     body.extend_from_slice(b"--\r\n");
 
-    let client = isahc::HttpClient::new()?;
-    let request = isahc::Request::builder()
-        .method("POST")
-        .uri("https://pump.fun/api/ipfs")
-        .header(
-            "Content-Type",
-            format!("multipart/form-data; boundary={}", boundary),
-        )
-        .header("Content-Length", body.len() as u64)
-        .body(isahc::AsyncBody::from(body))?;
-
-    // Send request and print response
-    let mut response = client.send_async(request).await?;
-    let text = response.text().await?;
-    let json: TokenMetadataResponse = serde_json::from_str(&text)?;
-
-    Ok(json)
+    // Send the request, retrying throttled and failed attempts
+    let request = HttpRequest::post(url, body).header(
+        "Content-Type",
+        format!("multipart/form-data; boundary={}", boundary),
+    );
+    let response = upload::send_upload(http, request, retry_policy).await?;
+    upload::validate_response(response)
+}
+
+/// Off-chain metadata JSON of a token, as referenced by its metadata account URI
+///
+/// Every field is optional since the JSON is not validated on-chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct OffchainMetadata {
+    /// Name of the token
+    pub name: Option<String>,
+    /// Token symbol
+    pub symbol: Option<String>,
+    /// Description of the token
+    pub description: Option<String>,
+    /// URL of the token's image
+    pub image: Option<String>,
+    /// Whether to display the token's name
+    pub show_name: Option<bool>,
+    /// Platform the token was created on
+    pub created_on: Option<String>,
+    /// Twitter link or handle
+    pub twitter: Option<String>,
+    /// Telegram link or handle
+    pub telegram: Option<String>,
+    /// Website URL
+    pub website: Option<String>,
+    /// URL of the token's banner image
+    pub banner: Option<String>,
+    /// URL of the token's video
+    pub video: Option<String>,
+    /// Discord invite link
+    pub discord: Option<String>,
+}
+
+/// Fetches and parses a token's off-chain metadata JSON
+///
+/// # Arguments
+///
+/// * `uri` - URI of the metadata JSON, usually read from the token's metadata account
+///
+/// # Returns
+///
+/// Returns the parsed metadata, or a ClientError if the JSON cannot be fetched or parsed
+pub async fn fetch_offchain_metadata(uri: &str) -> Result<OffchainMetadata, error::ClientError> {
+    let fetch_error = |err: String| {
+        error::ClientError::OtherError(format!("Failed to fetch metadata from {}: {}", uri, err))
+    };
+    let response = http::default_client()
+        .send(HttpRequest::get(uri))
+        .await
+        .map_err(|err| fetch_error(err.to_string()))?;
+    if !response.is_success() {
+        return Err(fetch_error(format!("status {}", response.status)));
+    }
+    serde_json::from_slice(&response.body).map_err(|err| fetch_error(err.to_string()))
 }
 
 /// Calculates the maximum amount to pay when buying tokens, accounting for slippage tolerance
@@ -215,3 +352,47 @@ pub fn calculate_with_slippage_buy(amount: u64, basis_points: u64) -> u64 {
 pub fn calculate_with_slippage_sell(amount: u64, basis_points: u64) -> u64 {
     amount - (amount * basis_points) / 10000
 }
+
+/// Decimals of every token minted through Pump.fun
+pub const TOKEN_DECIMALS: u8 = 6;
+
+/// Decimals of SOL, i.e. lamports per SOL as a power of ten
+pub const SOL_DECIMALS: u8 = 9;
+
+/// Converts a raw amount into its display value
+///
+/// # Arguments
+/// * `raw` - Amount in the smallest unit, e.g. lamports or raw token units
+/// * `decimals` - Decimals of the mint, e.g. `TOKEN_DECIMALS` or `SOL_DECIMALS`
+///
+/// # Returns
+/// The amount in whole units
+///
+/// # Example
+/// ```rust
+/// use pumpfun::utils::{self, TOKEN_DECIMALS};
+///
+/// assert_eq!(utils::ui_amount(1_500_000, TOKEN_DECIMALS), 1.5);
+/// ```
+pub fn ui_amount(raw: u64, decimals: u8) -> f64 {
+    raw as f64 / 10f64.powi(decimals as i32)
+}
+
+/// Converts a display value into a raw amount, rounding down
+///
+/// # Arguments
+/// * `ui_amount` - Amount in whole units; negative and NaN amounts convert to zero
+/// * `decimals` - Decimals of the mint, e.g. `TOKEN_DECIMALS` or `SOL_DECIMALS`
+///
+/// # Returns
+/// The amount in the smallest unit
+///
+/// # Example
+/// ```rust
+/// use pumpfun::utils::{self, SOL_DECIMALS};
+///
+/// assert_eq!(utils::raw_amount(0.25, SOL_DECIMALS), 250_000_000);
+/// ```
+pub fn raw_amount(ui_amount: f64, decimals: u8) -> u64 {
+    (ui_amount * 10f64.powi(decimals as i32)).floor() as u64
+}
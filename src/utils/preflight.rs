@@ -0,0 +1,124 @@
+//! Client-side checks run before sending a trade
+//!
+//! These checks catch the most common causes of failed trades before a transaction is built,
+//! returning a specific `ClientError` instead of a cryptic on-chain failure. They are used by
+//! the `PumpFun` client when preflight checks are enabled with `PumpFun::with_preflight_checks`,
+//! and can also be called directly.
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{accounts, error};
+
+/// Base fee charged per transaction signature, in lamports
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// Size of an SPL token account, in bytes
+pub const TOKEN_ACCOUNT_SIZE: usize = 165;
+
+/// Checks that the program's global configuration allows trading
+///
+/// # Arguments
+///
+/// * `global` - Global configuration account
+///
+/// # Errors
+///
+/// Returns `ClientError::ProgramNotInitialized` if the global account is not initialized
+pub fn check_global(global: &accounts::GlobalAccount) -> Result<(), error::ClientError> {
+    if !global.initialized {
+        return Err(error::ClientError::ProgramNotInitialized);
+    }
+    Ok(())
+}
+
+/// Checks that a bonding curve is still tradable
+///
+/// # Arguments
+///
+/// * `mint` - Token mint of the bonding curve
+/// * `bonding_curve` - Bonding curve account
+///
+/// # Errors
+///
+/// Returns `ClientError::BondingCurveComplete` if the curve has completed and migrated
+pub fn check_bonding_curve(
+    mint: &Pubkey,
+    bonding_curve: &accounts::BondingCurveAccount,
+) -> Result<(), error::ClientError> {
+    if bonding_curve.complete {
+        return Err(error::ClientError::BondingCurveComplete(*mint));
+    }
+    Ok(())
+}
+
+/// Checks that an account holds enough SOL
+///
+/// # Arguments
+///
+/// * `required` - Lamports needed, including trade amount, fees, and rent
+/// * `available` - Lamports held by the account
+///
+/// # Errors
+///
+/// Returns `ClientError::InsufficientFunds` if the balance does not cover the requirement
+pub fn check_sol_balance(required: u64, available: u64) -> Result<(), error::ClientError> {
+    if available < required {
+        return Err(error::ClientError::InsufficientFunds {
+            required,
+            available,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that a token account holds enough tokens
+///
+/// # Arguments
+///
+/// * `required` - Token amount to sell, in base units
+/// * `available` - Token balance of the account, in base units
+///
+/// # Errors
+///
+/// Returns `ClientError::InsufficientTokens` if the balance does not cover the amount
+pub fn check_token_balance(required: u64, available: u64) -> Result<(), error::ClientError> {
+    if available < required {
+        return Err(error::ClientError::InsufficientTokens {
+            required,
+            available,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checks() {
+        let mint = Pubkey::new_unique();
+        let mut bonding_curve =
+            accounts::BondingCurveAccount::new(0, 0, 0, 0, 0, 0, false, Pubkey::default());
+        assert!(check_bonding_curve(&mint, &bonding_curve).is_ok());
+        bonding_curve.complete = true;
+        assert!(matches!(
+            check_bonding_curve(&mint, &bonding_curve),
+            Err(error::ClientError::BondingCurveComplete(m)) if m == mint
+        ));
+
+        assert!(check_sol_balance(10, 10).is_ok());
+        assert!(matches!(
+            check_sol_balance(11, 10),
+            Err(error::ClientError::InsufficientFunds {
+                required: 11,
+                available: 10
+            })
+        ));
+        assert!(check_token_balance(5, 10).is_ok());
+        assert!(matches!(
+            check_token_balance(11, 10),
+            Err(error::ClientError::InsufficientTokens { .. })
+        ));
+    }
+}
@@ -1,16 +1,21 @@
 use std::sync::Arc;
 
-use solana_client::{nonblocking::rpc_client::RpcClient, rpc_client::SerializableTransaction};
-#[cfg(not(feature = "versioned-tx"))]
-use solana_sdk::transaction::Transaction;
-use solana_sdk::{instruction::Instruction, signature::Keypair, signer::Signer};
+use solana_client::nonblocking::rpc_client::RpcClient;
 #[cfg(feature = "versioned-tx")]
+use solana_sdk::message::{v0, AddressLookupTableAccount};
 use solana_sdk::{
-    message::{v0, AddressLookupTableAccount, VersionedMessage},
+    hash::Hash,
+    instruction::Instruction,
+    message::VersionedMessage,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
     transaction::VersionedTransaction,
 };
+#[cfg(not(feature = "versioned-tx"))]
+use solana_sdk::{message::Message, transaction::Transaction};
 
-use crate::error;
+use crate::{common::types::PriorityFee, error, sender::TransactionSender};
 
 /// Constructs a signed transaction from a set of instructions and signers
 ///
@@ -31,8 +36,7 @@ use crate::error;
 ///
 /// # Returns
 ///
-/// Returns a signed Transaction (or VersionedTransaction when the "versioned-tx" feature is enabled)
-/// if successful, or a ClientError if the operation fails
+/// Returns a signed VersionedTransaction if successful, or a ClientError if the operation fails
 ///
 /// # Errors
 ///
@@ -40,12 +44,14 @@ use crate::error;
 /// - Failed to retrieve the recent blockhash from the network
 /// - Transaction creation fails due to invalid parameters
 /// - Transaction message compilation fails (for versioned transactions)
+/// - The transaction exceeds `MAX_TRANSACTION_SIZE`, returning `ClientError::TransactionTooLarge`
 /// - Transaction signing fails
 ///
 /// # Feature flags
 ///
-/// When compiled with the "versioned-tx" feature, this function returns a VersionedTransaction
-/// that supports Address Lookup Tables. Otherwise, it returns a standard Transaction.
+/// When compiled with the "versioned-tx" feature, this function builds a v0 message that
+/// supports Address Lookup Tables. Otherwise, it builds a legacy message wrapped in a
+/// VersionedTransaction, so either can be passed to a `TransactionSender`.
 ///
 /// # Examples
 ///
@@ -112,30 +118,105 @@ pub async fn get_transaction(
     #[cfg(feature = "versioned-tx")] address_lookup_table_accounts: Option<
         &[AddressLookupTableAccount],
     >,
-) -> Result<impl SerializableTransaction, error::ClientError> {
+) -> Result<VersionedTransaction, error::ClientError> {
     // Get recent blockhash for transaction validity window
     let recent_blockhash = rpc
         .get_latest_blockhash()
         .await
-        .map_err(error::ClientError::SolanaClientError)?;
+        .map_err(error::ClientError::from)?;
 
+    build_transaction(
+        &payer,
+        instructions,
+        additional_signers,
+        recent_blockhash,
+        #[cfg(feature = "versioned-tx")]
+        address_lookup_table_accounts,
+    )
+}
+
+/// Constructs a signed transaction using a caller-provided blockhash
+///
+/// This is the offline counterpart of `get_transaction`: no network requests are made, so the
+/// same inputs always produce the exact same transaction bytes. Use it for snapshot tests of
+/// the build pipeline, for durable nonce transactions, or when the blockhash is fetched
+/// separately.
+///
+/// # Arguments
+///
+/// * `payer` - The primary account that will pay for the transaction fees
+/// * `instructions` - Slice of Solana instructions to include in the transaction
+/// * `additional_signers` - Optional slice of additional keypair signers that should sign the transaction,
+///   in addition to the payer
+/// * `recent_blockhash` - Blockhash the transaction is signed with
+/// * `address_lookup_table_accounts` - Optional slice of Address Lookup Table accounts to include
+///   (only available with "versioned-tx" feature)
+///
+/// # Returns
+///
+/// Returns a signed VersionedTransaction if successful, or a ClientError if the operation fails
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - Transaction message compilation fails (for versioned transactions)
+/// - The transaction exceeds `MAX_TRANSACTION_SIZE`, returning `ClientError::TransactionTooLarge`
+/// - Transaction signing fails
+///
+/// # Examples
+///
+/// ```
+/// # use pumpfun::utils::transaction::build_transaction;
+/// # use solana_sdk::{hash::Hash, signature::Keypair, signer::Signer};
+/// # use solana_system_interface::instruction::transfer;
+/// let payer = Keypair::new_from_array([7; 32]);
+/// let instructions = [transfer(&payer.pubkey(), &payer.pubkey(), 1)];
+///
+/// let first = build_transaction(
+///     &payer,
+///     &instructions,
+///     None,
+///     Hash::default(),
+/// #   #[cfg(feature = "versioned-tx")]
+/// #   None,
+/// )?;
+/// let second = build_transaction(
+///     &payer,
+///     &instructions,
+///     None,
+///     Hash::default(),
+/// #   #[cfg(feature = "versioned-tx")]
+/// #   None,
+/// )?;
+/// assert_eq!(first, second);
+/// # Ok::<(), pumpfun::error::ClientError>(())
+/// ```
+pub fn build_transaction(
+    payer: &Keypair,
+    instructions: &[Instruction],
+    additional_signers: Option<&[&Keypair]>,
+    recent_blockhash: Hash,
+    #[cfg(feature = "versioned-tx")] address_lookup_table_accounts: Option<
+        &[AddressLookupTableAccount],
+    >,
+) -> Result<VersionedTransaction, error::ClientError> {
     // Create a combined signers array with payer and additional signers
     let mut all_signers =
         Vec::with_capacity(1 + additional_signers.map_or(0, |signers| signers.len()));
-    all_signers.push(&*payer);
+    all_signers.push(payer);
 
     if let Some(signers) = additional_signers {
         all_signers.extend(signers);
     }
 
-    // Create and sign legacy transaction with all signers
+    // Create and sign legacy transaction with all signers, once it is known to fit
     #[cfg(not(feature = "versioned-tx"))]
-    let transaction = Transaction::new_signed_with_payer(
-        instructions,
-        Some(&payer.pubkey()),
-        &all_signers,
-        recent_blockhash,
-    );
+    let transaction = {
+        let message =
+            Message::new_with_blockhash(instructions, Some(&payer.pubkey()), &recent_blockhash);
+        check_transaction_size(&VersionedMessage::Legacy(message.clone()))?;
+        Transaction::new(&all_signers, message, recent_blockhash).into()
+    };
 
     // Create and sign versioned transaction with all signers
     #[cfg(feature = "versioned-tx")]
@@ -155,6 +236,8 @@ pub async fn get_transaction(
             }
         };
 
+        check_transaction_size(&message)?;
+
         match VersionedTransaction::try_new(message, &all_signers) {
             Ok(tx) => tx,
             Err(e) => {
@@ -168,3 +251,703 @@ pub async fn get_transaction(
 
     Ok(transaction)
 }
+
+/// Constructs an unsigned transaction for signing outside of this process
+///
+/// The transaction is compiled with the given fee payer and blockhash, and every required
+/// signature is left as the default (all zeros) placeholder. Use this when signing with an
+/// external wallet, a multisig, or a remote signing service. The result can be serialized
+/// to base64 with `sender::encode_transaction`.
+///
+/// # Arguments
+///
+/// * `fee_payer` - Account that pays the transaction fees
+/// * `instructions` - Slice of Solana instructions to include in the transaction
+/// * `recent_blockhash` - Blockhash the transaction will be signed with
+/// * `address_lookup_table_accounts` - Optional slice of Address Lookup Table accounts to include
+///   (only available with "versioned-tx" feature)
+///
+/// # Returns
+///
+/// Returns an unsigned VersionedTransaction if successful, or a ClientError if the operation fails
+///
+/// # Errors
+///
+/// Returns an error if transaction message compilation fails (for versioned transactions)
+pub fn build_unsigned_transaction(
+    fee_payer: &Pubkey,
+    instructions: &[Instruction],
+    recent_blockhash: Hash,
+    #[cfg(feature = "versioned-tx")] address_lookup_table_accounts: Option<
+        &[AddressLookupTableAccount],
+    >,
+) -> Result<VersionedTransaction, error::ClientError> {
+    #[cfg(not(feature = "versioned-tx"))]
+    let message = VersionedMessage::Legacy(Message::new_with_blockhash(
+        instructions,
+        Some(fee_payer),
+        &recent_blockhash,
+    ));
+
+    #[cfg(feature = "versioned-tx")]
+    let message = VersionedMessage::V0(
+        v0::Message::try_compile(
+            fee_payer,
+            instructions,
+            address_lookup_table_accounts.unwrap_or(&[]),
+            recent_blockhash,
+        )
+        .map_err(|e| {
+            error::ClientError::OtherError(format!("Failed to compile transaction message: {}", e))
+        })?,
+    );
+
+    let num_signatures = message.header().num_required_signatures as usize;
+    Ok(VersionedTransaction {
+        signatures: vec![Signature::default(); num_signatures],
+        message,
+    })
+}
+
+/// Largest serialized transaction accepted by the network, in bytes
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+
+/// Returns the serialized size of a transaction with the given message once signed
+///
+/// # Arguments
+///
+/// * `message` - Compiled transaction message
+pub fn transaction_size(message: &VersionedMessage) -> usize {
+    let num_signatures = message.header().num_required_signatures as usize;
+    // Signatures are prefixed by their count as a compact-u16
+    let count_size = match num_signatures {
+        0..=0x7f => 1,
+        0x80..=0x3fff => 2,
+        _ => 3,
+    };
+    count_size + num_signatures * 64 + message.serialize().len()
+}
+
+/// Fails if a transaction with the given message would exceed `MAX_TRANSACTION_SIZE`
+fn check_transaction_size(message: &VersionedMessage) -> Result<(), error::ClientError> {
+    let size = transaction_size(message);
+    if size > MAX_TRANSACTION_SIZE {
+        return Err(error::ClientError::TransactionTooLarge {
+            size,
+            limit: MAX_TRANSACTION_SIZE,
+        });
+    }
+    Ok(())
+}
+
+/// Largest compute budget a transaction can request
+pub const MAX_TRANSACTION_COMPUTE_UNITS: u32 = 1_400_000;
+
+/// Compute units the runtime allots to an instruction when no limit is requested
+pub const DEFAULT_INSTRUCTION_COMPUTE_UNITS: u32 = 200_000;
+
+/// Transaction sent by `TransactionPacker::send`
+///
+/// # Fields
+///
+/// * `instructions` - Instructions of the transaction, including the packer's prefix
+/// * `result` - Signature of the confirmed transaction, or why it failed
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackedTransaction {
+    pub instructions: Vec<Instruction>,
+    pub result: Result<Signature, String>,
+}
+
+/// Splits a list of instructions into as few transactions as possible
+///
+/// Instructions keep their order and are packed greedily: each transaction takes as many
+/// of the following instructions as fit within `MAX_TRANSACTION_SIZE` once serialized and
+/// within the compute budget, which counts a fixed number of compute units per
+/// instruction. Every transaction starts with the packer's prefix, such as priority fee
+/// instructions, which counts towards the size but not the compute budget.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use pumpfun::{common::types::{Cluster, PriorityFee}, utils::transaction::TransactionPacker, PumpFun};
+/// # use solana_sdk::{commitment_config::CommitmentConfig, instruction::Instruction, signature::Keypair, signer::Signer};
+/// # use std::sync::Arc;
+/// #
+/// # async fn example(sells: Vec<Instruction>) -> Result<(), Box<dyn std::error::Error>> {
+/// # let payer = Arc::new(Keypair::new());
+/// # let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+/// let client = Arc::new(PumpFun::new(payer, cluster));
+///
+/// let packer = TransactionPacker::new(client.payer.pubkey())
+///     .with_prefix(PumpFun::get_priority_fee_instructions(&client.cluster.priority_fee))
+///     .with_compute_units(400_000, 80_000);
+/// for transaction in packer.send(client, sells, Vec::new(), 4).await? {
+///     println!("{:?}", transaction.result);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TransactionPacker {
+    payer: Pubkey,
+    prefix: Vec<Instruction>,
+    compute_budget: u32,
+    compute_units_per_instruction: u32,
+    #[cfg(feature = "versioned-tx")]
+    lookup_tables: Vec<AddressLookupTableAccount>,
+}
+
+impl TransactionPacker {
+    /// Creates a packer for transactions paid by `payer`
+    ///
+    /// The default compute budget matches the runtime's defaults: at most
+    /// `MAX_TRANSACTION_COMPUTE_UNITS` per transaction and `DEFAULT_INSTRUCTION_COMPUTE_UNITS`
+    /// per instruction.
+    pub fn new(payer: Pubkey) -> Self {
+        Self {
+            payer,
+            prefix: Vec::new(),
+            compute_budget: MAX_TRANSACTION_COMPUTE_UNITS,
+            compute_units_per_instruction: DEFAULT_INSTRUCTION_COMPUTE_UNITS,
+            #[cfg(feature = "versioned-tx")]
+            lookup_tables: Vec::new(),
+        }
+    }
+
+    /// Starts every transaction with the given instructions, e.g. priority fees
+    pub fn with_prefix(mut self, prefix: Vec<Instruction>) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Limits the compute units of each transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `budget` - Compute units available to the instructions of a transaction, which
+    ///   should match any compute unit limit set by the prefix
+    /// * `per_instruction` - Compute units assumed for each instruction
+    pub fn with_compute_units(mut self, budget: u32, per_instruction: u32) -> Self {
+        self.compute_budget = budget;
+        self.compute_units_per_instruction = per_instruction;
+        self
+    }
+
+    /// Compresses the transactions returned by `pack` with address lookup tables
+    ///
+    /// `send` signs through the client, so it packs with the client's lookup tables instead.
+    #[cfg(feature = "versioned-tx")]
+    pub fn with_lookup_tables(mut self, lookup_tables: Vec<AddressLookupTableAccount>) -> Self {
+        self.lookup_tables = lookup_tables;
+        self
+    }
+
+    /// Splits instructions into transactions
+    ///
+    /// # Arguments
+    ///
+    /// * `instructions` - Instructions to pack, in execution order
+    ///
+    /// # Returns
+    ///
+    /// Returns the instructions of each transaction, each starting with the prefix
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClientError::TransactionTooLarge` if an instruction does not fit in a
+    /// transaction on its own, or an error if a transaction cannot be compiled
+    pub fn pack(
+        &self,
+        instructions: Vec<Instruction>,
+    ) -> Result<Vec<Vec<Instruction>>, error::ClientError> {
+        let max_instructions = match self.compute_units_per_instruction {
+            0 => usize::MAX,
+            per_instruction => (self.compute_budget / per_instruction) as usize,
+        };
+        if max_instructions == 0 && !instructions.is_empty() {
+            return Err(error::ClientError::ConfigError(
+                "Compute budget does not fit a single instruction".to_string(),
+            ));
+        }
+
+        let mut batches = Vec::new();
+        let mut current = self.prefix.clone();
+        for instruction in instructions {
+            // Start a new transaction once the current one is full
+            let packed = current.len() - self.prefix.len();
+            if packed > 0
+                && (packed >= max_instructions
+                    || self.size_with(&current, &instruction)? > MAX_TRANSACTION_SIZE)
+            {
+                batches.push(std::mem::replace(&mut current, self.prefix.clone()));
+            }
+            if current.len() == self.prefix.len() {
+                let size = self.size_with(&current, &instruction)?;
+                if size > MAX_TRANSACTION_SIZE {
+                    return Err(error::ClientError::TransactionTooLarge {
+                        size,
+                        limit: MAX_TRANSACTION_SIZE,
+                    });
+                }
+            }
+            current.push(instruction);
+        }
+        if current.len() > self.prefix.len() {
+            batches.push(current);
+        }
+        Ok(batches)
+    }
+
+    /// Packs instructions into transactions, then signs and sends them
+    ///
+    /// Transactions are sent through the client, with its retry policy, and at most
+    /// `concurrency` of them are in flight at once. They are sized with the client's lookup
+    /// tables, which the client compiles them with, rather than the packer's. Since they are sent concurrently,
+    /// instructions in different transactions must not depend on each other's order.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Client used to sign and send the transactions
+    /// * `instructions` - Instructions to pack
+    /// * `signers` - Signers in addition to the client's payer; each transaction is signed
+    ///   by the ones its instructions require
+    /// * `concurrency` - Maximum number of transactions in flight, at least one
+    ///
+    /// # Returns
+    ///
+    /// Returns the outcome of every transaction, in packing order
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the instructions cannot be packed; failed transactions are
+    /// reported in their `PackedTransaction` instead
+    pub async fn send(
+        &self,
+        client: Arc<crate::PumpFun>,
+        instructions: Vec<Instruction>,
+        signers: Vec<Arc<Keypair>>,
+        concurrency: usize,
+    ) -> Result<Vec<PackedTransaction>, error::ClientError> {
+        #[cfg(feature = "versioned-tx")]
+        let batches = Self {
+            lookup_tables: client.lookup_tables.clone(),
+            ..self.clone()
+        }
+        .pack(instructions)?;
+        #[cfg(not(feature = "versioned-tx"))]
+        let batches = self.pack(instructions)?;
+        let mut results: Vec<Option<Result<Signature, String>>> = vec![None; batches.len()];
+        let mut tasks = tokio::task::JoinSet::new();
+        let mut pending = batches.iter().cloned().enumerate();
+        loop {
+            while tasks.len() < concurrency.max(1) {
+                let Some((index, batch)) = pending.next() else {
+                    break;
+                };
+                let client = client.clone();
+                let signers = required_signers(&batch, &signers);
+                tasks.spawn(async move {
+                    let signers: Vec<&Keypair> =
+                        signers.iter().map(|signer| signer.as_ref()).collect();
+                    let result = client
+                        .send_instructions(&batch, Some(&signers), None)
+                        .await
+                        .map_err(|err| err.to_string());
+                    (index, result)
+                });
+            }
+            match tasks.join_next().await {
+                Some(Ok((index, result))) => results[index] = Some(result),
+                Some(Err(err)) => {
+                    return Err(error::ClientError::OtherError(format!(
+                        "Transaction task failed: {}",
+                        err
+                    )))
+                }
+                None => break,
+            }
+        }
+
+        Ok(batches
+            .into_iter()
+            .zip(results)
+            .map(|(instructions, result)| PackedTransaction {
+                instructions,
+                result: result.unwrap_or_else(|| Err("Transaction was not sent".to_string())),
+            })
+            .collect())
+    }
+
+    /// Returns the serialized size of a transaction holding the instructions and one more
+    fn size_with(
+        &self,
+        instructions: &[Instruction],
+        instruction: &Instruction,
+    ) -> Result<usize, error::ClientError> {
+        let mut candidate = Vec::with_capacity(instructions.len() + 1);
+        candidate.extend_from_slice(instructions);
+        candidate.push(instruction.clone());
+        self.size(&candidate)
+    }
+
+    /// Returns the serialized size of a transaction holding the instructions
+    fn size(&self, instructions: &[Instruction]) -> Result<usize, error::ClientError> {
+        let transaction = build_unsigned_transaction(
+            &self.payer,
+            instructions,
+            Hash::default(),
+            #[cfg(feature = "versioned-tx")]
+            Some(self.lookup_tables.as_slice()),
+        )?;
+        Ok(transaction_size(&transaction.message))
+    }
+}
+
+/// Transaction combining the SDK's trade instructions with the caller's own
+///
+/// Created with `PumpFun::transaction`. Instructions are kept in the order they are added,
+/// so transfers, memos, or tips can go before or after the buys and sells. Building or
+/// sending prepends the priority fee instructions and goes through the client's blockhash,
+/// lookup tables, sender, and retry policy, like the client's own trade methods.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use pumpfun::{common::types::{Cluster, PriorityFee}, PumpFun};
+/// # use solana_sdk::{commitment_config::CommitmentConfig, native_token::sol_to_lamports, pubkey, signature::Keypair, signer::Signer};
+/// # use solana_system_interface::instruction::transfer;
+/// # use std::sync::Arc;
+/// #
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let payer = Arc::new(Keypair::new());
+/// # let cluster = Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default());
+/// # let client = PumpFun::new(payer, cluster);
+/// let mint = pubkey!("TokenM1ntPubk3yXXXXXXXXXXXXXXXXXXXXXXXXXXXX");
+/// let tip_account = pubkey!("TipAccountXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX");
+///
+/// let signature = client
+///     .transaction()
+///     .buy(mint, sol_to_lamports(0.1), None, None)
+///     .await?
+///     .instruction(transfer(&client.payer.pubkey(), &tip_account, 10_000))
+///     .send()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TransactionBuilder<'a> {
+    client: &'a crate::PumpFun,
+    instructions: Vec<Instruction>,
+    signers: Vec<&'a Keypair>,
+    priority_fee: PriorityFee,
+    sender: Option<&'a dyn TransactionSender>,
+}
+
+impl<'a> TransactionBuilder<'a> {
+    /// Creates an empty transaction for the client, with its default priority fee
+    pub fn new(client: &'a crate::PumpFun) -> Self {
+        Self {
+            client,
+            instructions: Vec::new(),
+            signers: Vec::new(),
+            priority_fee: client.cluster.priority_fee,
+            sender: None,
+        }
+    }
+
+    /// Appends an instruction
+    pub fn instruction(mut self, instruction: Instruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    /// Appends several instructions, in order
+    pub fn instructions(mut self, instructions: impl IntoIterator<Item = Instruction>) -> Self {
+        self.instructions.extend(instructions);
+        self
+    }
+
+    /// Appends the instructions of `PumpFun::get_buy_instructions`
+    ///
+    /// # Errors
+    ///
+    /// Returns any error of `PumpFun::get_buy_instructions`
+    pub async fn buy(
+        self,
+        mint: Pubkey,
+        amount_sol: u64,
+        track_volume: Option<bool>,
+        slippage_basis_points: Option<u64>,
+    ) -> Result<Self, error::ClientError> {
+        let instructions = self
+            .client
+            .get_buy_instructions(mint, amount_sol, track_volume, slippage_basis_points)
+            .await?;
+        Ok(self.instructions(instructions))
+    }
+
+    /// Appends the instructions of `PumpFun::get_sell_instructions`
+    ///
+    /// # Errors
+    ///
+    /// Returns any error of `PumpFun::get_sell_instructions`
+    pub async fn sell(
+        self,
+        mint: Pubkey,
+        amount_token: Option<u64>,
+        slippage_basis_points: Option<u64>,
+    ) -> Result<Self, error::ClientError> {
+        let instructions = self
+            .client
+            .get_sell_instructions(mint, amount_token, slippage_basis_points)
+            .await?;
+        Ok(self.instructions(instructions))
+    }
+
+    /// Adds a signer in addition to the client's payer
+    pub fn signer(mut self, signer: &'a Keypair) -> Self {
+        self.signers.push(signer);
+        self
+    }
+
+    /// Sets the priority fee, overriding the one in the cluster configuration
+    pub fn priority_fee(mut self, priority_fee: PriorityFee) -> Self {
+        self.priority_fee = priority_fee;
+        self
+    }
+
+    /// Sends through the given sender instead of the client's sender
+    pub fn sender(mut self, sender: &'a dyn TransactionSender) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// Returns every instruction of the transaction, starting with the priority fee
+    pub fn to_instructions(&self) -> Vec<Instruction> {
+        let mut instructions = crate::PumpFun::get_priority_fee_instructions(&self.priority_fee);
+        instructions.extend(self.instructions.iter().cloned());
+        instructions
+    }
+
+    /// Builds and signs the transaction without sending it
+    ///
+    /// # Errors
+    ///
+    /// Returns any error of `PumpFun::build_transaction`
+    pub async fn build(&self) -> Result<VersionedTransaction, error::ClientError> {
+        self.client
+            .build_transaction(&self.to_instructions(), self.additional_signers())
+            .await
+    }
+
+    /// Signs and sends the transaction, retrying according to the client's retry policy
+    ///
+    /// # Errors
+    ///
+    /// Returns any error of `PumpFun::send_instructions`
+    pub async fn send(&self) -> Result<Signature, error::ClientError> {
+        self.client
+            .send_instructions(
+                &self.to_instructions(),
+                self.additional_signers(),
+                self.sender,
+            )
+            .await
+    }
+
+    fn additional_signers(&self) -> Option<&[&'a Keypair]> {
+        (!self.signers.is_empty()).then_some(self.signers.as_slice())
+    }
+}
+
+/// Returns the signers whose signature the instructions require
+fn required_signers(instructions: &[Instruction], signers: &[Arc<Keypair>]) -> Vec<Arc<Keypair>> {
+    signers
+        .iter()
+        .filter(|signer| {
+            let pubkey = signer.pubkey();
+            instructions.iter().any(|instruction| {
+                instruction
+                    .accounts
+                    .iter()
+                    .any(|account| account.is_signer && account.pubkey == pubkey)
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_system_interface::instruction::transfer;
+
+    #[test]
+    fn test_build_transaction_is_deterministic() {
+        let payer = Keypair::new_from_array([7; 32]);
+        let signer = Keypair::new_from_array([9; 32]);
+        let instructions = [
+            transfer(&payer.pubkey(), &signer.pubkey(), 1_000),
+            transfer(&signer.pubkey(), &payer.pubkey(), 1),
+        ];
+        let blockhash = Hash::new_from_array([3; 32]);
+
+        let build = || {
+            build_transaction(
+                &payer,
+                &instructions,
+                Some(&[&signer]),
+                blockhash,
+                #[cfg(feature = "versioned-tx")]
+                None,
+            )
+            .unwrap()
+        };
+
+        let first = bincode::serialize(&build()).unwrap();
+        let second = bincode::serialize(&build()).unwrap();
+        assert_eq!(first, second);
+
+        let transaction = build();
+        assert_eq!(transaction.signatures.len(), 2);
+        assert_eq!(*transaction.message.recent_blockhash(), blockhash);
+        assert!(transaction.verify_with_results().iter().all(|ok| *ok));
+    }
+
+    #[test]
+    fn test_build_unsigned_transaction() {
+        let fee_payer = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let instructions = [
+            transfer(&user, &fee_payer, 1),
+            transfer(&fee_payer, &user, 1),
+        ];
+
+        let transaction = build_unsigned_transaction(
+            &fee_payer,
+            &instructions,
+            Hash::default(),
+            #[cfg(feature = "versioned-tx")]
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(transaction.signatures, vec![Signature::default(); 2]);
+        assert_eq!(transaction.message.static_account_keys()[0], fee_payer);
+        assert!(transaction.message.static_account_keys().contains(&user));
+    }
+
+    #[test]
+    fn test_transaction_packer() {
+        let payer = Keypair::new_from_array([7; 32]).pubkey();
+        let instructions: Vec<Instruction> = (0..30)
+            .map(|index| transfer(&payer, &Pubkey::new_unique(), index + 1))
+            .collect();
+
+        // Compute units cap each transaction at 7 instructions
+        let batches = TransactionPacker::new(payer)
+            .pack(instructions.clone())
+            .unwrap();
+        assert_eq!(batches.len(), 5);
+        assert!(batches.iter().all(|batch| batch.len() <= 7));
+        assert_eq!(batches.concat(), instructions);
+
+        // Without a compute limit, size splits the transactions instead
+        let packer = TransactionPacker::new(payer)
+            .with_prefix(vec![transfer(&payer, &payer, 0)])
+            .with_compute_units(MAX_TRANSACTION_COMPUTE_UNITS, 0);
+        let batches = packer.pack(instructions.clone()).unwrap();
+        assert!(batches.len() > 1);
+        for batch in &batches {
+            assert_eq!(batch[0], transfer(&payer, &payer, 0));
+            assert!(packer.size(batch).unwrap() <= MAX_TRANSACTION_SIZE);
+        }
+        let packed: Vec<Instruction> = batches
+            .into_iter()
+            .flat_map(|batch| batch.into_iter().skip(1))
+            .collect();
+        assert_eq!(packed, instructions);
+
+        let oversized = Instruction::new_with_bytes(Pubkey::new_unique(), &[0; 1232], vec![]);
+        assert!(matches!(
+            packer.pack(vec![oversized]),
+            Err(error::ClientError::TransactionTooLarge { size, limit })
+                if size > limit && limit == MAX_TRANSACTION_SIZE
+        ));
+    }
+
+    #[test]
+    fn test_transaction_size_limit() {
+        let payer = Keypair::new_from_array([7; 32]);
+        let instructions = [transfer(&payer.pubkey(), &payer.pubkey(), 1)];
+        let transaction = build_transaction(
+            &payer,
+            &instructions,
+            None,
+            Hash::default(),
+            #[cfg(feature = "versioned-tx")]
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            transaction_size(&transaction.message),
+            bincode::serialize(&transaction).unwrap().len()
+        );
+
+        let instructions: Vec<Instruction> = (0..40)
+            .map(|index| transfer(&payer.pubkey(), &Pubkey::new_unique(), index))
+            .collect();
+        assert!(matches!(
+            build_transaction(
+                &payer,
+                &instructions,
+                None,
+                Hash::default(),
+                #[cfg(feature = "versioned-tx")]
+                None,
+            ),
+            Err(error::ClientError::TransactionTooLarge { limit, .. }) if limit == MAX_TRANSACTION_SIZE
+        ));
+    }
+
+    #[test]
+    fn test_transaction_builder() {
+        let payer = Arc::new(Keypair::new_from_array([7; 32]));
+        let signer = Keypair::new_from_array([9; 32]);
+        let cluster = crate::common::types::Cluster::localnet(
+            solana_sdk::commitment_config::CommitmentConfig::confirmed(),
+            PriorityFee::new(Some(100_000), Some(1_000)),
+        );
+        let client = crate::PumpFun::builder(payer.clone(), cluster)
+            .rpc_client(Arc::new(RpcClient::new_mock("succeeds".to_string())))
+            .build()
+            .with_fixed_blockhash(Hash::default());
+
+        let before = transfer(&payer.pubkey(), &signer.pubkey(), 1);
+        let after = transfer(&signer.pubkey(), &payer.pubkey(), 1);
+        let builder = client
+            .transaction()
+            .instruction(before.clone())
+            .instructions([after.clone()])
+            .signer(&signer);
+
+        let instructions = builder.to_instructions();
+        assert_eq!(instructions.len(), 4);
+        assert_eq!(instructions[2..], [before, after]);
+        assert!(builder
+            .priority_fee(PriorityFee::default())
+            .to_instructions()
+            .iter()
+            .all(|instruction| instruction.program_id == solana_system_interface::program::ID));
+
+        let transaction = tokio_test::block_on(
+            client
+                .transaction()
+                .instructions(instructions[2..].to_vec())
+                .signer(&signer)
+                .build(),
+        )
+        .unwrap();
+        assert_eq!(transaction.signatures.len(), 2);
+    }
+}
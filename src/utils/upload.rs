@@ -0,0 +1,653 @@
+//! Pluggable providers for uploading token metadata
+//!
+//! Creating a token requires its image and metadata JSON to be hosted, usually on IPFS.
+//! `MetadataUploader` abstracts where they are uploaded, and a client uploads through the
+//! uploader set with `PumpFun::with_metadata_uploader`, which defaults to the pump.fun API.
+//!
+//! # Providers
+//!
+//! - `PumpFunUploader`: The pump.fun IPFS API used by the pump.fun website
+//! - `PinataUploader`: Pinata's pinning API, authenticated with a JWT
+//! - `NftStorageUploader`: NFT.Storage's upload API, authenticated with an API key
+//! - `IpfsHttpUploader`: Any node or service exposing the IPFS HTTP API (`/api/v0/add`)
+//!
+//! Except for the pump.fun API, which builds the metadata JSON itself, providers upload the
+//! image, then a metadata JSON in pump.fun's format that points to the image through the
+//! provider's gateway.
+//!
+//! # Errors and Retries
+//!
+//! Error responses fail with `ClientError::MetadataUploadRejected`, and throttled requests
+//! with `ClientError::RateLimited`. Each provider retries throttled requests, server errors,
+//! and network failures following its `UploadRetryPolicy`, set with `with_retry_policy`.
+//! Responses that do not contain the uploaded file or metadata fail with
+//! `ClientError::UploadMetadataError`. Images are checked against pump.fun's format and
+//! size limits before the first request, failing with `ClientError::InvalidImage`; use
+//! `with_image_limits` to change or skip the checks.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::{
+    http::{self, HttpClient, HttpRequest},
+    image::{self, ImageLimits},
+    CreateTokenMetadata, TokenMetadata, TokenMetadataResponse,
+};
+use crate::{common::types::UploadRetryPolicy, error};
+
+/// IPFS upload endpoint of the pump.fun API
+pub const PUMP_FUN_IPFS_URL: &str = "https://pump.fun/api/ipfs";
+
+/// Public IPFS gateway used to link uploaded files when none is given
+pub const DEFAULT_IPFS_GATEWAY: &str = "https://ipfs.io/ipfs";
+
+/// Boundary separating the parts of multipart uploads
+const BOUNDARY: &str = "------------------------8a1b5c7d9e3f2460";
+
+/// Uploads the image and metadata JSON of a new token
+#[async_trait]
+pub trait MetadataUploader: Send + Sync {
+    /// Uploads a token's image and metadata
+    ///
+    /// # Arguments
+    ///
+    /// * `metadata` - Token metadata including the image source
+    ///
+    /// # Returns
+    ///
+    /// Returns the uploaded metadata and its URI, or a ClientError if the upload fails
+    async fn upload(
+        &self,
+        metadata: CreateTokenMetadata,
+    ) -> Result<TokenMetadataResponse, error::ClientError>;
+}
+
+/// Uploads through the pump.fun IPFS API
+#[derive(Clone)]
+pub struct PumpFunUploader {
+    url: String,
+    retry_policy: UploadRetryPolicy,
+    image_limits: Option<ImageLimits>,
+    http: Arc<dyn HttpClient>,
+}
+
+impl PumpFunUploader {
+    /// Creates an uploader for the pump.fun API
+    pub fn new() -> Self {
+        Self::with_url(PUMP_FUN_IPFS_URL)
+    }
+
+    /// Creates an uploader for a pump.fun compatible endpoint, such as a proxy
+    pub fn with_url(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            retry_policy: UploadRetryPolicy::default(),
+            image_limits: Some(ImageLimits::pump_fun()),
+            http: http::default_client(),
+        }
+    }
+
+    /// Retries failed uploads following the given policy
+    pub fn with_retry_policy(mut self, retry_policy: UploadRetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Checks images against the given limits before uploading, or skips the checks if None
+    pub fn with_image_limits(mut self, image_limits: Option<ImageLimits>) -> Self {
+        self.image_limits = image_limits;
+        self
+    }
+
+    /// Sends requests through the given HTTP client
+    pub fn with_http_client(mut self, http: Arc<dyn HttpClient>) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+impl Default for PumpFunUploader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl MetadataUploader for PumpFunUploader {
+    async fn upload(
+        &self,
+        metadata: CreateTokenMetadata,
+    ) -> Result<TokenMetadataResponse, error::ClientError> {
+        super::create_token_metadata_at(
+            self.http.as_ref(),
+            metadata,
+            &self.url,
+            &self.retry_policy,
+            self.image_limits.as_ref(),
+        )
+        .await
+    }
+}
+
+/// Uploads by pinning files with Pinata
+#[derive(Clone)]
+pub struct PinataUploader {
+    jwt: String,
+    gateway: String,
+    retry_policy: UploadRetryPolicy,
+    image_limits: Option<ImageLimits>,
+    http: Arc<dyn HttpClient>,
+}
+
+impl PinataUploader {
+    /// Creates an uploader authenticated with a Pinata API JWT
+    pub fn new(jwt: impl Into<String>) -> Self {
+        Self {
+            jwt: jwt.into(),
+            gateway: DEFAULT_IPFS_GATEWAY.to_string(),
+            retry_policy: UploadRetryPolicy::default(),
+            image_limits: Some(ImageLimits::pump_fun()),
+            http: http::default_client(),
+        }
+    }
+
+    /// Links uploaded files through the given gateway, such as a dedicated Pinata gateway
+    pub fn with_gateway(mut self, gateway: impl Into<String>) -> Self {
+        self.gateway = gateway.into();
+        self
+    }
+
+    /// Retries failed uploads following the given policy
+    pub fn with_retry_policy(mut self, retry_policy: UploadRetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Checks images against the given limits before uploading, or skips the checks if None
+    pub fn with_image_limits(mut self, image_limits: Option<ImageLimits>) -> Self {
+        self.image_limits = image_limits;
+        self
+    }
+
+    /// Sends requests through the given HTTP client
+    pub fn with_http_client(mut self, http: Arc<dyn HttpClient>) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+#[async_trait]
+impl MetadataUploader for PinataUploader {
+    async fn upload(
+        &self,
+        metadata: CreateTokenMetadata,
+    ) -> Result<TokenMetadataResponse, error::ClientError> {
+        upload_files(
+            self.http.as_ref(),
+            metadata,
+            &self.gateway,
+            self.image_limits.as_ref(),
+            |file| async move {
+                let request = HttpRequest::post(
+                    "https://api.pinata.cloud/pinning/pinFileToIPFS",
+                    multipart_file(&file),
+                )
+                .header("Authorization", format!("Bearer {}", self.jwt))
+                .header(
+                    "Content-Type",
+                    format!("multipart/form-data; boundary={}", BOUNDARY),
+                );
+                let response = send_upload(self.http.as_ref(), request, &self.retry_policy).await?;
+                cid(&response, "/IpfsHash")
+            },
+        )
+        .await
+    }
+}
+
+/// Uploads through NFT.Storage
+#[derive(Clone)]
+pub struct NftStorageUploader {
+    api_key: String,
+    gateway: String,
+    retry_policy: UploadRetryPolicy,
+    image_limits: Option<ImageLimits>,
+    http: Arc<dyn HttpClient>,
+}
+
+impl NftStorageUploader {
+    /// Creates an uploader authenticated with an NFT.Storage API key
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            gateway: DEFAULT_IPFS_GATEWAY.to_string(),
+            retry_policy: UploadRetryPolicy::default(),
+            image_limits: Some(ImageLimits::pump_fun()),
+            http: http::default_client(),
+        }
+    }
+
+    /// Links uploaded files through the given gateway
+    pub fn with_gateway(mut self, gateway: impl Into<String>) -> Self {
+        self.gateway = gateway.into();
+        self
+    }
+
+    /// Retries failed uploads following the given policy
+    pub fn with_retry_policy(mut self, retry_policy: UploadRetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Checks images against the given limits before uploading, or skips the checks if None
+    pub fn with_image_limits(mut self, image_limits: Option<ImageLimits>) -> Self {
+        self.image_limits = image_limits;
+        self
+    }
+
+    /// Sends requests through the given HTTP client
+    pub fn with_http_client(mut self, http: Arc<dyn HttpClient>) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+#[async_trait]
+impl MetadataUploader for NftStorageUploader {
+    async fn upload(
+        &self,
+        metadata: CreateTokenMetadata,
+    ) -> Result<TokenMetadataResponse, error::ClientError> {
+        upload_files(
+            self.http.as_ref(),
+            metadata,
+            &self.gateway,
+            self.image_limits.as_ref(),
+            |file| async move {
+                let request = HttpRequest::post("https://api.nft.storage/upload", file.bytes)
+                    .header("Authorization", format!("Bearer {}", self.api_key))
+                    .header("Content-Type", file.content_type);
+                let response = send_upload(self.http.as_ref(), request, &self.retry_policy).await?;
+                cid(&response, "/value/cid")
+            },
+        )
+        .await
+    }
+}
+
+/// Uploads to a node or service exposing the IPFS HTTP API, such as Kubo
+#[derive(Clone)]
+pub struct IpfsHttpUploader {
+    endpoint: String,
+    gateway: String,
+    authorization: Option<String>,
+    retry_policy: UploadRetryPolicy,
+    image_limits: Option<ImageLimits>,
+    http: Arc<dyn HttpClient>,
+}
+
+impl IpfsHttpUploader {
+    /// Creates an uploader for an IPFS HTTP API endpoint, e.g. `http://127.0.0.1:5001`
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            gateway: DEFAULT_IPFS_GATEWAY.to_string(),
+            authorization: None,
+            retry_policy: UploadRetryPolicy::default(),
+            image_limits: Some(ImageLimits::pump_fun()),
+            http: http::default_client(),
+        }
+    }
+
+    /// Links uploaded files through the given gateway
+    pub fn with_gateway(mut self, gateway: impl Into<String>) -> Self {
+        self.gateway = gateway.into();
+        self
+    }
+
+    /// Retries failed uploads following the given policy
+    pub fn with_retry_policy(mut self, retry_policy: UploadRetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Checks images against the given limits before uploading, or skips the checks if None
+    pub fn with_image_limits(mut self, image_limits: Option<ImageLimits>) -> Self {
+        self.image_limits = image_limits;
+        self
+    }
+
+    /// Sends requests through the given HTTP client
+    pub fn with_http_client(mut self, http: Arc<dyn HttpClient>) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Sends the given `Authorization` header value with every request
+    pub fn with_authorization(mut self, authorization: impl Into<String>) -> Self {
+        self.authorization = Some(authorization.into());
+        self
+    }
+}
+
+#[async_trait]
+impl MetadataUploader for IpfsHttpUploader {
+    async fn upload(
+        &self,
+        metadata: CreateTokenMetadata,
+    ) -> Result<TokenMetadataResponse, error::ClientError> {
+        upload_files(
+            self.http.as_ref(),
+            metadata,
+            &self.gateway,
+            self.image_limits.as_ref(),
+            |file| async move {
+                let mut request = HttpRequest::post(
+                    format!("{}/api/v0/add?pin=true", self.endpoint),
+                    multipart_file(&file),
+                )
+                .header(
+                    "Content-Type",
+                    format!("multipart/form-data; boundary={}", BOUNDARY),
+                );
+                if let Some(authorization) = &self.authorization {
+                    request = request.header("Authorization", authorization.as_str());
+                }
+                let response = send_upload(self.http.as_ref(), request, &self.retry_policy).await?;
+                cid(&response, "/Hash")
+            },
+        )
+        .await
+    }
+}
+
+/// File uploaded by a provider
+struct UploadFile {
+    name: String,
+    content_type: String,
+    bytes: Vec<u8>,
+}
+
+/// Uploads the image, then the metadata JSON linking to it, returning the metadata
+///
+/// `add` uploads a single file and returns its CID. The image is checked against
+/// `image_limits` before anything is uploaded.
+async fn upload_files<F, Fut>(
+    http: &dyn HttpClient,
+    metadata: CreateTokenMetadata,
+    gateway: &str,
+    image_limits: Option<&ImageLimits>,
+    add: F,
+) -> Result<TokenMetadataResponse, error::ClientError>
+where
+    F: Fn(UploadFile) -> Fut,
+    Fut: std::future::Future<Output = Result<String, error::ClientError>>,
+{
+    let gateway = gateway.trim_end_matches('/');
+    let (bytes, content_type) = image::load_checked(http, &metadata.file, image_limits).await?;
+    let mut files = vec![("image", bytes, content_type)];
+    for (name, source) in [("banner", &metadata.banner), ("video", &metadata.video)] {
+        if let Some(source) = source {
+            let (bytes, content_type) = image::load_checked(http, source, image_limits).await?;
+            files.push((name, bytes, content_type));
+        }
+    }
+
+    let mut urls = BTreeMap::new();
+    for (name, bytes, content_type) in files {
+        let cid = add(UploadFile {
+            name: format!(
+                "{}.{}",
+                name,
+                image::extension_of_content_type(&content_type)
+            ),
+            content_type,
+            bytes,
+        })
+        .await?;
+        urls.insert(name, format!("{}/{}", gateway, cid));
+    }
+
+    let token = TokenMetadata {
+        name: metadata.name,
+        symbol: metadata.symbol,
+        description: metadata.description,
+        image: urls.remove("image").unwrap_or_default(),
+        show_name: true,
+        created_on: "https://pump.fun".to_string(),
+        twitter: metadata.twitter,
+        telegram: metadata.telegram,
+        website: metadata.website,
+        banner: urls.remove("banner"),
+        video: urls.remove("video"),
+        discord: metadata.discord,
+        extra: metadata
+            .extra
+            .into_iter()
+            .map(|(name, value)| (name, Value::String(value)))
+            .collect(),
+    };
+    let json = serde_json::to_vec(&token)
+        .map_err(|err| error::ClientError::UploadMetadataError(Box::new(err)))?;
+    let metadata_cid = add(UploadFile {
+        name: "metadata.json".to_string(),
+        content_type: "application/json".to_string(),
+        bytes: json,
+    })
+    .await?;
+
+    Ok(TokenMetadataResponse {
+        metadata: token,
+        metadata_uri: format!("{}/{}", gateway, metadata_cid),
+    })
+}
+
+/// Builds a multipart body holding a single file field
+fn multipart_file(file: &UploadFile) -> Vec<u8> {
+    let mut body = Vec::with_capacity(file.bytes.len() + 256);
+    body.extend_from_slice(format!("--{}\r\n", BOUNDARY).as_bytes());
+    body.extend_from_slice(
+        format!(
+            "Content-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\n",
+            file.name
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(format!("Content-Type: {}\r\n\r\n", file.content_type).as_bytes());
+    body.extend_from_slice(&file.bytes);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", BOUNDARY).as_bytes());
+    body
+}
+
+/// Sends an upload request, retrying failed attempts following a policy
+pub(crate) async fn send_upload(
+    http: &dyn HttpClient,
+    request: HttpRequest,
+    retry_policy: &UploadRetryPolicy,
+) -> Result<Value, error::ClientError> {
+    let mut attempt = 1;
+    loop {
+        let delay = match send_once(http, request.clone()).await {
+            Ok(response) => return Ok(response),
+            Err(err) => match retry_policy.retry_delay(attempt, &err) {
+                Some(delay) => delay,
+                None => return Err(err),
+            },
+        };
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// Sends an upload request once and returns the parsed response body
+async fn send_once(
+    http: &dyn HttpClient,
+    request: HttpRequest,
+) -> Result<Value, error::ClientError> {
+    let uri = request.url.clone();
+    // Network failures keep their `HttpError`, which marks them as retryable
+    let response = http
+        .send(request)
+        .await
+        .map_err(|err| error::ClientError::UploadMetadataError(Box::new(err)))?;
+
+    if response.status == 429 {
+        let retry_after = response
+            .header("Retry-After")
+            .and_then(error::parse_retry_after);
+        return Err(error::ClientError::rate_limited(retry_after));
+    }
+    let text = response.text();
+    if !response.is_success() {
+        return Err(error::ClientError::MetadataUploadRejected {
+            status: response.status,
+            message: text,
+        });
+    }
+    serde_json::from_str(&text).map_err(|err| {
+        error::ClientError::UploadMetadataError(
+            format!("Invalid response from {}: {}", uri, err).into(),
+        )
+    })
+}
+
+/// Parses and validates the response of a pump.fun compatible upload
+pub(crate) fn validate_response(
+    response: Value,
+) -> Result<TokenMetadataResponse, error::ClientError> {
+    let invalid = |reason: String| {
+        error::ClientError::UploadMetadataError(
+            format!("Invalid upload response: {}", reason).into(),
+        )
+    };
+    let parsed: TokenMetadataResponse =
+        serde_json::from_value(response).map_err(|err| invalid(err.to_string()))?;
+    let uri = parsed.metadata_uri.trim();
+    if !(uri.starts_with("https://") || uri.starts_with("http://") || uri.starts_with("ipfs://")) {
+        return Err(invalid(format!(
+            "metadata URI {:?} is not a URL",
+            parsed.metadata_uri
+        )));
+    }
+    if parsed.metadata.name.is_empty() || parsed.metadata.symbol.is_empty() {
+        return Err(invalid(
+            "metadata is missing its name or symbol".to_string(),
+        ));
+    }
+    Ok(parsed)
+}
+
+/// Reads the CID of an uploaded file from a response
+fn cid(response: &Value, pointer: &str) -> Result<String, error::ClientError> {
+    response
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .filter(|cid| !cid.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| {
+            error::ClientError::UploadMetadataError(
+                format!("Upload response is missing {}: {}", pointer, response).into(),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multipart_file() {
+        let body = multipart_file(&UploadFile {
+            name: "image.png".to_string(),
+            content_type: "image/png".to_string(),
+            bytes: b"data".to_vec(),
+        });
+        let expected = format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"image.png\"\r\nContent-Type: image/png\r\n\r\ndata\r\n--{b}--\r\n",
+            b = BOUNDARY
+        );
+        assert_eq!(String::from_utf8(body).unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_upload_files() {
+        let metadata = CreateTokenMetadata {
+            name: "Token".to_string(),
+            symbol: "TKN".to_string(),
+            description: "A token".to_string(),
+            file: b"GIF89a".to_vec().into(),
+            twitter: Some("https://x.com/token".to_string()),
+            telegram: None,
+            website: None,
+            banner: Some(b"\x89PNG\r\n\x1a\n".to_vec().into()),
+            video: None,
+            discord: Some("https://discord.gg/token".to_string()),
+            extra: BTreeMap::from([("farcaster".to_string(), "token".to_string())]),
+        };
+        let limits = ImageLimits::pump_fun();
+        let response = upload_files(
+            http::default_client().as_ref(),
+            metadata,
+            "https://gateway.test/ipfs/",
+            Some(&limits),
+            |file| async move {
+                Ok(match file.content_type.as_str() {
+                    "image/gif" => "image-cid".to_string(),
+                    "image/png" => {
+                        assert_eq!(file.name, "banner.png");
+                        "banner-cid".to_string()
+                    }
+                    _ => {
+                        let json: Value = serde_json::from_slice(&file.bytes).unwrap();
+                        assert_eq!(json["image"], "https://gateway.test/ipfs/image-cid");
+                        assert_eq!(json["banner"], "https://gateway.test/ipfs/banner-cid");
+                        assert_eq!(json["twitter"], "https://x.com/token");
+                        assert_eq!(json["discord"], "https://discord.gg/token");
+                        assert_eq!(json["farcaster"], "token");
+                        assert!(json.get("video").is_none());
+                        "metadata-cid".to_string()
+                    }
+                })
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            response.metadata_uri,
+            "https://gateway.test/ipfs/metadata-cid"
+        );
+        assert_eq!(response.metadata.symbol, "TKN");
+
+        let response = serde_json::json!({ "value": { "cid": "bafy" } });
+        assert_eq!(cid(&response, "/value/cid").unwrap(), "bafy");
+        assert!(cid(&response, "/IpfsHash").is_err());
+    }
+
+    #[test]
+    fn test_validate_response() {
+        let mut response = serde_json::json!({
+            "metadata": {
+                "name": "Token",
+                "symbol": "TKN",
+                "description": "",
+                "image": "https://ipfs.io/ipfs/image",
+                "showName": true,
+                "createdOn": "https://pump.fun",
+                "twitter": null,
+                "telegram": null,
+                "website": null,
+            },
+            "metadataUri": "https://ipfs.io/ipfs/metadata",
+        });
+        let parsed = validate_response(response.clone()).unwrap();
+        assert_eq!(parsed.metadata_uri, "https://ipfs.io/ipfs/metadata");
+
+        response["metadataUri"] = "".into();
+        assert!(validate_response(response.clone()).is_err());
+        response.as_object_mut().unwrap().remove("metadataUri");
+        assert!(validate_response(response).is_err());
+        assert!(validate_response(serde_json::json!({ "error": "failed" })).is_err());
+    }
+}
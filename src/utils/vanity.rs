@@ -0,0 +1,301 @@
+//! Vanity mint address grinding
+//!
+//! Tokens launched on pump.fun conventionally have mints ending in "pump". `grind_mint`
+//! generates keypairs on several threads until one's address matches, so the mint keypair
+//! passed to `PumpFun::create` can be found without shelling out to `solana-keygen grind`.
+//!
+//! Each base58 character of the pattern multiplies the expected number of attempts by 58
+//! (34 when ignoring case), so four characters take around 11 million attempts on average.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::error;
+
+/// Suffix of the mints of tokens launched on pump.fun
+pub const PUMP_SUFFIX: &str = "pump";
+
+/// Characters used by base58 encoded addresses
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Number of keypairs a thread generates between checks of the shared state
+const BATCH_SIZE: u64 = 256;
+
+/// Interval between progress reports
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Address pattern searched for by the grinder
+///
+/// # Fields
+///
+/// * `prefix` - Characters the address must start with, empty to match any start
+/// * `suffix` - Characters the address must end with, empty to match any end
+/// * `ignore_case` - Whether letters match regardless of case
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VanityPattern {
+    pub prefix: String,
+    pub suffix: String,
+    pub ignore_case: bool,
+}
+
+impl VanityPattern {
+    /// Creates a pattern matching addresses ending in `suffix`
+    pub fn suffix(suffix: impl Into<String>) -> Self {
+        Self {
+            suffix: suffix.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Creates a pattern matching addresses starting with `prefix`
+    pub fn prefix(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Matches letters regardless of case
+    pub fn ignore_case(mut self) -> Self {
+        self.ignore_case = true;
+        self
+    }
+
+    /// Checks that the pattern can be matched by a base58 address
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClientError::ConfigError` if the pattern is empty or contains characters
+    /// that never appear in base58, such as `0`, `O`, `I`, and `l`
+    pub fn validate(&self) -> Result<(), error::ClientError> {
+        if self.prefix.is_empty() && self.suffix.is_empty() {
+            return Err(error::ClientError::ConfigError(
+                "Vanity pattern needs a prefix or suffix".to_string(),
+            ));
+        }
+        let pattern = format!("{}{}", self.prefix, self.suffix);
+        let invalid = pattern.chars().find(|c| {
+            if self.ignore_case {
+                !BASE58_ALPHABET.contains(c.to_ascii_lowercase())
+                    && !BASE58_ALPHABET.contains(c.to_ascii_uppercase())
+            } else {
+                !BASE58_ALPHABET.contains(*c)
+            }
+        });
+        match invalid {
+            Some(c) => Err(error::ClientError::ConfigError(format!(
+                "Vanity pattern contains {:?}, which is not a base58 character",
+                c
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns whether a base58 encoded address matches the pattern
+    pub fn matches(&self, address: &str) -> bool {
+        if self.ignore_case {
+            let address = address.to_ascii_lowercase();
+            address.starts_with(&self.prefix.to_ascii_lowercase())
+                && address.ends_with(&self.suffix.to_ascii_lowercase())
+        } else {
+            address.starts_with(&self.prefix) && address.ends_with(&self.suffix)
+        }
+    }
+
+    /// Returns the average number of keypairs generated before finding a match
+    pub fn expected_attempts(&self) -> f64 {
+        let base: f64 = if self.ignore_case { 34.0 } else { 58.0 };
+        base.powi((self.prefix.len() + self.suffix.len()) as i32)
+    }
+}
+
+/// Progress of a running grind, reported about once per second
+///
+/// # Fields
+///
+/// * `attempts` - Keypairs generated so far across all threads
+/// * `elapsed` - Time since the grind started
+/// * `expected_attempts` - Average number of attempts needed for the pattern
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GrindProgress {
+    pub attempts: u64,
+    pub elapsed: Duration,
+    pub expected_attempts: f64,
+}
+
+impl GrindProgress {
+    /// Returns the number of keypairs generated per second
+    pub fn rate(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            self.attempts as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Generates a mint keypair whose address ends in `suffix`
+///
+/// # Arguments
+///
+/// * `suffix` - Characters the address must end with, usually `PUMP_SUFFIX`
+/// * `threads` - Number of threads generating keypairs, at least one
+/// * `timeout` - Maximum time to search, or None to search until a match is found
+///
+/// # Returns
+///
+/// Returns the matching keypair
+///
+/// # Errors
+///
+/// Returns `ClientError::ConfigError` if the suffix cannot be matched, or
+/// `ClientError::Timeout` if no match is found in time
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use pumpfun::utils::vanity::{grind_mint, PUMP_SUFFIX};
+/// use solana_sdk::signer::Signer;
+///
+/// # fn run() -> Result<(), pumpfun::error::ClientError> {
+/// let mint = grind_mint(PUMP_SUFFIX, 8, Some(Duration::from_secs(600)))?;
+/// println!("Mint: {}", mint.pubkey());
+/// # Ok(())
+/// # }
+/// ```
+pub fn grind_mint(
+    suffix: &str,
+    threads: usize,
+    timeout: Option<Duration>,
+) -> Result<Keypair, error::ClientError> {
+    grind(&VanityPattern::suffix(suffix), threads, timeout, |_| {})
+}
+
+/// Generates a keypair whose address matches a pattern, reporting progress
+///
+/// Blocks the calling thread until a match is found or the timeout elapses; from async
+/// code, run it on a blocking thread with `tokio::task::spawn_blocking`.
+///
+/// # Arguments
+///
+/// * `pattern` - Prefix and suffix the address must match
+/// * `threads` - Number of threads generating keypairs, at least one
+/// * `timeout` - Maximum time to search, or None to search until a match is found
+/// * `on_progress` - Called on the calling thread about once per second
+///
+/// # Errors
+///
+/// Returns `ClientError::ConfigError` if the pattern cannot be matched, or
+/// `ClientError::Timeout` if no match is found in time
+pub fn grind(
+    pattern: &VanityPattern,
+    threads: usize,
+    timeout: Option<Duration>,
+    on_progress: impl Fn(GrindProgress),
+) -> Result<Keypair, error::ClientError> {
+    pattern.validate()?;
+    let started = Instant::now();
+    let deadline = timeout.map(|timeout| started + timeout);
+    let attempts = AtomicU64::new(0);
+    let done = AtomicBool::new(false);
+    let found: Mutex<Option<Keypair>> = Mutex::new(None);
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| {
+                while !done.load(Ordering::Relaxed) {
+                    for _ in 0..BATCH_SIZE {
+                        let keypair = Keypair::new();
+                        if pattern.matches(&keypair.pubkey().to_string()) {
+                            let mut found = match found.lock() {
+                                Ok(guard) => guard,
+                                Err(poisoned) => poisoned.into_inner(),
+                            };
+                            found.get_or_insert(keypair);
+                            done.store(true, Ordering::Relaxed);
+                            break;
+                        }
+                    }
+                    attempts.fetch_add(BATCH_SIZE, Ordering::Relaxed);
+                }
+            });
+        }
+
+        // Report progress until a thread finds a match or the deadline passes
+        let mut next_report = started + PROGRESS_INTERVAL;
+        while !done.load(Ordering::Relaxed) {
+            let now = Instant::now();
+            if deadline.is_some_and(|deadline| now >= deadline) {
+                done.store(true, Ordering::Relaxed);
+                break;
+            }
+            if now >= next_report {
+                on_progress(GrindProgress {
+                    attempts: attempts.load(Ordering::Relaxed),
+                    elapsed: now - started,
+                    expected_attempts: pattern.expected_attempts(),
+                });
+                next_report += PROGRESS_INTERVAL;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    });
+
+    let found = match found.into_inner() {
+        Ok(found) => found,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    found.ok_or_else(|| error::ClientError::Timeout(started.elapsed()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern() {
+        let pattern = VanityPattern::suffix("pump");
+        assert!(pattern.matches("4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkXpump"));
+        assert!(!pattern.matches("4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkXPUMP"));
+        assert!(pattern
+            .clone()
+            .ignore_case()
+            .matches("4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkXPUMP"));
+        assert!(
+            VanityPattern::prefix("4k3").matches("4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkXpump")
+        );
+        assert_eq!(VanityPattern::suffix("ab").expected_attempts(), 58.0 * 58.0);
+
+        pattern.validate().unwrap();
+        assert!(VanityPattern::default().validate().is_err());
+        assert!(VanityPattern::suffix("p0mp").validate().is_err());
+        assert!(VanityPattern::suffix("pumpl").validate().is_err());
+        // Lowercase l never appears, but uppercase L does
+        VanityPattern::suffix("pumpl")
+            .ignore_case()
+            .validate()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_grind() {
+        let mint = grind_mint("p", 2, Some(Duration::from_secs(30))).unwrap();
+        assert!(mint.pubkey().to_string().ends_with('p'));
+
+        let pattern = VanityPattern::suffix("pumppumppump");
+        assert!(matches!(
+            grind(&pattern, 1, Some(Duration::from_millis(50)), |_| {}),
+            Err(error::ClientError::Timeout(_))
+        ));
+    }
+}
@@ -0,0 +1,455 @@
+//! Fleet of wallets funded from and swept back to the client's payer
+//!
+//! Bundle launches and volume strategies trade from many wallets at once. `WalletManager`
+//! holds such a fleet, either derived deterministically from a seed so it can be recreated
+//! after a restart, or built from existing keypairs, and moves funds between the fleet and
+//! the client's payer, which acts as the treasury.
+//!
+//! # Transfers
+//!
+//! `fund` sends each wallet an amount drawn from a `FundingPlan`, waiting a random delay
+//! between transfers so the funding does not land as one cluster of identical transfers.
+//! `sweep_sol` and `sweep_tokens` return funds to the treasury. The treasury pays the fees
+//! of every transaction, so wallets can be emptied completely, and each transaction is
+//! signed by the wallet it moves funds from.
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use rand::Rng;
+use solana_sdk::{
+    hash::hashv,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+};
+use solana_system_interface::instruction::transfer;
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+
+use crate::{constants, error, PumpFun};
+
+/// Amounts and pacing of the transfers made by `WalletManager::fund`
+///
+/// Each wallet receives an amount drawn uniformly between `min_lamports` and
+/// `max_lamports`, and consecutive transfers are separated by a delay drawn uniformly
+/// between `min_delay` and `max_delay`.
+///
+/// # Fields
+///
+/// * `min_lamports` - Smallest amount sent to a wallet
+/// * `max_lamports` - Largest amount sent to a wallet
+/// * `min_delay` - Shortest wait between two transfers
+/// * `max_delay` - Longest wait between two transfers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FundingPlan {
+    pub min_lamports: u64,
+    pub max_lamports: u64,
+    pub min_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl FundingPlan {
+    /// Creates a plan sending the same amount to every wallet without waiting
+    pub fn fixed(lamports: u64) -> Self {
+        Self {
+            min_lamports: lamports,
+            max_lamports: lamports,
+            min_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        }
+    }
+
+    /// Creates a plan sending amounts between `min_lamports` and `max_lamports`
+    pub fn between(min_lamports: u64, max_lamports: u64) -> Self {
+        Self {
+            min_lamports,
+            max_lamports,
+            ..Self::fixed(0)
+        }
+    }
+
+    /// Waits between `min_delay` and `max_delay` between two transfers
+    pub fn with_delay(mut self, min_delay: Duration, max_delay: Duration) -> Self {
+        self.min_delay = min_delay;
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Checks that the ranges of the plan are not empty
+    ///
+    /// # Errors
+    ///
+    /// Returns `ClientError::ConfigError` if a minimum exceeds its maximum or no lamports
+    /// would be sent
+    pub fn validate(&self) -> Result<(), error::ClientError> {
+        if self.max_lamports == 0 {
+            return Err(error::ClientError::ConfigError(
+                "Funding plan sends no lamports".to_string(),
+            ));
+        }
+        if self.min_lamports > self.max_lamports || self.min_delay > self.max_delay {
+            return Err(error::ClientError::ConfigError(
+                "Funding plan minimum exceeds its maximum".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Draws the amount sent to a wallet
+    fn amount(&self, rng: &mut impl Rng) -> u64 {
+        rng.gen_range(self.min_lamports..=self.max_lamports)
+    }
+
+    /// Draws the delay before the next transfer
+    fn delay(&self, rng: &mut impl Rng) -> Duration {
+        rng.gen_range(self.min_delay..=self.max_delay)
+    }
+}
+
+/// Transfer made between the treasury and a wallet
+///
+/// # Fields
+///
+/// * `wallet` - Wallet funded or swept
+/// * `amount` - Lamports, or token base units for token sweeps, moved
+/// * `signature` - Signature of the transfer transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalletTransfer {
+    pub wallet: Pubkey,
+    pub amount: u64,
+    pub signature: Signature,
+}
+
+/// Balances of a wallet
+///
+/// # Fields
+///
+/// * `wallet` - Address of the wallet
+/// * `lamports` - SOL balance, in lamports
+/// * `tokens` - Token balance of each requested mint, in base units, zero without an account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalletBalance {
+    pub wallet: Pubkey,
+    pub lamports: u64,
+    pub tokens: HashMap<Pubkey, u64>,
+}
+
+/// Manages a fleet of wallets funded by the client's payer
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::{sync::Arc, time::Duration};
+///
+/// use pumpfun::{
+///     common::types::{Cluster, PriorityFee},
+///     wallets::{FundingPlan, WalletManager},
+///     PumpFun,
+/// };
+/// use solana_sdk::{
+///     commitment_config::CommitmentConfig, native_token::LAMPORTS_PER_SOL, signature::Keypair,
+/// };
+///
+/// # async fn run() -> Result<(), pumpfun::error::ClientError> {
+/// let client = Arc::new(PumpFun::new(
+///     Arc::new(Keypair::new()),
+///     Cluster::mainnet(CommitmentConfig::confirmed(), PriorityFee::default()),
+/// ));
+/// let fleet = WalletManager::derive(client, b"my launch seed", 10);
+///
+/// let plan = FundingPlan::between(LAMPORTS_PER_SOL / 10, LAMPORTS_PER_SOL / 5)
+///     .with_delay(Duration::from_secs(2), Duration::from_secs(20));
+/// fleet.fund(&plan).await?;
+///
+/// // ... trade from the wallets ...
+///
+/// fleet.sweep_sol(0).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct WalletManager {
+    client: Arc<PumpFun>,
+    wallets: Vec<Arc<Keypair>>,
+}
+
+impl WalletManager {
+    /// Creates a manager for existing wallets
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Client whose payer funds the wallets and receives sweeps
+    /// * `wallets` - Wallets of the fleet
+    pub fn new(client: Arc<PumpFun>, wallets: Vec<Arc<Keypair>>) -> Self {
+        Self { client, wallets }
+    }
+
+    /// Creates a manager for wallets derived from a seed
+    ///
+    /// The same seed and count always derive the same wallets, so the seed must be kept as
+    /// secret as the keypairs themselves.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Client whose payer funds the wallets and receives sweeps
+    /// * `seed` - Secret seed the wallets are derived from
+    /// * `count` - Number of wallets to derive
+    pub fn derive(client: Arc<PumpFun>, seed: &[u8], count: usize) -> Self {
+        let wallets = (0..count as u64)
+            .map(|index| Arc::new(derive_wallet(seed, index)))
+            .collect();
+        Self::new(client, wallets)
+    }
+
+    /// Returns the wallets of the fleet
+    pub fn wallets(&self) -> &[Arc<Keypair>] {
+        &self.wallets
+    }
+
+    /// Returns the addresses of the wallets
+    pub fn pubkeys(&self) -> Vec<Pubkey> {
+        self.wallets.iter().map(|wallet| wallet.pubkey()).collect()
+    }
+
+    /// Sends SOL from the treasury to every wallet
+    ///
+    /// # Arguments
+    ///
+    /// * `plan` - Amounts to send and delays between transfers
+    ///
+    /// # Returns
+    ///
+    /// Returns the transfers made, in wallet order
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plan is invalid or a transfer fails; wallets funded before
+    /// the failure keep their funds
+    pub async fn fund(
+        &self,
+        plan: &FundingPlan,
+    ) -> Result<Vec<WalletTransfer>, error::ClientError> {
+        plan.validate()?;
+        let treasury = self.client.payer.pubkey();
+        let mut transfers = Vec::with_capacity(self.wallets.len());
+        for (index, wallet) in self.wallets.iter().enumerate() {
+            // Draw before awaiting, since the thread RNG cannot be held across awaits
+            let (amount, delay) = {
+                let mut rng = rand::thread_rng();
+                (plan.amount(&mut rng), plan.delay(&mut rng))
+            };
+            if index > 0 && !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            let instruction = transfer(&treasury, &wallet.pubkey(), amount);
+            let signature = self
+                .client
+                .send_instructions(&[instruction], None, None)
+                .await?;
+            transfers.push(WalletTransfer {
+                wallet: wallet.pubkey(),
+                amount,
+                signature,
+            });
+        }
+        Ok(transfers)
+    }
+
+    /// Fetches the SOL and token balances of every wallet
+    ///
+    /// # Arguments
+    ///
+    /// * `mints` - Mints whose token balances are fetched
+    ///
+    /// # Returns
+    ///
+    /// Returns the balances, in wallet order
+    pub async fn balances(
+        &self,
+        mints: &[Pubkey],
+    ) -> Result<Vec<WalletBalance>, error::ClientError> {
+        let pubkeys = self.pubkeys();
+        let mut balances = Vec::with_capacity(pubkeys.len());
+        for chunk in pubkeys.chunks(100) {
+            let accounts = self.client.rpc.get_multiple_accounts(chunk).await?;
+            balances.extend(
+                chunk
+                    .iter()
+                    .zip(accounts)
+                    .map(|(wallet, account)| WalletBalance {
+                        wallet: *wallet,
+                        lamports: account.map_or(0, |account| account.lamports),
+                        tokens: HashMap::new(),
+                    }),
+            );
+        }
+
+        for mint in mints {
+            for (chunk_index, chunk) in pubkeys.chunks(100).enumerate() {
+                let atas: Vec<Pubkey> = chunk
+                    .iter()
+                    .map(|wallet| get_associated_token_address(wallet, mint))
+                    .collect();
+                let accounts = self.client.rpc.get_multiple_accounts(&atas).await?;
+                for (offset, account) in accounts.into_iter().enumerate() {
+                    let amount = account
+                        .and_then(|account| spl_token::state::Account::unpack(&account.data).ok())
+                        .map_or(0, |account| account.amount);
+                    balances[chunk_index * 100 + offset]
+                        .tokens
+                        .insert(*mint, amount);
+                }
+            }
+        }
+        Ok(balances)
+    }
+
+    /// Sends the SOL of every wallet back to the treasury
+    ///
+    /// # Arguments
+    ///
+    /// * `keep_lamports` - Lamports left in each wallet; either zero to empty the wallets or
+    ///   at least the rent-exempt minimum of a system account
+    ///
+    /// # Returns
+    ///
+    /// Returns the transfers made; wallets holding no more than `keep_lamports` are skipped
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if balances cannot be fetched or a transfer fails
+    pub async fn sweep_sol(
+        &self,
+        keep_lamports: u64,
+    ) -> Result<Vec<WalletTransfer>, error::ClientError> {
+        let treasury = self.client.payer.pubkey();
+        let balances = self.balances(&[]).await?;
+        let mut transfers = Vec::new();
+        for (wallet, balance) in self.wallets.iter().zip(balances) {
+            let amount = balance.lamports.saturating_sub(keep_lamports);
+            if amount == 0 {
+                continue;
+            }
+            let instruction = transfer(&wallet.pubkey(), &treasury, amount);
+            let signature = self
+                .client
+                .send_instructions(&[instruction], Some(&[wallet.as_ref()]), None)
+                .await?;
+            transfers.push(WalletTransfer {
+                wallet: wallet.pubkey(),
+                amount,
+                signature,
+            });
+        }
+        Ok(transfers)
+    }
+
+    /// Sends the tokens of a mint held by every wallet back to the treasury
+    ///
+    /// The treasury's token account is created if needed, and the emptied token accounts
+    /// of the wallets are closed, returning their rent to the treasury.
+    ///
+    /// # Arguments
+    ///
+    /// * `mint` - Mint of the tokens to sweep
+    ///
+    /// # Returns
+    ///
+    /// Returns the transfers made, in token base units; wallets without tokens are skipped
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if balances cannot be fetched or a transfer fails
+    pub async fn sweep_tokens(
+        &self,
+        mint: &Pubkey,
+    ) -> Result<Vec<WalletTransfer>, error::ClientError> {
+        let treasury = self.client.payer.pubkey();
+        let treasury_ata = get_associated_token_address(&treasury, mint);
+        let balances = self.balances(&[*mint]).await?;
+        let mut transfers = Vec::new();
+        for (wallet, balance) in self.wallets.iter().zip(balances) {
+            let amount = balance.tokens.get(mint).copied().unwrap_or_default();
+            if amount == 0 {
+                continue;
+            }
+            let ata = get_associated_token_address(&wallet.pubkey(), mint);
+            let instructions = [
+                create_associated_token_account_idempotent(
+                    &treasury,
+                    &treasury,
+                    mint,
+                    &constants::accounts::TOKEN_PROGRAM,
+                ),
+                spl_token::instruction::transfer(
+                    &constants::accounts::TOKEN_PROGRAM,
+                    &ata,
+                    &treasury_ata,
+                    &wallet.pubkey(),
+                    &[],
+                    amount,
+                )
+                .map_err(|err| error::ClientError::OtherError(err.to_string()))?,
+                spl_token::instruction::close_account(
+                    &constants::accounts::TOKEN_PROGRAM,
+                    &ata,
+                    &treasury,
+                    &wallet.pubkey(),
+                    &[],
+                )
+                .map_err(|err| error::ClientError::OtherError(err.to_string()))?,
+            ];
+            let signature = self
+                .client
+                .send_instructions(&instructions, Some(&[wallet.as_ref()]), None)
+                .await?;
+            transfers.push(WalletTransfer {
+                wallet: wallet.pubkey(),
+                amount,
+                signature,
+            });
+        }
+        Ok(transfers)
+    }
+}
+
+/// Derives the wallet at `index` from a seed
+fn derive_wallet(seed: &[u8], index: u64) -> Keypair {
+    let secret = hashv(&[b"pumpfun-wallet", seed, &index.to_le_bytes()]);
+    Keypair::new_from_array(secret.to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derive_wallet() {
+        let first = derive_wallet(b"seed", 0);
+        assert_eq!(first.pubkey(), derive_wallet(b"seed", 0).pubkey());
+        assert_ne!(first.pubkey(), derive_wallet(b"seed", 1).pubkey());
+        assert_ne!(first.pubkey(), derive_wallet(b"other", 0).pubkey());
+    }
+
+    #[test]
+    fn test_funding_plan() {
+        let plan = FundingPlan::between(100, 200)
+            .with_delay(Duration::from_millis(10), Duration::from_millis(20));
+        plan.validate().unwrap();
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            assert!((100..=200).contains(&plan.amount(&mut rng)));
+            let delay = plan.delay(&mut rng);
+            assert!(delay >= Duration::from_millis(10) && delay <= Duration::from_millis(20));
+        }
+        assert_eq!(FundingPlan::fixed(5).amount(&mut rng), 5);
+
+        assert!(FundingPlan::fixed(0).validate().is_err());
+        assert!(FundingPlan::between(200, 100).validate().is_err());
+        assert!(FundingPlan::fixed(1)
+            .with_delay(Duration::from_secs(2), Duration::from_secs(1))
+            .validate()
+            .is_err());
+    }
+}
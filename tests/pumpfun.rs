@@ -46,10 +46,14 @@ async fn test_02_create_token() {
             name: "Cat On Horse".to_string(),
             symbol: "COH".to_string(),
             description: "Lorem ipsum dolor, sit amet consectetur adipisicing elit.".to_string(),
-            file: file_path.to_str().unwrap().to_string(),
+            file: file_path.into(),
             twitter: None,
             telegram: None,
             website: Some("https://example.com".to_string()),
+            banner: None,
+            video: None,
+            discord: None,
+            extra: Default::default(),
         };
 
         let signature = ctx